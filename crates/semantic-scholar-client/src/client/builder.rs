@@ -0,0 +1,175 @@
+//! Builder for [`SemanticScholarClient`].
+
+use std::time::Duration;
+
+use super::SemanticScholarClient;
+use crate::config::ClientConfig;
+use crate::error::ClientResult;
+
+/// Builds a [`SemanticScholarClient`], starting from [`ClientConfig`]
+/// defaults.
+///
+/// ```no_run
+/// # fn main() -> semantic_scholar_client::ClientResult<()> {
+/// use std::time::Duration;
+///
+/// use semantic_scholar_client::SemanticScholarClient;
+///
+/// let client = SemanticScholarClient::builder()
+///     .api_key("my-key")
+///     .request_timeout(Duration::from_secs(30))
+///     .cache(Duration::from_mins(10), 5000)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct SemanticScholarClientBuilder {
+    config: ClientConfig,
+}
+
+impl SemanticScholarClientBuilder {
+    /// Start from the defaults for anonymous use.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from `config`.
+    pub const fn from_config(config: ClientConfig) -> Self {
+        Self { config }
+    }
+
+    /// Send `key` with every request, switching to the higher rate limits
+    /// granted to API keys (unless set explicitly afterwards).
+    pub fn api_key(self, key: impl Into<String>) -> Self {
+        let config = ClientConfig::new(Some(key.into()));
+        Self {
+            config: ClientConfig {
+                rate_limit_delay: config.rate_limit_delay,
+                batch_rate_limit_delay: config.batch_rate_limit_delay,
+                api_key: config.api_key,
+                ..self.config
+            },
+        }
+    }
+
+    /// Serve both APIs from `base_url` (e.g. a mock), as
+    /// `{base_url}/graph/v1` and `{base_url}/recommendations/v1`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        self.config.graph_api_url = format!("{base_url}/graph/v1");
+        self.config.recommendations_api_url = format!("{base_url}/recommendations/v1");
+        self
+    }
+
+    /// Graph API base URL.
+    pub fn graph_api_url(mut self, url: impl Into<String>) -> Self {
+        self.config.graph_api_url = url.into();
+        self
+    }
+
+    /// Recommendations API base URL.
+    pub fn recommendations_api_url(mut self, url: impl Into<String>) -> Self {
+        self.config.recommendations_api_url = url.into();
+        self
+    }
+
+    /// Timeout for a whole request.
+    pub const fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.config.request_timeout = timeout;
+        self
+    }
+
+    /// Timeout for establishing a connection.
+    pub const fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = timeout;
+        self
+    }
+
+    /// Minimum delay between requests, and between batch requests.
+    pub const fn rate_limits(mut self, delay: Duration, batch_delay: Duration) -> Self {
+        self.config.rate_limit_delay = delay;
+        self.config.batch_rate_limit_delay = batch_delay;
+        self
+    }
+
+    /// Cache responses for `ttl`, keeping at most `max_entries`.
+    pub const fn cache(mut self, ttl: Duration, max_entries: u64) -> Self {
+        self.config.cache_ttl = ttl;
+        self.config.cache_max_size = max_entries;
+        self
+    }
+
+    /// Do not cache responses.
+    pub const fn no_cache(self) -> Self {
+        self.cache(Duration::ZERO, 0)
+    }
+
+    /// Requests allowed in flight at once.
+    pub const fn max_connections(mut self, max: usize) -> Self {
+        self.config.max_connections = max;
+        self
+    }
+
+    /// Idle connections kept per host, and for how long.
+    pub const fn keepalive(mut self, max_idle: usize, expiry: Duration) -> Self {
+        self.config.max_keepalive = max_idle;
+        self.config.keepalive_expiry = expiry;
+        self
+    }
+
+    /// Configuration built so far.
+    #[must_use]
+    pub const fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Build the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::Config`](crate::ClientError::Config) for an API
+    /// key that cannot be sent as a header, or an error if the HTTP client
+    /// cannot be initialized.
+    pub fn build(self) -> ClientResult<SemanticScholarClient> {
+        SemanticScholarClient::new(self.config)
+    }
+}
+
+impl SemanticScholarClient {
+    /// Start building a client.
+    pub fn builder() -> SemanticScholarClientBuilder {
+        SemanticScholarClientBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::api;
+
+    #[test]
+    fn test_builder_settings() {
+        let builder = SemanticScholarClient::builder()
+            .no_cache()
+            .api_key("key")
+            .base_url("http://localhost:8900/")
+            .request_timeout(Duration::from_secs(3))
+            .max_connections(4);
+        let config = builder.config();
+        assert_eq!(config.api_key.as_deref(), Some("key"));
+        assert_eq!(config.rate_limit_delay, api::RATE_LIMIT_DELAY_WITH_KEY);
+        assert_eq!(config.cache_max_size, 0);
+        assert_eq!(config.graph_api_url, "http://localhost:8900/graph/v1");
+        assert_eq!(config.request_timeout, Duration::from_secs(3));
+        assert_eq!(config.max_connections, 4);
+    }
+
+    #[tokio::test]
+    async fn test_build_rejects_unsendable_api_key() {
+        let error = SemanticScholarClient::builder().api_key("bad\nkey").build().unwrap_err();
+        assert!(matches!(error, crate::ClientError::Config(_)), "{error}");
+        assert!(SemanticScholarClient::builder().api_key("key").build().is_ok());
+    }
+}
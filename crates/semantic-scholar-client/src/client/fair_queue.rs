@@ -0,0 +1,144 @@
+//! Fair scheduling of upstream requests across tenants.
+//!
+//! Requests wait for a slot from the shared rate limiter. When they have to
+//! queue, slots go round-robin between tenants rather than first-come
+//! first-served, so one tenant's burst (say a depth-3 citation snowball)
+//! delays everyone else by at most one request per turn.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use governor::DefaultDirectRateLimiter;
+use tokio::sync::oneshot;
+
+#[derive(Debug, Default)]
+struct QueueState {
+    /// Tenants with waiting requests, in serving order.
+    turns: VecDeque<String>,
+    waiting: HashMap<String, VecDeque<oneshot::Sender<()>>>,
+    dispatching: bool,
+}
+
+impl QueueState {
+    /// Next waiter, moving its tenant to the back of the line.
+    fn pop_next(&mut self) -> Option<oneshot::Sender<()>> {
+        let tenant = self.turns.pop_front()?;
+        let queue = self.waiting.get_mut(&tenant)?;
+        let next = queue.pop_front();
+        if queue.is_empty() {
+            self.waiting.remove(&tenant);
+        } else {
+            self.turns.push_back(tenant);
+        }
+        next
+    }
+}
+
+/// Round-robin queue in front of a rate limiter.
+#[derive(Debug)]
+pub struct FairQueue {
+    limiter: DefaultDirectRateLimiter,
+    state: Mutex<QueueState>,
+}
+
+impl FairQueue {
+    pub fn new(limiter: DefaultDirectRateLimiter) -> Self {
+        Self { limiter, state: Mutex::new(QueueState::default()) }
+    }
+
+    /// Wait for a request slot on behalf of `tenant`.
+    pub async fn acquire(self: &Arc<Self>, tenant: &str) {
+        let slot = {
+            let mut state = self.state.lock().unwrap();
+            if state.waiting.is_empty() && self.limiter.check().is_ok() {
+                return;
+            }
+            tracing::info!(tenant, "Rate limit reached, waiting for a request slot");
+            let (tx, rx) = oneshot::channel();
+            if !state.waiting.contains_key(tenant) {
+                state.turns.push_back(tenant.to_owned());
+            }
+            state.waiting.entry(tenant.to_owned()).or_default().push_back(tx);
+            if !state.dispatching {
+                state.dispatching = true;
+                tokio::spawn(Arc::clone(self).dispatch());
+            }
+            rx
+        };
+        // The dispatcher only drops a sender after granting it.
+        let _ = slot.await;
+    }
+
+    /// Grant slots as the limiter allows until no one is waiting. Each slot
+    /// goes to whoever's turn it is when it becomes free.
+    async fn dispatch(self: Arc<Self>) {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.waiting.is_empty() {
+                    state.dispatching = false;
+                    return;
+                }
+            }
+            self.limiter.until_ready().await;
+            let next = {
+                let mut state = self.state.lock().unwrap();
+                // Skip callers that gave up (e.g. cancelled tool calls).
+                std::iter::from_fn(|| state.pop_next()).find(|tx| !tx.is_closed())
+            };
+            if let Some(next) = next {
+                let _ = next.send(());
+            }
+        }
+    }
+
+    /// Number of queued requests per tenant.
+    pub fn waiting(&self) -> super::QueueDepths {
+        let state = self.state.lock().unwrap();
+        let mut waiting: Vec<_> = state.waiting.iter().map(|(t, q)| (t.clone(), q.len())).collect();
+        waiting.sort();
+        waiting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use governor::{Quota, RateLimiter};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slots_alternate_between_tenants() {
+        let quota = Quota::per_second(NonZeroU32::new(50).unwrap())
+            .allow_burst(NonZeroU32::new(1).unwrap());
+        let queue = Arc::new(FairQueue::new(RateLimiter::direct(quota)));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        // Tenant "a" takes the only burst slot, then queues four more.
+        queue.acquire("a").await;
+        let mut tasks = Vec::new();
+        for tenant in ["a", "a", "a", "a", "b"] {
+            let queue = Arc::clone(&queue);
+            let tx = tx.clone();
+            tasks.push(tokio::spawn(async move {
+                queue.acquire(tenant).await;
+                tx.send(tenant).unwrap();
+            }));
+            // Enqueue in a known order.
+            tokio::task::yield_now().await;
+        }
+        for task in tasks {
+            task.await.unwrap();
+        }
+        drop(tx);
+
+        let mut order = Vec::new();
+        while let Some(tenant) = rx.recv().await {
+            order.push(tenant);
+        }
+        assert_eq!(order, ["a", "b", "a", "a", "a"]);
+        assert!(queue.waiting().is_empty());
+    }
+}
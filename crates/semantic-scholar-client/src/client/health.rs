@@ -0,0 +1,191 @@
+//! Upstream reachability and rate-limit backoff, for readiness checks.
+//!
+//! Every response the client receives counts as evidence that Semantic
+//! Scholar is reachable; a 429 puts the client in backoff until its
+//! `Retry-After` passes or another request succeeds. When nothing has been
+//! heard for [`PROBE_INTERVAL`], a readiness check sends a cheap probe.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use serde::Serialize;
+
+/// How long an upstream answer counts as recent.
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Backoff after a 429 without a usable `Retry-After` header.
+pub const DEFAULT_BACKOFF: Duration = Duration::from_mins(1);
+
+/// Timeout of a readiness probe.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default)]
+struct State {
+    /// Last response that was not a server error.
+    last_answer: Option<Instant>,
+    /// Last server error or transport failure.
+    last_failure: Option<(Instant, String)>,
+    /// End of the current 429 backoff.
+    backoff_until: Option<Instant>,
+    /// Last readiness probe, successful or not.
+    last_probe: Option<Instant>,
+}
+
+/// What the client has heard from Semantic Scholar, shared by all handles.
+#[derive(Debug, Default)]
+pub struct UpstreamHealth {
+    state: Mutex<State>,
+    /// Lets one readiness check probe at a time.
+    probe_lock: tokio::sync::Mutex<()>,
+}
+
+/// Upstream state reported by a readiness check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UpstreamStatus {
+    /// An answer (anything but a server error) arrived within
+    /// [`PROBE_INTERVAL`].
+    pub reachable: bool,
+    /// Seconds since the last answer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_answer_secs: Option<u64>,
+    /// The most recent failure, if it came after the last answer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl UpstreamHealth {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Record a response. `retry_after` is the 429's `Retry-After`, if any.
+    pub fn record_status(&self, status: StatusCode, retry_after: Option<Duration>) {
+        let now = Instant::now();
+        let mut state = self.lock();
+        if status.is_server_error() {
+            state.last_failure = Some((now, format!("HTTP {}", status.as_u16())));
+            return;
+        }
+        state.last_answer = Some(now);
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            state.backoff_until = Some(now + retry_after.unwrap_or(DEFAULT_BACKOFF));
+        } else if status.is_success() {
+            state.backoff_until = None;
+        }
+    }
+
+    /// Record a request that got no response.
+    pub fn record_error(&self, error: &str) {
+        self.lock().last_failure = Some((Instant::now(), error.to_owned()));
+    }
+
+    /// Time left in the current 429 backoff.
+    #[must_use]
+    pub fn backoff_remaining(&self) -> Option<Duration> {
+        let until = self.lock().backoff_until?;
+        until.checked_duration_since(Instant::now()).filter(|d| !d.is_zero())
+    }
+
+    /// Whether nothing recent is known, so a readiness check should probe.
+    fn needs_probe(&self) -> bool {
+        let state = self.lock();
+        let recent = |at: Option<Instant>| at.is_some_and(|at| at.elapsed() < PROBE_INTERVAL);
+        !recent(state.last_answer) && !recent(state.last_probe)
+    }
+
+    /// Probe with `send` unless the upstream answered recently or a 429
+    /// backoff is running, then report.
+    pub(crate) async fn check<F, Fut>(&self, send: F) -> UpstreamStatus
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = reqwest::Result<reqwest::Response>>,
+    {
+        if self.needs_probe() && self.backoff_remaining().is_none() {
+            let _guard = self.probe_lock.lock().await;
+            // Another check may have probed while we waited.
+            if self.needs_probe() {
+                self.lock().last_probe = Some(Instant::now());
+                match send().await {
+                    Ok(response) => {
+                        self.record_status(response.status(), retry_after(response.headers()));
+                    }
+                    Err(e) => self.record_error(&e.to_string()),
+                }
+            }
+        }
+        self.status()
+    }
+
+    /// Report without probing.
+    #[must_use]
+    pub fn status(&self) -> UpstreamStatus {
+        let state = self.lock();
+        let answered = state.last_answer.map(|at| at.elapsed());
+        let error = match (&state.last_failure, state.last_answer) {
+            (Some((failed, error)), Some(answered)) if *failed > answered => Some(error.clone()),
+            (Some((_, error)), None) => Some(error.clone()),
+            _ => None,
+        };
+        UpstreamStatus {
+            reachable: answered.is_some_and(|d| d < PROBE_INTERVAL) && error.is_none(),
+            last_answer_secs: answered.map(|d| d.as_secs()),
+            error,
+        }
+    }
+}
+
+/// Parse a `Retry-After` header given in seconds.
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_failures_and_backoff() {
+        let health = UpstreamHealth::new();
+        let status = health.status();
+        assert!(!status.reachable);
+        assert_eq!(status.last_answer_secs, None);
+
+        health.record_status(StatusCode::NOT_FOUND, None);
+        assert!(health.status().reachable, "a 404 is still an answer");
+
+        health.record_status(StatusCode::BAD_GATEWAY, None);
+        let status = health.status();
+        assert!(!status.reachable);
+        assert_eq!(status.error.as_deref(), Some("HTTP 502"));
+
+        health.record_status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_mins(2)));
+        let status = health.status();
+        assert!(status.reachable);
+        let backoff = health.backoff_remaining().unwrap();
+        assert!(backoff > Duration::from_secs(100) && backoff <= Duration::from_mins(2));
+
+        health.record_status(StatusCode::OK, None);
+        assert_eq!(health.backoff_remaining(), None, "a success ends the backoff");
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(reqwest::header::RETRY_AFTER, "30".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(30)));
+        headers
+            .insert(reqwest::header::RETRY_AFTER, "Wed, 21 Oct 2026 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+    }
+}
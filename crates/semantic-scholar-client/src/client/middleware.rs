@@ -0,0 +1,106 @@
+//! Middleware for the HTTP client.
+//!
+//! Rate limiting and caching are handled directly in the client module;
+//! middleware here sees every attempt the retry layer makes.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next, Result};
+
+use super::health::{UpstreamHealth, retry_after};
+use crate::metrics::{ClientMetrics, endpoint_label};
+
+/// Attempts made so far for one request; the retry layer passes the same
+/// extensions to each attempt.
+#[derive(Debug, Clone, Copy)]
+struct Attempts(u32);
+
+/// Records each upstream attempt: its endpoint, status and duration in the
+/// metrics, and the attempt count and last status on the current
+/// `s2.request` span.
+///
+/// Installed below the retry middleware.
+#[derive(Debug)]
+pub struct MetricsMiddleware {
+    metrics: Arc<ClientMetrics>,
+}
+
+impl MetricsMiddleware {
+    pub const fn new(metrics: Arc<ClientMetrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for MetricsMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let attempt = extensions.get::<Attempts>().map_or(1, |a| a.0 + 1);
+        extensions.insert(Attempts(attempt));
+        if attempt > 1 {
+            self.metrics.upstream_retries.inc();
+        }
+        let endpoint = endpoint_label(req.url().path());
+        let started = Instant::now();
+        let result = next.run(req, extensions).await;
+
+        let status = result
+            .as_ref()
+            .map_or_else(|_| "error".to_owned(), |r| r.status().as_u16().to_string());
+        self.metrics
+            .upstream_requests
+            .with_label_values(&[endpoint.as_str(), status.as_str()])
+            .inc();
+        self.metrics
+            .upstream_duration
+            .with_label_values(&[endpoint.as_str()])
+            .observe(started.elapsed().as_secs_f64());
+
+        // As i64: the OpenTelemetry layer exports unsigned values as strings.
+        let span = tracing::Span::current();
+        span.record("attempts", i64::from(attempt));
+        if let Ok(ref response) = result {
+            span.record("http.response.status_code", i64::from(response.status().as_u16()));
+        }
+        result
+    }
+}
+
+/// Feeds every upstream attempt into [`UpstreamHealth`], so readiness
+/// checks see 429 backoffs and server errors even between retries.
+#[derive(Debug)]
+pub struct HealthMiddleware {
+    health: Arc<UpstreamHealth>,
+}
+
+impl HealthMiddleware {
+    pub const fn new(health: Arc<UpstreamHealth>) -> Self {
+        Self { health }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for HealthMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let result = next.run(req, extensions).await;
+        match result {
+            Ok(ref response) => {
+                self.health.record_status(response.status(), retry_after(response.headers()));
+            }
+            Err(ref e) => self.health.record_error(&e.to_string()),
+        }
+        result
+    }
+}
@@ -1,30 +1,55 @@
 //! Semantic Scholar API client.
 //!
 //! Provides async HTTP client with:
-//! - Connection pooling via reqwest
+//! - Connection pooling via reqwest, with a cap on requests in flight
 //! - Retry middleware with exponential backoff
-//! - Rate limiting (5 req/s normal, 1 req/s batch)
+//! - Rate limiting (5 req/s normal, 1 req/s batch), shared fairly between
+//!   tenants
+//! - Optional per-tenant request quotas
 //! - Response caching with 5-minute TTL
+//! - Prometheus metrics for upstream requests, retries, cache lookups and
+//!   rate-limit waits
+//! - Upstream reachability and 429 backoff tracking for readiness checks
 
+mod builder;
+mod fair_queue;
+pub mod health;
 mod middleware;
+pub mod quota;
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 
-use governor::{Quota, RateLimiter, DefaultDirectRateLimiter};
+use governor::{Quota, RateLimiter};
 use moka::future::Cache;
 use reqwest::Client;
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::{RetryTransientMiddleware, policies::ExponentialBackoff};
-
-use crate::config::{Config, api};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::Instrument;
+
+pub use self::builder::SemanticScholarClientBuilder;
+use self::fair_queue::FairQueue;
+use self::health::{PROBE_TIMEOUT, UpstreamHealth, UpstreamStatus};
+use self::middleware::{HealthMiddleware, MetricsMiddleware};
+use self::quota::{QuotaLedger, QuotaLimits, TenantUsage};
+use crate::config::ClientConfig;
 use crate::error::{ClientError, ClientResult};
+use crate::metrics::{ClientMetrics, endpoint_label};
 use crate::models::{
     Author, AuthorSearchResult, BulkSearchResult, Paper, SearchResult, SnippetSearchResult,
 };
 
-/// Type alias for the global rate limiter.
-type SharedRateLimiter = Arc<DefaultDirectRateLimiter>;
+/// Number of queued requests per tenant.
+pub type QueueDepths = Vec<(String, usize)>;
+
+/// Who requests are charged to (an OAuth client on the HTTP transport).
+#[derive(Debug, Clone)]
+struct Tenant {
+    key: String,
+    limits: QuotaLimits,
+}
 
 /// Semantic Scholar API client.
 #[derive(Clone)]
@@ -44,20 +69,53 @@ pub struct SemanticScholarClient {
     /// Recommendations API base URL.
     recommendations_api_url: String,
 
-    /// Global rate limiter for normal requests.
-    rate_limiter: SharedRateLimiter,
+    /// Rate limiter for normal requests, shared round-robin.
+    rate_queue: Arc<FairQueue>,
+
+    /// Rate limiter for batch requests, shared round-robin.
+    batch_queue: Arc<FairQueue>,
+
+    /// Permits for requests in flight, shared by every handle.
+    connections: Arc<Semaphore>,
+
+    /// Request counts per tenant.
+    quota_ledger: Arc<QuotaLedger>,
+
+    /// Tenant this handle charges requests to (`None`: the server itself).
+    tenant: Option<Tenant>,
+
+    /// Counts requests sent through this handle, when set.
+    request_counter: Option<Arc<AtomicU32>>,
 
-    /// Global rate limiter for batch requests.
-    batch_rate_limiter: SharedRateLimiter,
+    /// Metrics shared by every handle of this client.
+    metrics: Arc<ClientMetrics>,
+
+    /// What this client has heard from the API, shared by every handle.
+    health: Arc<UpstreamHealth>,
+
+    /// Client without retries, for readiness probes.
+    probe_client: Client,
+}
+
+/// Response cache size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct CacheStats {
+    pub entries: u64,
+    /// Maximum entries (0: caching is disabled).
+    pub capacity: u64,
 }
 
 impl SemanticScholarClient {
     /// Create a new client with the given configuration.
     ///
+    /// Nothing global is touched: the client owns its connection pool and
+    /// limit, cache, rate limiters and metrics registry.
+    ///
     /// # Errors
     ///
     /// Returns error if HTTP client initialization fails.
-    pub fn new(config: Config) -> anyhow::Result<Self> {
+    pub fn new(config: impl Into<ClientConfig>) -> ClientResult<Self> {
+        let config = config.into();
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert(
             reqwest::header::CONTENT_TYPE,
@@ -65,15 +123,16 @@ impl SemanticScholarClient {
         );
 
         if let Some(ref key) = config.api_key {
-            headers.insert("x-api-key", key.parse()?);
+            let key = key.parse().map_err(|_| ClientError::Config("invalid API key".into()))?;
+            headers.insert("x-api-key", key);
         }
 
         let client = Client::builder()
             .default_headers(headers)
             .timeout(config.request_timeout)
             .connect_timeout(config.connect_timeout)
-            .pool_max_idle_per_host(api::MAX_KEEPALIVE)
-            .pool_idle_timeout(api::KEEPALIVE_EXPIRY)
+            .pool_max_idle_per_host(config.max_keepalive)
+            .pool_idle_timeout(config.keepalive_expiry)
             .gzip(true)
             .build()?;
 
@@ -81,8 +140,13 @@ impl SemanticScholarClient {
             .retry_bounds(Duration::from_secs(1), Duration::from_secs(30))
             .build_with_max_retries(3);
 
+        let metrics = Arc::new(ClientMetrics::new());
+        let health = Arc::new(UpstreamHealth::new());
+        let probe_client = client.clone();
         let client = ClientBuilder::new(client)
             .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+            .with(MetricsMiddleware::new(Arc::clone(&metrics)))
+            .with(HealthMiddleware::new(Arc::clone(&health)))
             .build();
 
         let cache = Cache::builder()
@@ -90,16 +154,16 @@ impl SemanticScholarClient {
             .time_to_live(config.cache_ttl)
             .build();
 
-        // Initialize global rate limiters from config delays
+        // Rate limiters from config delays, shared by this client's handles
         let rate_limit = (1.0 / config.rate_limit_delay.as_secs_f64()).round() as u32;
         let batch_limit = (1.0 / config.batch_rate_limit_delay.as_secs_f64()).round() as u32;
 
-        let rate_limiter = Arc::new(RateLimiter::direct(Quota::per_second(
+        let rate_queue = Arc::new(FairQueue::new(RateLimiter::direct(Quota::per_second(
             std::num::NonZeroU32::new(rate_limit.max(1)).unwrap(),
-        )));
-        let batch_rate_limiter = Arc::new(RateLimiter::direct(Quota::per_second(
+        ))));
+        let batch_queue = Arc::new(FairQueue::new(RateLimiter::direct(Quota::per_second(
             std::num::NonZeroU32::new(batch_limit.max(1)).unwrap(),
-        )));
+        ))));
 
         Ok(Self {
             client,
@@ -107,11 +171,112 @@ impl SemanticScholarClient {
             api_key: config.api_key,
             graph_api_url: config.graph_api_url,
             recommendations_api_url: config.recommendations_api_url,
-            rate_limiter,
-            batch_rate_limiter,
+            rate_queue,
+            batch_queue,
+            connections: Arc::new(Semaphore::new(config.max_connections.max(1))),
+            quota_ledger: Arc::new(QuotaLedger::new()),
+            tenant: None,
+            request_counter: None,
+            metrics,
+            health,
+            probe_client,
         })
     }
 
+    /// A handle that charges requests to `tenant` within `limits`.
+    ///
+    /// It shares the connection pool and limit, cache, rate limiters and
+    /// quota ledger with `self`; when requests queue, tenants take turns.
+    #[must_use]
+    pub fn for_tenant(&self, tenant: impl Into<String>, limits: QuotaLimits) -> Self {
+        Self { tenant: Some(Tenant { key: tenant.into(), limits }), ..self.clone() }
+    }
+
+    /// A handle that adds one to `counter` for every request it sends to
+    /// Semantic Scholar (cache hits and refused requests are not counted).
+    #[must_use]
+    pub fn with_request_counter(&self, counter: Arc<AtomicU32>) -> Self {
+        Self { request_counter: Some(counter), ..self.clone() }
+    }
+
+    /// Metrics of this client, for the application to expose.
+    #[must_use]
+    pub const fn metrics(&self) -> &Arc<ClientMetrics> {
+        &self.metrics
+    }
+
+    /// Whether the API answered within [`health::PROBE_INTERVAL`], sending a
+    /// cheap autocomplete probe if nothing has been heard since. No probe is
+    /// sent during a 429 backoff.
+    pub async fn check_upstream(&self) -> UpstreamStatus {
+        let url = format!("{}/paper/autocomplete", self.graph_api_url);
+        self.health
+            .check(|| {
+                self.probe_client
+                    .get(url)
+                    .query(&[("query", "readiness")])
+                    .timeout(PROBE_TIMEOUT)
+                    .send()
+            })
+            .await
+    }
+
+    /// Time left in the backoff imposed by the API's last 429.
+    #[must_use]
+    pub fn rate_limit_backoff(&self) -> Option<Duration> {
+        self.health.backoff_remaining()
+    }
+
+    /// Response cache size.
+    #[must_use]
+    pub fn cache_stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.cache.entry_count(),
+            capacity: self.cache.policy().max_capacity().unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Today's request counts per tenant.
+    #[must_use]
+    pub fn quota_usage(&self) -> Vec<TenantUsage> {
+        self.quota_ledger.snapshot()
+    }
+
+    /// Requests waiting for a rate-limit slot, per tenant (normal, batch).
+    #[must_use]
+    pub fn queued_requests(&self) -> (QueueDepths, QueueDepths) {
+        (self.rate_queue.waiting(), self.batch_queue.waiting())
+    }
+
+    /// Charge the tenant's quota, then wait for a slot on `queue`.
+    async fn admit(&self, queue: &Arc<FairQueue>, queue_name: &str) -> ClientResult<()> {
+        let key = match self.tenant {
+            Some(ref tenant) => {
+                self.quota_ledger
+                    .charge(&tenant.key, tenant.limits)
+                    .map_err(ClientError::QuotaExceeded)?;
+                tenant.key.as_str()
+            }
+            None => "",
+        };
+        let started = Instant::now();
+        queue.acquire(key).await;
+        self.metrics
+            .rate_limit_wait
+            .with_label_values(&[queue_name])
+            .observe(started.elapsed().as_secs_f64());
+        if let Some(ref counter) = self.request_counter {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Wait until fewer than `max_connections` requests are in flight; the
+    /// permit is held until the response body has been read.
+    async fn connection(&self) -> SemaphorePermit<'_> {
+        self.connections.acquire().await.expect("connection semaphore is never closed")
+    }
+
     /// Check if an API key is configured.
     #[must_use]
     pub fn has_api_key(&self) -> bool {
@@ -539,17 +704,28 @@ impl SemanticScholarClient {
 
     /// Make a GET request.
     async fn get<T>(&self, url: &str, params: &[(String, String)]) -> ClientResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.get_uninstrumented(url, params).instrument(upstream_span("GET", url)).await
+    }
+
+    async fn get_uninstrumented<T>(&self, url: &str, params: &[(String, String)]) -> ClientResult<T>
     where
         T: serde::de::DeserializeOwned,
     {
         // Check cache
         let cache_key = self.cache_key("GET", url, params);
-        if let Some(cached) = self.cache.get(&cache_key).await {
+        let cached = self.cache.get(&cache_key).await;
+        tracing::Span::current().record("cache_hit", cached.is_some());
+        if let Some(cached) = cached {
+            self.metrics.cache_lookups.with_label_values(&["hit"]).inc();
             return serde_json::from_value(cached).map_err(ClientError::from);
         }
+        self.metrics.cache_lookups.with_label_values(&["miss"]).inc();
 
-        // Global rate limiting
-        self.rate_limiter.until_ready().await;
+        self.admit(&self.rate_queue, "normal").await?;
+        let _connection = self.connection().await;
 
         let response = self.client.get(url).query(params).send().await?;
 
@@ -572,8 +748,20 @@ impl SemanticScholarClient {
     where
         T: serde::de::DeserializeOwned,
     {
-        // Global rate limiting (batch-specific)
-        self.batch_rate_limiter.until_ready().await;
+        self.post_uninstrumented(url, params, body).instrument(upstream_span("POST", url)).await
+    }
+
+    async fn post_uninstrumented<T>(
+        &self,
+        url: &str,
+        params: &[(String, String)],
+        body: &serde_json::Value,
+    ) -> ClientResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.admit(&self.batch_queue, "batch").await?;
+        let _connection = self.connection().await;
 
         let body_str = serde_json::to_string(body)?;
 
@@ -605,12 +793,9 @@ impl SemanticScholarClient {
 
         match status.as_u16() {
             429 => {
-                let retry_after = response
-                    .headers()
-                    .get("Retry-After")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse().ok())
-                    .unwrap_or(60);
+                let retry_after = health::retry_after(response.headers())
+                    .unwrap_or(health::DEFAULT_BACKOFF)
+                    .as_secs();
 
                 Err(ClientError::rate_limited(retry_after))
             }
@@ -654,6 +839,21 @@ impl SemanticScholarClient {
     }
 }
 
+/// Span for one API call; the metrics middleware records its status and
+/// attempts.
+fn upstream_span(method: &str, url: &str) -> tracing::Span {
+    let path = reqwest::Url::parse(url).map(|u| u.path().to_owned()).unwrap_or_default();
+    tracing::info_span!(
+        "s2.request",
+        otel.kind = "client",
+        http.request.method = method,
+        s2.endpoint = endpoint_label(&path),
+        cache_hit = tracing::field::Empty,
+        http.response.status_code = tracing::field::Empty,
+        attempts = tracing::field::Empty,
+    )
+}
+
 impl std::fmt::Debug for SemanticScholarClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SemanticScholarClient").field("has_api_key", &self.has_api_key()).finish()
@@ -0,0 +1,215 @@
+//! Per-tenant request budgets.
+//!
+//! A tenant (an OAuth client on the HTTP transport) may be limited to a
+//! number of upstream requests per minute and per UTC day. Only requests
+//! that reach Semantic Scholar count; cache hits are free.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Upstream request limits for one tenant. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_minute: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_day: Option<u32>,
+}
+
+/// Which budget ran out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaWindow {
+    Minute,
+    Day,
+}
+
+impl fmt::Display for QuotaWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Minute => "minute",
+            Self::Day => "day",
+        })
+    }
+}
+
+/// A request refused because its tenant's budget is spent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    pub window: QuotaWindow,
+    pub limit: u32,
+    /// Time until the budget resets.
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} upstream requests per {} used", self.limit, self.window)
+    }
+}
+
+/// Requests counted in the current minute and day.
+#[derive(Debug, Clone, Copy)]
+struct Usage {
+    minute_start: i64,
+    minute_count: u32,
+    day: NaiveDate,
+    day_count: u32,
+}
+
+impl Usage {
+    fn at(now: DateTime<Utc>) -> Self {
+        Self { minute_start: now.timestamp(), minute_count: 0, day: now.date_naive(), day_count: 0 }
+    }
+
+    /// Reset windows that have ended by `now`.
+    fn roll(&mut self, now: DateTime<Utc>) {
+        if now.timestamp() - self.minute_start >= 60 {
+            self.minute_start = now.timestamp();
+            self.minute_count = 0;
+        }
+        if now.date_naive() != self.day {
+            self.day = now.date_naive();
+            self.day_count = 0;
+        }
+    }
+}
+
+/// Usage of one tenant, as reported by the admin endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TenantUsage {
+    pub tenant: String,
+    pub minute_used: u32,
+    pub day_used: u32,
+    /// Seconds until the per-minute count resets.
+    pub minute_resets_in: u64,
+    /// Seconds until the daily count resets (UTC midnight).
+    pub day_resets_in: u64,
+}
+
+/// Request counts for every tenant seen today.
+#[derive(Debug, Default)]
+pub struct QuotaLedger {
+    usage: Mutex<HashMap<String, Usage>>,
+}
+
+impl QuotaLedger {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one request for `tenant`, or refuse it if a budget is spent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QuotaExceeded`] naming the exhausted window.
+    pub fn charge(&self, tenant: &str, limits: QuotaLimits) -> Result<(), QuotaExceeded> {
+        self.charge_at(tenant, limits, Utc::now())
+    }
+
+    fn charge_at(
+        &self,
+        tenant: &str,
+        limits: QuotaLimits,
+        now: DateTime<Utc>,
+    ) -> Result<(), QuotaExceeded> {
+        let mut all = self.usage.lock().unwrap();
+        let usage = all.entry(tenant.to_owned()).or_insert_with(|| Usage::at(now));
+        usage.roll(now);
+
+        if let Some(limit) = limits.per_day.filter(|&l| usage.day_count >= l) {
+            return Err(QuotaExceeded {
+                window: QuotaWindow::Day,
+                limit,
+                retry_after: Duration::from_secs(until_midnight(now)),
+            });
+        }
+        if let Some(limit) = limits.per_minute.filter(|&l| usage.minute_count >= l) {
+            return Err(QuotaExceeded {
+                window: QuotaWindow::Minute,
+                limit,
+                retry_after: Duration::from_secs(until_minute_end(usage, now)),
+            });
+        }
+        usage.minute_count += 1;
+        usage.day_count += 1;
+        Ok(())
+    }
+
+    /// Current usage of every tenant, sorted by name.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<TenantUsage> {
+        let now = Utc::now();
+        let mut all = self.usage.lock().unwrap();
+        all.retain(|_, usage| {
+            usage.roll(now);
+            usage.day_count > 0
+        });
+        let mut tenants: Vec<TenantUsage> = all
+            .iter()
+            .map(|(tenant, usage)| TenantUsage {
+                tenant: tenant.clone(),
+                minute_used: usage.minute_count,
+                day_used: usage.day_count,
+                minute_resets_in: until_minute_end(usage, now),
+                day_resets_in: until_midnight(now),
+            })
+            .collect();
+        tenants.sort_by(|a, b| a.tenant.cmp(&b.tenant));
+        tenants
+    }
+}
+
+fn until_minute_end(usage: &Usage, now: DateTime<Utc>) -> u64 {
+    (usage.minute_start + 60 - now.timestamp()).max(1) as u64
+}
+
+fn until_midnight(now: DateTime<Utc>) -> u64 {
+    let midnight = now.date_naive().succ_opt().and_then(|d| d.and_hms_opt(0, 0, 0));
+    midnight.map_or(1, |m| (m.and_utc().timestamp() - now.timestamp()).max(1) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_per_minute_budget_resets() {
+        let ledger = QuotaLedger::new();
+        let limits = QuotaLimits { per_minute: Some(2), per_day: None };
+        let t0 = at("2026-01-01T12:00:00Z");
+
+        assert!(ledger.charge_at("a", limits, t0).is_ok());
+        assert!(ledger.charge_at("a", limits, t0).is_ok());
+        let err = ledger.charge_at("a", limits, at("2026-01-01T12:00:45Z")).unwrap_err();
+        assert_eq!(err.window, QuotaWindow::Minute);
+        assert_eq!(err.retry_after, Duration::from_secs(15));
+
+        // Other tenants have their own budget; the window ends after 60s.
+        assert!(ledger.charge_at("b", limits, t0).is_ok());
+        assert!(ledger.charge_at("a", limits, at("2026-01-01T12:01:00Z")).is_ok());
+    }
+
+    #[test]
+    fn test_daily_budget_resets_at_utc_midnight() {
+        let ledger = QuotaLedger::new();
+        let limits = QuotaLimits { per_minute: None, per_day: Some(1) };
+
+        assert!(ledger.charge_at("a", limits, at("2026-01-01T23:00:00Z")).is_ok());
+        let err = ledger.charge_at("a", limits, at("2026-01-01T23:30:00Z")).unwrap_err();
+        assert_eq!(err.window, QuotaWindow::Day);
+        assert_eq!(err.retry_after, Duration::from_mins(30));
+        assert!(ledger.charge_at("a", limits, at("2026-01-02T00:00:01Z")).is_ok());
+    }
+}
@@ -1,4 +1,10 @@
-//! Configuration for the Semantic Scholar MCP server.
+//! Client configuration.
+//!
+//! [`ClientConfig`] holds everything [`SemanticScholarClient`] needs; the
+//! constants below are its defaults. Nothing is read from the environment:
+//! applications decide where an API key comes from.
+//!
+//! [`SemanticScholarClient`]: crate::client::SemanticScholarClient
 
 use std::time::Duration;
 
@@ -34,12 +40,12 @@ pub mod api {
     pub const BATCH_RATE_LIMIT_DELAY_WITH_KEY: Duration = Duration::from_millis(100);
 
     /// Cache TTL (5 minutes).
-    pub const CACHE_TTL: Duration = Duration::from_secs(300);
+    pub const CACHE_TTL: Duration = Duration::from_mins(5);
 
     /// Maximum cache size.
     pub const CACHE_MAX_SIZE: u64 = 1000;
 
-    /// Maximum connections.
+    /// Maximum requests in flight at once.
     pub const MAX_CONNECTIONS: usize = 20;
 
     /// Maximum keepalive connections.
@@ -93,15 +99,39 @@ pub mod fields {
         &["authorId", "name", "affiliations", "homepage", "paperCount", "citationCount", "hIndex"];
 }
 
-/// Server configuration.
+/// Fields of study recognised by the Semantic Scholar API (`fieldsOfStudy` filter).
+pub const FIELDS_OF_STUDY: &[&str] = &[
+    "Computer Science",
+    "Medicine",
+    "Chemistry",
+    "Biology",
+    "Materials Science",
+    "Physics",
+    "Geology",
+    "Psychology",
+    "Art",
+    "History",
+    "Geography",
+    "Sociology",
+    "Business",
+    "Political Science",
+    "Economics",
+    "Philosophy",
+    "Mathematics",
+    "Engineering",
+    "Environmental Science",
+    "Agricultural and Food Sciences",
+    "Education",
+    "Law",
+    "Linguistics",
+];
+
+/// Client configuration.
 #[derive(Debug, Clone)]
-pub struct Config {
+pub struct ClientConfig {
     /// Semantic Scholar API key (optional).
     pub api_key: Option<String>,
 
-    /// Authentication token for the MCP server (optional).
-    pub auth_token: Option<String>,
-
     /// Base URL for Graph API (for testing with mock servers).
     pub graph_api_url: String,
 
@@ -125,20 +155,28 @@ pub struct Config {
 
     /// Maximum cache size.
     pub cache_max_size: u64,
+
+    /// Maximum requests in flight at once; further requests wait.
+    pub max_connections: usize,
+
+    /// Idle connections kept per host.
+    pub max_keepalive: usize,
+
+    /// How long idle connections are kept.
+    pub keepalive_expiry: Duration,
 }
 
-impl Config {
+impl ClientConfig {
     /// Create a new configuration with optional API key.
     ///
     /// Rate limits are automatically adjusted based on API key presence:
     /// - Without key: 5 req/s normal, 1 req/s batch
     /// - With key: 100 req/s normal, 10 req/s batch
     #[must_use]
-    pub fn new(api_key: Option<String>, auth_token: Option<String>) -> Self {
+    pub fn new(api_key: Option<String>) -> Self {
         let has_key = api_key.is_some();
         Self {
             api_key,
-            auth_token,
             graph_api_url: api::GRAPH_API.to_string(),
             recommendations_api_url: api::RECOMMENDATIONS_API.to_string(),
             request_timeout: api::REQUEST_TIMEOUT,
@@ -155,6 +193,9 @@ impl Config {
             },
             cache_ttl: api::CACHE_TTL,
             cache_max_size: api::CACHE_MAX_SIZE,
+            max_connections: api::MAX_CONNECTIONS,
+            max_keepalive: api::MAX_KEEPALIVE,
+            keepalive_expiry: api::KEEPALIVE_EXPIRY,
         }
     }
 
@@ -163,7 +204,6 @@ impl Config {
     pub fn for_testing(base_url: &str) -> Self {
         Self {
             api_key: None,
-            auth_token: None,
             graph_api_url: format!("{}/graph/v1", base_url),
             recommendations_api_url: format!("{}/recommendations/v1", base_url),
             request_timeout: Duration::from_secs(5),
@@ -172,20 +212,12 @@ impl Config {
             batch_rate_limit_delay: Duration::from_millis(0),
             cache_ttl: Duration::from_secs(0), // No caching in tests
             cache_max_size: 0,
+            max_connections: api::MAX_CONNECTIONS,
+            max_keepalive: api::MAX_KEEPALIVE,
+            keepalive_expiry: api::KEEPALIVE_EXPIRY,
         }
     }
 
-    /// Create configuration from environment variables.
-    ///
-    /// # Errors
-    ///
-    /// Returns error if environment variables are invalid.
-    pub fn from_env() -> anyhow::Result<Self> {
-        let api_key = std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok();
-        let auth_token = std::env::var("MCP_SERVER_AUTH_TOKEN").ok();
-        Ok(Self::new(api_key, auth_token))
-    }
-
     /// Check if an API key is configured.
     #[must_use]
     pub const fn has_api_key(&self) -> bool {
@@ -193,9 +225,9 @@ impl Config {
     }
 }
 
-impl Default for Config {
+impl Default for ClientConfig {
     fn default() -> Self {
-        Self::new(None, None)
+        Self::new(None)
     }
 }
 
@@ -203,18 +235,12 @@ impl Default for Config {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_config_default() {
-        let config = Config::default();
-        assert!(config.api_key.is_none());
-        assert!(!config.has_api_key());
-    }
-
     #[test]
     fn test_config_with_api_key() {
-        let config = Config::new(Some("test-key".to_string()), None);
+        let config = ClientConfig::new(Some("test-key".to_string()));
         assert!(config.has_api_key());
-        assert_eq!(config.api_key, Some("test-key".to_string()));
+        assert_eq!(config.rate_limit_delay, api::RATE_LIMIT_DELAY_WITH_KEY);
+        assert!(!ClientConfig::default().has_api_key());
     }
 
     #[test]
@@ -0,0 +1,164 @@
+//! Error types for the Semantic Scholar API client.
+//!
+//! Uses `thiserror` for structured error handling with automatic `From` implementations.
+
+use std::error::Error;
+use std::time::Duration;
+
+use crate::client::quota::QuotaExceeded;
+
+/// Errors from the HTTP client layer.
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    /// Response body error (e.g. JSON deserialization via reqwest)
+    #[error("Response error: {0}")]
+    Response(#[from] reqwest::Error),
+
+    /// Middleware error
+    #[error("Middleware error: {0}")]
+    Middleware(#[from] reqwest_middleware::Error),
+
+    /// Rate limited by Semantic Scholar API (429 response)
+    #[error("Rate limited, retry after {retry_after:?}")]
+    RateLimited {
+        /// Suggested wait time before retry
+        retry_after: Duration,
+    },
+
+    /// Resource not found (404 response)
+    #[error("Resource not found: {resource}")]
+    NotFound {
+        /// Description of the missing resource
+        resource: String,
+    },
+
+    /// Invalid request parameters (400 response)
+    #[error("Bad request: {message}")]
+    BadRequest {
+        /// Error message from API
+        message: String,
+    },
+
+    /// Request timeout
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// JSON parsing error
+    #[error("Failed to parse response: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    /// Server error (5xx response)
+    #[error("Server error ({status}): {message}")]
+    Server {
+        /// HTTP status code
+        status: u16,
+        /// Error message
+        message: String,
+    },
+
+    /// Unexpected HTTP status
+    #[error("Unexpected status {status}: {message}")]
+    UnexpectedStatus {
+        /// HTTP status code
+        status: u16,
+        /// Response body or message
+        message: String,
+    },
+
+    /// This server's per-client quota is spent (no request was sent)
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(QuotaExceeded),
+
+    /// The client could not be built from its configuration
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+}
+
+impl ClientError {
+    /// Create a rate limited error with retry-after duration.
+    #[must_use]
+    pub fn rate_limited(seconds: u64) -> Self {
+        Self::RateLimited { retry_after: Duration::from_secs(seconds) }
+    }
+
+    /// Create a not found error.
+    #[must_use]
+    pub fn not_found(resource: impl Into<String>) -> Self {
+        Self::NotFound { resource: resource.into() }
+    }
+
+    /// Create a bad request error.
+    #[must_use]
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::BadRequest { message: message.into() }
+    }
+
+    /// Create a server error.
+    #[must_use]
+    pub fn server(status: u16, message: impl Into<String>) -> Self {
+        Self::Server { status, message: message.into() }
+    }
+
+    /// Returns true if this error is retryable.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::Timeout(_) | Self::Server { .. } => true,
+            Self::Middleware(e) => {
+                // reqwest-retry wraps errors through RetryError -> anyhow -> Middleware,
+                // so walk the full source chain to find the underlying reqwest::Error
+                let mut source: Option<&(dyn Error + 'static)> = Some(e);
+                while let Some(err) = source {
+                    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
+                        return reqwest_err.is_timeout() || reqwest_err.is_connect();
+                    }
+                    source = err.source();
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the retry-after duration if this is a rate limit error.
+    #[must_use]
+    pub const fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } => Some(*retry_after),
+            Self::QuotaExceeded(exceeded) => Some(exceeded.retry_after),
+            _ => None,
+        }
+    }
+}
+
+/// Result type alias for client operations.
+pub type ClientResult<T> = Result<T, ClientError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_error_retryable() {
+        assert!(ClientError::rate_limited(60).is_retryable());
+        assert!(ClientError::Timeout(Duration::from_secs(30)).is_retryable());
+        assert!(ClientError::server(500, "Internal error").is_retryable());
+
+        assert!(!ClientError::not_found("paper123").is_retryable());
+        assert!(!ClientError::bad_request("invalid query").is_retryable());
+
+        // Middleware-wrapped non-reqwest errors are not retryable
+        let middleware_err =
+            reqwest_middleware::Error::Middleware(anyhow::anyhow!("some other error"));
+        assert!(!ClientError::Middleware(middleware_err).is_retryable());
+    }
+
+    #[test]
+    fn test_client_error_retry_after() {
+        let err = ClientError::rate_limited(60);
+        assert_eq!(err.retry_after(), Some(Duration::from_mins(1)));
+
+        let err = ClientError::not_found("paper");
+        assert_eq!(err.retry_after(), None);
+    }
+}
@@ -0,0 +1,42 @@
+//! Semantic Scholar API client
+//!
+//! An async client for the Semantic Scholar Graph and Recommendations APIs,
+//! with the API's data models. It is the library the MCP server in
+//! `semantic-scholar-mcp` is built on, usable on its own without the server,
+//! its tools or its OAuth stack.
+//!
+//! # Features
+//!
+//! - **Rate-limited**: Respects Semantic Scholar API limits, higher with a key
+//! - **Retried**: Transient failures back off exponentially
+//! - **Cached**: 5-minute TTL cache reduces API calls
+//! - **Self-contained**: No global state; each client owns its connection
+//!   pool, cache, rate limiters and Prometheus registry
+//!
+//! # Example
+//!
+//! ```no_run
+//! use semantic_scholar_client::{SemanticScholarClient, config::fields};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), semantic_scholar_client::ClientError> {
+//!     // Add `.api_key(...)` for the higher rate limits of a key.
+//!     let client = SemanticScholarClient::builder().build()?;
+//!
+//!     let results = client.search_papers("attention", 0, 10, fields::DEFAULT, &[]).await?;
+//!     for paper in results.data {
+//!         println!("{}", paper.title_or_default());
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod metrics;
+pub mod models;
+
+pub use client::{SemanticScholarClient, SemanticScholarClientBuilder};
+pub use config::ClientConfig;
+pub use error::{ClientError, ClientResult};
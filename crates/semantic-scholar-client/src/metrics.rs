@@ -0,0 +1,181 @@
+//! Prometheus metrics for upstream traffic.
+//!
+//! Each [`SemanticScholarClient`](crate::client::SemanticScholarClient) owns
+//! a [`ClientMetrics`] registry (shared by its tenant handles) that records
+//! its requests, retries, cache lookups and rate-limit waits. Nothing is
+//! registered globally, so several clients can run in one process; an
+//! application exposes them by encoding the registry alongside its own.
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+
+/// Buckets (seconds) for upstream request durations.
+const DURATION_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// Buckets (seconds) for time spent waiting for a rate-limit slot.
+const WAIT_BUCKETS: &[f64] = &[0.001, 0.01, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// Path segments of the Semantic Scholar API kept in the `endpoint` label;
+/// anything else (paper and author IDs) becomes `{id}`.
+const ENDPOINT_WORDS: &[&str] = &[
+    "paper",
+    "papers",
+    "author",
+    "authors",
+    "search",
+    "bulk",
+    "match",
+    "batch",
+    "autocomplete",
+    "citations",
+    "references",
+    "snippet",
+    "forpaper",
+];
+
+/// Metric families of one client.
+#[derive(Debug, Clone)]
+pub struct ClientMetrics {
+    registry: Registry,
+    /// Requests sent to Semantic Scholar by `endpoint` and `status` (HTTP
+    /// status, or `error` when no response arrived). Retries count again.
+    pub upstream_requests: IntCounterVec,
+    /// Upstream request duration by `endpoint`.
+    pub upstream_duration: HistogramVec,
+    /// Retried upstream requests.
+    pub upstream_retries: IntCounter,
+    /// Response cache lookups by `result` (`hit`, `miss`).
+    pub cache_lookups: IntCounterVec,
+    /// Time spent waiting for a rate-limit slot by `queue` (`normal`, `batch`).
+    pub rate_limit_wait: HistogramVec,
+}
+
+impl ClientMetrics {
+    /// Create and register every metric family.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: names and labels are static and valid.
+    #[must_use]
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let counter = |name: &str, help: &str, labels: &[&str]| {
+            let metric = IntCounterVec::new(Opts::new(name, help), labels).unwrap();
+            registry.register(Box::new(metric.clone())).unwrap();
+            metric
+        };
+        let histogram = |name: &str, help: &str, labels: &[&str], buckets: &[f64]| {
+            let opts = HistogramOpts::new(name, help).buckets(buckets.to_vec());
+            let metric = HistogramVec::new(opts, labels).unwrap();
+            registry.register(Box::new(metric.clone())).unwrap();
+            metric
+        };
+
+        let upstream_retries =
+            IntCounter::new("s2_upstream_retries_total", "Retried Semantic Scholar requests")
+                .unwrap();
+        registry.register(Box::new(upstream_retries.clone())).unwrap();
+
+        Self {
+            upstream_requests: counter(
+                "s2_upstream_requests_total",
+                "Requests sent to Semantic Scholar by endpoint and status",
+                &["endpoint", "status"],
+            ),
+            upstream_duration: histogram(
+                "s2_upstream_request_duration_seconds",
+                "Semantic Scholar request duration",
+                &["endpoint"],
+                DURATION_BUCKETS,
+            ),
+            upstream_retries,
+            cache_lookups: counter(
+                "s2_cache_lookups_total",
+                "Response cache lookups by result",
+                &["result"],
+            ),
+            rate_limit_wait: histogram(
+                "s2_rate_limit_wait_seconds",
+                "Time spent waiting for a rate-limit slot",
+                &["queue"],
+                WAIT_BUCKETS,
+            ),
+            registry,
+        }
+    }
+
+    /// Registry holding these families, for applications that gather it
+    /// together with their own.
+    #[must_use]
+    pub const fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Everything in the Prometheus text exposition format.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!(error = %e, "Failed to encode metrics");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for ClientMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Low-cardinality `endpoint` label for an API URL path, e.g.
+/// `/graph/v1/paper/DOI:10.1/x/citations` becomes `/paper/{id}/citations`.
+#[must_use]
+pub fn endpoint_label(path: &str) -> String {
+    let path = path
+        .strip_prefix("/graph/v1")
+        .or_else(|| path.strip_prefix("/recommendations/v1"))
+        .unwrap_or(path);
+    let mut label = String::new();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        let segment = if ENDPOINT_WORDS.contains(&segment) { segment } else { "{id}" };
+        // DOIs contain slashes: collapse them into one `{id}`.
+        if segment == "{id}" && label.ends_with("{id}") {
+            continue;
+        }
+        label.push('/');
+        label.push_str(segment);
+    }
+    if label.is_empty() { "/".to_owned() } else { label }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_label() {
+        assert_eq!(endpoint_label("/graph/v1/paper/search"), "/paper/search");
+        assert_eq!(
+            endpoint_label("/graph/v1/paper/DOI:10.1/abc/citations"),
+            "/paper/{id}/citations"
+        );
+        assert_eq!(endpoint_label("/graph/v1/author/123/papers"), "/author/{id}/papers");
+        assert_eq!(
+            endpoint_label("/recommendations/v1/papers/forpaper/abc"),
+            "/papers/forpaper/{id}"
+        );
+    }
+
+    #[test]
+    fn test_encode_includes_labels() {
+        let metrics = ClientMetrics::new();
+        metrics.upstream_requests.with_label_values(&["/paper/search", "429"]).inc();
+
+        let text = metrics.encode();
+        assert!(
+            text.contains(r#"s2_upstream_requests_total{endpoint="/paper/search",status="429"} 1"#)
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Mock Semantic Scholar API - Entry Point
+//!
+//! Serves the Graph and Recommendations endpoints from fixtures on disk so
+//! the MCP server, its tests and demos run without network access.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing_subscriber::EnvFilter;
+
+use semantic_scholar_mcp::mock::{self, Dataset, Faults, MockOptions};
+
+#[derive(Parser, Debug)]
+#[command(name = "semantic-scholar-mock")]
+#[command(about = "Mock Semantic Scholar API serving fixtures, with injectable faults")]
+#[command(version)]
+struct Cli {
+    /// Directory with papers.json and optionally authors.json (default: bundled sample)
+    #[arg(long, env = "MOCK_FIXTURES")]
+    fixtures: Option<PathBuf>,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: std::net::IpAddr,
+
+    /// Port to listen on
+    #[arg(long, default_value_t = mock::DEFAULT_PORT, env = "PORT")]
+    port: u16,
+
+    /// Papers per bulk search page (the real API returns 1000)
+    #[arg(long, default_value_t = mock::DEFAULT_BULK_PAGE_SIZE)]
+    bulk_page_size: usize,
+
+    /// Answer every Nth API request with 429
+    #[arg(long)]
+    rate_limit_every: Option<u64>,
+
+    /// Probability (0-1) of answering an API request with 429
+    #[arg(long, default_value_t = 0.0)]
+    rate_limit_probability: f64,
+
+    /// Retry-After seconds to send with injected 429s
+    #[arg(long)]
+    retry_after: Option<u64>,
+
+    /// Answer every Nth API request with --error-status
+    #[arg(long)]
+    error_every: Option<u64>,
+
+    /// Probability (0-1) of answering an API request with --error-status
+    #[arg(long, default_value_t = 0.0)]
+    error_probability: f64,
+
+    /// Status of injected server errors
+    #[arg(long, default_value_t = 500)]
+    error_status: u16,
+
+    /// Delay added to every API request, in milliseconds
+    #[arg(long, default_value_t = 0)]
+    latency_ms: u64,
+
+    /// Only inject faults into requests whose path starts with this
+    #[arg(long)]
+    fault_path: Option<String>,
+
+    /// Seed for probabilistic faults
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+
+    /// Log level: trace, debug, info, warn, error
+    #[arg(long, env = "RUST_LOG", default_value = "info")]
+    log_level: String,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&cli.log_level));
+    tracing_subscriber::fmt().with_env_filter(filter).compact().init();
+
+    let data = match &cli.fixtures {
+        Some(dir) => Dataset::load(dir)?,
+        None => Dataset::sample(),
+    };
+    let options = MockOptions {
+        faults: Faults {
+            rate_limit_every: cli.rate_limit_every,
+            rate_limit_probability: cli.rate_limit_probability,
+            retry_after_secs: cli.retry_after,
+            error_every: cli.error_every,
+            error_probability: cli.error_probability,
+            error_status: cli.error_status,
+            latency_ms: cli.latency_ms,
+            path_prefix: cli.fault_path,
+            seed: cli.seed,
+        },
+        bulk_page_size: cli.bulk_page_size,
+    };
+
+    let addr = SocketAddr::new(cli.host, cli.port);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(
+        papers = data.paper_count(),
+        authors = data.author_count(),
+        "Mock Semantic Scholar API on http://{addr} (graph: /graph/v1, recommendations: /recommendations/v1)"
+    );
+    axum::serve(listener, mock::router(data, options))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
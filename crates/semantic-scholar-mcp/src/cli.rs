@@ -0,0 +1,139 @@
+//! Running tools from the command line, without an MCP client.
+//!
+//! Backs the `list-tools`, `schema`, `call` and `batch` subcommands. Calls
+//! go through the same steps as `tools/call`: configured tool defaults are
+//! filled in, the arguments are validated against the input schema, and the
+//! tool runs with a shared [`ToolContext`].
+//!
+//! A batch reads one call per line:
+//!
+//! ```json
+//! {"id": 1, "tool": "prisma_search", "arguments": {"query": "..."}}
+//! ```
+//!
+//! and writes one result per line, in input order, echoing `id` and `tool`
+//! and carrying either the tool's structured `result` or an `error` object
+//! shaped like the MCP error data (`code`, `message`, ...). Blank lines are
+//! skipped; a line that is not a valid call gets an error result with its
+//! `line` number.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::Instrument;
+
+use crate::error::{ToolError, ToolResult};
+use crate::telemetry;
+use crate::tools::{McpTool, ToolContext, ToolOutput};
+
+/// Find the tool called `name`.
+///
+/// # Errors
+///
+/// Returns [`ToolError::NotFound`] if there is no such tool.
+pub fn find_tool<'a>(tools: &'a [Box<dyn McpTool>], name: &str) -> ToolResult<&'a dyn McpTool> {
+    tools
+        .iter()
+        .find(|t| t.name() == name)
+        .map(AsRef::as_ref)
+        .ok_or_else(|| ToolError::not_found(format!("tool '{name}'")))
+}
+
+/// Run one tool call: apply defaults, validate, execute.
+///
+/// # Errors
+///
+/// Returns an error if the tool does not exist, the arguments are invalid,
+/// or the tool fails.
+pub async fn call_tool(
+    tools: &[Box<dyn McpTool>],
+    ctx: &ToolContext,
+    name: &str,
+    arguments: Value,
+) -> ToolResult<ToolOutput> {
+    let tool = find_tool(tools, name)?;
+    let arguments = ctx.tool_defaults().apply(name, arguments);
+    tool.validate_input(&arguments)?;
+    tool.execute(ctx, arguments).instrument(telemetry::tool_span(name)).await
+}
+
+/// One line of batch input.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchCall {
+    #[serde(default)]
+    id: Option<Value>,
+    tool: String,
+    #[serde(default = "empty_arguments")]
+    arguments: Value,
+}
+
+fn empty_arguments() -> Value {
+    json!({})
+}
+
+/// Outcome of [`run_batch`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// Calls read, including malformed lines.
+    pub calls: usize,
+    /// Calls that produced an error result.
+    pub failed: usize,
+}
+
+/// Run the JSONL tool calls read from `input` one after another, writing a
+/// JSONL result for each to `output`.
+///
+/// Each result is flushed as soon as it is written, so a long batch can be
+/// followed as it runs. Failed calls do not stop the batch.
+///
+/// # Errors
+///
+/// Returns an error only if reading `input` or writing `output` fails.
+pub async fn run_batch<R, W>(
+    tools: &[Box<dyn McpTool>],
+    ctx: &ToolContext,
+    input: R,
+    mut output: W,
+) -> std::io::Result<BatchSummary>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut summary = BatchSummary::default();
+    let mut lines = input.lines();
+    let mut number = 0;
+    while let Some(line) = lines.next_line().await? {
+        number += 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        summary.calls += 1;
+        let result = match serde_json::from_str::<BatchCall>(&line) {
+            Ok(call) => {
+                tracing::info!(line = number, tool = %call.tool, "Executing tool");
+                let mut result = json!({"id": call.id, "tool": call.tool});
+                match call_tool(tools, ctx, &call.tool, call.arguments).await {
+                    Ok(out) => result["result"] = out.structured,
+                    Err(e) => {
+                        tracing::warn!(line = number, tool = %call.tool, error = %e, "Tool call failed");
+                        result["error"] = e.error_data();
+                    }
+                }
+                result
+            }
+            Err(e) => json!({
+                "line": number,
+                "error": {"code": crate::error::codes::VALIDATION, "message": format!("invalid call: {e}")},
+            }),
+        };
+        if result.get("error").is_some() {
+            summary.failed += 1;
+        }
+        let mut text = result.to_string();
+        text.push('\n');
+        output.write_all(text.as_bytes()).await?;
+        output.flush().await?;
+    }
+    Ok(summary)
+}
@@ -1,9 +0,0 @@
-//! Middleware for HTTP client (rate limiting, caching).
-//!
-//! This module provides additional middleware that can be composed
-//! with the reqwest client.
-
-// Placeholder for future middleware implementations.
-// The main rate limiting and caching is currently handled directly
-// in the client module. This file is reserved for more sophisticated
-// middleware patterns using Tower.
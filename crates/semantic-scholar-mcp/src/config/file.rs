@@ -0,0 +1,605 @@
+//! Layered TOML configuration file.
+//!
+//! ```toml
+//! profile = "laptop"        # used unless --profile is given
+//!
+//! [client]
+//! request_timeout_secs = 60
+//! cache_ttl_secs = 600
+//!
+//! [tools.exhaustive_search]
+//! maxResults = 200
+//! fieldsOfStudy = ["Computer Science"]
+//!
+//! [profiles.server.client]
+//! cache_max_size = 20000
+//! ```
+//!
+//! Settings apply in layers: built-in defaults, the file's top level, the
+//! selected profile, `S2_MCP__SECTION__KEY` environment variables, then the
+//! command line (`--api-key`, and `--set client.cache_ttl_secs=60` for any
+//! other key). Tool defaults fill in arguments a call leaves out and are
+//! checked against the tool's input schema when loaded. Errors name the
+//! offending key, e.g. `profiles.server.client.cache_ttl_secs`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::{ClientConfig, Config};
+use crate::error::ToolError;
+use crate::tools::{McpTool, validate_arguments};
+
+/// Environment variables starting with this set any key, with `__` between
+/// the parts: `S2_MCP__CLIENT__CACHE_TTL_SECS=60`.
+pub const ENV_PREFIX: &str = "S2_MCP__";
+
+/// Client settings. Unset fields keep the built-in defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientSettings {
+    /// Semantic Scholar API key; `SEMANTIC_SCHOLAR_API_KEY` and `--api-key`
+    /// take precedence.
+    pub api_key: Option<String>,
+    pub graph_api_url: Option<String>,
+    pub recommendations_api_url: Option<String>,
+    pub request_timeout_secs: Option<u64>,
+    pub connect_timeout_secs: Option<u64>,
+    /// Delay between requests; 0 disables rate limiting. The default
+    /// depends on whether an API key is set.
+    pub rate_limit_ms: Option<u64>,
+    /// Delay between batch requests.
+    pub batch_rate_limit_ms: Option<u64>,
+    /// Response cache TTL; 0 disables caching.
+    pub cache_ttl_secs: Option<u64>,
+    /// Maximum cached responses.
+    pub cache_max_size: Option<u64>,
+    /// Maximum requests in flight at once.
+    pub max_connections: Option<usize>,
+    /// Idle connections kept per host.
+    pub max_keepalive: Option<usize>,
+    pub keepalive_expiry_secs: Option<u64>,
+}
+
+/// Replace `low` with `high` if `high` is set.
+fn layer<T>(low: &mut Option<T>, high: Option<T>) {
+    if high.is_some() {
+        *low = high;
+    }
+}
+
+impl ClientSettings {
+    /// Overlay the fields `over` sets.
+    fn merge(&mut self, over: Self) {
+        layer(&mut self.api_key, over.api_key);
+        layer(&mut self.graph_api_url, over.graph_api_url);
+        layer(&mut self.recommendations_api_url, over.recommendations_api_url);
+        layer(&mut self.request_timeout_secs, over.request_timeout_secs);
+        layer(&mut self.connect_timeout_secs, over.connect_timeout_secs);
+        layer(&mut self.rate_limit_ms, over.rate_limit_ms);
+        layer(&mut self.batch_rate_limit_ms, over.batch_rate_limit_ms);
+        layer(&mut self.cache_ttl_secs, over.cache_ttl_secs);
+        layer(&mut self.cache_max_size, over.cache_max_size);
+        layer(&mut self.max_connections, over.max_connections);
+        layer(&mut self.max_keepalive, over.max_keepalive);
+        layer(&mut self.keepalive_expiry_secs, over.keepalive_expiry_secs);
+    }
+
+    fn validate(&self, prefix: &str) -> anyhow::Result<()> {
+        if self.api_key.as_deref().is_some_and(str::is_empty) {
+            bail!("{prefix}client.api_key: must not be empty");
+        }
+        for (key, url) in [
+            ("graph_api_url", &self.graph_api_url),
+            ("recommendations_api_url", &self.recommendations_api_url),
+        ] {
+            let Some(url) = url else { continue };
+            match url::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                _ => bail!("{prefix}client.{key}: expected an http(s) URL, got {url:?}"),
+            }
+        }
+        for (key, secs) in [
+            ("request_timeout_secs", self.request_timeout_secs),
+            ("connect_timeout_secs", self.connect_timeout_secs),
+        ] {
+            if secs == Some(0) {
+                bail!("{prefix}client.{key}: must be greater than 0");
+            }
+        }
+        if self.max_connections == Some(0) {
+            bail!("{prefix}client.max_connections: must be greater than 0");
+        }
+        Ok(())
+    }
+
+    /// Set the fields given here on `config`.
+    fn apply(&self, config: &mut ClientConfig) {
+        if let Some(ref url) = self.graph_api_url {
+            url.trim_end_matches('/').clone_into(&mut config.graph_api_url);
+        }
+        if let Some(ref url) = self.recommendations_api_url {
+            url.trim_end_matches('/').clone_into(&mut config.recommendations_api_url);
+        }
+        let secs = |s: Option<u64>| s.map(Duration::from_secs);
+        let millis = |ms: Option<u64>| ms.map(Duration::from_millis);
+        config.request_timeout = secs(self.request_timeout_secs).unwrap_or(config.request_timeout);
+        config.connect_timeout = secs(self.connect_timeout_secs).unwrap_or(config.connect_timeout);
+        config.rate_limit_delay = millis(self.rate_limit_ms).unwrap_or(config.rate_limit_delay);
+        config.batch_rate_limit_delay =
+            millis(self.batch_rate_limit_ms).unwrap_or(config.batch_rate_limit_delay);
+        config.cache_ttl = secs(self.cache_ttl_secs).unwrap_or(config.cache_ttl);
+        config.cache_max_size = self.cache_max_size.unwrap_or(config.cache_max_size);
+        config.max_connections = self.max_connections.unwrap_or(config.max_connections);
+        config.max_keepalive = self.max_keepalive.unwrap_or(config.max_keepalive);
+        config.keepalive_expiry =
+            secs(self.keepalive_expiry_secs).unwrap_or(config.keepalive_expiry);
+    }
+}
+
+/// Settings a profile or `--set` can change.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Layer {
+    #[serde(default)]
+    pub client: ClientSettings,
+    /// Default arguments keyed by tool name.
+    #[serde(default)]
+    pub tools: BTreeMap<String, toml::Table>,
+}
+
+/// A configuration file as written.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Profile to use when none is given on the command line.
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub client: ClientSettings,
+    #[serde(default)]
+    pub tools: BTreeMap<String, toml::Table>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Layer>,
+    /// Where the file was read from, for error messages.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+impl ConfigFile {
+    /// Read a configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid TOML of
+    /// the expected shape; parse errors give the line and key.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("cannot read config file {}: {e}", path.display()))?;
+        let file = Self::parse(&text)
+            .map_err(|e| anyhow!("invalid config file {}: {e}", path.display()))?;
+        Ok(Self { path: Some(path.to_owned()), ..file })
+    }
+
+    /// Parse configuration from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// Returns the TOML error, which points at the offending line and key.
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+}
+
+/// Default arguments per tool, filled in where a call leaves them out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolDefaults(HashMap<String, Map<String, Value>>);
+
+impl ToolDefaults {
+    /// Defaults for `tool`, if any.
+    #[must_use]
+    pub fn get(&self, tool: &str) -> Option<&Map<String, Value>> {
+        self.0.get(tool)
+    }
+
+    /// Set (or replace) one default argument.
+    pub fn insert(&mut self, tool: impl Into<String>, argument: impl Into<String>, value: Value) {
+        self.0.entry(tool.into()).or_default().insert(argument.into(), value);
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Fill in the defaults `arguments` leaves out.
+    #[must_use]
+    pub fn apply(&self, tool: &str, arguments: Value) -> Value {
+        let Some(defaults) = self.0.get(tool) else {
+            return arguments;
+        };
+        match arguments {
+            Value::Object(mut arguments) => {
+                for (key, value) in defaults {
+                    arguments.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+                Value::Object(arguments)
+            }
+            Value::Null => Value::Object(defaults.clone()),
+            other => other,
+        }
+    }
+
+    /// Advertise the defaults in `tool`'s input schema: they replace the
+    /// built-in `default`, and a defaulted argument is no longer required.
+    pub fn annotate_schema(&self, tool: &str, schema: &mut Value) {
+        let Some(defaults) = self.0.get(tool) else {
+            return;
+        };
+        if let Some(Value::Array(required)) = schema.get_mut("required") {
+            required.retain(|r| r.as_str().is_none_or(|r| !defaults.contains_key(r)));
+        }
+        let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) else {
+            return;
+        };
+        for (key, value) in defaults {
+            if let Some(property) = properties.get_mut(key).and_then(Value::as_object_mut) {
+                property.insert("default".into(), value.clone());
+            }
+        }
+    }
+
+    /// Check `layer`'s defaults against the tools' input schemas and add
+    /// them. `prefix` locates the layer in error messages.
+    fn merge(
+        &mut self,
+        prefix: &str,
+        layer: BTreeMap<String, toml::Table>,
+        tools: &[Box<dyn McpTool>],
+    ) -> anyhow::Result<()> {
+        for (name, arguments) in layer {
+            let key = format!("{prefix}tools.{name}");
+            let Some(tool) = tools.iter().find(|t| t.name() == name) else {
+                bail!("{key}: unknown tool");
+            };
+            let schema = tool.input_schema();
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (argument, value) in arguments {
+                let key = format!("{key}.{argument}");
+                let Some(property) = properties.and_then(|p| p.get(&argument)) else {
+                    bail!("{key}: {name} has no such argument");
+                };
+                let value = serde_json::to_value(value).map_err(|e| anyhow!("{key}: {e}"))?;
+                validate_arguments(property, &value).map_err(|e| match e {
+                    ToolError::Validation { field, message } if field == "arguments" => {
+                        anyhow!("{key}: {message}")
+                    }
+                    ToolError::Validation { field, message } if field.starts_with('[') => {
+                        anyhow!("{key}{field}: {message}")
+                    }
+                    ToolError::Validation { field, message } => {
+                        anyhow!("{key}.{field}: {message}")
+                    }
+                    other => anyhow!("{key}: {other}"),
+                })?;
+                self.insert(name.clone(), argument, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resolved settings: everything but the API key and auth token, which the
+/// caller layers on from the environment and command line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Settings {
+    /// The profile applied, if any.
+    pub profile: Option<String>,
+    pub client: ClientSettings,
+    pub tool_defaults: ToolDefaults,
+}
+
+impl Settings {
+    /// Load `path` (if given) and resolve it with [`resolve`](Self::resolve)
+    /// and the process environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the file and offending key.
+    pub fn load(
+        path: Option<&Path>,
+        profile: Option<&str>,
+        overrides: &[String],
+        tools: &[Box<dyn McpTool>],
+    ) -> anyhow::Result<Self> {
+        let file = path.map(ConfigFile::load).transpose()?.unwrap_or_default();
+        let env: Vec<(String, String)> = std::env::vars().collect();
+        Self::resolve(&file, profile, &env, overrides, tools)
+    }
+
+    /// Layer `file`'s top level, the profile (`profile`, or else the file's
+    /// `profile` key), the [`ENV_PREFIX`] variables in `env` and `overrides`
+    /// (`key=value`, the value in TOML syntax or a bare string), validating
+    /// each layer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the offending key, e.g.
+    /// `profiles.server.tools.exhaustive_search.maxResults: must be at most 1000`.
+    pub fn resolve(
+        file: &ConfigFile,
+        profile: Option<&str>,
+        env: &[(String, String)],
+        overrides: &[String],
+        tools: &[Box<dyn McpTool>],
+    ) -> anyhow::Result<Self> {
+        // Keys from the file are reported as `path: key`.
+        let origin = file.path.as_ref().map(|p| format!("{}: ", p.display())).unwrap_or_default();
+        let mut layers = vec![(
+            origin.clone(),
+            Layer { client: file.client.clone(), tools: file.tools.clone() },
+        )];
+
+        let (profile, source) = if profile.is_some() {
+            (profile, "--profile".to_owned())
+        } else {
+            (file.profile.as_deref(), format!("{origin}profile"))
+        };
+        if let Some(name) = profile {
+            let Some(layer) = file.profiles.get(name) else {
+                let defined: Vec<&str> = file.profiles.keys().map(String::as_str).collect();
+                bail!(
+                    "{source}: unknown profile {name:?} (defined: {})",
+                    if defined.is_empty() { "none".to_owned() } else { defined.join(", ") }
+                );
+            };
+            layers.push((format!("{origin}profiles.{name}."), layer.clone()));
+        }
+
+        layers.extend(env_layers(env, tools)?);
+        for arg in overrides {
+            layers.push(("--set ".to_owned(), parse_override(arg)?));
+        }
+
+        let mut settings = Self { profile: profile.map(str::to_owned), ..Self::default() };
+        for (prefix, layer) in layers {
+            layer.client.validate(&prefix)?;
+            settings.client.merge(layer.client);
+            settings.tool_defaults.merge(&prefix, layer.tools, tools)?;
+        }
+        Ok(settings)
+    }
+
+    /// The client configuration: `api_key` (from the environment or command
+    /// line) wins over the file's.
+    #[must_use]
+    pub fn config(&self, api_key: Option<String>, auth_token: Option<String>) -> Config {
+        let mut config = Config::new(api_key.or_else(|| self.client.api_key.clone()), auth_token);
+        self.client.apply(&mut config.client);
+        config
+    }
+}
+
+/// Parse `--set key=value` into a layer holding just that key.
+fn parse_override(arg: &str) -> anyhow::Result<Layer> {
+    let Some((key, raw)) = arg.split_once('=') else {
+        bail!("--set {arg}: expected KEY=VALUE");
+    };
+    let key = key.trim();
+    parse_setting(key, raw).map_err(|e| anyhow!("--set {key}: {}", e.message()))
+}
+
+/// The [`ENV_PREFIX`] variables in `env` as layers, in name order.
+/// `S2_MCP__CLIENT__CACHE_TTL_SECS=60` is `--set client.cache_ttl_secs=60`;
+/// names are not case-sensitive, so `S2_MCP__TOOLS__EXHAUSTIVE_SEARCH__MAXRESULTS`
+/// sets `maxResults`.
+fn env_layers(
+    env: &[(String, String)],
+    tools: &[Box<dyn McpTool>],
+) -> anyhow::Result<Vec<(String, Layer)>> {
+    let mut vars: Vec<(&str, &str, &str)> = env
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((name.as_str(), name.strip_prefix(ENV_PREFIX)?, value.as_str()))
+        })
+        .collect();
+    vars.sort_unstable();
+    vars.into_iter()
+        .map(|(name, key, raw)| {
+            let mut parts: Vec<String> = key.split("__").map(str::to_ascii_lowercase).collect();
+            if let [section, tool, argument] = parts.as_mut_slice()
+                && section == "tools"
+                && let Some(tool) = tools.iter().find(|t| t.name() == tool)
+            {
+                let schema = tool.input_schema();
+                let properties = schema.get("properties").and_then(Value::as_object);
+                if let Some(name) =
+                    properties.and_then(|p| p.keys().find(|k| k.eq_ignore_ascii_case(argument)))
+                {
+                    name.clone_into(argument);
+                }
+            }
+            let layer = parse_setting(&parts.join("."), raw)
+                .map_err(|e| anyhow!("{name}: {}", e.message()))?;
+            Ok((format!("{name}: "), layer))
+        })
+        .collect()
+}
+
+/// A layer holding just the dotted `key`, set to `raw` read as a TOML value
+/// or else as a bare string.
+fn parse_setting(key: &str, raw: &str) -> Result<Layer, toml::de::Error> {
+    let value = toml::from_str::<toml::Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_owned()));
+    let mut node = value;
+    for part in key.rsplit('.') {
+        node = toml::Value::Table(toml::Table::from_iter([(part.to_owned(), node)]));
+    }
+    node.try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tools::register_all_tools;
+
+    fn resolve(text: &str, profile: Option<&str>, overrides: &[&str]) -> anyhow::Result<Settings> {
+        resolve_with_env(text, profile, &[], overrides)
+    }
+
+    fn resolve_with_env(
+        text: &str,
+        profile: Option<&str>,
+        env: &[(&str, &str)],
+        overrides: &[&str],
+    ) -> anyhow::Result<Settings> {
+        let file = ConfigFile::parse(text)?;
+        let env: Vec<(String, String)> =
+            env.iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned())).collect();
+        let overrides: Vec<String> = overrides.iter().map(|s| (*s).to_owned()).collect();
+        Settings::resolve(&file, profile, &env, &overrides, &register_all_tools())
+    }
+
+    const FILE: &str = r#"
+        profile = "laptop"
+
+        [client]
+        request_timeout_secs = 60
+        cache_ttl_secs = 600
+
+        [tools.exhaustive_search]
+        maxResults = 50
+
+        [profiles.laptop.client]
+        cache_max_size = 100
+
+        [profiles.server.client]
+        cache_max_size = 20000
+        rate_limit_ms = 10
+
+        [profiles.server.tools.exhaustive_search]
+        maxResults = 500
+        fieldsOfStudy = ["Computer Science"]
+    "#;
+
+    #[test]
+    fn test_layers_apply_in_order() {
+        let settings = resolve(FILE, None, &[]).unwrap();
+        assert_eq!(settings.profile.as_deref(), Some("laptop"));
+        let config = settings.config(None, None);
+        assert_eq!(config.client.request_timeout, Duration::from_mins(1));
+        assert_eq!(config.client.cache_max_size, 100);
+        assert_eq!(config.client.rate_limit_delay, super::super::api::RATE_LIMIT_DELAY);
+
+        let settings =
+            resolve(FILE, Some("server"), &["client.cache_ttl_secs=5", "client.api_key=k"])
+                .unwrap();
+        let config = settings.config(None, None);
+        assert_eq!(config.client.cache_max_size, 20000);
+        assert_eq!(config.client.rate_limit_delay, Duration::from_millis(10));
+        assert_eq!(config.client.cache_ttl, Duration::from_secs(5));
+        assert_eq!(config.client.api_key.as_deref(), Some("k"));
+        assert_eq!(
+            settings.config(Some("env".into()), None).client.api_key.as_deref(),
+            Some("env")
+        );
+        assert_eq!(
+            settings.tool_defaults.apply("exhaustive_search", json!({"query": "x"})),
+            json!({"query": "x", "maxResults": 500, "fieldsOfStudy": ["Computer Science"]})
+        );
+        assert_eq!(
+            settings.tool_defaults.apply("exhaustive_search", json!({"maxResults": 7})),
+            json!({"maxResults": 7, "fieldsOfStudy": ["Computer Science"]})
+        );
+    }
+
+    #[test]
+    fn test_env_sits_between_profile_and_command_line() {
+        let env = [
+            ("S2_MCP__CLIENT__CACHE_MAX_SIZE", "300"),
+            ("S2_MCP__CLIENT__CACHE_TTL_SECS", "30"),
+            ("S2_MCP__CLIENT__MAX_CONNECTIONS", "4"),
+            ("S2_MCP__TOOLS__EXHAUSTIVE_SEARCH__MAXRESULTS", "70"),
+            ("SEMANTIC_SCHOLAR_API_KEY", "ignored"),
+        ];
+        let settings =
+            resolve_with_env(FILE, Some("server"), &env, &["client.cache_ttl_secs=5"]).unwrap();
+        let config = settings.config(None, None);
+        // Over the profile's 20000, under --set.
+        assert_eq!(config.client.cache_max_size, 300);
+        assert_eq!(config.client.cache_ttl, Duration::from_secs(5));
+        assert_eq!(config.client.max_connections, 4);
+        assert!(config.client.api_key.is_none());
+        assert_eq!(settings.tool_defaults.apply("exhaustive_search", json!({}))["maxResults"], 70);
+
+        let message = resolve_with_env("", None, &[("S2_MCP__CLIENT__MAX_CONNECTIONS", "0")], &[])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            message,
+            "S2_MCP__CLIENT__MAX_CONNECTIONS: client.max_connections: must be greater than 0"
+        );
+        let message = resolve_with_env("", None, &[("S2_MCP__CLIENT__NOPE", "1")], &[])
+            .unwrap_err()
+            .to_string();
+        assert!(message.starts_with("S2_MCP__CLIENT__NOPE: unknown field"), "{message}");
+    }
+
+    #[test]
+    fn test_errors_name_the_key() {
+        let error = |text: &str, profile: Option<&str>, overrides: &[&str]| {
+            resolve(text, profile, overrides).unwrap_err().to_string()
+        };
+
+        let message = error("[client]\nrequest_timeout = 5\n", None, &[]);
+        assert!(message.contains("line 2") && message.contains("request_timeout"), "{message}");
+
+        let message = error("[profiles.x.client]\nconnect_timeout_secs = 0\n", Some("x"), &[]);
+        assert_eq!(message, "profiles.x.client.connect_timeout_secs: must be greater than 0");
+
+        let message = error("[client]\ngraph_api_url = \"ftp://x\"\n", None, &[]);
+        assert!(message.starts_with("client.graph_api_url: expected an http(s) URL"), "{message}");
+
+        let message = error("[tools.no_such_tool]\nx = 1\n", None, &[]);
+        assert_eq!(message, "tools.no_such_tool: unknown tool");
+
+        let message = error("[tools.exhaustive_search]\nmaxResult = 1\n", None, &[]);
+        assert_eq!(
+            message,
+            "tools.exhaustive_search.maxResult: exhaustive_search has no such argument"
+        );
+
+        let message = error("[tools.exhaustive_search]\nmaxResults = \"many\"\n", None, &[]);
+        assert!(message.starts_with("tools.exhaustive_search.maxResults: expected"), "{message}");
+
+        let message = error(FILE, Some("nope"), &[]);
+        assert_eq!(message, "--profile: unknown profile \"nope\" (defined: laptop, server)");
+
+        let message = error("", None, &["client.cache_tll_secs=5"]);
+        assert!(message.starts_with("--set client.cache_tll_secs: unknown field"), "{message}");
+
+        let message = error("", None, &["client.cache_ttl_secs"]);
+        assert_eq!(message, "--set client.cache_ttl_secs: expected KEY=VALUE");
+    }
+
+    #[test]
+    fn test_defaults_annotate_schema() {
+        let mut defaults = ToolDefaults::default();
+        defaults.insert("t", "limit", json!(5));
+        defaults.insert("t", "query", json!("x"));
+        let mut schema = json!({
+            "properties": {"limit": {"type": "integer", "default": 10}, "query": {"type": "string"}},
+            "required": ["query"]
+        });
+        defaults.annotate_schema("t", &mut schema);
+        assert_eq!(schema["properties"]["limit"]["default"], 5);
+        assert_eq!(schema["required"], json!([]));
+    }
+}
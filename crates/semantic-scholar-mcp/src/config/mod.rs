@@ -0,0 +1,87 @@
+//! Configuration for the Semantic Scholar MCP server.
+//!
+//! [`Config`] holds the client settings and the server's auth token; [`file`]
+//! layers a TOML configuration file, profiles and command-line overrides on
+//! top of the client defaults re-exported below.
+
+pub mod file;
+
+pub use file::{ConfigFile, Settings, ToolDefaults};
+pub use semantic_scholar_client::config::{ClientConfig, FIELDS_OF_STUDY, api, fields};
+
+/// Server configuration.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Settings for the Semantic Scholar API client.
+    pub client: ClientConfig,
+
+    /// Authentication token for the MCP server (optional).
+    pub auth_token: Option<String>,
+}
+
+impl Config {
+    /// Create a new configuration with optional API key.
+    ///
+    /// Rate limits are automatically adjusted based on API key presence:
+    /// - Without key: 5 req/s normal, 1 req/s batch
+    /// - With key: 100 req/s normal, 10 req/s batch
+    #[must_use]
+    pub fn new(api_key: Option<String>, auth_token: Option<String>) -> Self {
+        Self { client: ClientConfig::new(api_key), auth_token }
+    }
+
+    /// Create a test configuration with custom URLs for mock servers.
+    #[must_use]
+    pub fn for_testing(base_url: &str) -> Self {
+        Self { client: ClientConfig::for_testing(base_url), auth_token: None }
+    }
+
+    /// Create configuration from environment variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if environment variables are invalid.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let api_key = std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok();
+        let auth_token = std::env::var("MCP_SERVER_AUTH_TOKEN").ok();
+        Ok(Self::new(api_key, auth_token))
+    }
+
+    /// Check if an API key is configured.
+    #[must_use]
+    pub const fn has_api_key(&self) -> bool {
+        self.client.api_key.is_some()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_default() {
+        let config = Config::default();
+        assert!(config.client.api_key.is_none());
+        assert!(!config.has_api_key());
+    }
+
+    #[test]
+    fn test_config_with_api_key() {
+        let config = Config::new(Some("test-key".to_string()), None);
+        assert!(config.has_api_key());
+        assert_eq!(config.client.api_key, Some("test-key".to_string()));
+    }
+
+    #[test]
+    fn test_fields() {
+        assert!(fields::MINIMAL.contains(&"paperId"));
+        assert!(fields::DEFAULT.contains(&"abstract"));
+        assert!(fields::EXTENDED.contains(&"embedding"));
+    }
+}
@@ -1,124 +1,9 @@
 //! Error types for the Semantic Scholar MCP server.
 //!
 //! Uses `thiserror` for structured error handling with automatic `From` implementations.
+//! Client errors come from `semantic-scholar-client` and are re-exported here.
 
-use std::error::Error;
-use std::time::Duration;
-
-/// Errors from the HTTP client layer.
-#[derive(thiserror::Error, Debug)]
-pub enum ClientError {
-    /// Response body error (e.g. JSON deserialization via reqwest)
-    #[error("Response error: {0}")]
-    Response(#[from] reqwest::Error),
-
-    /// Middleware error
-    #[error("Middleware error: {0}")]
-    Middleware(#[from] reqwest_middleware::Error),
-
-    /// Rate limited by Semantic Scholar API (429 response)
-    #[error("Rate limited, retry after {retry_after:?}")]
-    RateLimited {
-        /// Suggested wait time before retry
-        retry_after: Duration,
-    },
-
-    /// Resource not found (404 response)
-    #[error("Resource not found: {resource}")]
-    NotFound {
-        /// Description of the missing resource
-        resource: String,
-    },
-
-    /// Invalid request parameters (400 response)
-    #[error("Bad request: {message}")]
-    BadRequest {
-        /// Error message from API
-        message: String,
-    },
-
-    /// Request timeout
-    #[error("Request timed out after {0:?}")]
-    Timeout(Duration),
-
-    /// JSON parsing error
-    #[error("Failed to parse response: {0}")]
-    Parse(#[from] serde_json::Error),
-
-    /// Server error (5xx response)
-    #[error("Server error ({status}): {message}")]
-    Server {
-        /// HTTP status code
-        status: u16,
-        /// Error message
-        message: String,
-    },
-
-    /// Unexpected HTTP status
-    #[error("Unexpected status {status}: {message}")]
-    UnexpectedStatus {
-        /// HTTP status code
-        status: u16,
-        /// Response body or message
-        message: String,
-    },
-}
-
-impl ClientError {
-    /// Create a rate limited error with retry-after duration.
-    #[must_use]
-    pub fn rate_limited(seconds: u64) -> Self {
-        Self::RateLimited { retry_after: Duration::from_secs(seconds) }
-    }
-
-    /// Create a not found error.
-    #[must_use]
-    pub fn not_found(resource: impl Into<String>) -> Self {
-        Self::NotFound { resource: resource.into() }
-    }
-
-    /// Create a bad request error.
-    #[must_use]
-    pub fn bad_request(message: impl Into<String>) -> Self {
-        Self::BadRequest { message: message.into() }
-    }
-
-    /// Create a server error.
-    #[must_use]
-    pub fn server(status: u16, message: impl Into<String>) -> Self {
-        Self::Server { status, message: message.into() }
-    }
-
-    /// Returns true if this error is retryable.
-    #[must_use]
-    pub fn is_retryable(&self) -> bool {
-        match self {
-            Self::RateLimited { .. } | Self::Timeout(_) | Self::Server { .. } => true,
-            Self::Middleware(e) => {
-                // reqwest-retry wraps errors through RetryError -> anyhow -> Middleware,
-                // so walk the full source chain to find the underlying reqwest::Error
-                let mut source: Option<&(dyn Error + 'static)> = Some(e);
-                while let Some(err) = source {
-                    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
-                        return reqwest_err.is_timeout() || reqwest_err.is_connect();
-                    }
-                    source = err.source();
-                }
-                false
-            }
-            _ => false,
-        }
-    }
-
-    /// Get the retry-after duration if this is a rate limit error.
-    #[must_use]
-    pub const fn retry_after(&self) -> Option<Duration> {
-        match self {
-            Self::RateLimited { retry_after } => Some(*retry_after),
-            _ => None,
-        }
-    }
-}
+pub use semantic_scholar_client::error::{ClientError, ClientResult};
 
 /// Errors from MCP tool execution.
 #[derive(thiserror::Error, Debug)]
@@ -147,6 +32,14 @@ pub enum ToolError {
     /// Resource not available
     #[error("Resource unavailable: {0}")]
     Unavailable(String),
+
+    /// Server-side resource (e.g. a job) does not exist
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The caller may not make this call (e.g. rejected by tool middleware)
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
 }
 
 impl ToolError {
@@ -168,6 +61,18 @@ impl ToolError {
         Self::Unavailable(message.into())
     }
 
+    /// Create a not found error.
+    #[must_use]
+    pub fn not_found(what: impl Into<String>) -> Self {
+        Self::NotFound(what.into())
+    }
+
+    /// Create a forbidden error.
+    #[must_use]
+    pub fn forbidden(reason: impl Into<String>) -> Self {
+        Self::Forbidden(reason.into())
+    }
+
     /// Convert to a user-friendly error message for MCP response.
     #[must_use]
     pub fn to_user_message(&self) -> String {
@@ -181,6 +86,14 @@ impl ToolError {
             Self::Client(ClientError::NotFound { resource }) => {
                 format!("Not found: {resource}. Please check the ID is correct.")
             }
+            Self::Client(ClientError::QuotaExceeded(exceeded)) => {
+                format!(
+                    "Quota exceeded: this client may make {} upstream requests per {}. Please wait {}s before retrying.",
+                    exceeded.limit,
+                    exceeded.window,
+                    exceeded.retry_after.as_secs()
+                )
+            }
             Self::Validation { field, message } => {
                 format!("Invalid input for '{field}': {message}")
             }
@@ -189,38 +102,176 @@ impl ToolError {
     }
 }
 
-/// Result type alias for client operations.
-pub type ClientResult<T> = Result<T, ClientError>;
+/// Stable, machine-readable error codes reported in `isError` tool results.
+///
+/// These strings are part of the tool output contract; add new codes rather
+/// than renaming existing ones.
+pub mod codes {
+    /// Arguments were rejected (bad type, out of range, unknown ID format).
+    pub const VALIDATION: &str = "validation";
+    /// The requested paper/author does not exist.
+    pub const NOT_FOUND: &str = "not_found";
+    /// Semantic Scholar rate limit hit; see `retry_after`.
+    pub const RATE_LIMITED: &str = "rate_limited";
+    /// This client's request quota is spent; see `window`, `limit` and
+    /// `retry_after`.
+    pub const QUOTA_EXCEEDED: &str = "quota_exceeded";
+    /// Semantic Scholar is unreachable, timing out, or returning 5xx.
+    pub const UPSTREAM_UNAVAILABLE: &str = "upstream_unavailable";
+    /// Semantic Scholar returned a response we could not interpret.
+    pub const UPSTREAM_ERROR: &str = "upstream_error";
+    /// The caller is not allowed to make this call.
+    pub const FORBIDDEN: &str = "forbidden";
+    /// Bug or unexpected state inside the server.
+    pub const INTERNAL: &str = "internal";
+}
+
+impl ToolError {
+    /// Stable error code for this failure (see [`codes`]).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::Validation { .. }
+            | Self::Serialization(_)
+            | Self::Client(ClientError::BadRequest { .. }) => codes::VALIDATION,
+            Self::Client(ClientError::NotFound { .. }) | Self::NotFound(_) => codes::NOT_FOUND,
+            Self::Client(ClientError::RateLimited { .. }) => codes::RATE_LIMITED,
+            Self::Client(ClientError::QuotaExceeded(_)) => codes::QUOTA_EXCEEDED,
+            Self::Client(
+                ClientError::Timeout(_)
+                | ClientError::Server { .. }
+                | ClientError::Middleware(_)
+                | ClientError::Response(_),
+            )
+            | Self::Unavailable(_) => codes::UPSTREAM_UNAVAILABLE,
+            Self::Client(ClientError::Parse(_) | ClientError::UnexpectedStatus { .. }) => {
+                codes::UPSTREAM_ERROR
+            }
+            Self::Forbidden(_) => codes::FORBIDDEN,
+            Self::Client(ClientError::Config(_)) | Self::Internal(_) => codes::INTERNAL,
+        }
+    }
+
+    /// Name of the argument that caused the failure, when known.
+    ///
+    /// For argument parse failures this is recovered from serde's
+    /// "missing field `x`" message.
+    #[must_use]
+    pub fn field(&self) -> Option<String> {
+        match self {
+            Self::Validation { field, .. } => Some(field.clone()),
+            Self::Serialization(e) => {
+                let msg = e.to_string();
+                let rest = msg.split_once("missing field `")?.1;
+                rest.split_once('`').map(|(name, _)| name.to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Structured error payload: `code`, `message`, and when applicable
+    /// `field`, `retry_after` (seconds), and the quota `window` and `limit`.
+    #[must_use]
+    pub fn error_data(&self) -> serde_json::Value {
+        let mut data = serde_json::json!({
+            "code": self.code(),
+            "message": self.to_user_message(),
+        });
+        if let Some(field) = self.field() {
+            data["field"] = serde_json::json!(field);
+        }
+        if let Self::Client(client_err) = self {
+            if let Some(retry_after) = client_err.retry_after() {
+                data["retry_after"] = serde_json::json!(retry_after.as_secs());
+            }
+            if let ClientError::QuotaExceeded(exceeded) = client_err {
+                data["window"] = serde_json::json!(exceeded.window);
+                data["limit"] = serde_json::json!(exceeded.limit);
+            }
+        }
+        data
+    }
+
+    /// Build the MCP `CallToolResult` for a failed call (`isError: true`).
+    ///
+    /// Failures are reported as results rather than JSON-RPC errors so the
+    /// model sees them and can correct its arguments. The message goes in
+    /// `content`; [`error_data`](Self::error_data) goes in `_meta.error`,
+    /// since `structuredContent` must match the tool's output schema.
+    #[must_use]
+    pub fn to_call_result(&self) -> serde_json::Value {
+        serde_json::json!({
+            "content": [{
+                "type": "text",
+                "text": self.to_user_message()
+            }],
+            "_meta": { "error": self.error_data() },
+            "isError": true
+        })
+    }
+}
 
 /// Result type alias for tool operations.
 pub type ToolResult<T> = Result<T, ToolError>;
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
+    use crate::client::quota::QuotaExceeded;
 
     #[test]
-    fn test_client_error_retryable() {
-        assert!(ClientError::rate_limited(60).is_retryable());
-        assert!(ClientError::Timeout(Duration::from_secs(30)).is_retryable());
-        assert!(ClientError::server(500, "Internal error").is_retryable());
-
-        assert!(!ClientError::not_found("paper123").is_retryable());
-        assert!(!ClientError::bad_request("invalid query").is_retryable());
-
-        // Middleware-wrapped non-reqwest errors are not retryable
-        let middleware_err =
-            reqwest_middleware::Error::Middleware(anyhow::anyhow!("some other error"));
-        assert!(!ClientError::Middleware(middleware_err).is_retryable());
+    fn test_tool_error_codes() {
+        assert_eq!(ToolError::validation("limit", "too big").code(), codes::VALIDATION);
+        assert_eq!(ToolError::from(ClientError::not_found("paper")).code(), codes::NOT_FOUND);
+        assert_eq!(ToolError::from(ClientError::rate_limited(5)).code(), codes::RATE_LIMITED);
+        assert_eq!(
+            ToolError::from(ClientError::server(503, "down")).code(),
+            codes::UPSTREAM_UNAVAILABLE
+        );
+        assert_eq!(ToolError::unavailable("x").code(), codes::UPSTREAM_UNAVAILABLE);
+        assert_eq!(ToolError::forbidden("x").code(), codes::FORBIDDEN);
+        assert_eq!(ToolError::internal("x").code(), codes::INTERNAL);
     }
 
     #[test]
-    fn test_client_error_retry_after() {
-        let err = ClientError::rate_limited(60);
-        assert_eq!(err.retry_after(), Some(Duration::from_secs(60)));
+    fn test_tool_error_field_from_serde() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Input {
+            query: String,
+        }
+        let err: ToolError =
+            serde_json::from_value::<Input>(serde_json::json!({})).unwrap_err().into();
+        assert_eq!(err.code(), codes::VALIDATION);
+        assert_eq!(err.field().as_deref(), Some("query"));
+    }
 
-        let err = ClientError::not_found("paper");
-        assert_eq!(err.retry_after(), None);
+    #[test]
+    fn test_tool_error_call_result() {
+        let result = ToolError::from(ClientError::rate_limited(30)).to_call_result();
+        assert_eq!(result["isError"], true);
+        assert_eq!(result["_meta"]["error"]["code"], "rate_limited");
+        assert_eq!(result["_meta"]["error"]["retry_after"], 30);
+        assert!(result["content"][0]["text"].as_str().unwrap().contains("Rate limited"));
+    }
+
+    #[test]
+    fn test_quota_exceeded_call_result() {
+        use crate::client::quota::QuotaWindow;
+
+        let exceeded = QuotaExceeded {
+            window: QuotaWindow::Day,
+            limit: 500,
+            retry_after: Duration::from_hours(1),
+        };
+        let result = ToolError::from(ClientError::QuotaExceeded(exceeded)).to_call_result();
+        let error = &result["_meta"]["error"];
+        assert_eq!(error["code"], "quota_exceeded");
+        assert_eq!(error["window"], "day");
+        assert_eq!(error["limit"], 500);
+        assert_eq!(error["retry_after"], 3600);
     }
 
     #[test]
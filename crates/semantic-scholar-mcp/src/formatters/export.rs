@@ -0,0 +1,217 @@
+//! Reference manager export formats: RIS, BibTeX, CSV and EndNote.
+
+use crate::models::Paper;
+
+/// Format papers as RIS.
+#[must_use]
+pub fn format_ris(papers: &[Paper], include_abstract: bool) -> String {
+    let mut output = String::new();
+
+    for paper in papers {
+        output.push_str("TY  - JOUR\n");
+        output.push_str(&format!("TI  - {}\n", single_line(paper.title_or_default())));
+
+        for author in &paper.authors {
+            if let Some(name) = &author.name {
+                output.push_str(&format!("AU  - {}\n", single_line(name)));
+            }
+        }
+
+        if let Some(year) = paper.year {
+            output.push_str(&format!("PY  - {year}\n"));
+        }
+
+        if let Some(venue) = &paper.venue {
+            output.push_str(&format!("JO  - {}\n", single_line(venue)));
+        }
+
+        if include_abstract {
+            if let Some(abs) = &paper.r#abstract {
+                output.push_str(&format!("AB  - {}\n", single_line(abs)));
+            }
+        }
+
+        if let Some(doi) = paper.doi() {
+            output.push_str(&format!("DO  - {}\n", single_line(doi)));
+        }
+
+        output.push_str(&format!("ID  - {}\n", single_line(&paper.paper_id)));
+        output.push_str("ER  - \n\n");
+    }
+
+    output
+}
+
+/// Format papers as BibTeX.
+#[must_use]
+pub fn format_bibtex(papers: &[Paper], include_abstract: bool) -> String {
+    let mut output = String::new();
+
+    for paper in papers {
+        let year = paper.year.unwrap_or(0);
+        output.push_str(&format!("@article{{{},\n", bibtex_key(paper)));
+        output.push_str(&format!("  title = {{{}}},\n", escape_bibtex(paper.title_or_default())));
+        output.push_str(&format!("  author = {{{}}},\n", escape_bibtex(&paper.author_names())));
+
+        if year > 0 {
+            output.push_str(&format!("  year = {{{year}}},\n"));
+        }
+
+        if let Some(venue) = &paper.venue {
+            output.push_str(&format!("  journal = {{{}}},\n", escape_bibtex(venue)));
+        }
+
+        if include_abstract {
+            if let Some(abs) = &paper.r#abstract {
+                output.push_str(&format!("  abstract = {{{}}},\n", escape_bibtex(abs)));
+            }
+        }
+
+        if let Some(doi) = paper.doi() {
+            output.push_str(&format!("  doi = {{{}}},\n", escape_bibtex(doi)));
+        }
+
+        output.push_str("}\n\n");
+    }
+
+    output
+}
+
+/// Citation key: the first author's last name and the year, e.g.
+/// `Vaswani2017`. Characters that may not appear in a key are dropped.
+fn bibtex_key(paper: &Paper) -> String {
+    let last_name = paper
+        .first_author()
+        .and_then(|name| name.split_whitespace().last())
+        .map(|name| name.chars().filter(|c| c.is_alphanumeric()).collect::<String>())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "Unknown".to_string());
+    format!("{last_name}{}", paper.year.unwrap_or(0))
+}
+
+/// Format papers as CSV.
+#[must_use]
+pub fn format_csv(papers: &[Paper], include_abstract: bool) -> String {
+    let mut output = String::new();
+
+    // Header
+    if include_abstract {
+        output.push_str("paper_id,title,authors,year,venue,citations,doi,abstract\n");
+    } else {
+        output.push_str("paper_id,title,authors,year,venue,citations,doi\n");
+    }
+
+    for paper in papers {
+        let id = csv_escape(&paper.paper_id);
+        let title = csv_escape(paper.title_or_default());
+        let authors = csv_escape(&paper.author_names());
+        let year = paper.year.map_or(String::new(), |y| y.to_string());
+        let venue = csv_escape(paper.venue.as_deref().unwrap_or(""));
+        let citations = paper.citations().to_string();
+        let doi = csv_escape(paper.doi().unwrap_or(""));
+
+        if include_abstract {
+            let abs = csv_escape(paper.r#abstract.as_deref().unwrap_or(""));
+            output.push_str(&format!(
+                "{id},{title},{authors},{year},{venue},{citations},{doi},{abs}\n"
+            ));
+        } else {
+            output.push_str(&format!("{id},{title},{authors},{year},{venue},{citations},{doi}\n"));
+        }
+    }
+
+    output
+}
+
+/// Format papers as EndNote.
+#[must_use]
+pub fn format_endnote(papers: &[Paper], include_abstract: bool) -> String {
+    let mut output = String::new();
+
+    for paper in papers {
+        output.push_str("%0 Journal Article\n");
+        output.push_str(&format!("%T {}\n", single_line(paper.title_or_default())));
+
+        for author in &paper.authors {
+            if let Some(name) = &author.name {
+                output.push_str(&format!("%A {}\n", single_line(name)));
+            }
+        }
+
+        if let Some(year) = paper.year {
+            output.push_str(&format!("%D {year}\n"));
+        }
+
+        if let Some(venue) = &paper.venue {
+            output.push_str(&format!("%J {}\n", single_line(venue)));
+        }
+
+        if include_abstract {
+            if let Some(abs) = &paper.r#abstract {
+                output.push_str(&format!("%X {}\n", single_line(abs)));
+            }
+        }
+
+        if let Some(doi) = paper.doi() {
+            output.push_str(&format!("%R {}\n", single_line(doi)));
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+/// A value for a line-based format (RIS, EndNote), where a line break would
+/// start a new tag: CR is dropped and LF becomes a space.
+fn single_line(s: &str) -> String {
+    s.replace('\r', "").replace('\n', " ")
+}
+
+/// Escape a string for a BibTeX field value.
+///
+/// BibTeX counts every brace, escaped or not, so braces are written as
+/// `\textbraceleft{}`/`\textbraceright{}` rather than `\{`/`\}`, which would
+/// leave the value unbalanced.
+#[must_use]
+pub fn escape_bibtex(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' => escaped.push_str("\\textbraceleft{}"),
+            '}' => escaped.push_str("\\textbraceright{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape a string for a CSV field.
+#[must_use]
+pub fn csv_escape(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r') {
+        // Prefix with single quote to prevent formula injection in spreadsheets
+        let escaped = s.replace('"', "\"\"");
+        if escaped.starts_with('=')
+            || escaped.starts_with('+')
+            || escaped.starts_with('-')
+            || escaped.starts_with('@')
+        {
+            format!("\"'{}\"", escaped)
+        } else {
+            format!("\"{}\"", escaped)
+        }
+    } else if s.starts_with('=') || s.starts_with('+') || s.starts_with('-') || s.starts_with('@') {
+        // Prevent CSV injection
+        format!("'{}", s)
+    } else {
+        s.to_string()
+    }
+}
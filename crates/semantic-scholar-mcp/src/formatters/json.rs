@@ -78,6 +78,53 @@ pub fn compact_author(author: &Author) -> Value {
     obj
 }
 
+/// JSON Schema for [`compact_paper`] output.
+#[must_use]
+pub fn compact_paper_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string"},
+            "title": {"type": "string"},
+            "year": {"type": ["integer", "null"]},
+            "citations": {"type": "integer"},
+            "authors": {"type": "array", "items": {"type": "string"}},
+            "venue": {"type": "string"},
+            "doi": {"type": "string"},
+            "arxiv": {"type": "string"},
+            "pdf": {"type": "string"},
+            "tldr": {"type": "string"},
+            "fields": {"type": "array", "items": {"type": "string"}}
+        },
+        "required": ["id", "title", "citations"]
+    })
+}
+
+/// JSON Schema for [`compact_author`] output.
+#[must_use]
+pub fn compact_author_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string"},
+            "name": {"type": "string"},
+            "hIndex": {"type": "integer"},
+            "citations": {"type": "integer"},
+            "papers": {"type": "integer"},
+            "affiliations": {"type": "array", "items": {"type": "string"}},
+            "orcid": {"type": "string"},
+            "homepage": {"type": "string"}
+        },
+        "required": ["id", "name"]
+    })
+}
+
+/// JSON Schema for an array of [`compact_paper`] objects.
+#[must_use]
+pub fn compact_paper_list_schema() -> Value {
+    json!({"type": "array", "items": compact_paper_schema()})
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
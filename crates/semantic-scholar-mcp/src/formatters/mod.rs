@@ -1,7 +1,9 @@
-//! Output formatters for Markdown and JSON.
+//! Output formatters for Markdown, JSON and reference manager exports.
 
+mod export;
 mod json;
 mod markdown;
 
 pub use self::json::*;
+pub use export::*;
 pub use markdown::*;
@@ -11,6 +11,10 @@
 //! - **Rate-limited**: Respects Semantic Scholar API limits
 //! - **Cached**: 5-minute TTL cache reduces API calls
 //!
+//! The API client and data models live in the `semantic-scholar-client`
+//! crate, re-exported here as [`client`] and [`models`], for use without the
+//! server.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -19,21 +23,27 @@
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
 //!     let config = Config::from_env()?;
-//!     let client = SemanticScholarClient::new(config)?;
+//!     let client = SemanticScholarClient::new(config.client)?;
 //!
 //!     // Use client for API calls
 //!     Ok(())
 //! }
 //! ```
 
-pub mod client;
+pub mod cli;
 pub mod config;
 pub mod error;
 pub mod formatters;
+pub mod metrics;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod models;
 pub mod server;
+pub mod telemetry;
 pub mod tools;
 
+pub use semantic_scholar_client::client;
+
 pub use client::SemanticScholarClient;
 pub use config::Config;
 pub use error::{ClientError, ToolError};
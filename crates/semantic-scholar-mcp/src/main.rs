@@ -1,25 +1,63 @@
 //! Semantic Scholar MCP Server - Entry Point
 //!
-//! Provides both stdio (for Claude Desktop) and HTTP transports.
+//! Provides both stdio (for Claude Desktop) and HTTP transports, and
+//! subcommands that call tools directly.
 
-use clap::Parser;
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use semantic_scholar_mcp::{SemanticScholarClient, config::Config, server::McpServer};
+use clap::{Parser, Subcommand};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry, layer::SubscriberExt, util::SubscriberInitExt,
+};
+
+use semantic_scholar_mcp::{
+    SemanticScholarClient, cli,
+    config::Settings,
+    server::McpServer,
+    server::accounts::hash_password,
+    server::audit::{self, AuditConfig},
+    server::logging::McpLogLayer,
+    server::transport::McpToolInfo,
+    telemetry,
+    tools::{self, McpTool, ToolContext, ToolFilter},
+};
 
 #[derive(Parser, Debug)]
 #[command(name = "semantic-scholar-mcp")]
 #[command(about = "MCP server for Semantic Scholar API")]
 #[command(version)]
 struct Cli {
+    /// Call tools directly instead of running a server
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Semantic Scholar API key (optional, enables higher rate limits)
-    #[arg(long, env = "SEMANTIC_SCHOLAR_API_KEY")]
+    #[arg(long, env = "SEMANTIC_SCHOLAR_API_KEY", global = true)]
     api_key: Option<String>,
 
-    /// Transport mode: stdio or http
+    /// TOML configuration file with client settings, tool defaults and profiles
+    #[arg(long, env = "MCP_CONFIG", global = true)]
+    config: Option<PathBuf>,
+
+    /// Configuration profile to apply (overrides the file's `profile`)
+    #[arg(long, env = "MCP_PROFILE", global = true)]
+    profile: Option<String>,
+
+    /// Override a configuration key, e.g. --set client.cache_ttl_secs=60 (repeatable)
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    overrides: Vec<String>,
+
+    /// Transport mode: stdio, http or unix
     #[arg(long, default_value = "stdio")]
     transport: Transport,
 
+    /// Socket path (only used with --transport unix)
+    #[arg(long, env = "MCP_SOCKET", required_if_eq("transport", "unix"))]
+    socket: Option<PathBuf>,
+
     /// Authentication token for HTTP transport (optional)
     #[arg(long, env = "MCP_SERVER_AUTH_TOKEN")]
     auth_token: Option<String>,
@@ -32,13 +70,113 @@ struct Cli {
     #[arg(long, env = "BASE_URL")]
     base_url: Option<String>,
 
-    /// Log level (trace, debug, info, warn, error)
-    #[arg(long, default_value = "info", env = "RUST_LOG")]
-    log_level: String,
+    /// Only expose these tools or tool groups (comma-separated, e.g. discovery,export)
+    #[arg(long, env = "MCP_ENABLE_TOOLS", value_delimiter = ',', global = true)]
+    enable_tools: Vec<String>,
 
-    /// Output logs as JSON
+    /// Hide these tools or tool groups (comma-separated)
+    #[arg(long, env = "MCP_DISABLE_TOOLS", value_delimiter = ',', global = true)]
+    disable_tools: Vec<String>,
+
+    /// JSON tool policy with deployment and per-OAuth-client filters (reloaded on change)
+    #[arg(long, env = "MCP_TOOL_POLICY")]
+    tool_policy: Option<PathBuf>,
+
+    /// Directory for persistent HTTP sessions (share it between replicas)
+    #[arg(long, env = "MCP_SESSION_DIR")]
+    session_dir: Option<PathBuf>,
+
+    /// File for persistent OAuth clients and tokens (tokens are stored hashed)
+    #[arg(long, env = "MCP_OAUTH_STORE")]
+    oauth_store: Option<PathBuf>,
+
+    /// JSON user accounts; enables the OAuth login form and scoped tokens
+    #[arg(long, env = "MCP_ACCOUNTS")]
+    accounts: Option<PathBuf>,
+
+    /// JSON OpenID Connect config; delegates the OAuth login to an upstream provider
+    #[arg(long, env = "MCP_OIDC", conflicts_with = "accounts")]
+    oidc: Option<PathBuf>,
+
+    /// Append every tool call to this JSON-lines audit log
+    #[arg(long, env = "MCP_AUDIT_LOG")]
+    audit_log: Option<PathBuf>,
+
+    /// Rotate the audit log when it reaches this many bytes
+    #[arg(long, env = "MCP_AUDIT_LOG_MAX_BYTES", default_value_t = audit::DEFAULT_MAX_BYTES)]
+    audit_log_max_bytes: u64,
+
+    /// Number of rotated audit log files to keep
+    #[arg(long, env = "MCP_AUDIT_LOG_KEEP", default_value_t = audit::DEFAULT_KEEP)]
+    audit_log_keep: usize,
+
+    /// Record tool arguments in the audit log, not just their SHA-256
+    #[arg(long, env = "MCP_AUDIT_ARGUMENTS")]
+    audit_arguments: bool,
+
+    /// Browser origins, besides the server's own, allowed to open WebSockets (comma-separated)
+    #[arg(long = "allowed-origin", env = "MCP_ALLOWED_ORIGINS", value_delimiter = ',')]
+    allowed_origins: Vec<String>,
+
+    /// Read a password from stdin, print its Argon2 hash for the accounts file, and exit
     #[arg(long)]
+    hash_password: bool,
+
+    /// Log level: trace, debug, info, warn, error (default: info, or warn for subcommands)
+    #[arg(long, env = "RUST_LOG", global = true)]
+    log_level: Option<String>,
+
+    /// Output logs as JSON
+    #[arg(long, global = true)]
     json_logs: bool,
+
+    /// Export traces to this OTLP/HTTP collector (e.g. <http://localhost:4318>)
+    #[arg(long, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+
+    /// Service name reported with exported traces
+    #[arg(long, env = "OTEL_SERVICE_NAME", default_value = telemetry::DEFAULT_SERVICE_NAME)]
+    otel_service_name: String,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the available tools
+    ListTools {
+        /// Print the full tool descriptions as JSON, as in `tools/list`
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a tool's input schema, including configured defaults
+    Schema {
+        /// Tool name
+        tool: String,
+        /// Print the output schema instead
+        #[arg(long)]
+        output: bool,
+    },
+    /// Call a tool and print its result
+    Call {
+        /// Tool name
+        tool: String,
+        /// Arguments as a JSON object
+        #[arg(long, conflicts_with = "args_file")]
+        args: Option<String>,
+        /// Read the arguments from a JSON file (`-` for stdin)
+        #[arg(long)]
+        args_file: Option<PathBuf>,
+        /// Print the structured result as JSON instead of the text rendering
+        #[arg(long)]
+        structured: bool,
+    },
+    /// Run JSON-lines tool calls and print a JSON-lines result for each
+    ///
+    /// Each input line is {"id": ..., "tool": "...", "arguments": {...}}.
+    /// Exits with an error if any call failed.
+    Batch {
+        /// Input file (defaults to stdin)
+        input: Option<PathBuf>,
+    },
 }
 
 #[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
@@ -46,30 +184,38 @@ enum Transport {
     /// Standard input/output (for Claude Desktop)
     #[default]
     Stdio,
-    /// HTTP with Server-Sent Events
+    /// HTTP with Server-Sent Events and WebSocket (`/ws`)
     Http,
+    /// The HTTP transport on a Unix domain socket (see --socket)
+    Unix,
 }
 
-fn init_tracing(log_level: &str, json: bool, use_stderr: bool) {
+fn init_tracing(
+    log_level: &str,
+    json: bool,
+    use_stderr: bool,
+    tracer_provider: Option<&SdkTracerProvider>,
+) {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(log_level));
 
-    let subscriber = tracing_subscriber::registry().with(filter);
-
-    if json {
-        if use_stderr {
-            subscriber
-                .with(tracing_subscriber::fmt::layer().json().with_writer(std::io::stderr))
-                .init();
-        } else {
-            subscriber.with(tracing_subscriber::fmt::layer().json()).init();
+    let fmt_layer: Box<dyn Layer<Registry> + Send + Sync> = match (json, use_stderr) {
+        (true, true) => {
+            tracing_subscriber::fmt::layer().json().with_writer(std::io::stderr).boxed()
         }
-    } else if use_stderr {
-        subscriber
-            .with(tracing_subscriber::fmt::layer().compact().with_writer(std::io::stderr))
-            .init();
-    } else {
-        subscriber.with(tracing_subscriber::fmt::layer().compact()).init();
-    }
+        (true, false) => tracing_subscriber::fmt::layer().json().boxed(),
+        (false, true) => {
+            tracing_subscriber::fmt::layer().compact().with_writer(std::io::stderr).boxed()
+        }
+        (false, false) => tracing_subscriber::fmt::layer().compact().boxed(),
+    };
+
+    // The env filter only applies to local output; the MCP log layer has its
+    // own target filter and the client chooses its level via logging/setLevel.
+    tracing_subscriber::registry()
+        .with(fmt_layer.with_filter(filter))
+        .with(McpLogLayer::filtered())
+        .with(tracer_provider.map(telemetry::layer))
+        .init();
 }
 
 #[tokio::main]
@@ -79,20 +225,89 @@ async fn main() -> anyhow::Result<()> {
 
     let cli = Cli::parse();
 
-    // In stdio mode, logs MUST go to stderr to avoid corrupting JSON-RPC protocol
-    let use_stderr = matches!(cli.transport, Transport::Stdio);
-    init_tracing(&cli.log_level, cli.json_logs, use_stderr);
+    if cli.hash_password {
+        let mut password = String::new();
+        std::io::stdin().read_line(&mut password)?;
+        let password = password.trim_end_matches(['\r', '\n']);
+        anyhow::ensure!(!password.is_empty(), "no password given on stdin");
+        println!("{}", hash_password(password));
+        return Ok(());
+    }
+
+    // In stdio mode, logs MUST go to stderr to avoid corrupting JSON-RPC
+    // protocol; subcommands print their results on stdout.
+    let use_stderr = matches!(cli.transport, Transport::Stdio) || cli.command.is_some();
+    let tracer_provider = cli
+        .otlp_endpoint
+        .as_deref()
+        .map(|endpoint| telemetry::tracer_provider(endpoint, &cli.otel_service_name))
+        .transpose()?;
+    let log_level = cli.log_level.as_deref().unwrap_or(if use_stderr && cli.command.is_some() {
+        "warn"
+    } else {
+        "info"
+    });
+    init_tracing(log_level, cli.json_logs, use_stderr, tracer_provider.as_ref());
+
+    if cli.command.is_none() {
+        tracing::info!(
+            version = env!("CARGO_PKG_VERSION"),
+            transport = ?cli.transport,
+            "Starting Semantic Scholar MCP server"
+        );
+    }
 
+    let settings = Settings::load(
+        cli.config.as_deref(),
+        cli.profile.as_deref(),
+        &cli.overrides,
+        &tools::register_all_tools(),
+    )?;
+    let config = settings.config(cli.api_key, cli.auth_token.clone());
     tracing::info!(
-        version = env!("CARGO_PKG_VERSION"),
-        transport = ?cli.transport,
-        "Starting Semantic Scholar MCP server"
+        has_api_key = config.has_api_key(),
+        profile = settings.profile.as_deref(),
+        "API configuration"
     );
+    let client = SemanticScholarClient::new(config.client)?;
 
-    let config = Config::new(cli.api_key, cli.auth_token.clone());
-    tracing::info!(has_api_key = config.has_api_key(), "API configuration");
-    let client = SemanticScholarClient::new(config)?;
-    let server = McpServer::new(client);
+    if let Some(command) = cli.command {
+        let mut tools = tools::register_all_tools();
+        let filter = ToolFilter::new(cli.enable_tools, cli.disable_tools);
+        tools.retain(|t| filter.permits(t.name(), t.group()));
+        let ctx = ToolContext::new(Arc::new(client)).with_tool_defaults(settings.tool_defaults);
+        let result = run_command(command, &tools, &ctx).await;
+        shutdown_tracing(tracer_provider);
+        return result;
+    }
+
+    let mut server = McpServer::new(client)
+        .with_tool_defaults(settings.tool_defaults)
+        .with_tool_filter(ToolFilter::new(cli.enable_tools, cli.disable_tools));
+    if let Some(path) = cli.tool_policy {
+        server = server.with_tool_policy_file(path);
+    }
+    if let Some(dir) = cli.session_dir {
+        server = server.with_session_dir(dir);
+    }
+    if let Some(path) = cli.oauth_store {
+        server = server.with_oauth_store_file(path);
+    }
+    if let Some(path) = cli.accounts {
+        server = server.with_accounts_file(path);
+    }
+    if let Some(path) = cli.oidc {
+        server = server.with_oidc_file(path);
+    }
+    if let Some(path) = cli.audit_log {
+        server = server.with_audit_log(AuditConfig {
+            max_bytes: cli.audit_log_max_bytes,
+            keep: cli.audit_log_keep,
+            record_arguments: cli.audit_arguments,
+            ..AuditConfig::new(path)
+        });
+    }
+    server = server.with_allowed_origins(cli.allowed_origins);
 
     match cli.transport {
         Transport::Stdio => {
@@ -103,7 +318,117 @@ async fn main() -> anyhow::Result<()> {
             tracing::info!(port = cli.port, base_url = ?cli.base_url, "Running in HTTP mode");
             server.run_http(cli.port, cli.base_url, cli.auth_token).await?;
         }
+        Transport::Unix => {
+            let socket = cli.socket.unwrap_or_default();
+            #[cfg(unix)]
+            server.run_unix(socket, cli.base_url, cli.auth_token).await?;
+            #[cfg(not(unix))]
+            anyhow::bail!("cannot listen on {}: Unix sockets are not supported", socket.display());
+        }
     }
 
+    shutdown_tracing(tracer_provider);
     Ok(())
 }
+
+/// Flush the last batch of spans.
+fn shutdown_tracing(provider: Option<SdkTracerProvider>) {
+    if let Some(provider) = provider
+        && let Err(e) = provider.shutdown()
+    {
+        eprintln!("Failed to flush traces: {e}");
+    }
+}
+
+/// Run a subcommand against `tools`.
+async fn run_command(
+    command: Command,
+    tools: &[Box<dyn McpTool>],
+    ctx: &ToolContext,
+) -> anyhow::Result<()> {
+    let text = match command {
+        Command::ListTools { json: true } => {
+            let infos: Vec<_> = tools
+                .iter()
+                .map(|t| McpToolInfo::from_tool(t.as_ref()).with_defaults(ctx.tool_defaults()))
+                .collect();
+            serde_json::to_string_pretty(&infos)?
+        }
+        Command::ListTools { json: false } => {
+            let width = tools.iter().map(|t| t.name().len()).max().unwrap_or(0);
+            let lines: Vec<_> = tools
+                .iter()
+                .map(|t| {
+                    let summary = t.description().lines().next().unwrap_or_default();
+                    format!("{:width$}  {:13}  {summary}", t.name(), t.group().as_str())
+                })
+                .collect();
+            lines.join("\n")
+        }
+        Command::Schema { tool, output } => {
+            let tool = cli::find_tool(tools, &tool).map_err(|e| anyhow::anyhow!("{e}"))?;
+            let schema = if output {
+                tool.output_schema()
+            } else {
+                McpToolInfo::from_tool(tool).with_defaults(ctx.tool_defaults()).input_schema
+            };
+            serde_json::to_string_pretty(&schema)?
+        }
+        Command::Call { tool, args, args_file, structured } => {
+            let arguments = match (args, args_file) {
+                (Some(text), _) => serde_json::from_str(&text)
+                    .map_err(|e| anyhow::anyhow!("invalid --args: {e}"))?,
+                (None, Some(path)) => read_arguments(&path)?,
+                (None, None) => serde_json::json!({}),
+            };
+            let output = cli::call_tool(tools, ctx, &tool, arguments)
+                .await
+                .map_err(|e| anyhow::anyhow!("{tool}: {} ({})", e.to_user_message(), e.code()))?;
+            if structured {
+                serde_json::to_string_pretty(&output.structured)?
+            } else {
+                output.text.trim_end_matches('\n').to_owned()
+            }
+        }
+        Command::Batch { input } => {
+            let stdout = tokio::io::stdout();
+            let summary = if let Some(path) = input.filter(|p| p != Path::new("-")) {
+                let file = tokio::fs::File::open(&path).await.map_err(|e| {
+                    anyhow::anyhow!("cannot read batch file {}: {e}", path.display())
+                })?;
+                cli::run_batch(tools, ctx, tokio::io::BufReader::new(file), stdout).await
+            } else {
+                let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+                cli::run_batch(tools, ctx, stdin, stdout).await
+            };
+            let summary = match summary {
+                Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => return Ok(()),
+                result => result?,
+            };
+            tracing::info!(calls = summary.calls, failed = summary.failed, "Batch finished");
+            anyhow::ensure!(
+                summary.failed == 0,
+                "{} of {} calls failed",
+                summary.failed,
+                summary.calls
+            );
+            return Ok(());
+        }
+    };
+    // A closed pipe (e.g. `| head`) is not an error.
+    match writeln!(std::io::stdout(), "{text}") {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Read tool arguments from a JSON file, or stdin for `-`.
+fn read_arguments(path: &Path) -> anyhow::Result<serde_json::Value> {
+    let text = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("cannot read {}: {e}", path.display()))?
+    };
+    serde_json::from_str(&text).map_err(|e| anyhow::anyhow!("invalid {}: {e}", path.display()))
+}
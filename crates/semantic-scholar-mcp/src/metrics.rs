@@ -0,0 +1,129 @@
+//! Prometheus metrics.
+//!
+//! Each [`SemanticScholarClient`](crate::client::SemanticScholarClient) owns
+//! a [`ClientMetrics`] registry that records its upstream traffic; the HTTP
+//! server keeps tool calls, sessions and OAuth token issuance in a
+//! [`Metrics`] registry of its own and serves both at `/metrics`. Nothing is
+//! registered globally, so several servers can run in one process.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+
+pub use semantic_scholar_client::metrics::{ClientMetrics, endpoint_label};
+
+/// Buckets (seconds) for tool call durations.
+const DURATION_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// Metric families of one server.
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    registry: Registry,
+    /// Upstream metrics of the server's client, exposed with these.
+    client: Arc<ClientMetrics>,
+    /// Tool calls by `tool` and `outcome` (`ok`, `error`, `rejected`).
+    pub tool_calls: IntCounterVec,
+    /// Tool call duration by `tool`.
+    pub tool_duration: HistogramVec,
+    /// Sessions (across replicas sharing a session store).
+    pub active_sessions: IntGauge,
+    /// Open SSE streams on this replica.
+    pub sse_subscribers: IntGauge,
+    /// OAuth access tokens issued by `grant_type`.
+    pub oauth_tokens_issued: IntCounterVec,
+}
+
+impl Metrics {
+    /// Create and register every metric family, exposed together with
+    /// `client`'s.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: names and labels are static and valid.
+    #[must_use]
+    pub fn new(client: Arc<ClientMetrics>) -> Self {
+        let registry = Registry::new();
+        let counter = |name: &str, help: &str, labels: &[&str]| {
+            let metric = IntCounterVec::new(Opts::new(name, help), labels).unwrap();
+            registry.register(Box::new(metric.clone())).unwrap();
+            metric
+        };
+        let gauge = |name: &str, help: &str| {
+            let metric = IntGauge::new(name, help).unwrap();
+            registry.register(Box::new(metric.clone())).unwrap();
+            metric
+        };
+
+        let opts = HistogramOpts::new("mcp_tool_call_duration_seconds", "Tool call duration")
+            .buckets(DURATION_BUCKETS.to_vec());
+        let tool_duration = HistogramVec::new(opts, &["tool"]).unwrap();
+        registry.register(Box::new(tool_duration.clone())).unwrap();
+
+        Self {
+            tool_calls: counter(
+                "mcp_tool_calls_total",
+                "Tool calls by tool and outcome",
+                &["tool", "outcome"],
+            ),
+            tool_duration,
+            active_sessions: gauge("mcp_active_sessions", "MCP sessions"),
+            sse_subscribers: gauge("mcp_sse_subscribers", "Open SSE streams"),
+            oauth_tokens_issued: counter(
+                "mcp_oauth_tokens_issued_total",
+                "OAuth access tokens issued by grant type",
+                &["grant_type"],
+            ),
+            client,
+            registry,
+        }
+    }
+
+    /// Upstream metrics of the server's client.
+    #[must_use]
+    pub const fn client(&self) -> &Arc<ClientMetrics> {
+        &self.client
+    }
+
+    /// Record a finished tool call.
+    pub fn observe_tool_call(&self, tool: &str, outcome: &str, elapsed: Duration) {
+        self.tool_calls.with_label_values(&[tool, outcome]).inc();
+        self.tool_duration.with_label_values(&[tool]).observe(elapsed.as_secs_f64());
+    }
+
+    /// Everything, the client's families included, in the Prometheus text
+    /// exposition format.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let mut families = self.registry.gather();
+        families.extend(self.client.registry().gather());
+        families.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&families, &mut buffer) {
+            tracing::error!(error = %e, "Failed to encode metrics");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_includes_labels() {
+        let metrics = Metrics::new(Arc::new(ClientMetrics::new()));
+        metrics.observe_tool_call("paper_autocomplete", "ok", Duration::from_millis(20));
+        metrics.client().upstream_requests.with_label_values(&["/paper/search", "429"]).inc();
+
+        let text = metrics.encode();
+        assert!(text.contains(r#"mcp_tool_calls_total{outcome="ok",tool="paper_autocomplete"} 1"#));
+        assert!(
+            text.contains(r#"s2_upstream_requests_total{endpoint="/paper/search",status="429"} 1"#)
+        );
+        assert!(text.contains("mcp_tool_call_duration_seconds_bucket"));
+    }
+}
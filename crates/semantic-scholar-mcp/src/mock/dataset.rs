@@ -0,0 +1,434 @@
+//! Fixture dataset served by the mock API.
+//!
+//! A dataset is a directory with `papers.json`, an array of paper objects in
+//! the Graph API's shape, and optionally `authors.json` with author details.
+//! Each paper lists the IDs of the papers it cites under `references`;
+//! citations are the inverse. Authors not described in `authors.json` are
+//! taken from the papers' author lists, and their paper, citation and
+//! h-index counts are always computed from the papers.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
+use serde_json::{Map, Value, json};
+
+const SAMPLE_PAPERS: &str = include_str!("../../fixtures/mock/papers.json");
+const SAMPLE_AUTHORS: &str = include_str!("../../fixtures/mock/authors.json");
+
+/// Paper fields the mock understands; anything else is rejected like the
+/// real API does.
+pub const PAPER_FIELDS: &[&str] = &[
+    "paperId",
+    "corpusId",
+    "url",
+    "title",
+    "abstract",
+    "venue",
+    "publicationVenue",
+    "year",
+    "referenceCount",
+    "citationCount",
+    "influentialCitationCount",
+    "isOpenAccess",
+    "openAccessPdf",
+    "fieldsOfStudy",
+    "s2FieldsOfStudy",
+    "publicationTypes",
+    "publicationDate",
+    "journal",
+    "citationStyles",
+    "authors",
+    "citations",
+    "references",
+    "embedding",
+    "tldr",
+    "externalIds",
+];
+
+/// Author fields the mock understands.
+pub const AUTHOR_FIELDS: &[&str] = &[
+    "authorId",
+    "externalIds",
+    "url",
+    "name",
+    "affiliations",
+    "homepage",
+    "paperCount",
+    "citationCount",
+    "hIndex",
+    "papers",
+];
+
+/// Fields that are lists in the API, returned as `[]` rather than `null`
+/// when a fixture leaves them out.
+const LIST_FIELDS: &[&str] = &["authors", "citations", "references", "affiliations", "papers"];
+
+/// External ID kinds usable as `KIND:value` paper IDs, with the
+/// `externalIds` key each one is read from.
+const PAPER_ID_KINDS: &[(&str, &str)] = &[
+    ("corpusid", "CorpusId"),
+    ("doi", "DOI"),
+    ("arxiv", "ArXiv"),
+    ("mag", "MAG"),
+    ("acl", "ACL"),
+    ("pmid", "PubMed"),
+    ("pmcid", "PubMedCentral"),
+    ("dblp", "DBLP"),
+];
+
+/// Papers and authors served by the mock API.
+#[derive(Debug, Clone, Default)]
+pub struct Dataset {
+    papers: Vec<Map<String, Value>>,
+    references: Vec<Vec<usize>>,
+    citations: Vec<Vec<usize>>,
+    paper_index: HashMap<String, usize>,
+    authors: Vec<Map<String, Value>>,
+    author_papers: Vec<Vec<usize>>,
+    author_index: HashMap<String, usize>,
+}
+
+impl Dataset {
+    /// The dataset bundled with the crate: a small, interlinked set of
+    /// well-known machine learning papers.
+    ///
+    /// # Panics
+    ///
+    /// Never: the bundled fixtures are checked by the test suite.
+    #[must_use]
+    pub fn sample() -> Self {
+        Self::from_json(SAMPLE_PAPERS, Some(SAMPLE_AUTHORS)).expect("bundled fixtures are valid")
+    }
+
+    /// Load `papers.json` and, if present, `authors.json` from `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or is not valid.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let papers_path = dir.join("papers.json");
+        let papers = std::fs::read_to_string(&papers_path)
+            .with_context(|| format!("cannot read {}", papers_path.display()))?;
+        let authors_path = dir.join("authors.json");
+        let authors = match std::fs::read_to_string(&authors_path) {
+            Ok(text) => Some(text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).context(format!("cannot read {}", authors_path.display())),
+        };
+        Self::from_json(&papers, authors.as_deref())
+            .with_context(|| format!("invalid dataset in {}", dir.display()))
+    }
+
+    /// Build a dataset from the contents of `papers.json` and `authors.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed, a paper has no `paperId`,
+    /// or two papers share one.
+    pub fn from_json(papers: &str, authors: Option<&str>) -> anyhow::Result<Self> {
+        let papers: Vec<Map<String, Value>> =
+            serde_json::from_str(papers).context("papers.json must be an array of objects")?;
+        let described: Vec<Map<String, Value>> = match authors {
+            Some(text) => {
+                serde_json::from_str(text).context("authors.json must be an array of objects")?
+            }
+            None => Vec::new(),
+        };
+
+        let mut dataset = Self::default();
+        let mut cited_ids = Vec::with_capacity(papers.len());
+        for (i, mut paper) in papers.into_iter().enumerate() {
+            let id = paper
+                .get("paperId")
+                .and_then(Value::as_str)
+                .with_context(|| format!("paper {i} has no paperId"))?
+                .to_owned();
+            if dataset.paper_index.insert(id.to_lowercase(), i).is_some() {
+                anyhow::bail!("duplicate paperId {id}");
+            }
+            cited_ids.push(match paper.remove("references") {
+                Some(Value::Array(ids)) => ids,
+                Some(Value::Null) | None => Vec::new(),
+                Some(_) => anyhow::bail!("references of paper {id} must be an array of IDs"),
+            });
+            dataset.papers.push(paper);
+        }
+        for i in 0..dataset.papers.len() {
+            for (alias, _) in dataset.paper_aliases(i) {
+                dataset.paper_index.entry(alias).or_insert(i);
+            }
+        }
+
+        dataset.references = vec![Vec::new(); dataset.papers.len()];
+        dataset.citations = vec![Vec::new(); dataset.papers.len()];
+        for (i, ids) in cited_ids.into_iter().enumerate() {
+            // References to papers outside the dataset are dropped.
+            let cited: Vec<usize> =
+                ids.iter().filter_map(|id| dataset.paper(id.as_str()?)).collect();
+            for cited in cited {
+                dataset.references[i].push(cited);
+                dataset.citations[cited].push(i);
+            }
+        }
+
+        for author in described {
+            let id = author
+                .get("authorId")
+                .and_then(Value::as_str)
+                .context("every author needs an authorId")?
+                .to_owned();
+            dataset.author_index.insert(id, dataset.authors.len());
+            dataset.authors.push(author);
+            dataset.author_papers.push(Vec::new());
+        }
+        for (i, paper) in dataset.papers.iter().enumerate() {
+            for author in paper.get("authors").and_then(Value::as_array).into_iter().flatten() {
+                let Some(id) = author.get("authorId").and_then(Value::as_str) else {
+                    continue;
+                };
+                let slot = *dataset.author_index.entry(id.to_owned()).or_insert_with(|| {
+                    dataset.authors.push(Map::from_iter([
+                        ("authorId".to_owned(), json!(id)),
+                        ("name".to_owned(), author.get("name").cloned().unwrap_or(Value::Null)),
+                    ]));
+                    dataset.author_papers.push(Vec::new());
+                    dataset.authors.len() - 1
+                });
+                dataset.author_papers[slot].push(i);
+            }
+        }
+        Ok(dataset)
+    }
+
+    /// Number of papers.
+    #[must_use]
+    pub fn paper_count(&self) -> usize {
+        self.papers.len()
+    }
+
+    /// Number of authors, described or derived.
+    #[must_use]
+    pub fn author_count(&self) -> usize {
+        self.authors.len()
+    }
+
+    /// `KIND:value` aliases of a paper, with the original-case value.
+    fn paper_aliases(&self, paper: usize) -> Vec<(String, String)> {
+        let external = self.papers[paper].get("externalIds").and_then(Value::as_object);
+        let corpus_id = self.papers[paper].get("corpusId").filter(|v| !v.is_null());
+        PAPER_ID_KINDS
+            .iter()
+            .filter_map(|(kind, key)| {
+                let value = external.and_then(|ids| ids.get(*key)).or(match *kind {
+                    "corpusid" => corpus_id,
+                    _ => None,
+                })?;
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    _ => return None,
+                };
+                Some((format!("{kind}:{}", value.to_lowercase()), value))
+            })
+            .collect()
+    }
+
+    /// Resolve a paper ID: a Semantic Scholar ID or `KIND:value` (e.g.
+    /// `DOI:10.18653/v1/N19-1423`, `ARXIV:1706.03762`, `CorpusId:13756489`).
+    #[must_use]
+    pub fn paper(&self, id: &str) -> Option<usize> {
+        self.paper_index.get(&id.trim().to_lowercase()).copied()
+    }
+
+    /// Resolve an author ID.
+    #[must_use]
+    pub fn author(&self, id: &str) -> Option<usize> {
+        self.author_index.get(id.trim()).copied()
+    }
+
+    /// Every paper, in fixture order.
+    pub fn papers(&self) -> impl Iterator<Item = usize> + '_ {
+        0..self.papers.len()
+    }
+
+    /// Every author.
+    pub fn authors(&self) -> impl Iterator<Item = usize> + '_ {
+        0..self.authors.len()
+    }
+
+    /// Papers cited by `paper`.
+    #[must_use]
+    pub fn references_of(&self, paper: usize) -> &[usize] {
+        &self.references[paper]
+    }
+
+    /// Papers citing `paper`.
+    #[must_use]
+    pub fn citations_of(&self, paper: usize) -> &[usize] {
+        &self.citations[paper]
+    }
+
+    /// Papers written by `author`.
+    #[must_use]
+    pub fn papers_of(&self, author: usize) -> &[usize] {
+        &self.author_papers[author]
+    }
+
+    /// A stored paper field, after filling in derived values.
+    #[must_use]
+    pub fn paper_field(&self, paper: usize, field: &str) -> Value {
+        let stored = self.papers[paper].get(field).filter(|v| !v.is_null());
+        match (field, stored) {
+            (_, Some(value)) if field != "citations" && field != "references" => value.clone(),
+            ("referenceCount", None) => json!(self.references[paper].len()),
+            ("citationCount", None) => json!(self.citations[paper].len()),
+            ("url", None) => {
+                json!(format!("https://www.semanticscholar.org/paper/{}", self.paper_id(paper)))
+            }
+            ("corpusId", None) => self.papers[paper]
+                .get("externalIds")
+                .and_then(|ids| ids.get("CorpusId"))
+                .cloned()
+                .unwrap_or(Value::Null),
+            ("citations" | "references", _) => {
+                let linked =
+                    if field == "citations" { &self.citations } else { &self.references }[paper]
+                        .iter()
+                        .map(|&p| json!({"paperId": self.paper_id(p), "title": self.paper_field(p, "title")}))
+                        .collect();
+                Value::Array(linked)
+            }
+            _ if LIST_FIELDS.contains(&field) => json!([]),
+            _ => Value::Null,
+        }
+    }
+
+    /// The paper's Semantic Scholar ID.
+    #[must_use]
+    pub fn paper_id(&self, paper: usize) -> &str {
+        self.papers[paper].get("paperId").and_then(Value::as_str).unwrap_or_default()
+    }
+
+    /// The paper's title and abstract, lowercased, for matching queries.
+    #[must_use]
+    pub fn paper_text(&self, paper: usize) -> String {
+        let field = |name| self.papers[paper].get(name).and_then(Value::as_str).unwrap_or_default();
+        format!("{} {}", field("title"), field("abstract")).to_lowercase()
+    }
+
+    /// The paper as the API returns it with `fields` selected.
+    #[must_use]
+    pub fn project_paper(&self, paper: usize, fields: &[String]) -> Value {
+        let mut out = Map::new();
+        out.insert("paperId".to_owned(), json!(self.paper_id(paper)));
+        for field in fields {
+            if field != "paperId" {
+                out.insert(field.clone(), self.paper_field(paper, field));
+            }
+        }
+        Value::Object(out)
+    }
+
+    /// A stored author field, after filling in counts computed from papers.
+    #[must_use]
+    pub fn author_field(&self, author: usize, field: &str) -> Value {
+        let papers = &self.author_papers[author];
+        let citation_counts =
+            || papers.iter().map(|&p| self.paper_field(p, "citationCount").as_u64().unwrap_or(0));
+        match field {
+            "paperCount" => json!(papers.len()),
+            "citationCount" => json!(citation_counts().sum::<u64>()),
+            "hIndex" => {
+                let mut counts: Vec<u64> = citation_counts().collect();
+                counts.sort_unstable_by(|a, b| b.cmp(a));
+                json!(counts.iter().zip(1..).take_while(|(c, rank)| **c >= *rank).count())
+            }
+            "url" => json!(format!(
+                "https://www.semanticscholar.org/author/{}",
+                self.author_id(author)
+            )),
+            "papers" => Value::Array(
+                papers
+                    .iter()
+                    .map(|&p| json!({"paperId": self.paper_id(p), "title": self.paper_field(p, "title")}))
+                    .collect(),
+            ),
+            _ => match self.authors[author].get(field).filter(|v| !v.is_null()) {
+                Some(value) => value.clone(),
+                None if LIST_FIELDS.contains(&field) => json!([]),
+                None => Value::Null,
+            },
+        }
+    }
+
+    /// The author's Semantic Scholar ID.
+    #[must_use]
+    pub fn author_id(&self, author: usize) -> &str {
+        self.authors[author].get("authorId").and_then(Value::as_str).unwrap_or_default()
+    }
+
+    /// The author's name, lowercased, for matching queries.
+    #[must_use]
+    pub fn author_name(&self, author: usize) -> String {
+        self.authors[author].get("name").and_then(Value::as_str).unwrap_or_default().to_lowercase()
+    }
+
+    /// The author as the API returns it with `fields` selected.
+    #[must_use]
+    pub fn project_author(&self, author: usize, fields: &[String]) -> Value {
+        let mut out = Map::new();
+        out.insert("authorId".to_owned(), json!(self.author_id(author)));
+        for field in fields {
+            if field != "authorId" {
+                out.insert(field.clone(), self.author_field(author, field));
+            }
+        }
+        Value::Object(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_links_citations_and_aliases() {
+        let data = Dataset::sample();
+        assert!(data.paper_count() >= 15);
+
+        let attention = data.paper("ARXIV:1706.03762").unwrap();
+        assert_eq!(data.paper("CorpusId:13756489"), Some(attention));
+        assert_eq!(data.paper(&data.paper_id(attention).to_uppercase()), Some(attention));
+        let bert = data.paper("doi:10.18653/V1/N19-1423").unwrap();
+        assert!(data.references_of(bert).contains(&attention));
+        assert!(data.citations_of(attention).contains(&bert));
+        assert_eq!(data.paper_field(bert, "referenceCount"), json!(data.references_of(bert).len()));
+    }
+
+    #[test]
+    fn test_projection_fills_missing_fields() {
+        let data = Dataset::from_json(
+            r#"[{"paperId": "p1", "title": "One", "references": ["p2", "missing"]},
+                {"paperId": "p2", "authors": [{"authorId": "a1", "name": "Ada"}]}]"#,
+            None,
+        )
+        .unwrap();
+        let fields = ["title", "year", "authors"].map(str::to_owned);
+        assert_eq!(
+            data.project_paper(0, &fields),
+            json!({"paperId": "p1", "title": "One", "year": null, "authors": []})
+        );
+        assert_eq!(data.references_of(0), [1]);
+
+        let ada = data.author("a1").unwrap();
+        let fields = ["name", "paperCount", "affiliations"].map(str::to_owned);
+        assert_eq!(
+            data.project_author(ada, &fields),
+            json!({"authorId": "a1", "name": "Ada", "paperCount": 1, "affiliations": []})
+        );
+
+        let error = Dataset::from_json(r#"[{"paperId": "p1"}, {"paperId": "p1"}]"#, None);
+        assert!(error.unwrap_err().to_string().contains("duplicate"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Injectable faults: rate limiting, server errors and latency.
+//!
+//! Faults are applied to API requests (not to the `/_mock` admin endpoints)
+//! before they reach a handler. Deterministic faults fire on every Nth
+//! matching request; probabilistic ones draw from a seeded generator so a
+//! run can be reproduced.
+
+use std::time::Duration;
+
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+
+/// Faults to inject, as set on the command line or with `PUT /_mock/faults`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct Faults {
+    /// Answer every Nth matching request with `429 Too Many Requests`.
+    pub rate_limit_every: Option<u64>,
+    /// Probability of answering a matching request with 429.
+    pub rate_limit_probability: f64,
+    /// `Retry-After` seconds sent with injected 429s (none if unset).
+    pub retry_after_secs: Option<u64>,
+    /// Answer every Nth matching request with `error_status`.
+    pub error_every: Option<u64>,
+    /// Probability of answering a matching request with `error_status`.
+    pub error_probability: f64,
+    /// Status of injected errors.
+    pub error_status: u16,
+    /// Delay added to every matching request.
+    pub latency_ms: u64,
+    /// Only requests whose path starts with this are affected.
+    pub path_prefix: Option<String>,
+    /// Seed for the probabilistic faults.
+    pub seed: u64,
+}
+
+impl Default for Faults {
+    fn default() -> Self {
+        Self {
+            rate_limit_every: None,
+            rate_limit_probability: 0.0,
+            retry_after_secs: None,
+            error_every: None,
+            error_probability: 0.0,
+            error_status: 500,
+            latency_ms: 0,
+            path_prefix: None,
+            seed: 1,
+        }
+    }
+}
+
+/// A fault chosen for one request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Answer with 429.
+    RateLimited {
+        /// `Retry-After` to send, if any.
+        retry_after_secs: Option<u64>,
+    },
+    /// Answer with this server error status.
+    Error(u16),
+}
+
+impl IntoResponse for Fault {
+    fn into_response(self) -> Response {
+        match self {
+            Self::RateLimited { retry_after_secs } => {
+                let body = Json(serde_json::json!({
+                    "message": "Too Many Requests. Please wait and try again or apply for a key for higher rate limits. https://www.semanticscholar.org/product/api#api-key-form",
+                    "code": "429"
+                }));
+                let mut res = (StatusCode::TOO_MANY_REQUESTS, body).into_response();
+                if let Some(secs) = retry_after_secs {
+                    res.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(secs));
+                }
+                res
+            }
+            Self::Error(status) => {
+                let status =
+                    StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                let message = status.canonical_reason().unwrap_or("Internal Server Error");
+                (status, Json(serde_json::json!({"message": message}))).into_response()
+            }
+        }
+    }
+}
+
+/// Request and fault counters, returned by `GET /_mock/stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultStats {
+    /// API requests received.
+    pub requests: u64,
+    /// Requests answered with an injected 429.
+    pub rate_limited: u64,
+    /// Requests answered with an injected server error.
+    pub errors: u64,
+}
+
+/// Fault settings with the state needed to apply them.
+#[derive(Debug)]
+pub struct FaultInjector {
+    faults: Faults,
+    matched: u64,
+    rng: u64,
+    stats: FaultStats,
+}
+
+impl FaultInjector {
+    /// Start injecting `faults`.
+    #[must_use]
+    pub fn new(faults: Faults) -> Self {
+        // xorshift gets stuck at zero.
+        let rng = faults.seed.max(1);
+        Self { faults, matched: 0, rng, stats: FaultStats::default() }
+    }
+
+    /// Current settings.
+    #[must_use]
+    pub fn faults(&self) -> &Faults {
+        &self.faults
+    }
+
+    /// Replace the settings, restarting the every-Nth counters; the request
+    /// statistics are kept.
+    pub fn set(&mut self, faults: Faults) {
+        let stats = self.stats;
+        *self = Self::new(faults);
+        self.stats = stats;
+    }
+
+    /// Counters so far.
+    #[must_use]
+    pub fn stats(&self) -> FaultStats {
+        self.stats
+    }
+
+    /// Count a request to `path` and choose the fault for it, if any,
+    /// along with the latency to add first.
+    pub fn next(&mut self, path: &str) -> (Duration, Option<Fault>) {
+        self.stats.requests += 1;
+        if self.faults.path_prefix.as_deref().is_some_and(|p| !path.starts_with(p)) {
+            return (Duration::ZERO, None);
+        }
+        self.matched += 1;
+        let latency = Duration::from_millis(self.faults.latency_ms);
+        let nth = |every: Option<u64>, n: u64| every.is_some_and(|e| e > 0 && n.is_multiple_of(e));
+
+        let fault = if nth(self.faults.rate_limit_every, self.matched)
+            || self.chance(self.faults.rate_limit_probability)
+        {
+            self.stats.rate_limited += 1;
+            Some(Fault::RateLimited { retry_after_secs: self.faults.retry_after_secs })
+        } else if nth(self.faults.error_every, self.matched)
+            || self.chance(self.faults.error_probability)
+        {
+            self.stats.errors += 1;
+            Some(Fault::Error(self.faults.error_status))
+        } else {
+            None
+        };
+        (latency, fault)
+    }
+
+    /// Draw from the xorshift generator.
+    fn chance(&mut self, probability: f64) -> bool {
+        if probability <= 0.0 {
+            return false;
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        #[allow(clippy::cast_precision_loss)]
+        let draw = (self.rng >> 11) as f64 / (1u64 << 53) as f64;
+        draw < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_nth_and_path_prefix() {
+        let mut injector = FaultInjector::new(Faults {
+            rate_limit_every: Some(2),
+            error_every: Some(3),
+            path_prefix: Some("/graph/".to_owned()),
+            ..Faults::default()
+        });
+        let faults: Vec<_> = ["/graph/v1/a", "/graph/v1/b", "/recommendations/v1/c", "/graph/v1/d"]
+            .iter()
+            .map(|path| injector.next(path).1)
+            .collect();
+        assert_eq!(
+            faults,
+            [
+                None,
+                Some(Fault::RateLimited { retry_after_secs: None }),
+                None,
+                Some(Fault::Error(500))
+            ]
+        );
+        assert_eq!(injector.stats(), FaultStats { requests: 4, rate_limited: 1, errors: 1 });
+
+        injector.set(Faults::default());
+        assert_eq!(injector.next("/graph/v1/e").1, None);
+        assert_eq!(injector.stats().requests, 5);
+    }
+
+    #[test]
+    fn test_probabilities_are_reproducible() {
+        let draws = |seed| {
+            let mut injector =
+                FaultInjector::new(Faults { error_probability: 0.5, seed, ..Faults::default() });
+            (0..200).map(|_| injector.next("/").1.is_some()).collect::<Vec<_>>()
+        };
+        let first = draws(7);
+        assert_eq!(first, draws(7));
+        let hits = first.iter().filter(|hit| **hit).count();
+        assert!((60..140).contains(&hits), "{hits}");
+    }
+}
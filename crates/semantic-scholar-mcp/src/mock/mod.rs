@@ -0,0 +1,715 @@
+//! Mock Semantic Scholar API for offline development and tests.
+//!
+//! Serves the Graph API endpoints the client uses under `/graph/v1` and the
+//! Recommendations API under `/recommendations/v1`, from a [`Dataset`] on
+//! disk, so pointing `graph_api_url` and `recommendations_api_url` at it
+//! (the `offline` profile in `config.example.toml`) runs every tool without
+//! network access. Responses follow the real API: field selection,
+//! `offset`/`limit`/`next` pagination with its limits, opaque continuation
+//! tokens for bulk search, `null` entries for unknown IDs in batches, and
+//! `{"error": ...}` bodies for 400 and 404.
+//!
+//! [`Faults`] make it misbehave on purpose (429 with or without
+//! `Retry-After`, 5xx, latency). They can be changed while it runs:
+//!
+//! - `GET /_mock/faults` / `PUT /_mock/faults` read and replace the faults
+//! - `GET /_mock/stats` returns request and injected-fault counters
+
+pub mod dataset;
+pub mod faults;
+mod query;
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+pub use dataset::Dataset;
+pub use faults::{FaultStats, Faults};
+
+use dataset::{AUTHOR_FIELDS, PAPER_FIELDS};
+use faults::FaultInjector;
+use query::{BooleanQuery, Filters, parse_fields, parse_number, score, words};
+
+/// Default port of the mock server, as used by the `offline` profile.
+pub const DEFAULT_PORT: u16 = 8900;
+
+/// Papers per bulk search page in the real API.
+pub const DEFAULT_BULK_PAGE_SIZE: usize = 1000;
+
+/// Largest batch the paper batch endpoint accepts.
+const MAX_PAPER_BATCH: usize = 500;
+
+/// Largest batch the author batch endpoint accepts.
+const MAX_AUTHOR_BATCH: usize = 1000;
+
+/// Relevance search only pages through this many results.
+const SEARCH_WINDOW: usize = 1000;
+
+/// Options for [`router`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockOptions {
+    /// Faults to inject from the start.
+    pub faults: Faults,
+    /// Papers per bulk search page; small values exercise token paging.
+    pub bulk_page_size: usize,
+}
+
+impl Default for MockOptions {
+    fn default() -> Self {
+        Self { faults: Faults::default(), bulk_page_size: DEFAULT_BULK_PAGE_SIZE }
+    }
+}
+
+struct MockState {
+    data: Dataset,
+    bulk_page_size: usize,
+    faults: Mutex<FaultInjector>,
+}
+
+impl MockState {
+    fn faults(&self) -> std::sync::MutexGuard<'_, FaultInjector> {
+        self.faults.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Build the mock API serving `data`.
+pub fn router(data: Dataset, options: MockOptions) -> Router {
+    let state = Arc::new(MockState {
+        data,
+        bulk_page_size: options.bulk_page_size.max(1),
+        faults: Mutex::new(FaultInjector::new(options.faults)),
+    });
+    Router::new()
+        .route("/graph/v1/paper/search", get(paper_search))
+        .route("/graph/v1/paper/search/bulk", get(paper_search_bulk))
+        .route("/graph/v1/paper/search/match", get(paper_title_match))
+        .route("/graph/v1/paper/autocomplete", get(paper_autocomplete))
+        .route("/graph/v1/paper/batch", post(paper_batch))
+        // Paper IDs such as `DOI:10.18653/v1/N19-1423` contain slashes.
+        .route("/graph/v1/paper/{*path}", get(paper_path))
+        .route("/graph/v1/author/search", get(author_search))
+        .route("/graph/v1/author/batch", post(author_batch))
+        .route("/graph/v1/author/{id}", get(author_details))
+        .route("/graph/v1/author/{id}/papers", get(author_papers))
+        .route("/graph/v1/snippet/search", get(snippet_search))
+        .route("/recommendations/v1/papers/forpaper/{*id}", get(recommend_for_paper))
+        .route("/recommendations/v1/papers", post(recommend_for_papers))
+        .route("/recommendations/v1/papers/", post(recommend_for_papers))
+        .route("/_mock/faults", get(get_faults).put(put_faults))
+        .route("/_mock/stats", get(get_stats))
+        .fallback(|| async { ApiError::not_found("Endpoint not found") })
+        .layer(axum::middleware::from_fn_with_state(Arc::clone(&state), inject_faults))
+        .with_state(state)
+}
+
+/// Error response in the API's `{"error": ...}` shape.
+#[derive(Debug)]
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self(StatusCode::BAD_REQUEST, message.into())
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self(StatusCode::NOT_FOUND, message.into())
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        Self::bad_request(message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({"error": self.1}))).into_response()
+    }
+}
+
+type ApiResult = Result<Json<Value>, ApiError>;
+type Params = Query<HashMap<String, String>>;
+
+async fn inject_faults(
+    State(state): State<Arc<MockState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_owned();
+    if path.starts_with("/_mock/") {
+        return next.run(request).await;
+    }
+    let (latency, fault) = state.faults().next(&path);
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+    if let Some(fault) = fault {
+        tracing::debug!(path, ?fault, "Injecting fault");
+        return fault.into_response();
+    }
+    next.run(request).await
+}
+
+async fn get_faults(State(state): State<Arc<MockState>>) -> Json<Faults> {
+    Json(state.faults().faults().clone())
+}
+
+async fn put_faults(
+    State(state): State<Arc<MockState>>,
+    Json(faults): Json<Faults>,
+) -> Json<Faults> {
+    tracing::info!(?faults, "Faults updated");
+    state.faults().set(faults.clone());
+    Json(faults)
+}
+
+async fn get_stats(State(state): State<Arc<MockState>>) -> Json<FaultStats> {
+    Json(state.faults().stats())
+}
+
+/// The page of `total` items selected by `offset` and `limit`.
+///
+/// Requests reaching past `window` items are rejected like the real API
+/// does for relevance search.
+fn page(
+    params: &HashMap<String, String>,
+    default_limit: usize,
+    max_limit: usize,
+    window: Option<usize>,
+) -> Result<(usize, usize), ApiError> {
+    let offset = parse_number(params, "offset", 0)?;
+    let limit = parse_number(params, "limit", default_limit)?;
+    if limit > max_limit {
+        return Err(ApiError::bad_request(format!("Limit must be <= {max_limit}")));
+    }
+    if window.is_some_and(|w| offset + limit > w) {
+        return Err(ApiError::bad_request(
+            "Requested data for this limit and/or offset is not available",
+        ));
+    }
+    Ok((offset, limit))
+}
+
+/// A paginated response body: `total` only where the API reports it, and
+/// `next` only when there are more items.
+fn paginated(data: Vec<Value>, range: Range<usize>, total: usize, with_total: bool) -> Value {
+    let mut body = json!({"offset": range.start});
+    body["data"] = Value::Array(data);
+    if range.end < total {
+        body["next"] = json!(range.end);
+    }
+    if with_total {
+        body["total"] = json!(total);
+    }
+    body
+}
+
+fn slice(total: usize, offset: usize, limit: usize) -> Range<usize> {
+    offset.min(total)..(offset + limit).min(total)
+}
+
+fn required<'a>(params: &'a HashMap<String, String>, name: &str) -> Result<&'a str, ApiError> {
+    params
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| ApiError::bad_request(format!("Missing required parameter: '{name}'")))
+}
+
+fn paper_fields(params: &HashMap<String, String>, prefix: &str) -> Result<Vec<String>, ApiError> {
+    Ok(parse_fields(params.get("fields"), prefix, PAPER_FIELDS, &["title"])?)
+}
+
+fn author_fields(params: &HashMap<String, String>) -> Result<Vec<String>, ApiError> {
+    Ok(parse_fields(params.get("fields"), "", AUTHOR_FIELDS, &["name"])?)
+}
+
+fn citation_count(data: &Dataset, paper: usize) -> u64 {
+    data.paper_field(paper, "citationCount").as_u64().unwrap_or(0)
+}
+
+async fn paper_search(State(state): State<Arc<MockState>>, Query(params): Params) -> ApiResult {
+    let data = &state.data;
+    let terms = words(required(&params, "query")?);
+    let filters = Filters::parse(&params, data)?;
+    let fields = paper_fields(&params, "")?;
+    let (offset, limit) = page(&params, 100, 100, Some(SEARCH_WINDOW))?;
+
+    let mut hits: Vec<(usize, usize)> = data
+        .papers()
+        .map(|p| (p, score(&data.paper_text(p), &terms)))
+        .filter(|&(p, score)| score > 0 && filters.matches(data, p))
+        .collect();
+    hits.sort_by_key(|&(p, score)| {
+        (std::cmp::Reverse(score), std::cmp::Reverse(citation_count(data, p)))
+    });
+
+    let range = slice(hits.len(), offset, limit);
+    let page = hits[range.clone()].iter().map(|&(p, _)| data.project_paper(p, &fields)).collect();
+    Ok(Json(paginated(page, range, hits.len(), true)))
+}
+
+async fn paper_search_bulk(
+    State(state): State<Arc<MockState>>,
+    Query(params): Params,
+) -> ApiResult {
+    let data = &state.data;
+    let query = BooleanQuery::parse(params.get("query").map_or("", String::as_str));
+    let filters = Filters::parse(&params, data)?;
+    let fields = paper_fields(&params, "")?;
+    let offset = match params.get("token") {
+        Some(token) => decode_token(token).ok_or_else(|| ApiError::bad_request("Invalid token"))?,
+        None => 0,
+    };
+
+    let mut hits: Vec<usize> = data
+        .papers()
+        .filter(|&p| query.matches(&data.paper_text(p)) && filters.matches(data, p))
+        .collect();
+    sort_bulk(data, &mut hits, params.get("sort").map(String::as_str))?;
+
+    let range = slice(hits.len(), offset, state.bulk_page_size);
+    let token = (range.end < hits.len()).then(|| encode_token(range.end));
+    let page: Vec<Value> = hits[range].iter().map(|&p| data.project_paper(p, &fields)).collect();
+    Ok(Json(json!({"total": hits.len(), "token": token, "data": page})))
+}
+
+fn encode_token(offset: usize) -> String {
+    URL_SAFE_NO_PAD.encode(format!("mock:{offset}"))
+}
+
+fn decode_token(token: &str) -> Option<usize> {
+    let decoded = URL_SAFE_NO_PAD.decode(token).ok()?;
+    String::from_utf8(decoded).ok()?.strip_prefix("mock:")?.parse().ok()
+}
+
+/// Order bulk results by `field[:asc|desc]` (`paperId`, `publicationDate`
+/// or `citationCount`; `paperId:asc` by default).
+fn sort_bulk(data: &Dataset, hits: &mut [usize], sort: Option<&str>) -> Result<(), ApiError> {
+    let sort = sort.unwrap_or("paperId:asc");
+    let (field, order) = sort.split_once(':').unwrap_or((sort, "asc"));
+    let descending = match order {
+        "asc" => false,
+        "desc" => true,
+        _ => return Err(ApiError::bad_request(format!("Invalid sort order: {order}"))),
+    };
+    match field {
+        "paperId" => hits.sort_by(|&a, &b| data.paper_id(a).cmp(data.paper_id(b))),
+        "publicationDate" => hits.sort_by_key(|&p| {
+            let published = data.paper_field(p, "publicationDate");
+            // Papers without a date sort first, as the real API does.
+            published.as_str().map(str::to_owned).unwrap_or_default()
+        }),
+        "citationCount" => hits.sort_by_key(|&p| citation_count(data, p)),
+        _ => return Err(ApiError::bad_request(format!("Invalid sort field: {field}"))),
+    }
+    if descending {
+        hits.reverse();
+    }
+    Ok(())
+}
+
+async fn paper_title_match(
+    State(state): State<Arc<MockState>>,
+    Query(params): Params,
+) -> ApiResult {
+    let data = &state.data;
+    let wanted: HashSet<String> = words(required(&params, "query")?).into_iter().collect();
+    let fields = paper_fields(&params, "")?;
+
+    // Jaccard similarity of title words; weak matches count as no match.
+    let best = data
+        .papers()
+        .filter_map(|p| {
+            let title = data.paper_field(p, "title");
+            let title: HashSet<String> = words(title.as_str()?).into_iter().collect();
+            let union = title.union(&wanted).count();
+            #[allow(clippy::cast_precision_loss)]
+            let similarity = title.intersection(&wanted).count() as f64 / union.max(1) as f64;
+            Some((p, similarity))
+        })
+        .filter(|&(_, similarity)| similarity >= 0.8)
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    let Some((paper, similarity)) = best else {
+        return Err(ApiError::not_found("Title match not found"));
+    };
+    let mut matched = data.project_paper(paper, &fields);
+    matched["matchScore"] = json!((similarity * 100.0).round());
+    Ok(Json(json!({"data": [matched]})))
+}
+
+async fn paper_autocomplete(
+    State(state): State<Arc<MockState>>,
+    Query(params): Params,
+) -> ApiResult {
+    let data = &state.data;
+    let query = required(&params, "query")?.to_lowercase();
+    let matches: Vec<Value> = data
+        .papers()
+        .filter(|&p| {
+            data.paper_field(p, "title").as_str().is_some_and(|t| t.to_lowercase().contains(&query))
+        })
+        .take(10)
+        .map(|p| {
+            let first_author =
+                data.paper_field(p, "authors")[0]["name"].as_str().map(str::to_owned);
+            let year = data.paper_field(p, "year");
+            json!({
+                "id": data.paper_id(p),
+                "title": data.paper_field(p, "title"),
+                "authorsYear": match (first_author, year.as_i64()) {
+                    (Some(author), Some(year)) => format!("{author}, {year}"),
+                    (Some(author), None) => author,
+                    (None, Some(year)) => year.to_string(),
+                    (None, None) => String::new(),
+                },
+            })
+        })
+        .collect();
+    Ok(Json(json!({"matches": matches})))
+}
+
+#[derive(Deserialize)]
+struct BatchBody {
+    ids: Vec<String>,
+}
+
+async fn paper_batch(
+    State(state): State<Arc<MockState>>,
+    Query(params): Params,
+    body: Option<Json<BatchBody>>,
+) -> ApiResult {
+    let data = &state.data;
+    let Some(Json(body)) = body else {
+        return Err(ApiError::bad_request("Body must be {\"ids\": [...]}"));
+    };
+    if body.ids.len() > MAX_PAPER_BATCH {
+        return Err(ApiError::bad_request(format!(
+            "Cannot process more than {MAX_PAPER_BATCH} ids"
+        )));
+    }
+    let fields = paper_fields(&params, "")?;
+    let papers = body
+        .ids
+        .iter()
+        .map(|id| data.paper(id).map_or(Value::Null, |p| data.project_paper(p, &fields)))
+        .collect();
+    Ok(Json(Value::Array(papers)))
+}
+
+/// `/paper/{id}` and its `/citations`, `/references` and `/authors`.
+async fn paper_path(
+    State(state): State<Arc<MockState>>,
+    Path(path): Path<String>,
+    Query(params): Params,
+) -> ApiResult {
+    let data = &state.data;
+    let (id, relation) = ["citations", "references", "authors"]
+        .iter()
+        .find_map(|r| Some((path.strip_suffix(&format!("/{r}"))?, *r)))
+        .unwrap_or((path.as_str(), ""));
+    let paper = data
+        .paper(id)
+        .ok_or_else(|| ApiError::not_found(format!("Paper with id {id} not found")))?;
+
+    match relation {
+        "citations" | "references" => {
+            let (key, linked) = if relation == "citations" {
+                ("citingPaper", data.citations_of(paper))
+            } else {
+                ("citedPaper", data.references_of(paper))
+            };
+            let fields = paper_fields(&params, &format!("{key}."))?;
+            let (offset, limit) = page(&params, 100, 1000, None)?;
+            let range = slice(linked.len(), offset, limit);
+            let page = linked[range.clone()]
+                .iter()
+                .map(|&other| {
+                    // Influence is not in the fixtures; call citations from
+                    // highly cited papers influential.
+                    let citing = if relation == "citations" { other } else { paper };
+                    json!({
+                        key: data.project_paper(other, &fields),
+                        "contexts": [],
+                        "intents": [],
+                        "isInfluential": citation_count(data, citing) >= 10_000,
+                    })
+                })
+                .collect();
+            Ok(Json(paginated(page, range, linked.len(), false)))
+        }
+        "authors" => {
+            let fields = author_fields(&params)?;
+            let (offset, limit) = page(&params, 100, 1000, None)?;
+            let authors: Vec<Value> = data
+                .paper_field(paper, "authors")
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|a| paper_author(data, a, &fields))
+                .collect();
+            let range = slice(authors.len(), offset, limit);
+            let total = authors.len();
+            Ok(Json(paginated(authors[range.clone()].to_vec(), range, total, false)))
+        }
+        _ => Ok(Json(data.project_paper(paper, &paper_fields(&params, "")?))),
+    }
+}
+
+/// An entry of a paper's author list with `fields` selected; authors
+/// without an ID have nothing but their name.
+fn paper_author(data: &Dataset, entry: &Value, fields: &[String]) -> Value {
+    if let Some(author) = entry["authorId"].as_str().and_then(|id| data.author(id)) {
+        return data.project_author(author, fields);
+    }
+    let mut anonymous = json!({"authorId": null});
+    for field in fields {
+        anonymous[field] = if field == "name" { entry["name"].clone() } else { Value::Null };
+    }
+    anonymous
+}
+
+async fn author_search(State(state): State<Arc<MockState>>, Query(params): Params) -> ApiResult {
+    let data = &state.data;
+    let terms = words(required(&params, "query")?);
+    let fields = author_fields(&params)?;
+    let (offset, limit) = page(&params, 100, 1000, Some(SEARCH_WINDOW))?;
+
+    let mut hits: Vec<usize> = data
+        .authors()
+        .filter(|&a| !terms.is_empty() && score(&data.author_name(a), &terms) == terms.len())
+        .collect();
+    hits.sort_by_key(|&a| std::cmp::Reverse(data.author_field(a, "citationCount").as_u64()));
+
+    let range = slice(hits.len(), offset, limit);
+    let page = hits[range.clone()].iter().map(|&a| data.project_author(a, &fields)).collect();
+    Ok(Json(paginated(page, range, hits.len(), true)))
+}
+
+async fn author_batch(
+    State(state): State<Arc<MockState>>,
+    Query(params): Params,
+    body: Option<Json<BatchBody>>,
+) -> ApiResult {
+    let data = &state.data;
+    let Some(Json(body)) = body else {
+        return Err(ApiError::bad_request("Body must be {\"ids\": [...]}"));
+    };
+    if body.ids.len() > MAX_AUTHOR_BATCH {
+        return Err(ApiError::bad_request(format!(
+            "Cannot process more than {MAX_AUTHOR_BATCH} ids"
+        )));
+    }
+    let fields = author_fields(&params)?;
+    let authors = body
+        .ids
+        .iter()
+        .map(|id| data.author(id).map_or(Value::Null, |a| data.project_author(a, &fields)))
+        .collect();
+    Ok(Json(Value::Array(authors)))
+}
+
+fn find_author(data: &Dataset, id: &str) -> Result<usize, ApiError> {
+    data.author(id).ok_or_else(|| ApiError::not_found(format!("Author with id {id} not found")))
+}
+
+async fn author_details(
+    State(state): State<Arc<MockState>>,
+    Path(id): Path<String>,
+    Query(params): Params,
+) -> ApiResult {
+    let author = find_author(&state.data, &id)?;
+    Ok(Json(state.data.project_author(author, &author_fields(&params)?)))
+}
+
+async fn author_papers(
+    State(state): State<Arc<MockState>>,
+    Path(id): Path<String>,
+    Query(params): Params,
+) -> ApiResult {
+    let data = &state.data;
+    let author = find_author(data, &id)?;
+    let fields = paper_fields(&params, "")?;
+    let (offset, limit) = page(&params, 100, 1000, None)?;
+
+    // Newest first, like the real endpoint.
+    let mut papers = data.papers_of(author).to_vec();
+    papers.sort_by_key(|&p| std::cmp::Reverse(data.paper_field(p, "year").as_i64()));
+
+    let range = slice(papers.len(), offset, limit);
+    let page = papers[range.clone()].iter().map(|&p| data.project_paper(p, &fields)).collect();
+    Ok(Json(paginated(page, range, papers.len(), false)))
+}
+
+async fn snippet_search(State(state): State<Arc<MockState>>, Query(params): Params) -> ApiResult {
+    let data = &state.data;
+    let terms = words(required(&params, "query")?);
+    let filters = Filters::parse(&params, data)?;
+    let limit = parse_number(&params, "limit", 10)?;
+    if limit > 1000 {
+        return Err(ApiError::bad_request("Limit must be <= 1000"));
+    }
+
+    // The best-matching sentence of each paper's title or abstract.
+    let mut hits: Vec<(usize, usize, &str, String)> = Vec::new();
+    for paper in data.papers().filter(|&p| filters.matches(data, p)) {
+        let title = data.paper_field(paper, "title");
+        let abstract_ = data.paper_field(paper, "abstract");
+        let sentences = title.as_str().map(|t| ("title", t.to_owned())).into_iter().chain(
+            abstract_.as_str().into_iter().flat_map(|a| {
+                a.split_inclusive(". ")
+                    .map(|s| ("abstract", s.trim().to_owned()))
+                    .collect::<Vec<_>>()
+            }),
+        );
+        if let Some((kind, text, hits_in_text)) = sentences
+            .map(|(kind, text)| {
+                let n = score(&text, &terms);
+                (kind, text, n)
+            })
+            .max_by_key(|&(_, _, n)| n)
+            .filter(|&(_, _, n)| n > 0)
+        {
+            hits.push((paper, hits_in_text, kind, text));
+        }
+    }
+    hits.sort_by_key(|&(p, n, _, _)| {
+        (std::cmp::Reverse(n), std::cmp::Reverse(citation_count(data, p)))
+    });
+    hits.truncate(limit);
+
+    let snippets: Vec<Value> = hits
+        .into_iter()
+        .map(|(paper, n, kind, text)| {
+            let authors: Vec<Value> = data
+                .paper_field(paper, "authors")
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|a| a["name"].clone())
+                .collect();
+            #[allow(clippy::cast_precision_loss)]
+            let score = n as f64 / terms.len().max(1) as f64;
+            json!({
+                "paper": {
+                    "paperId": data.paper_id(paper),
+                    "corpusId": data.paper_field(paper, "corpusId"),
+                    "title": data.paper_field(paper, "title"),
+                    "year": data.paper_field(paper, "year"),
+                    "authors": authors,
+                },
+                "score": score,
+                "snippet": {"text": text, "snippetKind": kind, "section": null},
+            })
+        })
+        .collect();
+    Ok(Json(json!({"total": snippets.len(), "data": snippets, "retrievalVersion": "mock"})))
+}
+
+/// How closely two papers are related: a direct citation counts double,
+/// and each shared reference or citing paper once.
+fn relatedness(data: &Dataset, a: usize, b: usize) -> usize {
+    let shared = |x: &[usize], y: &[usize]| x.iter().filter(|p| y.contains(p)).count();
+    let direct =
+        usize::from(data.references_of(a).contains(&b) || data.citations_of(a).contains(&b));
+    2 * direct
+        + shared(data.references_of(a), data.references_of(b))
+        + shared(data.citations_of(a), data.citations_of(b))
+}
+
+/// Papers most related to `positive` and least to `negative`.
+fn recommend(
+    data: &Dataset,
+    params: &HashMap<String, String>,
+    positive: &[usize],
+    negative: &[usize],
+) -> ApiResult {
+    let fields = paper_fields(params, "")?;
+    let limit = parse_number(params, "limit", 100)?;
+    if limit > 500 {
+        return Err(ApiError::bad_request("Limit must be <= 500"));
+    }
+    let mut scored: Vec<(usize, isize)> = data
+        .papers()
+        .filter(|p| !positive.contains(p) && !negative.contains(p))
+        .map(|p| {
+            let total = |seeds: &[usize]| -> isize {
+                seeds
+                    .iter()
+                    .map(|&s| relatedness(data, s, p))
+                    .sum::<usize>()
+                    .try_into()
+                    .unwrap_or(isize::MAX)
+            };
+            (p, total(positive) - total(negative))
+        })
+        .filter(|&(_, score)| score > 0)
+        .collect();
+    scored.sort_by_key(|&(p, score)| {
+        (std::cmp::Reverse(score), std::cmp::Reverse(citation_count(data, p)))
+    });
+    let papers: Vec<Value> =
+        scored.into_iter().take(limit).map(|(p, _)| data.project_paper(p, &fields)).collect();
+    Ok(Json(json!({"recommendedPapers": papers})))
+}
+
+fn resolve_papers(data: &Dataset, ids: &[String]) -> Result<Vec<usize>, ApiError> {
+    ids.iter()
+        .map(|id| {
+            data.paper(id)
+                .ok_or_else(|| ApiError::not_found(format!("Paper with id {id} not found")))
+        })
+        .collect()
+}
+
+async fn recommend_for_paper(
+    State(state): State<Arc<MockState>>,
+    Path(id): Path<String>,
+    Query(params): Params,
+) -> ApiResult {
+    let positive = resolve_papers(&state.data, &[id])?;
+    recommend(&state.data, &params, &positive, &[])
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecommendationBody {
+    positive_paper_ids: Vec<String>,
+    #[serde(default)]
+    negative_paper_ids: Vec<String>,
+}
+
+async fn recommend_for_papers(
+    State(state): State<Arc<MockState>>,
+    Query(params): Params,
+    body: Option<Json<RecommendationBody>>,
+) -> ApiResult {
+    let Some(Json(body)) = body else {
+        return Err(ApiError::bad_request("Body must be {\"positivePaperIds\": [...]}"));
+    };
+    let positive = resolve_papers(&state.data, &body.positive_paper_ids)?;
+    let negative = resolve_papers(&state.data, &body.negative_paper_ids)?;
+    if positive.is_empty() {
+        return Err(ApiError::bad_request("positivePaperIds must not be empty"));
+    }
+    if let Some(&both) = positive.iter().find(|p| negative.contains(p)) {
+        return Err(ApiError::bad_request(format!(
+            "Paper {} is both a positive and a negative example",
+            state.data.paper_id(both)
+        )));
+    }
+    recommend(&state.data, &params, &positive, &negative)
+}
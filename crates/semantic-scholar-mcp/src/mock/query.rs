@@ -0,0 +1,346 @@
+//! Query parameters of the mock API: field lists, filters and search
+//! queries, interpreted the way the Graph API documents them.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::dataset::Dataset;
+
+/// Parse a `fields` parameter.
+///
+/// `prefix` is stripped from each field (`citingPaper.` on the citations
+/// endpoint), and nested selections such as `authors.name` select the whole
+/// field. Without the parameter only `default` is returned.
+///
+/// # Errors
+///
+/// Returns the API's error message if a field is not in `known`.
+pub fn parse_fields(
+    param: Option<&String>,
+    prefix: &str,
+    known: &[&str],
+    default: &[&str],
+) -> Result<Vec<String>, String> {
+    let Some(param) = param.filter(|p| !p.trim().is_empty()) else {
+        return Ok(default.iter().map(|&f| f.to_owned()).collect());
+    };
+    let mut fields: Vec<String> = Vec::new();
+    let mut unknown = Vec::new();
+    for raw in param.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        let field = raw.strip_prefix(prefix).unwrap_or(raw);
+        let field = field.split_once('.').map_or(field, |(head, _)| head);
+        if !known.contains(&field) {
+            unknown.push(raw.to_owned());
+        } else if !fields.iter().any(|f| f == field) {
+            fields.push(field.to_owned());
+        }
+    }
+    if unknown.is_empty() {
+        Ok(fields)
+    } else {
+        Err(format!("Unrecognized or unsupported fields: [{}]", unknown.join(", ")))
+    }
+}
+
+/// Parse a numeric parameter, falling back to `default` when it is absent.
+///
+/// # Errors
+///
+/// Returns the API's error message if the value is not a non-negative
+/// integer.
+pub fn parse_number(
+    params: &HashMap<String, String>,
+    name: &str,
+    default: usize,
+) -> Result<usize, String> {
+    params.get(name).map_or(Ok(default), |value| {
+        value.trim().parse().map_err(|_| format!("Invalid value for {name}: {value}"))
+    })
+}
+
+/// Words of `text`, lowercased, for matching.
+#[must_use]
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// How many of `terms` occur in the words of `text`.
+#[must_use]
+pub fn score(text: &str, terms: &[String]) -> usize {
+    let text = words(text);
+    terms.iter().filter(|term| text.contains(term)).count()
+}
+
+/// The paper filters shared by relevance, bulk and snippet search.
+#[derive(Debug, Clone, Default)]
+pub struct Filters {
+    year: Option<(Option<i64>, Option<i64>)>,
+    date: Option<(String, String)>,
+    fields_of_study: Vec<String>,
+    publication_types: Vec<String>,
+    venues: Vec<String>,
+    min_citations: Option<u64>,
+    open_access: bool,
+    paper_ids: Option<Vec<usize>>,
+    authors: Vec<String>,
+}
+
+impl Filters {
+    /// Read the filter parameters from a query string.
+    ///
+    /// # Errors
+    ///
+    /// Returns the API's error message for a malformed filter.
+    pub fn parse(params: &HashMap<String, String>, data: &Dataset) -> Result<Self, String> {
+        let list = |name: &str| -> Vec<String> {
+            params.get(name).map_or_else(Vec::new, |v| {
+                v.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()).collect()
+            })
+        };
+        let mut filters = Self {
+            fields_of_study: list("fieldsOfStudy"),
+            publication_types: list("publicationTypes"),
+            venues: list("venue"),
+            authors: list("authors"),
+            open_access: params.contains_key("openAccessPdf"),
+            ..Self::default()
+        };
+        if let Some(year) = params.get("year") {
+            filters.year = Some(parse_year_range(year)?);
+        }
+        if let Some(range) = params.get("publicationDateOrYear") {
+            let (start, end) = range.split_once(':').unwrap_or((range, range));
+            filters.date = Some((start.trim().to_owned(), end.trim().to_owned()));
+        }
+        if let Some(min) = params.get("minCitationCount") {
+            filters.min_citations =
+                Some(min.trim().parse().map_err(|_| format!("Invalid minCitationCount: {min}"))?);
+        }
+        if params.contains_key("paperIds") {
+            filters.paper_ids =
+                Some(list("paperIds").iter().filter_map(|id| data.paper(id)).collect());
+        }
+        Ok(filters)
+    }
+
+    /// Whether `paper` passes every filter.
+    #[must_use]
+    pub fn matches(&self, data: &Dataset, paper: usize) -> bool {
+        let field = |name| data.paper_field(paper, name);
+        let any_of = |wanted: &[String], value: Value| {
+            wanted.is_empty()
+                || value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .any(|v| wanted.contains(&v.to_lowercase()))
+        };
+        if let Some((from, to)) = self.year {
+            let Some(year) = field("year").as_i64() else { return false };
+            if from.is_some_and(|f| year < f) || to.is_some_and(|t| year > t) {
+                return false;
+            }
+        }
+        if let Some((start, end)) = &self.date {
+            let published = field("publicationDate")
+                .as_str()
+                .map(str::to_owned)
+                .or_else(|| field("year").as_i64().map(|y| y.to_string()));
+            let Some(published) = published else { return false };
+            // Bounds may be a year, a month or a day: compare at their precision.
+            let within = |bound: &str, ok: fn(&str, &str) -> bool| {
+                bound.is_empty() || ok(&published[..published.len().min(bound.len())], bound)
+            };
+            if !within(start, |d, b| d >= b) || !within(end, |d, b| d <= b) {
+                return false;
+            }
+        }
+        if !self.venues.is_empty()
+            && !field("venue").as_str().is_some_and(|v| self.venues.contains(&v.to_lowercase()))
+        {
+            return false;
+        }
+        if self.min_citations.is_some_and(|min| field("citationCount").as_u64().unwrap_or(0) < min)
+        {
+            return false;
+        }
+        if self.open_access && field("openAccessPdf").is_null() {
+            return false;
+        }
+        if self.paper_ids.as_ref().is_some_and(|ids| !ids.contains(&paper)) {
+            return false;
+        }
+        if !self.authors.is_empty() {
+            let names = field("authors");
+            let names = names
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|a| a.get("name").and_then(Value::as_str))
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>();
+            if !self.authors.iter().any(|wanted| names.iter().any(|n| n.contains(wanted.as_str())))
+            {
+                return false;
+            }
+        }
+        any_of(&self.fields_of_study, field("fieldsOfStudy"))
+            && any_of(&self.publication_types, field("publicationTypes"))
+    }
+}
+
+/// Parse `2019`, `2016-2020`, `2010-` or `-2015`.
+fn parse_year_range(value: &str) -> Result<(Option<i64>, Option<i64>), String> {
+    let invalid = || format!("Invalid year filter: {value}");
+    let bound = |s: &str| -> Result<Option<i64>, String> {
+        let s = s.trim();
+        if s.is_empty() { Ok(None) } else { s.parse().map(Some).map_err(|_| invalid()) }
+    };
+    if let Some((from, to)) = value.split_once('-') {
+        return Ok((bound(from)?, bound(to)?));
+    }
+    let year = bound(value)?.ok_or_else(invalid)?;
+    Ok((Some(year), Some(year)))
+}
+
+/// One term of a bulk search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    Word(String),
+    Prefix(String),
+    Phrase(Vec<String>),
+}
+
+impl Term {
+    fn matches(&self, text: &[String]) -> bool {
+        match self {
+            Self::Word(word) => text.contains(word),
+            Self::Prefix(prefix) => text.iter().any(|w| w.starts_with(prefix.as_str())),
+            Self::Phrase(phrase) => text.windows(phrase.len()).any(|w| w == phrase.as_slice()),
+        }
+    }
+}
+
+/// A bulk search query in the API's boolean syntax.
+///
+/// Terms are ANDed unless joined by `|`; `-term` excludes, a leading `+` is
+/// accepted and ignored, `"a phrase"` must occur verbatim, `term*` matches
+/// prefixes and `~N` fuzziness suffixes are ignored (matching is exact).
+/// Parentheses are not supported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BooleanQuery {
+    /// Every group must match; a group matches if any of its terms does.
+    required: Vec<Vec<Term>>,
+    excluded: Vec<Term>,
+}
+
+impl BooleanQuery {
+    /// Parse a query.
+    #[must_use]
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut join_next = false;
+        let mut rest = query.trim();
+        while !rest.is_empty() {
+            let (excluded, body) = rest.strip_prefix('-').map_or_else(
+                || (false, rest.strip_prefix('+').unwrap_or(rest)),
+                |body| (true, body),
+            );
+            let (token, quoted, tail) = next_token(body);
+            rest = tail.trim_start();
+            if token == "|" && !quoted {
+                join_next = true;
+                continue;
+            }
+            let Some(term) = parse_term(token, quoted) else { continue };
+            if excluded {
+                parsed.excluded.push(term);
+            } else if let Some(group) = parsed.required.last_mut().filter(|_| join_next) {
+                group.push(term);
+            } else {
+                parsed.required.push(vec![term]);
+            }
+            join_next = false;
+        }
+        parsed
+    }
+
+    /// Whether the words of a document satisfy the query.
+    #[must_use]
+    pub fn matches(&self, text: &str) -> bool {
+        let text = words(text);
+        self.required.iter().all(|group| group.iter().any(|t| t.matches(&text)))
+            && !self.excluded.iter().any(|t| t.matches(&text))
+    }
+}
+
+/// Split the next token off `input`: a quoted phrase or a run of
+/// non-whitespace, with `|` always a token of its own.
+fn next_token(input: &str) -> (&str, bool, &str) {
+    if let Some(quoted) = input.strip_prefix('"') {
+        let end = quoted.find('"').unwrap_or(quoted.len());
+        let tail = quoted.get(end + 1..).unwrap_or_default();
+        // A fuzziness suffix on a phrase (`"a b"~3`) is dropped.
+        let tail = tail
+            .strip_prefix('~')
+            .map_or(tail, |t| t.trim_start_matches(|c: char| c.is_ascii_digit()));
+        return (&quoted[..end], true, tail);
+    }
+    if let Some(tail) = input.strip_prefix('|') {
+        return ("|", false, tail);
+    }
+    let end = input.find(|c: char| c.is_whitespace() || c == '|').unwrap_or(input.len());
+    (&input[..end], false, &input[end..])
+}
+
+fn parse_term(token: &str, quoted: bool) -> Option<Term> {
+    let token = if quoted { token } else { token.split('~').next().unwrap_or_default() };
+    if quoted {
+        let phrase = words(token);
+        return (!phrase.is_empty()).then_some(Term::Phrase(phrase));
+    }
+    let prefix = token.ends_with('*');
+    let mut parts = words(token.trim_end_matches('*'));
+    match (parts.len(), prefix) {
+        (0, _) => None,
+        (1, true) => parts.pop().map(Term::Prefix),
+        (1, false) => parts.pop().map(Term::Word),
+        _ => Some(Term::Phrase(parts)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boolean_query_syntax() {
+        let query = BooleanQuery::parse(r#"transform* +"language model" -vision | image"#);
+        assert!(query.matches("Transformers are a Language Model family"));
+        assert!(!query.matches("Transformers for vision and language model heads"));
+        assert!(!query.matches("Transformers without the phrase"));
+
+        let query = BooleanQuery::parse("attention | recurrence~2 bert");
+        assert!(query.matches("recurrence in BERT"));
+        assert!(!query.matches("attention only"));
+        assert!(BooleanQuery::parse("").matches("anything"));
+    }
+
+    #[test]
+    fn test_parse_fields_strips_prefix_and_rejects_unknown() {
+        let param = "citingPaper.title,citingPaper.authors.name,citingPaper.title".to_owned();
+        assert_eq!(
+            parse_fields(Some(&param), "citingPaper.", &["title", "authors"], &[]).unwrap(),
+            ["title", "authors"]
+        );
+        let error = parse_fields(Some(&"title,colour".to_owned()), "", &["title"], &[]);
+        assert!(error.unwrap_err().contains("colour"));
+        assert_eq!(parse_year_range("2010-").unwrap(), (Some(2010), None));
+        assert!(parse_year_range("twenty").is_err());
+    }
+}
@@ -1,9 +1,10 @@
 //! Enumeration types for API and tool parameters.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Output format for tool responses.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ResponseFormat {
     /// Human-readable Markdown format.
@@ -28,7 +29,7 @@ impl ResponseFormat {
 }
 
 /// Direction for citation traversal.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SearchDirection {
     /// Papers that cite this paper (forward citations).
@@ -41,7 +42,7 @@ pub enum SearchDirection {
 }
 
 /// Export format for reference managers.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ExportFormat {
     /// Research Information Systems format.
@@ -56,6 +57,20 @@ pub enum ExportFormat {
 }
 
 impl ExportFormat {
+    /// All formats, in the order they are offered to clients.
+    pub const ALL: [Self; 4] = [Self::Ris, Self::Bibtex, Self::Csv, Self::Endnote];
+
+    /// Name as accepted in tool arguments.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ris => "ris",
+            Self::Bibtex => "bibtex",
+            Self::Csv => "csv",
+            Self::Endnote => "endnote",
+        }
+    }
+
     /// Get the file extension for this format.
     #[must_use]
     pub const fn extension(self) -> &'static str {
@@ -80,7 +95,7 @@ impl ExportFormat {
 }
 
 /// Time granularity for trend analysis.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TrendGranularity {
     /// Aggregate by year.
@@ -91,7 +106,7 @@ pub enum TrendGranularity {
 }
 
 /// Strategy for pearl growing search expansion.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum PearlGrowingStrategy {
     /// Expand using extracted keywords.
@@ -124,6 +139,13 @@ mod tests {
         assert_eq!(ExportFormat::Endnote.extension(), "enw");
     }
 
+    #[test]
+    fn test_export_format_names_match_serde() {
+        for format in ExportFormat::ALL {
+            assert_eq!(serde_json::to_value(format).unwrap(), format.as_str());
+        }
+    }
+
     #[test]
     fn test_serde_roundtrip() {
         let format = ResponseFormat::Json;
@@ -1,7 +1,10 @@
 //! Input models for MCP tool parameters.
 //!
-//! These map to the Pydantic input schemas in the Python version.
+//! These map to the Pydantic input schemas in the Python version. Each type
+//! derives [`JsonSchema`], which is the source of the tool's MCP `inputSchema`;
+//! `#[schemars(...)]` attributes carry the constraints enforced server-side.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::{
@@ -9,7 +12,7 @@ use super::{
 };
 
 /// Input for exhaustive paper search.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExhaustiveSearchInput {
     /// Search query (e.g., "transformer attention mechanisms").
@@ -53,7 +56,7 @@ fn default_max_results() -> i32 {
 }
 
 /// Input for paper recommendations.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RecommendationsInput {
     /// Paper IDs to use as positive examples.
@@ -81,7 +84,7 @@ fn default_limit() -> i32 {
 }
 
 /// Input for citation snowball search.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CitationSnowballInput {
     /// Starting paper IDs.
@@ -93,6 +96,7 @@ pub struct CitationSnowballInput {
 
     /// Traversal depth (1 = direct, 2 = citations of citations).
     #[serde(default = "default_depth")]
+    #[schemars(range(min = 1, max = 3))]
     pub depth: i32,
 
     /// Maximum citations/references per paper.
@@ -101,6 +105,7 @@ pub struct CitationSnowballInput {
 
     /// Minimum citations for included papers.
     #[serde(default)]
+    #[schemars(range(min = 0))]
     pub min_citations: Option<i32>,
 
     /// Remove duplicates from results.
@@ -125,10 +130,11 @@ fn default_true() -> bool {
 }
 
 /// Input for batch paper metadata retrieval.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchMetadataInput {
     /// Paper IDs (Semantic Scholar, DOI:, ARXIV:, PMID:).
+    #[schemars(length(max = 500))]
     pub paper_ids: Vec<String>,
 
     /// Fields to retrieve.
@@ -141,7 +147,7 @@ pub struct BatchMetadataInput {
 }
 
 /// Input for author search.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthorSearchInput {
     /// Author name to search for.
@@ -149,6 +155,7 @@ pub struct AuthorSearchInput {
 
     /// Maximum authors to return.
     #[serde(default = "default_author_limit")]
+    #[schemars(range(max = 100))]
     pub limit: i32,
 
     /// Output format.
@@ -161,7 +168,7 @@ fn default_author_limit() -> i32 {
 }
 
 /// Input for author papers retrieval.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthorPapersInput {
     /// Semantic Scholar author ID.
@@ -177,6 +184,7 @@ pub struct AuthorPapersInput {
 
     /// Maximum papers to return.
     #[serde(default = "default_limit")]
+    #[schemars(range(max = 1000))]
     pub limit: i32,
 
     /// Output format.
@@ -185,7 +193,7 @@ pub struct AuthorPapersInput {
 }
 
 /// Input for PRISMA-compliant systematic review search.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PrismaSearchInput {
     /// Search queries to run (will be deduplicated).
@@ -217,7 +225,7 @@ fn default_prisma_max() -> i32 {
 }
 
 /// Input for screening export.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScreeningExportInput {
     /// Paper IDs to export for screening.
@@ -241,10 +249,11 @@ fn default_json() -> ResponseFormat {
 }
 
 /// Input for reference export.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ReferenceExportInput {
     /// Paper IDs to export.
+    #[schemars(length(max = 500))]
     pub paper_ids: Vec<String>,
 
     /// Export format.
@@ -257,7 +266,7 @@ pub struct ReferenceExportInput {
 }
 
 /// Input for semantic similarity search.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SemanticSearchInput {
     /// Seed paper to find similar papers.
@@ -285,7 +294,7 @@ pub struct SemanticSearchInput {
 }
 
 /// Input for literature review pipeline.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LiteratureReviewInput {
     /// Initial search query.
@@ -325,7 +334,7 @@ fn default_review_max() -> i32 {
 }
 
 /// Input for author network discovery.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthorNetworkInput {
     /// Semantic Scholar author ID.
@@ -333,6 +342,7 @@ pub struct AuthorNetworkInput {
 
     /// Collaboration depth (1 = direct, 2 = collaborators of collaborators).
     #[serde(default = "default_network_depth")]
+    #[schemars(range(min = 1, max = 2))]
     pub depth: i32,
 
     /// Minimum shared papers to include.
@@ -361,7 +371,7 @@ fn default_max_collaborators() -> i32 {
 }
 
 /// Input for research trend analysis.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TrendAnalysisInput {
     /// Research topic to analyze.
@@ -387,7 +397,7 @@ pub struct TrendAnalysisInput {
 }
 
 /// Input for venue analytics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct VenueAnalyticsInput {
     /// Venue name (e.g., "NeurIPS", "Nature Medicine").
@@ -411,7 +421,7 @@ pub struct VenueAnalyticsInput {
 }
 
 /// Input for hot papers detection.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct HotPapersInput {
     /// Search query for candidate papers.
@@ -451,7 +461,7 @@ fn default_hot_papers_max() -> i32 {
 }
 
 /// Input for pearl growing search expansion.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PearlGrowingInput {
     /// Initial seed paper IDs.
@@ -459,6 +469,7 @@ pub struct PearlGrowingInput {
 
     /// Number of growth iterations.
     #[serde(default = "default_iterations")]
+    #[schemars(range(min = 1, max = 3))]
     pub iterations: i32,
 
     /// Growth strategy.
@@ -483,7 +494,7 @@ fn default_iterations() -> i32 {
 }
 
 /// Input for field-weighted citation impact.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FieldWeightedImpactInput {
     /// Paper IDs to analyze.
@@ -503,13 +514,13 @@ fn default_baseline_sample() -> i32 {
 }
 
 /// Input for highly cited papers detection.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct HighlyCitedPapersInput {
     /// Paper IDs to evaluate.
     pub paper_ids: Vec<String>,
 
-    /// Top X percentile threshold.
+    /// Top X percentile threshold (1.0 = top 1%).
     #[serde(default = "default_percentile")]
     pub percentile_threshold: f64,
 
@@ -523,7 +534,7 @@ fn default_percentile() -> f64 {
 }
 
 /// Input for citation half-life calculation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CitationHalfLifeInput {
     /// Paper ID to analyze.
@@ -535,7 +546,7 @@ pub struct CitationHalfLifeInput {
 }
 
 /// Input for co-citation analysis.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CocitationAnalysisInput {
     /// Focal paper ID.
@@ -563,7 +574,7 @@ fn default_min_cocitations() -> i32 {
 }
 
 /// Input for bibliographic coupling.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BibliographicCouplingInput {
     /// Focal paper ID.
@@ -591,7 +602,7 @@ fn default_min_shared_refs() -> i32 {
 }
 
 /// Input for ORCID author lookup.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OrcidAuthorLookupInput {
     /// ORCID iD (e.g., "0000-0002-1825-0097").
@@ -611,7 +622,7 @@ pub struct OrcidAuthorLookupInput {
 }
 
 /// Input for PRISMA flow diagram generation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PrismaFlowDiagramInput {
     /// Identification phase data.
@@ -634,7 +645,7 @@ pub struct PrismaFlowDiagramInput {
 }
 
 /// PRISMA identification phase data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PrismaIdentificationData {
     /// Database search results.
     pub databases: Vec<DatabaseSearchData>,
@@ -645,7 +656,7 @@ pub struct PrismaIdentificationData {
 }
 
 /// Database search metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DatabaseSearchData {
     /// Database name.
     pub name: String,
@@ -663,7 +674,7 @@ pub struct DatabaseSearchData {
 }
 
 /// Other source metadata.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OtherSourceData {
     /// Source name.
     pub name: String,
@@ -677,7 +688,7 @@ pub struct OtherSourceData {
 }
 
 /// PRISMA screening phase data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PrismaScreeningData {
     /// Records after deduplication.
@@ -695,7 +706,7 @@ pub struct PrismaScreeningData {
 }
 
 /// PRISMA eligibility phase data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PrismaEligibilityData {
     /// Reports sought for retrieval.
@@ -720,7 +731,7 @@ pub struct PrismaEligibilityData {
 }
 
 /// PRISMA included phase data.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PrismaIncludedData {
     /// Studies included.
@@ -733,7 +744,7 @@ pub struct PrismaIncludedData {
 }
 
 /// Input for bulk boolean search (up to 10M papers).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BulkBooleanSearchInput {
     /// Boolean query: +term (AND), -term (NOT), |term (OR), "phrase", term*, term~N.
@@ -785,7 +796,7 @@ fn default_bulk_max() -> i32 {
 }
 
 /// Input for snippet search (full-text search with highlights).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SnippetSearchInput {
     /// Plain text search query.
@@ -821,6 +832,7 @@ pub struct SnippetSearchInput {
 
     /// Maximum snippets to return.
     #[serde(default = "default_snippet_limit")]
+    #[schemars(range(max = 1000))]
     pub limit: i32,
 
     /// Output format.
@@ -833,7 +845,7 @@ fn default_snippet_limit() -> i32 {
 }
 
 /// Input for paper autocomplete (title suggestions).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PaperAutocompleteInput {
     /// Partial title text to autocomplete.
@@ -845,7 +857,7 @@ pub struct PaperAutocompleteInput {
 }
 
 /// Input for paper title match (exact title search).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PaperTitleMatchInput {
     /// Exact or near-exact paper title.
@@ -857,7 +869,7 @@ pub struct PaperTitleMatchInput {
 }
 
 /// Input for paper authors (detailed author info for a paper).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PaperAuthorsInput {
     /// Paper ID (Semantic Scholar ID, DOI:, ARXIV:, etc.).
@@ -869,10 +881,11 @@ pub struct PaperAuthorsInput {
 }
 
 /// Input for batch author metadata retrieval.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthorBatchInput {
     /// Author IDs (up to 1000).
+    #[schemars(length(max = 1000))]
     pub author_ids: Vec<String>,
 
     /// Output format.
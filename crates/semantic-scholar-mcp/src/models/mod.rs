@@ -1,20 +1,13 @@
-//! Data models for Semantic Scholar API entities.
+//! Data models for Semantic Scholar API entities and MCP tool inputs.
 //!
-//! All models use `#[serde(default)]` for optional fields and
-//! `#[serde(rename = "camelCase")]` to match API naming.
+//! API entities come from `semantic-scholar-client` and are re-exported
+//! here; tool inputs derive [`schemars::JsonSchema`] for their MCP schemas.
 
-mod author;
 mod enums;
 mod inputs;
-mod paper;
 
-pub use author::{Author, AuthorRef, AuthorSearchResult};
 pub use enums::{
     ExportFormat, PearlGrowingStrategy, ResponseFormat, SearchDirection, TrendGranularity,
 };
 pub use inputs::*;
-pub use paper::{
-    AutocompleteMatch, AutocompleteResult, BulkSearchResult, CitationResult, ExternalIds,
-    OpenAccessPdf, Paper, PaperAuthorsResult, PaperRef, SearchResult, Snippet, SnippetPaper,
-    SnippetSearchResult, SnippetText, TitleMatchResult, Tldr,
-};
+pub use semantic_scholar_client::models::*;
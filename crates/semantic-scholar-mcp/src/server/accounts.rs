@@ -0,0 +1,391 @@
+//! User accounts for the HTTP server's OAuth login.
+//!
+//! Without an accounts file, `/authorize` auto-approves every registered
+//! client. With one, it shows a login form and every token is bound to the
+//! signed-in user and the scopes they hold:
+//!
+//! ```json
+//! {
+//!   "users": {
+//!     "alice": { "password_hash": "$argon2id$v=19$...", "scopes": ["read", "export"] },
+//!     "ops": { "password_hash": "$argon2id$v=19$...", "scopes": ["admin"] }
+//!   }
+//! }
+//! ```
+//!
+//! Hashes are Argon2 PHC strings (`semantic-scholar-mcp --hash-password`
+//! prints one). Scopes map to tools as follows:
+//!
+//! - `read`: every tool outside the `export` group
+//! - `export`: the `export` group
+//! - `admin`: every tool plus the admin endpoints
+//!
+//! A client that asks for no particular scope (just `mcp`) gets the user's
+//! tool scopes; `admin` is only granted when requested by name.
+//!
+//! After [`FREE_LOGIN_FAILURES`] wrong passwords for a user name from one
+//! client address, further attempts from it are refused with a doubling
+//! delay, up to [`MAX_LOGIN_BACKOFF`], without checking the password, so the
+//! Argon2 hash cannot be brute forced through the login form. Other
+//! addresses can still sign in as that user.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use argon2::Argon2;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use serde::Deserialize;
+
+use crate::tools::ToolGroup;
+
+/// A permission granted to an account and carried by its tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Search and analysis tools.
+    Read,
+    /// Export tools.
+    Export,
+    /// Everything, including admin endpoints.
+    Admin,
+}
+
+impl Scope {
+    /// Every scope, in the order they are advertised.
+    pub const ALL: [Self; 3] = [Self::Read, Self::Export, Self::Admin];
+
+    /// Scope name as used in OAuth requests.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Export => "export",
+            Self::Admin => "admin",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.as_str() == name)
+    }
+}
+
+/// A set of scopes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scopes(BTreeSet<Scope>);
+
+impl Scopes {
+    /// Parse a space-separated OAuth scope string, ignoring unknown scopes
+    /// (such as the generic `mcp`).
+    #[must_use]
+    pub fn parse(scope: &str) -> Self {
+        Self(scope.split_whitespace().filter_map(Scope::parse).collect())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, scope: Scope) -> bool {
+        self.0.contains(&scope)
+    }
+
+    /// Scopes in this set that `held` allows; `admin` allows every scope.
+    #[must_use]
+    pub fn allowed_by(&self, held: &Self) -> Self {
+        if held.contains(Scope::Admin) {
+            return self.clone();
+        }
+        Self(self.0.intersection(&held.0).copied().collect())
+    }
+
+    /// Scopes to grant a holder of these scopes for a `requested` set: the
+    /// requested ones they hold or, when nothing in particular was asked
+    /// for, every tool scope but never `admin` itself.
+    #[must_use]
+    pub fn grant(&self, requested: &Self) -> Self {
+        if !requested.is_empty() {
+            requested.allowed_by(self)
+        } else if self.contains(Scope::Admin) {
+            [Scope::Read, Scope::Export].into_iter().collect()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Whether these scopes allow calling tools in `group`. No scope but
+    /// `admin` covers a custom group.
+    #[must_use]
+    pub fn permits_tool(&self, group: ToolGroup) -> bool {
+        let needed = match group {
+            ToolGroup::Export => Scope::Export,
+            ToolGroup::Discovery
+            | ToolGroup::Enrichment
+            | ToolGroup::Systematic
+            | ToolGroup::Semantic
+            | ToolGroup::Networks
+            | ToolGroup::Trends
+            | ToolGroup::Bibliometrics
+            | ToolGroup::Advanced
+            | ToolGroup::Jobs => Scope::Read,
+            ToolGroup::Custom(_) => Scope::Admin,
+        };
+        self.contains(Scope::Admin) || self.contains(needed)
+    }
+}
+
+impl FromIterator<Scope> for Scopes {
+    fn from_iter<I: IntoIterator<Item = Scope>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl fmt::Display for Scopes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.0.iter().map(|s| s.as_str()).collect();
+        f.write_str(&names.join(" "))
+    }
+}
+
+/// The signed-in user behind a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    /// Account name.
+    pub user: String,
+    /// Scopes granted to this request (token scopes the account still holds).
+    pub scopes: Scopes,
+}
+
+impl Principal {
+    /// Whether this user may use the admin endpoints.
+    #[must_use]
+    pub fn is_admin(&self) -> bool {
+        self.scopes.contains(Scope::Admin)
+    }
+}
+
+/// One configured user.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Account {
+    /// Argon2 PHC string.
+    pub password_hash: String,
+    /// Scopes this user may be granted.
+    #[serde(default)]
+    pub scopes: Vec<Scope>,
+}
+
+/// Configured users, keyed by name.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Accounts {
+    #[serde(default)]
+    pub users: HashMap<String, Account>,
+}
+
+/// Verified against when the user does not exist, so unknown names take as
+/// long to reject as wrong passwords.
+static DUMMY_HASH: LazyLock<String> = LazyLock::new(|| hash_password("not a real password"));
+
+impl Accounts {
+    /// Read accounts from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not valid JSON, or
+    /// holds a password hash that is not a PHC string.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let accounts: Self = serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("invalid accounts file {}: {e}", path.display()))?;
+        for (name, account) in &accounts.users {
+            PasswordHash::new(&account.password_hash).map_err(|e| {
+                anyhow::anyhow!(
+                    "invalid password_hash for user '{name}' in {}: {e}",
+                    path.display()
+                )
+            })?;
+        }
+        Ok(accounts)
+    }
+
+    /// Scopes currently held by `user`, if the account exists.
+    #[must_use]
+    pub fn scopes(&self, user: &str) -> Option<Scopes> {
+        self.users.get(user).map(|a| a.scopes.iter().copied().collect())
+    }
+
+    /// Check a password, returning the user's scopes on success.
+    #[must_use]
+    pub fn verify(&self, user: &str, password: &str) -> Option<Scopes> {
+        let account = self.users.get(user);
+        let hash = account.map_or(DUMMY_HASH.as_str(), |a| a.password_hash.as_str());
+        let parsed = PasswordHash::new(hash).ok()?;
+        let ok = Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok();
+        if ok { self.scopes(user) } else { None }
+    }
+}
+
+/// Failed logins a user name gets from one address before attempts are
+/// spaced out.
+pub const FREE_LOGIN_FAILURES: u32 = 5;
+
+/// Longest wait imposed between login attempts.
+pub const MAX_LOGIN_BACKOFF: Duration = Duration::from_mins(15);
+
+/// Address and user name pairs tracked at once; the stalest make room
+/// first.
+const MAX_THROTTLED_LOGINS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Failures {
+    count: u32,
+    last: Instant,
+}
+
+/// Who is logging in: the client's address (`None` when the transport has
+/// none, as on a Unix socket) and the user name they entered.
+type LoginKey = (Option<IpAddr>, String);
+
+/// Backoff for failed password logins, per client address and user name.
+#[derive(Debug, Default)]
+pub struct LoginThrottle {
+    failures: Mutex<HashMap<LoginKey, Failures>>,
+}
+
+impl LoginThrottle {
+    /// Create a throttle with no recorded failures.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long `client` must wait before its next attempt as `user`, if at
+    /// all.
+    #[must_use]
+    pub fn retry_after(&self, client: Option<IpAddr>, user: &str) -> Option<Duration> {
+        let failures = self.failures.lock().expect("login throttle lock poisoned");
+        let entry = failures.get(&(client, user.to_owned()))?;
+        backoff(entry.count).checked_sub(entry.last.elapsed()).filter(|d| !d.is_zero())
+    }
+
+    /// Count a wrong password for `user` from `client`.
+    pub fn record_failure(&self, client: Option<IpAddr>, user: &str) {
+        let key = (client, user.to_owned());
+        let mut failures = self.failures.lock().expect("login throttle lock poisoned");
+        if failures.len() >= MAX_THROTTLED_LOGINS && !failures.contains_key(&key) {
+            failures.retain(|_, f| f.last.elapsed() < MAX_LOGIN_BACKOFF);
+            if failures.len() >= MAX_THROTTLED_LOGINS {
+                let stalest = failures.iter().min_by_key(|(_, f)| f.last).map(|(k, _)| k.clone());
+                if let Some(stalest) = stalest {
+                    failures.remove(&stalest);
+                }
+            }
+        }
+        let entry = failures.entry(key).or_insert(Failures { count: 0, last: Instant::now() });
+        entry.count = entry.count.saturating_add(1);
+        entry.last = Instant::now();
+    }
+
+    /// Forget the failures of `user` from `client` after a successful login.
+    pub fn record_success(&self, client: Option<IpAddr>, user: &str) {
+        self.failures
+            .lock()
+            .expect("login throttle lock poisoned")
+            .remove(&(client, user.to_owned()));
+    }
+}
+
+/// Wait required after `count` consecutive failures.
+fn backoff(count: u32) -> Duration {
+    count.checked_sub(FREE_LOGIN_FAILURES).map_or(Duration::ZERO, |extra| {
+        Duration::from_secs(1u64 << extra.min(20)).min(MAX_LOGIN_BACKOFF)
+    })
+}
+
+/// Hash a password into an Argon2id PHC string for the accounts file.
+#[must_use]
+pub fn hash_password(password: &str) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("Argon2 with default parameters accepts any password")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accounts() -> Accounts {
+        let mut users = HashMap::new();
+        users.insert(
+            "alice".to_string(),
+            Account { password_hash: hash_password("secret"), scopes: vec![Scope::Read] },
+        );
+        Accounts { users }
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let accounts = accounts();
+        assert_eq!(accounts.verify("alice", "secret"), Some(Scopes::parse("read")));
+        assert_eq!(accounts.verify("alice", "wrong"), None);
+        assert_eq!(accounts.verify("bob", "secret"), None);
+    }
+
+    #[test]
+    fn test_scope_parsing_and_tool_mapping() {
+        let scopes = Scopes::parse("mcp read export bogus");
+        assert_eq!(scopes.to_string(), "read export");
+        assert!(Scopes::parse("mcp").is_empty());
+
+        let read = Scopes::parse("read");
+        assert!(read.permits_tool(ToolGroup::Discovery));
+        assert!(!read.permits_tool(ToolGroup::Export));
+        assert!(Scopes::parse("export").permits_tool(ToolGroup::Export));
+        assert!(Scopes::parse("admin").permits_tool(ToolGroup::Export));
+        assert!(!Scopes::parse("read export").permits_tool(ToolGroup::Custom("internal")));
+        assert!(Scopes::parse("admin").permits_tool(ToolGroup::Custom("internal")));
+    }
+
+    #[test]
+    fn test_admin_granted_only_when_requested() {
+        let admin = Scopes::parse("admin");
+        assert_eq!(admin.grant(&Scopes::parse("mcp")), Scopes::parse("read export"));
+        assert_eq!(admin.grant(&Scopes::parse("mcp admin")), admin);
+        assert_eq!(admin.grant(&Scopes::parse("read")), Scopes::parse("read"));
+
+        let reader = Scopes::parse("read");
+        assert_eq!(reader.grant(&Scopes::parse("")), reader);
+        assert!(reader.grant(&Scopes::parse("admin export")).is_empty());
+    }
+
+    #[test]
+    fn test_login_backoff_doubles_after_free_failures() {
+        assert_eq!(backoff(FREE_LOGIN_FAILURES - 1), Duration::ZERO);
+        assert_eq!(backoff(FREE_LOGIN_FAILURES), Duration::from_secs(1));
+        assert_eq!(backoff(FREE_LOGIN_FAILURES + 3), Duration::from_secs(8));
+        assert_eq!(backoff(u32::MAX), MAX_LOGIN_BACKOFF);
+
+        let throttle = LoginThrottle::new();
+        let (attacker, user) = (Some([203, 0, 113, 7].into()), Some([198, 51, 100, 1].into()));
+        for _ in 0..FREE_LOGIN_FAILURES {
+            assert_eq!(throttle.retry_after(attacker, "alice"), None);
+            throttle.record_failure(attacker, "alice");
+        }
+        assert!(throttle.retry_after(attacker, "alice").is_some());
+        assert_eq!(throttle.retry_after(attacker, "bob"), None);
+        // Failures from one address don't lock the user out elsewhere.
+        assert_eq!(throttle.retry_after(user, "alice"), None);
+        assert_eq!(throttle.retry_after(None, "alice"), None);
+        throttle.record_success(attacker, "alice");
+        assert_eq!(throttle.retry_after(attacker, "alice"), None);
+    }
+}
@@ -0,0 +1,535 @@
+//! Audit trail of tool invocations.
+//!
+//! Every `tools/call` is appended to a JSON-lines file: when, who (the
+//! static token, an OAuth client, or stdio), which session, which tool,
+//! a SHA-256 of the arguments (and optionally the arguments themselves),
+//! how long it took, how many requests reached Semantic Scholar and how it
+//! ended. The file is rotated by size, keeping a fixed number of old files
+//! (`audit.jsonl.1` is the most recent), and can be searched through
+//! `GET /admin/audit`.
+//!
+//! Records are written by a dedicated thread, so auditing a call never
+//! blocks the async runtime on disk I/O. Write failures are logged and never
+//! fail the call being audited.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::transport::JsonRpcResponse;
+
+/// Rotate the log once it grows past this many bytes.
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated files kept besides the current one.
+pub const DEFAULT_KEEP: usize = 5;
+
+/// Most records one query returns.
+const MAX_QUERY_LIMIT: usize = 1000;
+
+/// Times a query re-reads the files when a rotation moved them mid-read,
+/// before it pauses the writer instead.
+const QUERY_ATTEMPTS: usize = 3;
+
+/// Where and how to write the audit log.
+#[derive(Debug, Clone)]
+pub struct AuditConfig {
+    pub path: PathBuf,
+    /// Size at which the current file is rotated.
+    pub max_bytes: u64,
+    /// Number of rotated files to keep.
+    pub keep: usize,
+    /// Record the arguments themselves, not just their hash.
+    pub record_arguments: bool,
+}
+
+impl AuditConfig {
+    /// Defaults for a log at `path`: 10 MiB files, five kept, hashes only.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: DEFAULT_MAX_BYTES,
+            keep: DEFAULT_KEEP,
+            record_arguments: false,
+        }
+    }
+}
+
+/// How a call ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    /// The tool returned a result.
+    Ok,
+    /// The tool ran and failed (`isError` result).
+    Error,
+    /// The call was refused before running: unknown tool or bad arguments.
+    Rejected,
+}
+
+impl AuditOutcome {
+    /// How the call answered by `response` ended, with the error code of a
+    /// failed call or the reason for a rejection.
+    #[must_use]
+    pub fn of(response: &JsonRpcResponse) -> (Self, Option<String>) {
+        if let Some(ref error) = response.error {
+            return (Self::Rejected, Some(error.message.clone()));
+        }
+        let result = response.result.as_ref();
+        if result.and_then(|r| r.get("isError")).and_then(serde_json::Value::as_bool) == Some(true)
+        {
+            let code = result
+                .and_then(|r| r.pointer("/_meta/error/code"))
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_owned);
+            return (Self::Error, code);
+        }
+        (Self::Ok, None)
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Error => "error",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// One audited `tools/call`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    /// `token` for the static Bearer token, the OAuth `client_id`, or `stdio`.
+    pub principal: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_name: Option<String>,
+    /// The signed-in user, when accounts or OIDC are configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub tool: String,
+    /// Hex SHA-256 of the arguments as serialized JSON.
+    pub arguments_sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<serde_json::Value>,
+    pub duration_ms: u64,
+    /// Requests that reached Semantic Scholar (cache hits are not counted).
+    pub upstream_requests: u32,
+    pub outcome: AuditOutcome,
+    /// Error code for failed calls, or the reason a call was rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    /// A record for a call of `tool` with `arguments`, finished now.
+    ///
+    /// The outcome is read from the JSON-RPC `response`; the principal and
+    /// session are left for the caller to fill in.
+    #[must_use]
+    pub fn new(tool: &str, arguments: &serde_json::Value, response: &JsonRpcResponse) -> Self {
+        let (outcome, error) = AuditOutcome::of(response);
+        Self {
+            timestamp: Utc::now(),
+            principal: String::new(),
+            client_name: None,
+            user: None,
+            session_id: None,
+            tool: tool.to_owned(),
+            arguments_sha256: hash_arguments(arguments),
+            arguments: Some(arguments.clone()),
+            duration_ms: 0,
+            upstream_requests: 0,
+            outcome,
+            error,
+        }
+    }
+}
+
+/// Hex SHA-256 of `arguments`, so identical searches can be matched without
+/// recording them.
+#[must_use]
+pub fn hash_arguments(arguments: &serde_json::Value) -> String {
+    let json = serde_json::to_string(arguments).unwrap_or_default();
+    format!("{:x}", Sha256::digest(json.as_bytes()))
+}
+
+/// Filters for [`AuditLog::query`]; every given field must match.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditQuery {
+    pub tool: Option<String>,
+    pub principal: Option<String>,
+    pub user: Option<String>,
+    pub session_id: Option<String>,
+    pub outcome: Option<AuditOutcome>,
+    /// Only records at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only records before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Most records to return (default 100, at most 1000).
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn matches(&self, record: &AuditRecord) -> bool {
+        self.tool.as_ref().is_none_or(|t| *t == record.tool)
+            && self.principal.as_ref().is_none_or(|p| *p == record.principal)
+            && self.user.as_ref().is_none_or(|u| record.user.as_ref() == Some(u))
+            && self.session_id.as_ref().is_none_or(|s| record.session_id.as_ref() == Some(s))
+            && self.outcome.is_none_or(|o| o == record.outcome)
+            && self.since.is_none_or(|t| record.timestamp >= t)
+            && self.until.is_none_or(|t| record.timestamp < t)
+    }
+}
+
+enum Command {
+    Write(Vec<u8>),
+    /// Answered once every earlier write has reached the file.
+    Flush(mpsc::Sender<()>),
+    /// Like `Flush`, then wait until the second channel's sender is dropped.
+    Pause(mpsc::Sender<()>, mpsc::Receiver<()>),
+}
+
+/// Owns the current file; runs on the writer thread.
+struct Writer {
+    config: AuditConfig,
+    file: File,
+    size: u64,
+    /// Odd while a rotation is moving files, bumped again when it is done.
+    rotation_seq: Arc<AtomicU64>,
+}
+
+impl Writer {
+    fn run(mut self, commands: &mpsc::Receiver<Command>) {
+        for command in commands {
+            match command {
+                Command::Write(line) => {
+                    if let Err(e) = self.write(&line) {
+                        tracing::error!(error = %e, path = %self.config.path.display(), "Failed to write audit record");
+                    }
+                }
+                Command::Flush(done) => {
+                    let _ = done.send(());
+                }
+                Command::Pause(paused, resume) => {
+                    let _ = paused.send(());
+                    let _ = resume.recv();
+                }
+            }
+        }
+    }
+
+    fn write(&mut self, line: &[u8]) -> io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 > self.config.max_bytes {
+            self.rotation_seq.fetch_add(1, Ordering::AcqRel);
+            let rotated = self.rotate();
+            self.rotation_seq.fetch_add(1, Ordering::AcqRel);
+            rotated?;
+        }
+        self.file.write_all(line)?;
+        self.file.flush()?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shift `log.N` to `log.N+1` (dropping the oldest), move the current
+    /// file to `log.1` and start a new one.
+    fn rotate(&mut self) -> io::Result<()> {
+        let keep = self.config.keep;
+        if keep == 0 {
+            self.file = File::create(&self.config.path)?;
+        } else {
+            let _ = fs::remove_file(rotated(&self.config.path, keep));
+            for n in (1..keep).rev() {
+                let from = rotated(&self.config.path, n);
+                if from.exists() {
+                    fs::rename(from, rotated(&self.config.path, n + 1))?;
+                }
+            }
+            fs::rename(&self.config.path, rotated(&self.config.path, 1))?;
+            self.file = append(&self.config.path)?;
+        }
+        self.size = 0;
+        tracing::info!(path = %self.config.path.display(), "Rotated audit log");
+        Ok(())
+    }
+}
+
+/// Append-only, size-rotated JSON-lines audit log.
+#[derive(Debug)]
+pub struct AuditLog {
+    config: AuditConfig,
+    commands: mpsc::Sender<Command>,
+    rotation_seq: Arc<AtomicU64>,
+}
+
+impl AuditLog {
+    /// Open (or create) the log, creating its directory if needed, and
+    /// start its writer thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened for appending or the
+    /// thread cannot be started.
+    pub fn open(config: AuditConfig) -> io::Result<Self> {
+        if let Some(dir) = config.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = append(&config.path)?;
+        let size = file.metadata()?.len();
+        let rotation_seq = Arc::new(AtomicU64::new(0));
+        let writer =
+            Writer { config: config.clone(), file, size, rotation_seq: Arc::clone(&rotation_seq) };
+        let (commands, received) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("audit-writer".into())
+            .spawn(move || writer.run(&received))?;
+        Ok(Self { config, commands, rotation_seq })
+    }
+
+    #[must_use]
+    pub const fn config(&self) -> &AuditConfig {
+        &self.config
+    }
+
+    /// Queue `record` for writing, dropping its arguments unless they are to
+    /// be kept. Returns without waiting for the disk.
+    pub fn record(&self, mut record: AuditRecord) {
+        if !self.config.record_arguments {
+            record.arguments = None;
+        }
+        let mut line = match serde_json::to_vec(&record) {
+            Ok(line) => line,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize audit record");
+                return;
+            }
+        };
+        line.push(b'\n');
+        if self.commands.send(Command::Write(line)).is_err() {
+            tracing::error!(path = %self.config.path.display(), "Audit writer has stopped");
+        }
+    }
+
+    /// Block until every record queued so far is on disk.
+    pub fn flush(&self) {
+        let (done, wait) = mpsc::channel();
+        if self.commands.send(Command::Flush(done)).is_ok() {
+            let _ = wait.recv();
+        }
+    }
+
+    /// Records matching `query`, newest first, across the current and
+    /// rotated files. Unreadable lines are skipped.
+    ///
+    /// Blocks on file I/O, so call it from a blocking task. The files are
+    /// read line by line without stopping the writer, keeping at most the
+    /// requested number of records in memory. A read that overlapped a
+    /// rotation is retried; if rotations keep overlapping, the writer is
+    /// paused for one last read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an existing file cannot be read.
+    pub fn query(&self, query: &AuditQuery) -> io::Result<Vec<AuditRecord>> {
+        let limit = query.limit.unwrap_or(100).min(MAX_QUERY_LIMIT);
+        self.flush();
+        for _ in 0..QUERY_ATTEMPTS {
+            let before = self.rotation_seq.load(Ordering::Acquire);
+            if before % 2 == 1 {
+                std::thread::yield_now();
+                continue;
+            }
+            let found = self.read_newest(query, limit)?;
+            if self.rotation_seq.load(Ordering::Acquire) == before {
+                return Ok(found);
+            }
+        }
+        let (resume, held) = mpsc::channel();
+        let (paused, wait) = mpsc::channel();
+        if self.commands.send(Command::Pause(paused, held)).is_ok() {
+            let _ = wait.recv();
+        }
+        let found = self.read_newest(query, limit);
+        drop(resume);
+        found
+    }
+
+    fn read_newest(&self, query: &AuditQuery, limit: usize) -> io::Result<Vec<AuditRecord>> {
+        let mut found = Vec::new();
+        let files = std::iter::once(self.config.path.clone())
+            .chain((1..=self.config.keep).map(|n| rotated(&self.config.path, n)));
+        for path in files {
+            if found.len() >= limit {
+                break;
+            }
+            found.extend(last_matching(&path, query, limit - found.len())?.into_iter().rev());
+        }
+        Ok(found)
+    }
+}
+
+/// Path of the `n`th rotated file.
+fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.to_path_buf().into_os_string();
+    name.push(format!(".{n}"));
+    name.into()
+}
+
+fn append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// The last `limit` records in `path` that match `query`, oldest first.
+fn last_matching(
+    path: &Path,
+    query: &AuditQuery,
+    limit: usize,
+) -> io::Result<VecDeque<AuditRecord>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(VecDeque::new()),
+        Err(e) => return Err(e),
+    };
+    let mut records = VecDeque::with_capacity(limit);
+    for line in BufReader::new(file).lines() {
+        let Ok(record) = serde_json::from_str::<AuditRecord>(&line?) else {
+            continue;
+        };
+        if !query.matches(&record) {
+            continue;
+        }
+        if records.len() == limit {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("s2-audit-{name}-{}", uuid::Uuid::new_v4()))
+            .join("audit.jsonl")
+    }
+
+    fn record(tool: &str, principal: &str) -> AuditRecord {
+        let response = JsonRpcResponse::success(None, serde_json::json!({"content": []}));
+        AuditRecord {
+            principal: principal.into(),
+            ..AuditRecord::new(tool, &serde_json::json!({"query": "llm"}), &response)
+        }
+    }
+
+    #[test]
+    fn test_rotation_keeps_newest_files() {
+        let path = temp_path("rotate");
+        let line_len = serde_json::to_vec(&AuditRecord { arguments: None, ..record("t0", "a") })
+            .unwrap()
+            .len() as u64;
+        // Room for two records (timestamps vary slightly in length).
+        let config = AuditConfig {
+            max_bytes: 2 * line_len + line_len / 2,
+            keep: 2,
+            ..AuditConfig::new(&path)
+        };
+        let log = AuditLog::open(config).unwrap();
+
+        for n in 0..7 {
+            log.record(record(&format!("t{n}"), "a"));
+        }
+        log.flush();
+        assert!(path.with_extension("jsonl.2").exists());
+        assert!(!path.with_extension("jsonl.3").exists());
+
+        // Two records per file: t6 now, t4-t5 in .1, t2-t3 in .2; t0-t1 dropped.
+        let all = log.query(&AuditQuery::default()).unwrap();
+        let tools: Vec<_> = all.iter().map(|r| r.tool.as_str()).collect();
+        assert_eq!(tools, ["t6", "t5", "t4", "t3", "t2"]);
+        assert!(all.iter().all(|r| r.arguments.is_none()));
+    }
+
+    #[test]
+    fn test_query_filters_and_limits() {
+        let log = AuditLog::open(AuditConfig::new(temp_path("query"))).unwrap();
+        log.record(record("paper_search", "a"));
+        log.record(record("author_search", "b"));
+        log.record(record("paper_search", "b"));
+
+        let query = AuditQuery { principal: Some("b".into()), ..AuditQuery::default() };
+        let found = log.query(&query).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].tool, "paper_search");
+
+        let query = AuditQuery { limit: Some(1), ..AuditQuery::default() };
+        assert_eq!(log.query(&query).unwrap().len(), 1);
+
+        let query = AuditQuery { since: Some(Utc::now()), ..AuditQuery::default() };
+        assert!(log.query(&query).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_query_while_rotating_sees_each_record_once() {
+        let path = temp_path("concurrent");
+        let line_len = serde_json::to_vec(&AuditRecord { arguments: None, ..record("t00", "a") })
+            .unwrap()
+            .len() as u64;
+        let config = AuditConfig { max_bytes: 3 * line_len, keep: 50, ..AuditConfig::new(&path) };
+        let log = Arc::new(AuditLog::open(config).unwrap());
+
+        let writer = {
+            let log = Arc::clone(&log);
+            std::thread::spawn(move || {
+                for n in 0..60 {
+                    log.record(record(&format!("t{n:02}"), "a"));
+                }
+            })
+        };
+        for _ in 0..20 {
+            let query = AuditQuery { limit: Some(1000), ..AuditQuery::default() };
+            let tools: Vec<String> =
+                log.query(&query).unwrap().into_iter().map(|r| r.tool).collect();
+            let mut sorted = tools.clone();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            sorted.dedup();
+            assert_eq!(tools, sorted, "newest first, no duplicates");
+        }
+        writer.join().unwrap();
+        assert_eq!(log.query(&AuditQuery::default()).unwrap().len(), 60);
+    }
+
+    #[test]
+    fn test_outcome_from_response() {
+        let args = serde_json::json!({});
+        let rejected = JsonRpcResponse::error(None, -32602, "Tool not found: x");
+        let record = AuditRecord::new("x", &args, &rejected);
+        assert_eq!(record.outcome, AuditOutcome::Rejected);
+        assert_eq!(record.error.as_deref(), Some("Tool not found: x"));
+
+        let failed = JsonRpcResponse::success(
+            None,
+            serde_json::json!({"isError": true, "_meta": {"error": {"code": "not_found"}}}),
+        );
+        let record = AuditRecord::new("x", &args, &failed);
+        assert_eq!(record.outcome, AuditOutcome::Error);
+        assert_eq!(record.error.as_deref(), Some("not_found"));
+        assert_eq!(record.arguments_sha256, hash_arguments(&serde_json::json!({})));
+    }
+}
@@ -0,0 +1,230 @@
+//! Argument completion (`completion/complete`).
+//!
+//! Completions are chosen by argument name, so the same argument completes
+//! the same way in every tool:
+//!
+//! - paper IDs (`paperId`, `seedPaperIds`, …) via paper title autocomplete
+//! - author IDs (`authorId`, `authorIds`) via author search
+//! - `fieldsOfStudy` from the fixed Semantic Scholar list
+//! - `venue`/`venueQuery` from venues of matching papers
+//! - `format` from [`ExportFormat`]
+//!
+//! Any other argument whose schema is an enum completes from the enum values.
+//! References may be `ref/tool` (naming a tool, whose input schema supplies
+//! the enums) or `ref/prompt`; no prompts are registered yet, so the latter
+//! completes by argument name alone.
+
+use serde_json::{Value, json};
+
+use crate::config::FIELDS_OF_STUDY;
+use crate::error::{ToolError, ToolResult};
+use crate::models::ExportFormat;
+use crate::tools::ToolContext;
+
+/// Maximum number of values returned (MCP limit).
+const MAX_VALUES: usize = 100;
+
+/// Shortest input that triggers an upstream lookup.
+const MIN_LOOKUP_LEN: usize = 3;
+
+/// How many authors/papers to fetch per lookup.
+const LOOKUP_LIMIT: i32 = 10;
+
+/// How values for an argument are produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgumentKind {
+    Paper,
+    Author,
+    FieldOfStudy,
+    Venue,
+    ExportFormat,
+}
+
+impl ArgumentKind {
+    fn for_argument(name: &str) -> Option<Self> {
+        match name {
+            "paperId" | "paperIds" | "seedPaperId" | "seedPaperIds" | "positivePaperIds"
+            | "negativePaperIds" => Some(Self::Paper),
+            "authorId" | "authorIds" => Some(Self::Author),
+            "fieldsOfStudy" => Some(Self::FieldOfStudy),
+            "venue" | "venueQuery" => Some(Self::Venue),
+            "format" => Some(Self::ExportFormat),
+            _ => None,
+        }
+    }
+}
+
+/// Handle `completion/complete`.
+///
+/// `input_schema` resolves a tool name to its input schema, or `None` if the
+/// tool does not exist (or is hidden from this client).
+///
+/// # Errors
+///
+/// Returns [`ToolError::Validation`] for a malformed `ref` or `argument`.
+pub async fn complete(
+    ctx: &ToolContext,
+    params: &Value,
+    input_schema: impl Fn(&str) -> Option<Value>,
+) -> ToolResult<Value> {
+    let reference = params.get("ref").ok_or_else(|| ToolError::validation("ref", "is required"))?;
+    let schema = match reference.get("type").and_then(Value::as_str) {
+        Some("ref/tool") => {
+            let name = reference
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| ToolError::validation("ref.name", "is required"))?;
+            Some(input_schema(name).ok_or_else(|| {
+                ToolError::validation("ref.name", format!("unknown tool '{name}'"))
+            })?)
+        }
+        Some("ref/prompt") => None,
+        _ => {
+            return Err(ToolError::validation(
+                "ref.type",
+                "must be one of \"ref/tool\", \"ref/prompt\"",
+            ));
+        }
+    };
+
+    let argument = params.get("argument");
+    let name = argument
+        .and_then(|a| a.get("name"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| ToolError::validation("argument.name", "is required"))?;
+    let value = argument.and_then(|a| a.get("value")).and_then(Value::as_str).unwrap_or("");
+
+    let values = match ArgumentKind::for_argument(name) {
+        Some(kind) => complete_kind(ctx, kind, value).await,
+        None => schema
+            .as_ref()
+            .map(|s| filter_prefix(schema_choices(s, name), value))
+            .unwrap_or_default(),
+    };
+
+    Ok(completion_result(values))
+}
+
+async fn complete_kind(ctx: &ToolContext, kind: ArgumentKind, value: &str) -> Vec<String> {
+    match kind {
+        ArgumentKind::FieldOfStudy => {
+            let needle = value.to_lowercase();
+            FIELDS_OF_STUDY
+                .iter()
+                .filter(|f| f.to_lowercase().contains(&needle))
+                .map(ToString::to_string)
+                .collect()
+        }
+        ArgumentKind::ExportFormat => {
+            filter_prefix(ExportFormat::ALL.iter().map(|f| f.as_str().to_string()).collect(), value)
+        }
+        _ if value.trim().chars().count() < MIN_LOOKUP_LEN => Vec::new(),
+        ArgumentKind::Paper => match ctx.client.autocomplete_papers(value).await {
+            Ok(matches) => matches.into_iter().map(|m| m.id).filter(|id| !id.is_empty()).collect(),
+            Err(e) => lookup_failed("paper", &e),
+        },
+        ArgumentKind::Author => {
+            match ctx.client.search_authors(value, 0, LOOKUP_LIMIT, &["authorId", "name"]).await {
+                Ok(result) => result.data.into_iter().map(|a| a.author_id).collect(),
+                Err(e) => lookup_failed("author", &e),
+            }
+        }
+        ArgumentKind::Venue => {
+            match ctx.client.search_papers(value, 0, LOOKUP_LIMIT * 2, &["venue"], &[]).await {
+                Ok(result) => {
+                    let needle = value.to_lowercase();
+                    let mut venues: Vec<String> = Vec::new();
+                    for venue in result.data.into_iter().filter_map(|p| p.venue) {
+                        if venue.to_lowercase().contains(&needle) && !venues.contains(&venue) {
+                            venues.push(venue);
+                        }
+                    }
+                    venues
+                }
+                Err(e) => lookup_failed("venue", &e),
+            }
+        }
+    }
+}
+
+/// Completion is best-effort: upstream failures yield no suggestions.
+fn lookup_failed(what: &str, error: &dyn std::fmt::Display) -> Vec<String> {
+    tracing::warn!(what, error = %error, "Completion lookup failed");
+    Vec::new()
+}
+
+/// String constants allowed by an argument's schema (or its array items).
+fn schema_choices(schema: &Value, argument: &str) -> Vec<String> {
+    let Some(prop) = schema.get("properties").and_then(|p| p.get(argument)) else {
+        return Vec::new();
+    };
+    let prop = prop.get("items").unwrap_or(prop);
+
+    let mut choices: Vec<String> = Vec::new();
+    let mut push_all = |values: &Value| {
+        for v in values.as_array().into_iter().flatten().filter_map(Value::as_str) {
+            choices.push(v.to_string());
+        }
+    };
+    if let Some(values) = prop.get("enum") {
+        push_all(values);
+    }
+    for key in ["oneOf", "anyOf"] {
+        for variant in prop.get(key).and_then(Value::as_array).into_iter().flatten() {
+            if let Some(constant) = variant.get("const") {
+                push_all(&json!([constant]));
+            }
+            if let Some(values) = variant.get("enum") {
+                push_all(values);
+            }
+        }
+    }
+    choices
+}
+
+fn filter_prefix(values: Vec<String>, prefix: &str) -> Vec<String> {
+    let prefix = prefix.to_lowercase();
+    values.into_iter().filter(|v| v.to_lowercase().starts_with(&prefix)).collect()
+}
+
+fn completion_result(mut values: Vec<String>) -> Value {
+    let total = values.len();
+    values.truncate(MAX_VALUES);
+    json!({
+        "completion": {
+            "values": values,
+            "total": total,
+            "hasMore": total > MAX_VALUES
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SemanticSearchInput;
+    use crate::tools::input_schema_for;
+
+    #[test]
+    fn test_argument_kinds() {
+        assert_eq!(ArgumentKind::for_argument("seedPaperIds"), Some(ArgumentKind::Paper));
+        assert_eq!(ArgumentKind::for_argument("authorId"), Some(ArgumentKind::Author));
+        assert_eq!(ArgumentKind::for_argument("query"), None);
+    }
+
+    #[test]
+    fn test_schema_choices_from_enum() {
+        let schema = input_schema_for::<SemanticSearchInput>();
+        let choices = schema_choices(&schema, "responseFormat");
+        assert_eq!(choices, vec!["markdown", "json"]);
+        assert!(schema_choices(&schema, "seedPaperId").is_empty());
+    }
+
+    #[test]
+    fn test_completion_result_caps_values() {
+        let result = completion_result((0..150).map(|i| i.to_string()).collect());
+        assert_eq!(result["completion"]["values"].as_array().unwrap().len(), MAX_VALUES);
+        assert_eq!(result["completion"]["total"], 150);
+        assert_eq!(result["completion"]["hasMore"], true);
+    }
+}
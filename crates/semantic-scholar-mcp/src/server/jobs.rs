@@ -0,0 +1,703 @@
+//! Background job mode for long-running tool calls.
+//!
+//! Large runs (multi-million record `bulk_boolean_search`, depth-3
+//! `citation_snowball`) outlive typical client request timeouts. Instead of
+//! calling the tool directly, a client calls `job_submit` with the tool name
+//! and arguments and gets a job ID back immediately. The tool then runs in a
+//! background task and the client uses the companion tools:
+//!
+//! - `job_status` — status plus partial results since a cursor
+//! - `job_result` — the final `CallToolResult` once finished
+//! - `job_cancel` — abort a running job
+//!
+//! Progress and completion are also pushed as JSON-RPC notifications
+//! (`notifications/job/progress`, `notifications/job/finished`) into the
+//! submitting [`Session`]'s event buffer, so an SSE client that reconnects
+//! with `Last-Event-ID` replays anything it missed. Progress notifications
+//! carry only the partial's index; the partials themselves are fetched with
+//! `job_status`, which keeps the most recent [`MAX_PARTIALS`] of them.
+//!
+//! A job belongs to whoever submitted it: the signed-in user, else the OAuth
+//! client, else the session. Other callers get `not_found` for its ID.
+//!
+//! Jobs are only offered on the HTTP transport.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::{RwLock, mpsc, oneshot};
+use tokio::task::AbortHandle;
+use tracing::Instrument;
+
+use super::audit::{AuditOutcome, AuditRecord};
+use super::policy::ClientIdentity;
+use super::session::Session;
+use super::transport::{HttpState, JsonRpcResponse, McpToolInfo};
+use crate::error::{ToolError, ToolResult};
+use crate::telemetry;
+use crate::tools::{ToolAnnotations, ToolContext, ToolOutput, input_schema_for};
+
+/// How long finished jobs are kept for `job_status`/`job_result`.
+const JOB_RETENTION: Duration = Duration::from_hours(1);
+
+/// Cleanup interval for finished jobs.
+const CLEANUP_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Partial results kept per job; older ones are dropped first.
+pub const MAX_PARTIALS: usize = 256;
+
+/// Companion tool names.
+pub const JOB_SUBMIT: &str = "job_submit";
+pub const JOB_STATUS: &str = "job_status";
+pub const JOB_RESULT: &str = "job_result";
+pub const JOB_CANCEL: &str = "job_cancel";
+
+/// Lifecycle state of a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Still executing.
+    Running,
+    /// Tool returned successfully.
+    Completed,
+    /// Tool returned an error (see `job_result`).
+    Failed,
+    /// Cancelled via `job_cancel`.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Whether the job has stopped running.
+    #[must_use]
+    pub const fn is_finished(self) -> bool {
+        !matches!(self, Self::Running)
+    }
+}
+
+struct JobState {
+    status: JobStatus,
+    partials: VecDeque<Value>,
+    /// Partials reported so far, including dropped ones.
+    partial_count: usize,
+    result: Option<Value>,
+    finished_at: Option<Instant>,
+}
+
+/// A single background tool run.
+pub struct Job {
+    /// Unique job identifier.
+    pub id: String,
+    /// Tool being run.
+    pub tool: String,
+    /// When the job was submitted.
+    pub created_at: DateTime<Utc>,
+    /// Who submitted the job; see [`JobOwner`].
+    owner: JobOwner,
+    session: Option<Arc<Session>>,
+    state: RwLock<JobState>,
+    abort: Mutex<Option<AbortHandle>>,
+}
+
+impl Job {
+    fn new(tool: &str, owner: JobOwner, session: Option<Arc<Session>>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool: tool.to_owned(),
+            created_at: Utc::now(),
+            owner,
+            session,
+            state: RwLock::new(JobState {
+                status: JobStatus::Running,
+                partials: VecDeque::new(),
+                partial_count: 0,
+                result: None,
+                finished_at: None,
+            }),
+            abort: Mutex::new(None),
+        }
+    }
+
+    /// Current status.
+    pub async fn status(&self) -> JobStatus {
+        self.state.read().await.status
+    }
+
+    /// Who submitted the job.
+    #[must_use]
+    pub const fn owner(&self) -> &JobOwner {
+        &self.owner
+    }
+
+    /// Status payload with partial results from index `since` onwards.
+    ///
+    /// Partials that were dropped to stay under [`MAX_PARTIALS`] are skipped.
+    pub async fn snapshot(&self, since: usize) -> Value {
+        let state = self.state.read().await;
+        let first = state.partial_count - state.partials.len();
+        let skip = since.clamp(first, state.partial_count) - first;
+        let partials: Vec<&Value> = state.partials.iter().skip(skip).collect();
+        json!({
+            "jobId": self.id,
+            "tool": self.tool,
+            "status": state.status,
+            "createdAt": self.created_at.to_rfc3339(),
+            "partialCount": state.partial_count,
+            "nextCursor": state.partial_count,
+            "partials": partials,
+        })
+    }
+
+    /// Final `CallToolResult`, once finished.
+    pub async fn result(&self) -> Option<Value> {
+        self.state.read().await.result.clone()
+    }
+
+    async fn push_partial(&self, partial: Value) {
+        let index = {
+            let mut state = self.state.write().await;
+            if state.partials.len() == MAX_PARTIALS {
+                state.partials.pop_front();
+            }
+            state.partials.push_back(partial);
+            state.partial_count += 1;
+            state.partial_count - 1
+        };
+        self.notify("notifications/job/progress", json!({"jobId": self.id, "index": index})).await;
+    }
+
+    async fn finish(&self, status: JobStatus, result: Option<Value>) {
+        {
+            let mut state = self.state.write().await;
+            if state.status.is_finished() {
+                return;
+            }
+            state.status = status;
+            state.result = result;
+            state.finished_at = Some(Instant::now());
+        }
+        tracing::info!(job_id = %self.id, tool = %self.tool, status = ?status, "Job finished");
+        self.notify("notifications/job/finished", json!({"jobId": self.id, "status": status}))
+            .await;
+    }
+
+    /// Abort the background task. Returns false if the job already finished.
+    pub async fn cancel(&self) -> bool {
+        if self.status().await.is_finished() {
+            return false;
+        }
+        let handle = self.abort.lock().expect("abort lock poisoned").take();
+        if let Some(handle) = handle {
+            handle.abort();
+        }
+        self.finish(JobStatus::Cancelled, None).await;
+        true
+    }
+
+    async fn notify(&self, method: &str, params: Value) {
+        if let Some(session) = &self.session {
+            let notification = json!({"jsonrpc": "2.0", "method": method, "params": params});
+            session.push_event("message", notification.to_string()).await;
+        }
+    }
+
+    async fn is_expired(&self) -> bool {
+        self.state.read().await.finished_at.is_some_and(|t| t.elapsed() > JOB_RETENTION)
+    }
+}
+
+/// Identity a job is tied to, taken from the submitting request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobOwner {
+    /// A signed-in user, whichever OAuth client they use.
+    User(String),
+    /// An OAuth client without a user behind it.
+    Client(String),
+    /// The MCP session, when the request carried no client identity.
+    Session(String),
+}
+
+impl JobOwner {
+    /// Owner for a request from `client` on `session`.
+    #[must_use]
+    pub fn of(client: Option<&ClientIdentity>, session: &Session) -> Self {
+        match client {
+            Some(ClientIdentity { principal: Some(principal), .. }) => {
+                Self::User(principal.user.clone())
+            }
+            Some(client) => Self::Client(client.client_id.clone()),
+            None => Self::Session(session.id.as_str().to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Debug for Job {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Job").field("id", &self.id).field("tool", &self.tool).finish()
+    }
+}
+
+/// Registry of background jobs.
+#[derive(Default)]
+pub struct JobManager {
+    jobs: RwLock<HashMap<String, Arc<Job>>>,
+}
+
+impl JobManager {
+    /// Create an empty job manager.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start `run` in the background as a job for `tool`.
+    ///
+    /// `run` receives a [`ToolContext`] wired to the job's progress sink;
+    /// partial results reported through it are recorded on the job and
+    /// announced to `session`.
+    pub async fn start<F, Fut>(
+        &self,
+        tool: &str,
+        owner: JobOwner,
+        session: Option<Arc<Session>>,
+        ctx: &ToolContext,
+        run: F,
+    ) -> Arc<Job>
+    where
+        F: FnOnce(ToolContext) -> Fut,
+        Fut: Future<Output = ToolResult<ToolOutput>> + Send + 'static,
+    {
+        let job = Arc::new(Job::new(tool, owner, session));
+        self.jobs.write().await.insert(job.id.clone(), Arc::clone(&job));
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let future = run(ctx.clone().with_progress(tx));
+        let runner = Arc::clone(&job);
+
+        // The task waits until its abort handle is registered, so a cancel can
+        // never slip in while there is nothing to abort.
+        let (registered, ready) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            if ready.await.is_err() {
+                return;
+            }
+            tokio::pin!(future);
+            let outcome = loop {
+                tokio::select! {
+                    biased;
+                    Some(partial) = rx.recv() => runner.push_partial(partial).await,
+                    outcome = &mut future => break outcome,
+                }
+            };
+            while let Ok(partial) = rx.try_recv() {
+                runner.push_partial(partial).await;
+            }
+            match outcome {
+                Ok(output) => runner.finish(JobStatus::Completed, Some(output.to_call_result())),
+                Err(e) => runner.finish(JobStatus::Failed, Some(e.to_call_result())),
+            }
+            .await;
+        });
+        *job.abort.lock().expect("abort lock poisoned") = Some(handle.abort_handle());
+        let _ = registered.send(());
+
+        tracing::info!(job_id = %job.id, tool = %tool, "Job started");
+        job
+    }
+
+    /// Look up a job by ID.
+    pub async fn get(&self, id: &str) -> Option<Arc<Job>> {
+        self.jobs.read().await.get(id).cloned()
+    }
+
+    /// Number of tracked jobs (running and retained).
+    pub async fn job_count(&self) -> usize {
+        self.jobs.read().await.len()
+    }
+
+    /// Drop finished jobs past their retention period.
+    pub async fn cleanup_expired(&self) -> usize {
+        let mut expired = Vec::new();
+        for (id, job) in self.jobs.read().await.iter() {
+            if job.is_expired().await {
+                expired.push(id.clone());
+            }
+        }
+        let mut jobs = self.jobs.write().await;
+        for id in &expired {
+            jobs.remove(id);
+        }
+        expired.len()
+    }
+
+    /// Start background cleanup task.
+    pub fn start_cleanup_task(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+            loop {
+                interval.tick().await;
+                let cleaned = self.cleanup_expired().await;
+                if cleaned > 0 {
+                    tracing::debug!(count = cleaned, "Job cleanup completed");
+                }
+            }
+        });
+    }
+}
+
+impl std::fmt::Debug for JobManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobManager").finish()
+    }
+}
+
+/// Input for `job_submit`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSubmitInput {
+    /// Name of the tool to run in the background (e.g. "bulk_boolean_search").
+    pub tool: String,
+
+    /// Arguments for the tool, exactly as for a direct call.
+    #[serde(default)]
+    pub arguments: serde_json::Map<String, Value>,
+}
+
+/// Input for `job_status`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatusInput {
+    /// Job ID returned by `job_submit`.
+    pub job_id: String,
+
+    /// Return partial results from this index on (use the previous `nextCursor`).
+    #[serde(default)]
+    pub since: usize,
+}
+
+/// Input for `job_result` and `job_cancel`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobIdInput {
+    /// Job ID returned by `job_submit`.
+    pub job_id: String,
+}
+
+/// Whether `name` is one of the job companion tools.
+#[must_use]
+pub fn is_job_tool(name: &str) -> bool {
+    matches!(name, JOB_SUBMIT | JOB_STATUS | JOB_RESULT | JOB_CANCEL)
+}
+
+fn status_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "jobId": {"type": "string"},
+            "tool": {"type": "string"},
+            "status": {"type": "string", "enum": ["running", "completed", "failed", "cancelled"]},
+            "createdAt": {"type": "string", "format": "date-time"},
+            "partialCount": {"type": "integer"},
+            "nextCursor": {"type": "integer"},
+            "partials": {"type": "array", "items": {"type": "object"}}
+        },
+        "required": ["jobId", "tool", "status"]
+    })
+}
+
+/// `tools/list` entries for the job companion tools.
+#[must_use]
+pub fn tool_infos() -> Vec<McpToolInfo> {
+    vec![
+        McpToolInfo {
+            name: JOB_SUBMIT.to_string(),
+            description: "Run any tool as a background job and return a job ID immediately. \
+                          Use for very large runs (bulk_boolean_search, deep citation_snowball) \
+                          that would exceed request timeouts; follow up with job_status, \
+                          job_result or job_cancel."
+                .to_string(),
+            input_schema: input_schema_for::<JobSubmitInput>(),
+            output_schema: status_schema(),
+            annotations: ToolAnnotations {
+                read_only_hint: false,
+                idempotent_hint: false,
+                ..ToolAnnotations::default()
+            },
+        },
+        McpToolInfo {
+            name: JOB_STATUS.to_string(),
+            description: "Get a job's status and any partial results produced since `since`."
+                .to_string(),
+            input_schema: input_schema_for::<JobStatusInput>(),
+            output_schema: status_schema(),
+            annotations: ToolAnnotations::default().local(),
+        },
+        McpToolInfo {
+            name: JOB_RESULT.to_string(),
+            description: "Get the final result of a finished job, in the same shape as a direct \
+                          call to the tool."
+                .to_string(),
+            input_schema: input_schema_for::<JobIdInput>(),
+            output_schema: json!({"type": "object"}),
+            annotations: ToolAnnotations::default().local(),
+        },
+        McpToolInfo {
+            name: JOB_CANCEL.to_string(),
+            description: "Cancel a running job.".to_string(),
+            input_schema: input_schema_for::<JobIdInput>(),
+            output_schema: status_schema(),
+            annotations: ToolAnnotations {
+                read_only_hint: false,
+                destructive_hint: true,
+                ..ToolAnnotations::default().local()
+            },
+        },
+    ]
+}
+
+/// Execute a job companion tool, returning the MCP `CallToolResult`.
+pub(super) async fn call_job_tool(
+    name: &str,
+    arguments: Value,
+    state: &Arc<HttpState>,
+    session: &Arc<Session>,
+    client: Option<&ClientIdentity>,
+) -> ToolResult<Value> {
+    let jobs = &state.jobs;
+    match name {
+        JOB_SUBMIT => {
+            let input: JobSubmitInput = serde_json::from_value(arguments)?;
+            let Some(tool) = state.find_tool(client, &input.tool) else {
+                return Err(ToolError::validation(
+                    "tool",
+                    format!("unknown tool '{}'", input.tool),
+                ));
+            };
+            let args = state.ctx.tool_defaults().apply(&input.tool, Value::Object(input.arguments));
+            tool.validate_input(&args).map_err(|e| match e {
+                ToolError::Validation { field, message } => {
+                    ToolError::validation(format!("arguments.{field}"), message)
+                }
+                other => other,
+            })?;
+
+            let ctx = state.tool_context(client);
+            let owner = JobOwner::of(client, session);
+            let state_for_job = Arc::clone(state);
+            let tool_name = input.tool.clone();
+            let job_client = client.cloned();
+            let job_session = Arc::clone(session);
+            let job = jobs
+                .start(&input.tool, owner, Some(Arc::clone(session)), &ctx, move |ctx| {
+                    run_job(state_for_job, tool_name, job_client, job_session, args, ctx)
+                })
+                .await;
+
+            let snapshot = job.snapshot(0).await;
+            let text = format!(
+                "Started job `{}` running `{}`. Poll with job_status or wait for \
+                 notifications/job/finished.",
+                job.id, job.tool
+            );
+            Ok(ToolOutput::new(text, snapshot).to_call_result())
+        }
+        JOB_STATUS => {
+            let input: JobStatusInput = serde_json::from_value(arguments)?;
+            let job = find(jobs, &input.job_id, client, session).await?;
+            ToolOutput::json(job.snapshot(input.since).await).map(|o| o.to_call_result())
+        }
+        JOB_RESULT => {
+            let input: JobIdInput = serde_json::from_value(arguments)?;
+            let job = find(jobs, &input.job_id, client, session).await?;
+            match (job.status().await, job.result().await) {
+                (_, Some(result)) => Ok(result),
+                (JobStatus::Running, None) => Err(ToolError::validation(
+                    "jobId",
+                    "job is still running; poll job_status until it finishes",
+                )),
+                (_, None) => Err(ToolError::not_found(format!("result for job {}", job.id))),
+            }
+        }
+        JOB_CANCEL => {
+            let input: JobIdInput = serde_json::from_value(arguments)?;
+            let job = find(jobs, &input.job_id, client, session).await?;
+            if !job.cancel().await {
+                return Err(ToolError::validation("jobId", "job has already finished"));
+            }
+            ToolOutput::json(job.snapshot(usize::MAX).await).map(|o| o.to_call_result())
+        }
+        _ => Err(ToolError::internal(format!("not a job tool: {name}"))),
+    }
+}
+
+/// Run a submitted tool in the background, metered and audited like a
+/// direct `tools/call`.
+async fn run_job(
+    state: Arc<HttpState>,
+    tool_name: String,
+    client: Option<ClientIdentity>,
+    session: Arc<Session>,
+    args: Value,
+    mut ctx: ToolContext,
+) -> ToolResult<ToolOutput> {
+    let tool = state
+        .tools
+        .iter()
+        .find(|t| t.name() == tool_name)
+        .ok_or_else(|| ToolError::internal("tool disappeared"))?;
+    let started = Instant::now();
+    let requests = Arc::new(AtomicU32::new(0));
+    ctx.client = Arc::new(ctx.client.with_request_counter(Arc::clone(&requests)));
+    let (log_sink, log_forwarder) = session.log_sink();
+    let outcome = log_sink
+        .scope(tool.execute(&ctx, args.clone()))
+        .instrument(telemetry::tool_span(&tool_name))
+        .await;
+    let _ = log_forwarder.await;
+
+    let response = JsonRpcResponse::success(
+        None,
+        match &outcome {
+            Ok(output) => output.to_call_result(),
+            Err(e) => e.to_call_result(),
+        },
+    );
+    let (result, _) = AuditOutcome::of(&response);
+    state.metrics.observe_tool_call(&tool_name, result.as_str(), started.elapsed());
+    if let Some(ref audit) = state.audit {
+        audit.record(AuditRecord {
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            upstream_requests: requests.load(Ordering::Relaxed),
+            ..state.audit_record(client.as_ref(), &session, &tool_name, &args, &response)
+        });
+    }
+    outcome
+}
+
+/// Look up a job the caller owns; someone else's job looks like a missing one.
+async fn find(
+    jobs: &JobManager,
+    id: &str,
+    client: Option<&ClientIdentity>,
+    session: &Session,
+) -> ToolResult<Arc<Job>> {
+    let owner = JobOwner::of(client, session);
+    jobs.get(id)
+        .await
+        .filter(|job| job.owner == owner)
+        .ok_or_else(|| ToolError::not_found(format!("job {id}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::SemanticScholarClient;
+    use crate::config::ClientConfig;
+
+    fn test_ctx() -> ToolContext {
+        let client =
+            SemanticScholarClient::new(ClientConfig::for_testing("http://unused.localhost"));
+        ToolContext::new(Arc::new(client.unwrap()))
+    }
+
+    fn owner() -> JobOwner {
+        JobOwner::User("alice".to_string())
+    }
+
+    async fn wait_until_finished(job: &Job) {
+        for _ in 0..50 {
+            if job.status().await.is_finished() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_records_partials_and_result() {
+        let manager = JobManager::new();
+        let session = Arc::new(Session::new());
+
+        let job = manager
+            .start("demo", owner(), Some(Arc::clone(&session)), &test_ctx(), |ctx| async move {
+                ctx.report_progress(json!({"page": 1}));
+                ctx.report_progress(json!({"page": 2}));
+                Ok(ToolOutput::new("done", json!({"total": 2})))
+            })
+            .await;
+        wait_until_finished(&job).await;
+
+        assert_eq!(job.status().await, JobStatus::Completed);
+        let snapshot = job.snapshot(1).await;
+        assert_eq!(snapshot["partialCount"], 2);
+        assert_eq!(snapshot["partials"], json!([{"page": 2}]));
+        assert_eq!(job.result().await.unwrap()["structuredContent"]["total"], 2);
+
+        // Two progress notifications plus the finish notification were buffered.
+        let events = session.get_events_after(0).await;
+        assert_eq!(events.len(), 3);
+        assert!(!events[0].data.contains("page"));
+        assert!(events[2].data.contains("notifications/job/finished"));
+    }
+
+    #[tokio::test]
+    async fn test_job_cancel() {
+        let manager = JobManager::new();
+        let job = manager
+            .start("slow", owner(), None, &test_ctx(), |_ctx| async move {
+                tokio::time::sleep(Duration::from_mins(1)).await;
+                Ok(ToolOutput::new("never", json!({})))
+            })
+            .await;
+
+        assert!(job.cancel().await);
+        assert_eq!(job.status().await, JobStatus::Cancelled);
+        assert!(!job.cancel().await);
+        assert!(manager.get(&job.id).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_job_keeps_only_recent_partials() {
+        let manager = JobManager::new();
+        let job = manager
+            .start("chatty", owner(), None, &test_ctx(), |ctx| async move {
+                for page in 0..MAX_PARTIALS + 10 {
+                    ctx.report_progress(json!({"page": page}));
+                }
+                Ok(ToolOutput::new("done", json!({})))
+            })
+            .await;
+        wait_until_finished(&job).await;
+
+        let snapshot = job.snapshot(0).await;
+        assert_eq!(snapshot["partialCount"], MAX_PARTIALS + 10);
+        assert_eq!(snapshot["partials"].as_array().unwrap().len(), MAX_PARTIALS);
+        assert_eq!(snapshot["partials"][0]["page"], 10);
+        let tail = job.snapshot(MAX_PARTIALS + 9).await;
+        assert_eq!(tail["partials"], json!([{"page": MAX_PARTIALS + 9}]));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_right_after_start_stops_the_task() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = Arc::clone(&ran);
+        let manager = JobManager::new();
+        let job = manager
+            .start("quick", owner(), None, &test_ctx(), |_ctx| async move {
+                flag.store(true, std::sync::atomic::Ordering::SeqCst);
+                Ok(ToolOutput::new("ran", json!({})))
+            })
+            .await;
+
+        assert!(job.cancel().await);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!ran.load(std::sync::atomic::Ordering::SeqCst));
+        assert_eq!(job.status().await, JobStatus::Cancelled);
+    }
+}
@@ -0,0 +1,318 @@
+//! MCP logging capability bridged from `tracing`.
+//!
+//! Tool and client code keeps logging through `tracing` as usual. While a
+//! tool runs, the transport installs a [`LogSink`] for the current task;
+//! [`McpLogLayer`] picks up events from the tool, API client and retry
+//! middleware and forwards those at or above the client's level (set with
+//! `logging/setLevel`) as `notifications/message`.
+//!
+//! Events emitted outside a sink scope (startup, transport internals) only
+//! go to the server's own log output.
+
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::{Filtered, Targets};
+use tracing_subscriber::layer::Context;
+
+tokio::task_local! {
+    static SINK: LogSink;
+}
+
+/// Targets whose events may be forwarded to clients.
+const FORWARDED_TARGETS: [&str; 3] =
+    ["semantic_scholar_mcp::tools", "semantic_scholar_client::client", "reqwest_retry"];
+
+/// Syslog severities used by MCP (`logging/setLevel`, `notifications/message`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+impl LogLevel {
+    const ALL: [Self; 8] = [
+        Self::Debug,
+        Self::Info,
+        Self::Notice,
+        Self::Warning,
+        Self::Error,
+        Self::Critical,
+        Self::Alert,
+        Self::Emergency,
+    ];
+
+    /// Level name as sent on the wire.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Notice => "notice",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+            Self::Alert => "alert",
+            Self::Emergency => "emergency",
+        }
+    }
+
+    /// Map a `tracing` level (TRACE folds into debug).
+    #[must_use]
+    pub fn from_tracing(level: Level) -> Self {
+        match level {
+            Level::TRACE | Level::DEBUG => Self::Debug,
+            Level::INFO => Self::Info,
+            Level::WARN => Self::Warning,
+            Level::ERROR => Self::Error,
+        }
+    }
+
+    fn from_u8(n: u8) -> Self {
+        Self::ALL.get(usize::from(n)).copied().unwrap_or(Self::Warning)
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|l| l.as_str() == s).ok_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|l| l.as_str()).collect();
+            format!("must be one of {}", names.join(", "))
+        })
+    }
+}
+
+/// A client's minimum log level, shared between its session and sinks.
+///
+/// Defaults to `warning`, so partial failures reach the client even if it
+/// never calls `logging/setLevel`.
+#[derive(Debug, Clone)]
+pub struct LogLevelHandle(Arc<AtomicU8>);
+
+impl LogLevelHandle {
+    /// Create a handle at `level`.
+    #[must_use]
+    pub fn new(level: LogLevel) -> Self {
+        Self(Arc::new(AtomicU8::new(level as u8)))
+    }
+
+    /// Current minimum level.
+    #[must_use]
+    pub fn get(&self) -> LogLevel {
+        LogLevel::from_u8(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Change the minimum level.
+    pub fn set(&self, level: LogLevel) {
+        self.0.store(level as u8, Ordering::Relaxed);
+    }
+}
+
+impl Default for LogLevelHandle {
+    fn default() -> Self {
+        Self::new(LogLevel::Warning)
+    }
+}
+
+/// Destination for log notifications produced while a tool runs.
+#[derive(Debug, Clone)]
+pub struct LogSink {
+    level: LogLevelHandle,
+    tx: mpsc::UnboundedSender<Value>,
+}
+
+impl LogSink {
+    /// Create a sink delivering `notifications/message` objects to `tx`.
+    #[must_use]
+    pub const fn new(level: LogLevelHandle, tx: mpsc::UnboundedSender<Value>) -> Self {
+        Self { level, tx }
+    }
+
+    /// Run `fut` with this sink installed for the current task.
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        SINK.scope(self, fut).await
+    }
+
+    fn forward(&self, level: LogLevel, logger: &str, data: &Value) {
+        if level < self.level.get() {
+            return;
+        }
+        // Receiver gone means the request already finished.
+        let _ = self.tx.send(notification(level, logger, data));
+    }
+}
+
+/// Build a `notifications/message` JSON-RPC notification.
+#[must_use]
+pub fn notification(level: LogLevel, logger: &str, data: &Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/message",
+        "params": {
+            "level": level,
+            "logger": logger,
+            "data": data
+        }
+    })
+}
+
+/// Parse `logging/setLevel` params and apply them.
+///
+/// # Errors
+///
+/// Returns `(field, message)` for a missing or unknown level.
+pub fn set_level(handle: &LogLevelHandle, params: &Value) -> Result<LogLevel, (String, String)> {
+    let Some(name) = params.get("level").and_then(Value::as_str) else {
+        return Err(("level".to_string(), "is required".to_string()));
+    };
+    let level = name.parse::<LogLevel>().map_err(|msg| ("level".to_string(), msg))?;
+    handle.set(level);
+    tracing::debug!(level = %level, "Client log level changed");
+    Ok(level)
+}
+
+/// `tracing` layer forwarding events to the current task's [`LogSink`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct McpLogLayer;
+
+impl McpLogLayer {
+    /// The layer, restricted to tool, API client and retry events.
+    #[must_use]
+    pub fn filtered<S>() -> Filtered<Self, Targets, S>
+    where
+        S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        let targets = FORWARDED_TARGETS
+            .iter()
+            .fold(Targets::new(), |t, target| t.with_target(*target, Level::DEBUG));
+        Self.with_filter(targets)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for McpLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let _ = SINK.try_with(|sink| {
+            let metadata = event.metadata();
+            let level = LogLevel::from_tracing(*metadata.level());
+            let mut visitor = JsonVisitor(Map::new());
+            event.record(&mut visitor);
+            let target = metadata.target();
+            let logger = target
+                .strip_prefix("semantic_scholar_mcp::")
+                .or_else(|| target.strip_prefix("semantic_scholar_client::"))
+                .unwrap_or(target);
+            sink.forward(level, logger, &Value::Object(visitor.0));
+        });
+    }
+}
+
+/// Collects event fields into a JSON object.
+struct JsonVisitor(Map<String, Value>);
+
+impl Visit for JsonVisitor {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    fn sink(level: LogLevel) -> (LogSink, mpsc::UnboundedReceiver<Value>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (LogSink::new(LogLevelHandle::new(level), tx), rx)
+    }
+
+    fn drain(rx: &mut mpsc::UnboundedReceiver<Value>) -> Vec<Value> {
+        std::iter::from_fn(|| rx.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn test_level_order_and_parse() {
+        assert!(LogLevel::Debug < LogLevel::Warning);
+        assert_eq!("notice".parse::<LogLevel>(), Ok(LogLevel::Notice));
+        assert!("verbose".parse::<LogLevel>().unwrap_err().contains("warning"));
+        assert_eq!(LogLevel::from_tracing(Level::WARN), LogLevel::Warning);
+    }
+
+    #[test]
+    fn test_set_level() {
+        let handle = LogLevelHandle::default();
+        assert_eq!(handle.get(), LogLevel::Warning);
+        assert_eq!(set_level(&handle, &json!({"level": "debug"})), Ok(LogLevel::Debug));
+        assert_eq!(handle.get(), LogLevel::Debug);
+        assert!(set_level(&handle, &json!({"level": "loud"})).is_err());
+        assert!(set_level(&handle, &json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_layer_forwards_tool_events_in_scope() {
+        let subscriber = tracing_subscriber::registry().with(McpLogLayer::filtered());
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let (sink, mut rx) = sink(LogLevel::Warning);
+
+        sink.scope(async {
+            tracing::warn!(
+                target: "semantic_scholar_mcp::tools::systematic",
+                query = "q1",
+                "Search query failed"
+            );
+            tracing::info!(target: "semantic_scholar_mcp::tools::systematic", "below level");
+            tracing::warn!(target: "semantic_scholar_mcp::server::transport", "not forwarded");
+        })
+        .await;
+        tracing::warn!(target: "semantic_scholar_mcp::tools::systematic", "outside scope");
+
+        let sent = drain(&mut rx);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0]["method"], "notifications/message");
+        assert_eq!(sent[0]["params"]["level"], "warning");
+        assert_eq!(sent[0]["params"]["logger"], "tools::systematic");
+        assert_eq!(sent[0]["params"]["data"]["query"], "q1");
+        assert_eq!(sent[0]["params"]["data"]["message"], "Search query failed");
+    }
+}
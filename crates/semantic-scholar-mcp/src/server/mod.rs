@@ -1,25 +1,43 @@
 //! MCP server implementation.
 //!
-//! Provides both stdio (for Claude Desktop) and HTTP transports.
+//! Provides stdio (for Claude Desktop) and HTTP transports; the HTTP router
+//! also serves WebSocket clients and can listen on a Unix domain socket.
 //!
 //! ## Never-Failing Architecture
 //!
 //! The HTTP transport implements a robust "mailbox" pattern:
-//! - Session-based message buffering with ring buffer
+//! - Session-based message buffering with ring buffer, optionally persisted
+//!   to disk so sessions survive restarts and can be shared by replicas
 //! - Last-Event-ID support for reconnection recovery
 //! - Broadcast channels for live event delivery
 //! - Background cleanup of stale sessions
 
+pub mod accounts;
+pub mod audit;
+pub mod completion;
+pub mod jobs;
+pub mod logging;
 pub mod oauth;
+pub mod policy;
 pub mod session;
+pub mod session_store;
 pub mod stdio;
 pub mod transport;
+#[cfg(unix)]
+pub mod unix;
+pub mod websocket;
 
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::client::SemanticScholarClient;
-use crate::tools::{self, McpTool, ToolContext};
+use crate::config::ToolDefaults;
+use crate::tools::{self, McpTool, ToolContext, ToolFilter, ToolRegistry};
+
+use audit::{AuditConfig, AuditLog};
+use policy::{POLICY_RELOAD_INTERVAL, ToolPolicy, ToolPolicyHandle};
+use session_store::{FileSessionStore, SessionStore};
 
 /// MCP server for Semantic Scholar.
 pub struct McpServer {
@@ -28,6 +46,30 @@ pub struct McpServer {
 
     /// Registered tools.
     tools: Vec<Box<dyn McpTool>>,
+
+    /// Deployment-wide tool filter.
+    tool_filter: ToolFilter,
+
+    /// Optional policy file with deployment and per-client filters.
+    tool_policy_file: Option<PathBuf>,
+
+    /// Optional directory for persistent HTTP sessions.
+    session_dir: Option<PathBuf>,
+
+    /// Optional file for persistent OAuth registrations and grants.
+    oauth_store_file: Option<PathBuf>,
+
+    /// Optional accounts file enabling user login and scoped tokens.
+    accounts_file: Option<PathBuf>,
+
+    /// Optional OpenID Connect config delegating login to an upstream provider.
+    oidc_file: Option<PathBuf>,
+
+    /// Optional audit log of tool calls.
+    audit: Option<AuditConfig>,
+
+    /// Browser origins, besides the server's own, that may open WebSockets.
+    allowed_origins: Vec<String>,
 }
 
 impl McpServer {
@@ -37,26 +79,146 @@ impl McpServer {
         let ctx = ToolContext::new(Arc::new(client));
         let tools = tools::register_all_tools();
 
-        Self { ctx, tools }
+        Self {
+            ctx,
+            tools,
+            tool_filter: ToolFilter::default(),
+            tool_policy_file: None,
+            session_dir: None,
+            oauth_store_file: None,
+            accounts_file: None,
+            oidc_file: None,
+            audit: None,
+            allowed_origins: Vec::new(),
+        }
+    }
+
+    /// Serve the tools of `registry` instead of the built-in ones.
+    #[must_use]
+    pub fn with_tools(mut self, registry: ToolRegistry) -> Self {
+        self.tools = registry.into_tools();
+        self
+    }
+
+    /// Restrict the exposed tools for this deployment.
+    #[must_use]
+    pub fn with_tool_filter(mut self, filter: ToolFilter) -> Self {
+        let known: Vec<&str> =
+            self.tools.iter().flat_map(|t| [t.name(), t.group().as_str()]).collect();
+        for entry in filter.unknown_entries(&known) {
+            tracing::warn!(entry, "Tool filter entry matches no tool or group");
+        }
+        self.tool_filter = filter;
+        self
+    }
+
+    /// Fill in configured default arguments on every tool call (see
+    /// [`Settings`](crate::config::Settings)).
+    #[must_use]
+    pub fn with_tool_defaults(mut self, defaults: ToolDefaults) -> Self {
+        self.ctx = self.ctx.with_tool_defaults(defaults);
+        self
+    }
+
+    /// Load deployment and per-client filters from a policy file.
+    ///
+    /// In HTTP mode the file is watched and reloaded on change.
+    #[must_use]
+    pub fn with_tool_policy_file(mut self, path: PathBuf) -> Self {
+        self.tool_policy_file = Some(path);
+        self
+    }
+
+    /// Keep HTTP sessions and their replay buffers in `dir`, so clients can
+    /// resume after a restart or on another replica sharing the directory.
+    #[must_use]
+    pub fn with_session_dir(mut self, dir: PathBuf) -> Self {
+        self.session_dir = Some(dir);
+        self
+    }
+
+    /// Persist OAuth clients and grants to `path`, so connectors stay
+    /// authorized across redeploys.
+    #[must_use]
+    pub fn with_oauth_store_file(mut self, path: PathBuf) -> Self {
+        self.oauth_store_file = Some(path);
+        self
+    }
+
+    /// Require users from `path` to sign in at `/authorize`, and limit each
+    /// token to the scopes its user holds.
+    #[must_use]
+    pub fn with_accounts_file(mut self, path: PathBuf) -> Self {
+        self.accounts_file = Some(path);
+        self
+    }
+
+    /// Send users to the OpenID Connect provider configured in `path` to
+    /// sign in, mapping their claims to scopes.
+    #[must_use]
+    pub fn with_oidc_file(mut self, path: PathBuf) -> Self {
+        self.oidc_file = Some(path);
+        self
+    }
+
+    /// Record every tool call in a rotated JSON-lines audit log.
+    #[must_use]
+    pub fn with_audit_log(mut self, config: AuditConfig) -> Self {
+        self.audit = Some(config);
+        self
+    }
+
+    /// Let pages served from `origins` (e.g. `https://app.example.org`) open
+    /// WebSocket connections; other cross-origin upgrades are refused.
+    #[must_use]
+    pub fn with_allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.allowed_origins = origins;
+        self
+    }
+
+    fn open_audit_log(&self) -> anyhow::Result<Option<Arc<AuditLog>>> {
+        let Some(ref config) = self.audit else {
+            return Ok(None);
+        };
+        let log = AuditLog::open(config.clone())
+            .map_err(|e| anyhow::anyhow!("cannot open audit log {}: {e}", config.path.display()))?;
+        tracing::info!(path = %config.path.display(), "Auditing tool calls");
+        Ok(Some(Arc::new(log)))
+    }
+
+    fn load_tool_policy(&self) -> anyhow::Result<ToolPolicy> {
+        self.tool_policy_file.as_deref().map_or_else(|| Ok(ToolPolicy::default()), ToolPolicy::load)
     }
 
     /// Run the server in stdio mode (for Claude Desktop).
     ///
     /// # Errors
     ///
-    /// Returns error on I/O failure.
+    /// Returns error on I/O failure, an invalid tool policy file or an
+    /// unwritable audit log.
     pub async fn run_stdio(self) -> anyhow::Result<()> {
         tracing::info!("Starting MCP server in stdio mode");
-        tracing::info!("Registered {} tools", self.tools.len());
 
-        stdio::run_stdio(self.tools, self.ctx).await
+        // stdio has a single, unauthenticated client: apply the deployment
+        // filters once up front.
+        let policy = self.load_tool_policy()?;
+        let audit = self.open_audit_log()?;
+        let mut tools = self.tools;
+        tools.retain(|t| {
+            self.tool_filter.permits(t.name(), t.group())
+                && policy.permits(None, t.name(), t.group())
+        });
+        tracing::info!("Registered {} tools", tools.len());
+
+        stdio::run_stdio(tools, self.ctx, audit).await
     }
 
     /// Run the server in HTTP mode.
     ///
     /// # Errors
     ///
-    /// Returns error on server failure.
+    /// Returns error on server failure, or any error of the router setup
+    /// (see [`Self::into_router`]).
     pub async fn run_http(
         self,
         port: u16,
@@ -64,23 +226,168 @@ impl McpServer {
         auth_token: Option<String>,
     ) -> anyhow::Result<()> {
         tracing::info!("Starting MCP server in HTTP mode on port {}", port);
-        tracing::info!("Registered {} tools", self.tools.len());
-        if auth_token.is_some() {
-            tracing::info!("Authentication enabled");
-        }
-
-        let router = transport::create_router(self.tools, self.ctx, base_url, auth_token);
+        let router = self.into_router(base_url, auth_token).await?;
         let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
         tracing::info!("HTTP server listening on http://{}", addr);
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, router).with_graceful_shutdown(shutdown_signal()).await?;
+        // Client addresses key the login throttle.
+        let service = router.into_make_service_with_connect_info::<SocketAddr>();
+        axum::serve(listener, service).with_graceful_shutdown(shutdown_signal()).await?;
 
         tracing::info!("HTTP server shut down");
         Ok(())
     }
 
+    /// Run the HTTP transport on a Unix domain socket at `path`, removing
+    /// the socket file on shutdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the socket cannot be bound (see [`unix::bind`]), on
+    /// server failure, or any error of the router setup (see
+    /// [`Self::into_router`]).
+    #[cfg(unix)]
+    pub async fn run_unix(
+        self,
+        path: PathBuf,
+        base_url: Option<String>,
+        auth_token: Option<String>,
+    ) -> anyhow::Result<()> {
+        tracing::info!(path = %path.display(), "Starting MCP server on a Unix socket");
+        let router = self.into_router(base_url, auth_token).await?;
+        let listener = unix::bind(&path)
+            .await
+            .map_err(|e| anyhow::anyhow!("cannot listen on {}: {e}", path.display()))?;
+
+        tracing::info!(path = %path.display(), "Unix socket server listening");
+
+        let served = axum::serve(listener, router).with_graceful_shutdown(shutdown_signal()).await;
+        if let Err(e) = std::fs::remove_file(&path) {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to remove socket");
+        }
+        served?;
+
+        tracing::info!("Unix socket server shut down");
+        Ok(())
+    }
+
+    /// Mount the HTTP transport at `path` (e.g. `/scholar`) inside `app`,
+    /// an application served at `origin` (e.g. `https://example.org`).
+    ///
+    /// Endpoints the server announces (SSE message URLs, OAuth metadata) are
+    /// under `{origin}{path}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of the router setup (see [`Self::into_router`]).
+    pub async fn mount<S>(
+        self,
+        app: axum::Router<S>,
+        path: &str,
+        origin: &str,
+        auth_token: Option<String>,
+    ) -> anyhow::Result<axum::Router<S>>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let path = path.trim_end_matches('/');
+        anyhow::ensure!(
+            path.len() > 1 && path.starts_with('/'),
+            "mount path must look like /scholar, got {path:?}"
+        );
+        let base_url = format!("{}{path}", origin.trim_end_matches('/'));
+        let router = self.into_router(Some(base_url), auth_token).await?;
+        Ok(app.nest_service(path, router))
+    }
+
+    /// Build the router shared by the HTTP and Unix socket transports, for
+    /// serving the MCP endpoints at `base_url`.
+    ///
+    /// Must be called inside a Tokio runtime: the router starts its session,
+    /// job and OAuth cleanup tasks.
+    ///
+    /// # Errors
+    ///
+    /// Returns error on an invalid tool policy file, an unusable session
+    /// directory, an unreadable OAuth store file, or an invalid accounts or
+    /// OIDC file (or one given without an auth token), an unreachable OIDC
+    /// provider, or an unwritable audit log.
+    pub async fn into_router(
+        self,
+        base_url: Option<String>,
+        auth_token: Option<String>,
+    ) -> anyhow::Result<axum::Router> {
+        tracing::info!("Registered {} tools", self.tools.len());
+        if auth_token.is_some() {
+            tracing::info!("Authentication enabled");
+        }
+
+        let tool_policy = ToolPolicyHandle::new(self.load_tool_policy()?);
+        let audit = self.open_audit_log()?;
+        if let Some(path) = self.tool_policy_file {
+            tool_policy.start_file_watch(path, POLICY_RELOAD_INTERVAL);
+        }
+
+        let session_store = match self.session_dir {
+            Some(dir) => {
+                tracing::info!(dir = %dir.display(), "Persisting sessions");
+                Some(Arc::new(FileSessionStore::open(dir)?) as Arc<dyn SessionStore>)
+            }
+            None => None,
+        };
+        let oauth_store = match self.oauth_store_file {
+            Some(path) => {
+                tracing::info!(path = %path.display(), "Persisting OAuth store");
+                Some(Arc::new(oauth::OAuthStore::open(path)?))
+            }
+            None => None,
+        };
+
+        let accounts = match self.accounts_file {
+            Some(path) => {
+                anyhow::ensure!(
+                    auth_token.is_some(),
+                    "--accounts requires an auth token (OAuth is only enabled with one)"
+                );
+                let accounts = accounts::Accounts::load(&path)?;
+                tracing::info!(users = accounts.users.len(), "User accounts enabled");
+                Some(Arc::new(accounts))
+            }
+            None => None,
+        };
+
+        let oidc = match self.oidc_file {
+            Some(path) => {
+                anyhow::ensure!(
+                    auth_token.is_some(),
+                    "--oidc requires an auth token (OAuth is only enabled with one)"
+                );
+                anyhow::ensure!(accounts.is_none(), "--oidc and --accounts cannot be combined");
+                let config = oauth::oidc::OidcConfig::load(&path)?;
+                let provider = oauth::oidc::OidcProvider::discover(config).await?;
+                tracing::info!(issuer = %provider.config().issuer, "OIDC login enabled");
+                Some(Arc::new(provider))
+            }
+            None => None,
+        };
+
+        let options = transport::HttpOptions {
+            base_url,
+            auth_token,
+            tool_filter: self.tool_filter,
+            tool_policy,
+            session_store,
+            oauth_store,
+            accounts,
+            oidc,
+            audit,
+            allowed_origins: self.allowed_origins,
+        };
+        Ok(transport::create_router_with_options(self.tools, self.ctx, options))
+    }
+
     /// Get tool by name.
     #[must_use]
     pub fn get_tool(&self, name: &str) -> Option<&dyn McpTool> {
@@ -6,20 +6,37 @@
 //! - RFC 7591: Dynamic Client Registration
 //! - RFC 7636: PKCE (S256)
 //! - RFC 6749: OAuth 2.0 Authorization Code Grant
+//! - RFC 7009: Token Revocation
+//! - RFC 7662: Token Introspection
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::{
-    Json,
-    extract::{Query, State},
+    Extension, Json,
+    extract::{ConnectInfo, Query, State},
     http::{HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
 };
 use serde::Deserialize;
 
+use super::login;
+use super::oidc;
 use super::pkce;
 use super::store::OAuthStore;
-use crate::server::transport::HttpState;
+use crate::server::accounts::{Scope, Scopes};
+use crate::server::policy::ClientIdentity;
+use crate::server::transport::{HttpState, StaticToken};
+
+/// Scopes advertised in metadata: the generic `mcp` plus account scopes
+/// when user accounts are configured.
+fn scopes_supported(state: &HttpState) -> Vec<&'static str> {
+    let mut scopes = vec!["mcp"];
+    if state.accounts.is_some() || state.oidc.is_some() {
+        scopes.extend(Scope::ALL.map(Scope::as_str));
+    }
+    scopes
+}
 
 // ─── RFC 9728: Protected Resource Metadata ───────────────────────────────────
 
@@ -31,7 +48,7 @@ pub async fn handle_protected_resource(State(state): State<Arc<HttpState>>) -> i
         "resource": state.base_url,
         "authorization_servers": [state.base_url],
         "bearer_methods_supported": ["header"],
-        "scopes_supported": ["mcp"]
+        "scopes_supported": scopes_supported(&state)
     }))
 }
 
@@ -46,11 +63,15 @@ pub async fn handle_auth_server_metadata(State(state): State<Arc<HttpState>>) ->
         "authorization_endpoint": format!("{}/authorize", state.base_url),
         "token_endpoint": format!("{}/token", state.base_url),
         "registration_endpoint": format!("{}/register", state.base_url),
-        "scopes_supported": ["mcp"],
+        "revocation_endpoint": format!("{}/revoke", state.base_url),
+        "introspection_endpoint": format!("{}/introspect", state.base_url),
+        "scopes_supported": scopes_supported(&state),
         "response_types_supported": ["code"],
         "grant_types_supported": ["authorization_code", "refresh_token"],
         "token_endpoint_auth_methods_supported": ["none"],
-        "code_challenge_methods_supported": ["S256"]
+        "code_challenge_methods_supported": ["S256"],
+        "revocation_endpoint_auth_methods_supported": ["none"],
+        "introspection_endpoint_auth_methods_supported": ["bearer"]
     }))
 }
 
@@ -123,10 +144,14 @@ pub struct AuthorizeQuery {
 
 /// `GET /authorize`
 ///
-/// Auto-approve the authorization request. This is a single-user server where
-/// the auth token is already configured server-side, so there's no need for an
-/// interactive login page. Any client that successfully completed dynamic
-/// registration and provides valid PKCE parameters is auto-approved.
+/// Without user accounts, auto-approve the authorization request. This is a
+/// single-user server where the auth token is already configured
+/// server-side, so there's no need for an interactive login page. Any client
+/// that successfully completed dynamic registration and provides valid PKCE
+/// parameters is auto-approved.
+///
+/// With user accounts, render the login form instead; it posts back to
+/// [`handle_authorize_post`].
 pub async fn handle_authorize_get(
     State(state): State<Arc<HttpState>>,
     Query(query): Query<AuthorizeQuery>,
@@ -166,6 +191,31 @@ pub async fn handle_authorize_get(
 
     let scope = query.scope.as_deref().unwrap_or("mcp");
 
+    if let Some(ref oidc) = state.oidc {
+        let location = oidc.start_login(
+            client_id.to_owned(),
+            redirect_uri.to_owned(),
+            query.state.clone(),
+            code_challenge.to_owned(),
+            scope.to_owned(),
+            &oidc_callback_url(&state),
+        );
+        return (StatusCode::FOUND, [("Location", location)]).into_response();
+    }
+
+    if state.accounts.is_some() {
+        let page = login::render_login_page(
+            client.client_name.as_deref().unwrap_or(client_id),
+            client_id,
+            redirect_uri,
+            query.state.as_deref().unwrap_or_default(),
+            code_challenge,
+            scope,
+            None,
+        );
+        return axum::response::Html(page).into_response();
+    }
+
     // Auto-approve: issue auth code immediately without interactive login
     let code = oauth_store
         .create_auth_code(
@@ -178,15 +228,204 @@ pub async fn handle_authorize_get(
 
     tracing::info!(client_id = %client_id, "Auto-approved authorization");
 
-    // Build redirect URL with code and state
-    let mut redirect_url = redirect_uri.to_owned();
-    redirect_url.push_str(if redirect_url.contains('?') { "&" } else { "?" });
-    redirect_url.push_str(&format!("code={code}"));
-    if let Some(ref oauth_state) = query.state {
-        redirect_url.push_str(&format!("&state={}", url_encode(oauth_state)));
+    redirect_with_code(redirect_uri, &code, query.state.as_deref())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginForm {
+    pub client_id: String,
+    pub redirect_uri: String,
+    #[serde(default)]
+    pub state: String,
+    pub code_challenge: String,
+    #[serde(default)]
+    pub scope: String,
+    pub username: String,
+    pub password: String,
+}
+
+/// `POST /authorize`
+///
+/// Check the login form against the accounts file and issue a code bound to
+/// the user. The granted scopes are those requested that the user holds; a
+/// request for just `mcp` gets the user's tool scopes but never `admin`.
+/// Repeated wrong passwords for a user name from one address are answered
+/// with 429 for a growing delay.
+pub async fn handle_authorize_post(
+    State(state): State<Arc<HttpState>>,
+    connection: Option<Extension<ConnectInfo<SocketAddr>>>,
+    axum::Form(form): axum::Form<LoginForm>,
+) -> Response {
+    let (Some(oauth_store), Some(accounts)) = (&state.oauth_store, &state.accounts) else {
+        return (StatusCode::NOT_FOUND, "Login not configured").into_response();
+    };
+
+    let Some(client) = oauth_store.get_client(&form.client_id).await else {
+        return (StatusCode::BAD_REQUEST, "Unknown client_id").into_response();
+    };
+    if !client.redirect_uris.contains(&form.redirect_uri) {
+        return (StatusCode::BAD_REQUEST, "redirect_uri not registered for this client")
+            .into_response();
+    }
+
+    let login_error = |status: StatusCode, message: &str| {
+        let page = login::render_login_page(
+            client.client_name.as_deref().unwrap_or(&form.client_id),
+            &form.client_id,
+            &form.redirect_uri,
+            &form.state,
+            &form.code_challenge,
+            &form.scope,
+            Some(message),
+        );
+        (status, axum::response::Html(page)).into_response()
+    };
+
+    let address = connection.map(|Extension(ConnectInfo(addr))| addr.ip());
+    if let Some(wait) = state.login_throttle.retry_after(address, &form.username) {
+        tracing::warn!(user = %form.username, ?address, "Login throttled after repeated failures");
+        let mut response =
+            login_error(StatusCode::TOO_MANY_REQUESTS, "Too many failed attempts; try again later");
+        response.headers_mut().insert(header::RETRY_AFTER, wait.as_secs().max(1).into());
+        return response;
+    }
+    // Argon2 is deliberately slow; keep it off the async workers.
+    let verified = {
+        let accounts = Arc::clone(accounts);
+        let (username, password) = (form.username.clone(), form.password.clone());
+        tokio::task::spawn_blocking(move || accounts.verify(&username, &password)).await
+    };
+    let Some(held) = verified.ok().flatten() else {
+        tracing::warn!(user = %form.username, ?address, "Failed login");
+        state.login_throttle.record_failure(address, &form.username);
+        return login_error(StatusCode::UNAUTHORIZED, "Invalid username or password");
+    };
+    state.login_throttle.record_success(address, &form.username);
+    let requested = Scopes::parse(&form.scope);
+    let granted = held.grant(&requested);
+    if granted.is_empty() {
+        return login_error(
+            StatusCode::FORBIDDEN,
+            "Your account does not have the requested access",
+        );
+    }
+
+    let code = oauth_store
+        .create_user_auth_code(
+            form.username.clone(),
+            form.client_id.clone(),
+            form.redirect_uri.clone(),
+            form.code_challenge.clone(),
+            granted.to_string(),
+            None,
+        )
+        .await;
+
+    tracing::info!(client_id = %form.client_id, user = %form.username, scope = %granted, "Approved authorization");
+
+    let oauth_state = Some(form.state.as_str()).filter(|s| !s.is_empty());
+    redirect_with_code(&form.redirect_uri, &code, oauth_state)
+}
+
+// ─── OIDC Callback ───────────────────────────────────────────────────────────
+
+fn oidc_callback_url(state: &HttpState) -> String {
+    format!("{}{}", state.base_url, oidc::CALLBACK_PATH)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+/// `GET /oauth/callback`
+///
+/// Return from the upstream OpenID Connect provider: map the user's claims
+/// to scopes and resume the MCP client's authorization request. Failures
+/// go back to the client as `access_denied`.
+pub async fn handle_oidc_callback(
+    State(state): State<Arc<HttpState>>,
+    Query(query): Query<OidcCallbackQuery>,
+) -> Response {
+    let (Some(oauth_store), Some(oidc)) = (&state.oauth_store, &state.oidc) else {
+        return (StatusCode::NOT_FOUND, "OIDC login not configured").into_response();
+    };
+    let Some(pending) = query.state.as_deref().and_then(|s| oidc.take_pending(s)) else {
+        return (StatusCode::BAD_REQUEST, "Unknown or expired login").into_response();
+    };
+    let deny = |description: &str| {
+        redirect_with_error(&pending.redirect_uri, description, pending.state.as_deref())
+    };
+
+    if let Some(error) = query.error {
+        tracing::warn!(client_id = %pending.client_id, error, "Upstream login failed");
+        return deny("Sign-in was not completed");
+    }
+    let Some(code) = query.code else {
+        return deny("Sign-in was not completed");
+    };
+
+    let claims = match oidc.finish_login(&code, &pending, &oidc_callback_url(&state)).await {
+        Ok(claims) => claims,
+        Err(e) => {
+            tracing::warn!(client_id = %pending.client_id, error = %e, "Upstream login rejected");
+            return deny("Sign-in could not be verified");
+        }
+    };
+
+    let held = oidc.config().scopes_for(&claims);
+    let requested = Scopes::parse(&pending.scope);
+    let granted = held.grant(&requested);
+    if granted.is_empty() {
+        tracing::warn!(user = %claims.username(), "Upstream user matches no scope rule");
+        return deny("Your account does not have the requested access");
+    }
+
+    let code = oauth_store
+        .create_user_auth_code(
+            claims.username().to_owned(),
+            pending.client_id.clone(),
+            pending.redirect_uri.clone(),
+            pending.code_challenge.clone(),
+            granted.to_string(),
+            Some(oidc.config().grant_lifetime_secs),
+        )
+        .await;
+
+    tracing::info!(client_id = %pending.client_id, user = %claims.username(), scope = %granted, "Approved authorization");
+
+    redirect_with_code(&pending.redirect_uri, &code, pending.state.as_deref())
+}
+
+/// Redirect back to the client with an `access_denied` error.
+fn redirect_with_error(
+    redirect_uri: &str,
+    description: &str,
+    oauth_state: Option<&str>,
+) -> Response {
+    let mut location = redirect_uri.to_owned();
+    location.push_str(if location.contains('?') { "&" } else { "?" });
+    location
+        .push_str(&format!("error=access_denied&error_description={}", url_encode(description)));
+    if let Some(oauth_state) = oauth_state {
+        location.push_str(&format!("&state={}", url_encode(oauth_state)));
+    }
+
+    (StatusCode::FOUND, [("Location", location)]).into_response()
+}
+
+/// Redirect back to the client with the authorization code and its `state`.
+fn redirect_with_code(redirect_uri: &str, code: &str, oauth_state: Option<&str>) -> Response {
+    let mut location = redirect_uri.to_owned();
+    location.push_str(if location.contains('?') { "&" } else { "?" });
+    location.push_str(&format!("code={code}"));
+    if let Some(oauth_state) = oauth_state {
+        location.push_str(&format!("&state={}", url_encode(oauth_state)));
     }
 
-    (StatusCode::FOUND, [("Location", redirect_url)]).into_response()
+    (StatusCode::FOUND, [("Location", location)]).into_response()
 }
 
 // ─── Token Endpoint ──────────────────────────────────────────────────────────
@@ -212,17 +451,23 @@ pub async fn handle_token(
         return (StatusCode::NOT_FOUND, "OAuth not configured").into_response();
     };
 
-    match form.grant_type.as_str() {
+    let response = match form.grant_type.as_str() {
         "authorization_code" => handle_authorization_code_grant(oauth_store, &form).await,
         "refresh_token" => handle_refresh_token_grant(oauth_store, &form).await,
-        _ => (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "error": "unsupported_grant_type"
-            })),
-        )
-            .into_response(),
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": "unsupported_grant_type"
+                })),
+            )
+                .into_response();
+        }
+    };
+    if response.status().is_success() {
+        state.metrics.oauth_tokens_issued.with_label_values(&[form.grant_type.as_str()]).inc();
     }
+    response
 }
 
 async fn handle_authorization_code_grant(store: &OAuthStore, form: &TokenRequest) -> Response {
@@ -251,7 +496,14 @@ async fn handle_authorization_code_grant(store: &OAuthStore, form: &TokenRequest
     }
 
     // Issue tokens
-    let pair = store.create_token_pair(&auth_code.client_id, &auth_code.scope).await;
+    let pair = store
+        .create_token_pair_for(
+            &auth_code.client_id,
+            auth_code.subject.as_deref(),
+            &auth_code.scope,
+            auth_code.grant_lifetime,
+        )
+        .await;
 
     tracing::info!(client_id = %auth_code.client_id, "Issued token pair");
 
@@ -263,7 +515,11 @@ async fn handle_refresh_token_grant(store: &OAuthStore, form: &TokenRequest) ->
         return token_error("invalid_request", "Missing refresh_token");
     };
 
-    let Some(pair) = store.refresh_token_pair(refresh_token).await else {
+    let Some(ref client_id) = form.client_id else {
+        return token_error("invalid_grant", "Missing client_id");
+    };
+
+    let Some(pair) = store.refresh_token_pair(refresh_token, client_id).await else {
         return token_error("invalid_grant", "Invalid or expired refresh token");
     };
 
@@ -272,6 +528,91 @@ async fn handle_refresh_token_grant(store: &OAuthStore, form: &TokenRequest) ->
     token_success(&pair)
 }
 
+// ─── RFC 7009: Token Revocation ──────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct RevokeRequest {
+    pub token: Option<String>,
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+}
+
+/// `POST /revoke`
+///
+/// Revoke an access or refresh token. Revoking a refresh token also revokes
+/// every token issued under the same grant. Unknown tokens still get 200, as
+/// RFC 7009 requires.
+pub async fn handle_revoke(
+    State(state): State<Arc<HttpState>>,
+    axum::Form(form): axum::Form<RevokeRequest>,
+) -> Response {
+    let Some(ref oauth_store) = state.oauth_store else {
+        return (StatusCode::NOT_FOUND, "OAuth not configured").into_response();
+    };
+    let Some(ref token) = form.token else {
+        return token_error("invalid_request", "Missing token");
+    };
+
+    if oauth_store.revoke_token(token, form.client_id.as_deref()).await {
+        tracing::info!(hint = ?form.token_type_hint, "Revoked token");
+    }
+
+    StatusCode::OK.into_response()
+}
+
+// ─── RFC 7662: Token Introspection ───────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct IntrospectRequest {
+    pub token: Option<String>,
+    pub token_type_hint: Option<String>,
+}
+
+/// `POST /introspect`
+///
+/// Report whether a token is active. Callers must present the static token
+/// or be a user holding `admin`.
+pub async fn handle_introspect(
+    State(state): State<Arc<HttpState>>,
+    token: Option<Extension<StaticToken>>,
+    client: Option<Extension<ClientIdentity>>,
+    axum::Form(form): axum::Form<IntrospectRequest>,
+) -> Response {
+    let Some(ref oauth_store) = state.oauth_store else {
+        return (StatusCode::NOT_FOUND, "OAuth not configured").into_response();
+    };
+    if !state.is_admin(token.map(|Extension(t)| t), client.as_ref().map(|Extension(c)| c)) {
+        return (StatusCode::FORBIDDEN, "Introspection requires the admin scope").into_response();
+    }
+    let Some(ref token) = form.token else {
+        return token_error("invalid_request", "Missing token");
+    };
+
+    let body = match oauth_store.introspect(token).await {
+        Some(info) => {
+            let mut body = serde_json::json!({
+                "active": true,
+                "client_id": info.client_id,
+                "scope": info.scope,
+                "token_type": info.token_type,
+                "iat": info.issued_at,
+                "exp": info.expires_at,
+                "iss": state.base_url
+            });
+            if let Some(subject) = info.subject {
+                body["sub"] = serde_json::json!(subject);
+                body["username"] = serde_json::json!(subject);
+            }
+            body
+        }
+        None => serde_json::json!({ "active": false }),
+    };
+
+    let mut response = Json(body).into_response();
+    response.headers_mut().insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    response
+}
+
 /// Build a token response with required OAuth 2.0 cache headers (RFC 6749 §5.1).
 fn token_success(pair: &super::store::TokenPair) -> Response {
     let mut response = Json(serde_json::json!({
@@ -2,7 +2,8 @@
 
 /// Render the authorization login page.
 ///
-/// All parameters are HTML-escaped to prevent XSS.
+/// All parameters are HTML-escaped to prevent XSS. The form posts to the
+/// relative `authorize`, so it works wherever the router is mounted.
 pub fn render_login_page(
     client_name: &str,
     client_id: &str,
@@ -34,8 +35,8 @@ body {{ font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans
 h1 {{ font-size: 20px; margin: 0 0 8px; color: #333; }}
 .subtitle {{ color: #666; font-size: 14px; margin: 0 0 24px; }}
 label {{ display: block; font-size: 14px; font-weight: 500; margin-bottom: 6px; color: #333; }}
-input[type="password"] {{ width: 100%; padding: 10px; border: 1px solid #ddd; border-radius: 4px; font-size: 14px; box-sizing: border-box; }}
-input[type="password"]:focus {{ outline: none; border-color: #4a90d9; box-shadow: 0 0 0 2px rgba(74,144,217,0.2); }}
+input[type="text"], input[type="password"] {{ width: 100%; padding: 10px; border: 1px solid #ddd; border-radius: 4px; font-size: 14px; box-sizing: border-box; margin-bottom: 12px; }}
+input[type="text"]:focus, input[type="password"]:focus {{ outline: none; border-color: #4a90d9; box-shadow: 0 0 0 2px rgba(74,144,217,0.2); }}
 button {{ width: 100%; padding: 10px; background: #4a90d9; color: #fff; border: none; border-radius: 4px; font-size: 14px; font-weight: 500; cursor: pointer; margin-top: 16px; }}
 button:hover {{ background: #357abd; }}
 </style>
@@ -45,14 +46,16 @@ button:hover {{ background: #357abd; }}
 <h1>Semantic Scholar MCP</h1>
 <p class="subtitle"><strong>{client_name}</strong> is requesting access</p>
 {error_html}
-<form method="POST" action="/authorize">
+<form method="POST" action="authorize">
 <input type="hidden" name="client_id" value="{client_id_escaped}">
 <input type="hidden" name="redirect_uri" value="{redirect_uri_escaped}">
 <input type="hidden" name="state" value="{state_escaped}">
 <input type="hidden" name="code_challenge" value="{code_challenge_escaped}">
 <input type="hidden" name="scope" value="{scope_escaped}">
-<label for="password">Server Password</label>
-<input type="password" id="password" name="password" placeholder="Enter MCP server password" required autofocus>
+<label for="username">Username</label>
+<input type="text" id="username" name="username" autocomplete="username" required autofocus>
+<label for="password">Password</label>
+<input type="password" id="password" name="password" autocomplete="current-password" required>
 <button type="submit">Approve</button>
 </form>
 </div>
@@ -9,8 +9,17 @@
 //! - RFC 7591: Dynamic Client Registration
 //! - RFC 7636: PKCE (S256)
 //! - RFC 6749: Authorization Code Grant
+//! - RFC 7009: Token Revocation
+//! - RFC 7662: Token Introspection
+//! - OpenID Connect: optional login delegation to an upstream provider
+//!   (see [`oidc`])
+//!
+//! Registrations and grants can be persisted to a file (see
+//! [`OAuthStore::open`]); codes and tokens are stored hashed.
 
 pub mod handlers;
+mod login;
+pub mod oidc;
 pub mod pkce;
 pub mod store;
 mod types;
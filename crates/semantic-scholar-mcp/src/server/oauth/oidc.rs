@@ -0,0 +1,500 @@
+//! Login delegation to an upstream OpenID Connect provider.
+//!
+//! With an OIDC config, `/authorize` sends the user to the provider (for
+//! example a university SSO) instead of showing the password form. The
+//! provider redirects back to `/oauth/callback`, where the code is exchanged,
+//! the ID token checked, and the user's claims mapped to scopes. From there
+//! the flow continues as usual: the MCP client gets its own authorization
+//! code and PKCE, registration and the token endpoint are unchanged.
+//!
+//! ```json
+//! {
+//!   "issuer": "https://sso.example.edu",
+//!   "client_id": "scholar-mcp",
+//!   "client_secret": "...",
+//!   "rules": [
+//!     { "email_domain": "example.edu", "scopes": ["read"] },
+//!     { "group": "library-staff", "scopes": ["read", "export"] },
+//!     { "group": "it-admins", "scopes": ["admin"] }
+//!   ]
+//! }
+//! ```
+//!
+//! A rule applies when all of its conditions match (a rule without
+//! conditions matches every user); the user gets the union of the scopes of
+//! every matching rule. Users matching no rule are turned away. Only an
+//! email the provider marks `email_verified: true` counts.
+//!
+//! Scopes are mapped once, at sign-in, so a grant from an upstream login
+//! lasts `grant_lifetime_secs` (a day by default) however often its tokens
+//! are refreshed; after that the user signs in upstream again and changed
+//! groups or rules take effect.
+//!
+//! The ID token comes straight from the provider's token endpoint over TLS,
+//! so its signature is not checked (OpenID Connect Core §3.1.3.7); issuer,
+//! audience, expiry and nonce are.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, bail, ensure};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use super::types::now;
+use crate::server::accounts::{Scope, Scopes};
+
+/// How long the user has to complete the upstream login.
+const PENDING_LOGIN_TTL_SECS: u64 = 600;
+
+/// Upstream logins in flight at once; the oldest make room first.
+const MAX_PENDING_LOGINS: usize = 1000;
+
+/// Path of the redirect URI registered with the provider.
+pub const CALLBACK_PATH: &str = "/oauth/callback";
+
+/// Upstream provider settings and claim-to-scope rules.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OidcConfig {
+    /// Issuer URL; discovery is fetched from
+    /// `{issuer}/.well-known/openid-configuration`.
+    pub issuer: String,
+    pub client_id: String,
+    /// Omit for a public client (PKCE only).
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// Scopes requested from the provider.
+    #[serde(default = "default_upstream_scopes")]
+    pub scopes: String,
+    /// Claim holding the user's groups.
+    #[serde(default = "default_groups_claim")]
+    pub groups_claim: String,
+    /// Seconds a sign-in lasts before refreshing stops and the user must
+    /// sign in upstream again.
+    #[serde(default = "default_grant_lifetime")]
+    pub grant_lifetime_secs: u64,
+    pub rules: Vec<ClaimRule>,
+}
+
+fn default_upstream_scopes() -> String {
+    "openid email profile".to_string()
+}
+
+fn default_groups_claim() -> String {
+    "groups".to_string()
+}
+
+const fn default_grant_lifetime() -> u64 {
+    24 * 3600
+}
+
+/// Grants `scopes` to users matching every condition given.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClaimRule {
+    /// Domain of a verified `email` claim, e.g. `example.edu`.
+    #[serde(default)]
+    pub email_domain: Option<String>,
+    /// Entry of the groups claim.
+    #[serde(default)]
+    pub group: Option<String>,
+    pub scopes: Vec<Scope>,
+}
+
+impl ClaimRule {
+    fn matches(&self, claims: &UserClaims) -> bool {
+        let domain_ok = self.email_domain.as_deref().is_none_or(|domain| {
+            claims
+                .email
+                .as_deref()
+                .and_then(|e| e.rsplit_once('@'))
+                .is_some_and(|(_, d)| d.eq_ignore_ascii_case(domain))
+        });
+        let group_ok = self.group.as_ref().is_none_or(|g| claims.groups.contains(g));
+        domain_ok && group_ok
+    }
+}
+
+impl OidcConfig {
+    /// Read the config from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is not valid JSON, or
+    /// has no rules.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let config: Self = serde_json::from_str(&text)
+            .with_context(|| format!("invalid OIDC config {}", path.display()))?;
+        ensure!(!config.rules.is_empty(), "OIDC config {} has no rules", path.display());
+        Ok(config)
+    }
+
+    /// Scopes granted to a user by the rules.
+    #[must_use]
+    pub fn scopes_for(&self, claims: &UserClaims) -> Scopes {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(claims))
+            .flat_map(|rule| rule.scopes.iter().copied())
+            .collect()
+    }
+}
+
+/// The claims that rules look at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserClaims {
+    /// Provider's stable user id.
+    pub subject: String,
+    /// Email address, only when the provider marks it verified.
+    pub email: Option<String>,
+    pub groups: Vec<String>,
+}
+
+impl UserClaims {
+    fn from_claims(claims: &Map<String, Value>, groups_claim: &str) -> anyhow::Result<Self> {
+        let subject = claims.get("sub").and_then(Value::as_str).context("missing sub claim")?;
+        let verified = claims.get("email_verified").and_then(Value::as_bool) == Some(true);
+        let email = claims.get("email").and_then(Value::as_str).filter(|_| verified);
+        let groups = match claims.get(groups_claim) {
+            Some(Value::Array(items)) => {
+                items.iter().filter_map(Value::as_str).map(str::to_owned).collect()
+            }
+            Some(Value::String(group)) => vec![group.clone()],
+            _ => Vec::new(),
+        };
+        Ok(Self { subject: subject.to_owned(), email: email.map(str::to_owned), groups })
+    }
+
+    /// Name shown in logs and introspection: the email when known.
+    #[must_use]
+    pub fn username(&self) -> &str {
+        self.email.as_deref().unwrap_or(&self.subject)
+    }
+}
+
+/// The MCP client's authorization request, parked while the user signs in
+/// upstream.
+#[derive(Debug, Clone)]
+pub struct PendingLogin {
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub state: Option<String>,
+    pub code_challenge: String,
+    pub scope: String,
+    nonce: String,
+    verifier: String,
+    created_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    #[serde(default)]
+    userinfo_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+    #[serde(default)]
+    access_token: Option<String>,
+}
+
+/// A discovered upstream provider and the logins in flight with it.
+#[derive(Debug)]
+pub struct OidcProvider {
+    config: OidcConfig,
+    metadata: ProviderMetadata,
+    authorization_url: url::Url,
+    http: reqwest::Client,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+impl OidcProvider {
+    /// Fetch the provider's discovery document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if discovery fails or names a different issuer.
+    pub async fn discover(config: OidcConfig) -> anyhow::Result<Self> {
+        let http = reqwest::Client::new();
+        let url =
+            format!("{}/.well-known/openid-configuration", config.issuer.trim_end_matches('/'));
+        let metadata: ProviderMetadata = http
+            .get(&url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("OIDC discovery failed: {url}"))?
+            .json()
+            .await
+            .with_context(|| format!("invalid OIDC discovery document: {url}"))?;
+        ensure!(
+            metadata.issuer == config.issuer,
+            "OIDC discovery issuer {} does not match configured issuer {}",
+            metadata.issuer,
+            config.issuer
+        );
+        let authorization_url = url::Url::parse(&metadata.authorization_endpoint)
+            .context("invalid OIDC authorization_endpoint")?;
+        Ok(Self { config, metadata, authorization_url, http, pending: Mutex::new(HashMap::new()) })
+    }
+
+    #[must_use]
+    pub const fn config(&self) -> &OidcConfig {
+        &self.config
+    }
+
+    /// Park an authorization request and return the provider URL to send
+    /// the user to.
+    pub fn start_login(
+        &self,
+        client_id: String,
+        redirect_uri: String,
+        state: Option<String>,
+        code_challenge: String,
+        scope: String,
+        callback_url: &str,
+    ) -> String {
+        let upstream_state = uuid::Uuid::new_v4().simple().to_string();
+        let nonce = uuid::Uuid::new_v4().simple().to_string();
+        let verifier =
+            format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+
+        let mut url = self.authorization_url.clone();
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", callback_url)
+            .append_pair("scope", &self.config.scopes)
+            .append_pair("state", &upstream_state)
+            .append_pair("nonce", &nonce)
+            .append_pair("code_challenge", &challenge)
+            .append_pair("code_challenge_method", "S256");
+
+        let login = PendingLogin {
+            client_id,
+            redirect_uri,
+            state,
+            code_challenge,
+            scope,
+            nonce,
+            verifier,
+            created_at: now(),
+        };
+        let mut pending = self.pending.lock().unwrap();
+        let cutoff = now().saturating_sub(PENDING_LOGIN_TTL_SECS);
+        pending.retain(|_, p| p.created_at >= cutoff);
+        while pending.len() >= MAX_PENDING_LOGINS {
+            let Some(oldest) =
+                pending.iter().min_by_key(|(_, p)| p.created_at).map(|(k, _)| k.clone())
+            else {
+                break;
+            };
+            pending.remove(&oldest);
+        }
+        pending.insert(upstream_state, login);
+        url.into()
+    }
+
+    /// Claim the parked request for an upstream `state`; each works once.
+    pub fn take_pending(&self, upstream_state: &str) -> Option<PendingLogin> {
+        let login = self.pending.lock().unwrap().remove(upstream_state)?;
+        (now().saturating_sub(login.created_at) <= PENDING_LOGIN_TTL_SECS).then_some(login)
+    }
+
+    /// Exchange the provider's code and return the user's verified claims.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exchange fails or the ID token is invalid.
+    pub async fn finish_login(
+        &self,
+        code: &str,
+        login: &PendingLogin,
+        callback_url: &str,
+    ) -> anyhow::Result<UserClaims> {
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", callback_url),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", login.verifier.as_str()),
+        ];
+        if let Some(ref secret) = self.config.client_secret {
+            form.push(("client_secret", secret));
+        }
+        let body = url::form_urlencoded::Serializer::new(String::new()).extend_pairs(form).finish();
+        let tokens: TokenResponse = self
+            .http
+            .post(&self.metadata.token_endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .context("OIDC token exchange failed")?
+            .json()
+            .await
+            .context("invalid OIDC token response")?;
+
+        let mut claims = self.validate_id_token(&tokens.id_token, &login.nonce)?;
+        if let (Some(endpoint), Some(access_token)) =
+            (&self.metadata.userinfo_endpoint, &tokens.access_token)
+        {
+            let userinfo: Map<String, Value> = self
+                .http
+                .get(endpoint)
+                .bearer_auth(access_token)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .context("OIDC userinfo request failed")?
+                .json()
+                .await
+                .context("invalid OIDC userinfo response")?;
+            ensure!(
+                userinfo.get("sub") == claims.get("sub"),
+                "userinfo sub does not match ID token"
+            );
+            claims.extend(userinfo);
+        }
+        UserClaims::from_claims(&claims, &self.config.groups_claim)
+    }
+
+    fn validate_id_token(&self, id_token: &str, nonce: &str) -> anyhow::Result<Map<String, Value>> {
+        let payload = id_token.split('.').nth(1).context("malformed ID token")?;
+        let bytes = URL_SAFE_NO_PAD
+            .decode(payload.trim_end_matches('='))
+            .context("malformed ID token payload")?;
+        let claims: Map<String, Value> =
+            serde_json::from_slice(&bytes).context("malformed ID token claims")?;
+
+        if claims.get("iss").and_then(Value::as_str) != Some(self.config.issuer.as_str()) {
+            bail!("ID token issuer mismatch");
+        }
+        let audience_ok = match claims.get("aud") {
+            Some(Value::String(aud)) => *aud == self.config.client_id,
+            Some(Value::Array(auds)) => {
+                auds.iter().any(|a| a.as_str() == Some(self.config.client_id.as_str()))
+            }
+            _ => false,
+        };
+        ensure!(audience_ok, "ID token audience mismatch");
+        let exp = claims.get("exp").and_then(Value::as_u64).context("ID token has no exp")?;
+        ensure!(exp > now(), "ID token expired");
+        ensure!(
+            claims.get("nonce").and_then(Value::as_str) == Some(nonce),
+            "ID token nonce mismatch"
+        );
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(rules: Vec<ClaimRule>) -> OidcConfig {
+        OidcConfig {
+            issuer: "https://sso.example.edu".into(),
+            client_id: "mcp".into(),
+            client_secret: None,
+            scopes: default_upstream_scopes(),
+            groups_claim: default_groups_claim(),
+            grant_lifetime_secs: default_grant_lifetime(),
+            rules,
+        }
+    }
+
+    fn claims(email: Option<&str>, groups: &[&str]) -> UserClaims {
+        UserClaims {
+            subject: "u1".into(),
+            email: email.map(str::to_owned),
+            groups: groups.iter().map(|g| (*g).to_owned()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_rules_union_matching_scopes() {
+        let config = config(vec![
+            ClaimRule {
+                email_domain: Some("example.edu".into()),
+                group: None,
+                scopes: vec![Scope::Read],
+            },
+            ClaimRule {
+                email_domain: None,
+                group: Some("staff".into()),
+                scopes: vec![Scope::Export],
+            },
+            ClaimRule {
+                email_domain: Some("other.org".into()),
+                group: Some("staff".into()),
+                scopes: vec![Scope::Admin],
+            },
+        ]);
+
+        let staff = claims(Some("a@Example.EDU"), &["staff"]);
+        assert_eq!(config.scopes_for(&staff).to_string(), "read export");
+        assert_eq!(config.scopes_for(&claims(Some("a@example.edu"), &[])).to_string(), "read");
+        assert!(config.scopes_for(&claims(Some("a@elsewhere.com"), &[])).is_empty());
+        assert!(config.scopes_for(&claims(None, &[])).is_empty());
+    }
+
+    #[test]
+    fn test_unverified_email_is_ignored() {
+        let raw = serde_json::json!({
+            "sub": "u1", "email": "a@example.edu", "email_verified": false, "roles": "staff"
+        });
+        let parsed = UserClaims::from_claims(raw.as_object().unwrap(), "roles").unwrap();
+        assert_eq!(parsed.email, None);
+        assert_eq!(parsed.groups, vec!["staff".to_string()]);
+        assert_eq!(parsed.username(), "u1");
+
+        let unmarked = serde_json::json!({"sub": "u1", "email": "a@example.edu"});
+        let parsed = UserClaims::from_claims(unmarked.as_object().unwrap(), "groups").unwrap();
+        assert_eq!(parsed.email, None);
+
+        let verified =
+            serde_json::json!({"sub": "u1", "email": "a@example.edu", "email_verified": true});
+        let parsed = UserClaims::from_claims(verified.as_object().unwrap(), "groups").unwrap();
+        assert_eq!(parsed.email.as_deref(), Some("a@example.edu"));
+    }
+
+    #[test]
+    fn test_pending_logins_are_capped() {
+        let provider = OidcProvider {
+            config: config(Vec::new()),
+            metadata: ProviderMetadata {
+                issuer: "https://sso.example.edu".into(),
+                authorization_endpoint: "https://sso.example.edu/authorize".into(),
+                token_endpoint: "https://sso.example.edu/token".into(),
+                userinfo_endpoint: None,
+            },
+            authorization_url: url::Url::parse("https://sso.example.edu/authorize").unwrap(),
+            http: reqwest::Client::new(),
+            pending: Mutex::new(HashMap::new()),
+        };
+        for _ in 0..=MAX_PENDING_LOGINS {
+            provider.start_login(
+                "client".into(),
+                "https://cb".into(),
+                None,
+                "challenge".into(),
+                "mcp".into(),
+                "https://mcp.example.com/oauth/callback",
+            );
+        }
+        assert_eq!(provider.pending.lock().unwrap().len(), MAX_PENDING_LOGINS);
+    }
+}
@@ -9,6 +9,7 @@ use sha2::{Digest, Sha256};
 /// Verify a PKCE S256 code challenge.
 ///
 /// Computes `BASE64URL(SHA256(code_verifier))` and compares to the stored challenge.
+#[must_use]
 pub fn verify_s256(code_verifier: &str, code_challenge: &str) -> bool {
     let hash = Sha256::digest(code_verifier.as_bytes());
     let computed = URL_SAFE_NO_PAD.encode(hash);
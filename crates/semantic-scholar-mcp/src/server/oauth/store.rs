@@ -1,12 +1,23 @@
-//! In-memory OAuth store following the `SessionManager` pattern.
+//! OAuth store following the `SessionManager` pattern.
+//!
+//! State lives in memory and, when opened with [`OAuthStore::open`], is
+//! written through to a JSON snapshot file so registrations and grants
+//! survive redeploys. Codes and tokens are only ever stored as SHA-256
+//! hashes; the plaintext is returned to the client once and never kept.
 
 use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use tokio::sync::RwLock;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::{Mutex, RwLock};
 
-use super::types::{AccessToken, AuthCode, OAuthClient, RefreshToken};
+use super::types::{AccessToken, AuthCode, OAuthClient, RefreshToken, now};
 
 /// Auth code lifetime: 10 minutes.
 const AUTH_CODE_LIFETIME: u64 = 600;
@@ -15,26 +26,179 @@ const ACCESS_TOKEN_LIFETIME: u64 = 3600;
 /// Refresh token lifetime: 30 days.
 const REFRESH_TOKEN_LIFETIME: u64 = 30 * 24 * 3600;
 /// Cleanup interval: 5 minutes.
-const CLEANUP_INTERVAL: Duration = Duration::from_secs(300);
+const CLEANUP_INTERVAL: Duration = Duration::from_mins(5);
+
+/// Everything the store knows, keyed by client ID or token hash.
+#[derive(Default, Serialize, Deserialize)]
+struct OAuthState {
+    #[serde(default)]
+    clients: HashMap<String, OAuthClient>,
+    #[serde(default)]
+    auth_codes: HashMap<String, AuthCode>,
+    #[serde(default)]
+    access_tokens: HashMap<String, AccessToken>,
+    #[serde(default)]
+    refresh_tokens: HashMap<String, RefreshToken>,
+}
+
+impl OAuthState {
+    /// Issue a token pair belonging to `family`, which ends at
+    /// `grant_expires_at` if given.
+    fn issue_pair(
+        &mut self,
+        client_id: &str,
+        subject: Option<&str>,
+        scope: &str,
+        family: &str,
+        grant_expires_at: Option<u64>,
+    ) -> TokenPair {
+        let access = OAuthStore::generate_token();
+        let refresh = OAuthStore::generate_token();
+        let access_hash = hash_token(&access);
+        let created_at = now();
+        let remaining = grant_expires_at.map_or(u64::MAX, |end| end.saturating_sub(created_at));
+        let access_lifetime = ACCESS_TOKEN_LIFETIME.min(remaining);
+
+        self.access_tokens.insert(
+            access_hash.clone(),
+            AccessToken {
+                client_id: client_id.to_owned(),
+                scope: scope.to_owned(),
+                subject: subject.map(str::to_owned),
+                family: family.to_owned(),
+                created_at,
+                expires_in: access_lifetime,
+            },
+        );
 
-/// In-memory OAuth state store.
+        self.refresh_tokens.insert(
+            hash_token(&refresh),
+            RefreshToken {
+                client_id: client_id.to_owned(),
+                access_token: access_hash,
+                scope: scope.to_owned(),
+                subject: subject.map(str::to_owned),
+                family: family.to_owned(),
+                grant_expires_at,
+                created_at,
+                expires_in: REFRESH_TOKEN_LIFETIME.min(remaining),
+                rotated: false,
+            },
+        );
+
+        TokenPair {
+            access_token: access,
+            refresh_token: refresh,
+            expires_in: access_lifetime,
+            scope: scope.to_owned(),
+        }
+    }
+
+    /// Drop every access and refresh token issued under `family`.
+    fn revoke_family(&mut self, family: &str) {
+        self.access_tokens.retain(|_, t| t.family != family);
+        self.refresh_tokens.retain(|_, t| t.family != family);
+    }
+}
+
+/// Hash a code or token for storage.
+fn hash_token(token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}
+
+/// OAuth state store.
 #[derive(Clone)]
 pub struct OAuthStore {
-    clients: Arc<RwLock<HashMap<String, OAuthClient>>>,
-    auth_codes: Arc<RwLock<HashMap<String, AuthCode>>>,
-    access_tokens: Arc<RwLock<HashMap<String, AccessToken>>>,
-    refresh_tokens: Arc<RwLock<HashMap<String, RefreshToken>>>,
+    state: Arc<RwLock<OAuthState>>,
+    /// Snapshot file, if persistent.
+    path: Option<PathBuf>,
+    /// Serializes snapshot writes so the newest state always lands last.
+    persist_lock: Arc<Mutex<()>>,
+    /// Why the last snapshot write failed, until one succeeds.
+    persist_error: Arc<std::sync::Mutex<Option<String>>>,
 }
 
 impl OAuthStore {
+    /// Create an in-memory store.
     #[must_use]
     pub fn new() -> Self {
         Self {
-            clients: Arc::new(RwLock::new(HashMap::new())),
-            auth_codes: Arc::new(RwLock::new(HashMap::new())),
-            access_tokens: Arc::new(RwLock::new(HashMap::new())),
-            refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
+            state: Arc::new(RwLock::new(OAuthState::default())),
+            path: None,
+            persist_lock: Arc::new(Mutex::new(())),
+            persist_error: Arc::default(),
+        }
+    }
+
+    /// Open a store persisted to `path`, loading it if the file exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let state = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => OAuthState::default(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { state: Arc::new(RwLock::new(state)), path: Some(path), ..Self::new() })
+    }
+
+    /// Write the current state to the snapshot file (if any).
+    async fn persist(&self) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        let _guard = self.persist_lock.lock().await;
+        let serialized = serde_json::to_vec(&*self.state.read().await);
+        let snapshot = match serialized {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to serialize OAuth store");
+                return;
+            }
+        };
+        let result = write_atomic(path, &snapshot).await;
+        if let Err(ref e) = result {
+            tracing::error!(path = %path.display(), error = %e, "Failed to persist OAuth store");
+        }
+        *self.persist_error.lock().unwrap_or_else(std::sync::PoisonError::into_inner) =
+            result.err().map(|e| e.to_string());
+    }
+
+    /// Whether the store can persist: the last snapshot write succeeded and
+    /// the snapshot's directory is still there. Always `Ok` in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last write error, or why the directory is unusable.
+    pub async fn check(&self) -> Result<(), String> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let persist_error =
+            self.persist_error.lock().unwrap_or_else(std::sync::PoisonError::into_inner).clone();
+        if let Some(error) = persist_error {
+            return Err(error);
         }
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        match tokio::fs::metadata(dir).await {
+            Ok(meta) if meta.is_dir() => Ok(()),
+            Ok(_) => Err(format!("{} is not a directory", dir.display())),
+            Err(e) => Err(format!("{}: {e}", dir.display())),
+        }
+    }
+
+    /// Whether the store is backed by a file.
+    #[must_use]
+    pub const fn is_persistent(&self) -> bool {
+        self.path.is_some()
     }
 
     /// Generate a random token using two UUIDs (256 bits).
@@ -54,26 +218,19 @@ impl OAuthStore {
             client_id: client_id.clone(),
             client_name,
             redirect_uris,
-            created_at: Instant::now(),
+            created_at: now(),
         };
 
-        self.clients.write().await.insert(
-            client_id,
-            OAuthClient {
-                client_id: client.client_id.clone(),
-                client_name: client.client_name.clone(),
-                redirect_uris: client.redirect_uris.clone(),
-                created_at: client.created_at,
-            },
-        );
+        self.state.write().await.clients.insert(client_id, client.clone());
+        self.persist().await;
 
         client
     }
 
     /// Look up a client by ID.
     pub async fn get_client(&self, client_id: &str) -> Option<ClientInfo> {
-        let clients = self.clients.read().await;
-        clients.get(client_id).map(|c| ClientInfo {
+        let state = self.state.read().await;
+        state.clients.get(client_id).map(|c| ClientInfo {
             client_id: c.client_id.clone(),
             client_name: c.client_name.clone(),
             redirect_uris: c.redirect_uris.clone(),
@@ -87,20 +244,59 @@ impl OAuthStore {
         redirect_uri: String,
         code_challenge: String,
         scope: String,
+    ) -> String {
+        self.insert_auth_code(None, client_id, redirect_uri, code_challenge, scope, None).await
+    }
+
+    /// Create an authorization code on behalf of a signed-in user.
+    ///
+    /// With `grant_lifetime`, the tokens it is exchanged for stop refreshing
+    /// that many seconds after the exchange.
+    pub async fn create_user_auth_code(
+        &self,
+        subject: String,
+        client_id: String,
+        redirect_uri: String,
+        code_challenge: String,
+        scope: String,
+        grant_lifetime: Option<u64>,
+    ) -> String {
+        self.insert_auth_code(
+            Some(subject),
+            client_id,
+            redirect_uri,
+            code_challenge,
+            scope,
+            grant_lifetime,
+        )
+        .await
+    }
+
+    async fn insert_auth_code(
+        &self,
+        subject: Option<String>,
+        client_id: String,
+        redirect_uri: String,
+        code_challenge: String,
+        scope: String,
+        grant_lifetime: Option<u64>,
     ) -> String {
         let code = Self::generate_token();
 
-        self.auth_codes.write().await.insert(
-            code.clone(),
+        self.state.write().await.auth_codes.insert(
+            hash_token(&code),
             AuthCode {
                 client_id,
                 redirect_uri,
                 code_challenge,
                 scope,
-                created_at: Instant::now(),
+                subject,
+                grant_lifetime,
+                created_at: now(),
                 used: false,
             },
         );
+        self.persist().await;
 
         code
     }
@@ -109,83 +305,173 @@ impl OAuthStore {
     ///
     /// Returns the code details if valid, unused, and not expired.
     pub async fn consume_auth_code(&self, code: &str) -> Option<AuthCodeInfo> {
-        let mut codes = self.auth_codes.write().await;
-        let auth_code = codes.get_mut(code)?;
+        let info = {
+            let mut state = self.state.write().await;
+            let auth_code = state.auth_codes.get_mut(&hash_token(code))?;
 
-        if auth_code.used || auth_code.is_expired() {
-            return None;
-        }
+            if auth_code.used || auth_code.is_expired() {
+                return None;
+            }
 
-        auth_code.used = true;
+            auth_code.used = true;
 
-        Some(AuthCodeInfo {
-            client_id: auth_code.client_id.clone(),
-            redirect_uri: auth_code.redirect_uri.clone(),
-            code_challenge: auth_code.code_challenge.clone(),
-            scope: auth_code.scope.clone(),
-        })
+            AuthCodeInfo {
+                client_id: auth_code.client_id.clone(),
+                redirect_uri: auth_code.redirect_uri.clone(),
+                code_challenge: auth_code.code_challenge.clone(),
+                scope: auth_code.scope.clone(),
+                subject: auth_code.subject.clone(),
+                grant_lifetime: auth_code.grant_lifetime,
+            }
+        };
+        self.persist().await;
+        Some(info)
     }
 
-    /// Create an access + refresh token pair.
+    /// Create an access + refresh token pair for a new grant.
     pub async fn create_token_pair(&self, client_id: &str, scope: &str) -> TokenPair {
-        let access = Self::generate_token();
-        let refresh = Self::generate_token();
-
-        self.access_tokens.write().await.insert(
-            access.clone(),
-            AccessToken {
-                client_id: client_id.to_owned(),
-                created_at: Instant::now(),
-                expires_in: ACCESS_TOKEN_LIFETIME,
-            },
-        );
+        self.create_token_pair_for(client_id, None, scope, None).await
+    }
 
-        self.refresh_tokens.write().await.insert(
-            refresh.clone(),
-            RefreshToken {
-                client_id: client_id.to_owned(),
-                access_token: access.clone(),
-                scope: scope.to_owned(),
-                created_at: Instant::now(),
-                expires_in: REFRESH_TOKEN_LIFETIME,
-            },
+    /// Create a token pair for a new grant, bound to `subject` if given. A
+    /// `grant_lifetime` in seconds caps how long the grant can be refreshed.
+    pub async fn create_token_pair_for(
+        &self,
+        client_id: &str,
+        subject: Option<&str>,
+        scope: &str,
+        grant_lifetime: Option<u64>,
+    ) -> TokenPair {
+        let family = uuid::Uuid::new_v4().simple().to_string();
+        let grant_expires_at = grant_lifetime.map(|secs| now().saturating_add(secs));
+        let pair = self.state.write().await.issue_pair(
+            client_id,
+            subject,
+            scope,
+            &family,
+            grant_expires_at,
         );
-
-        TokenPair {
-            access_token: access,
-            refresh_token: refresh,
-            expires_in: ACCESS_TOKEN_LIFETIME,
-            scope: scope.to_owned(),
-        }
+        self.persist().await;
+        pair
     }
 
     /// Validate an access token. Returns the client_id if valid.
     pub async fn validate_access_token(&self, token: &str) -> Option<String> {
-        let tokens = self.access_tokens.read().await;
-        let access = tokens.get(token)?;
+        self.access_grant(token).await.map(|grant| grant.client_id)
+    }
+
+    /// Validate an access token, returning what it grants.
+    pub async fn access_grant(&self, token: &str) -> Option<AccessGrant> {
+        let state = self.state.read().await;
+        let access = state.access_tokens.get(&hash_token(token))?;
         if access.is_expired() {
             return None;
         }
-        Some(access.client_id.clone())
+        Some(AccessGrant {
+            client_id: access.client_id.clone(),
+            subject: access.subject.clone(),
+            scope: access.scope.clone(),
+        })
     }
 
-    /// Refresh a token pair: invalidate old tokens and issue new ones.
-    pub async fn refresh_token_pair(&self, refresh_token: &str) -> Option<TokenPair> {
-        // Validate and remove the old refresh token
-        let old = {
-            let mut tokens = self.refresh_tokens.write().await;
-            tokens.remove(refresh_token)?
+    /// Rotate a refresh token: invalidate the old pair and issue a new one.
+    ///
+    /// Refresh tokens are single-use. Presenting one that was already
+    /// rotated means it leaked, so the whole grant is revoked. A token only
+    /// refreshes for the client it was issued to (RFC 6749 §6).
+    pub async fn refresh_token_pair(
+        &self,
+        refresh_token: &str,
+        client_id: &str,
+    ) -> Option<TokenPair> {
+        let pair = {
+            let mut state = self.state.write().await;
+            let hash = hash_token(refresh_token);
+            let old = state.refresh_tokens.get_mut(&hash)?;
+
+            if old.client_id != client_id || old.is_expired() {
+                return None;
+            }
+            if old.rotated {
+                let (client_id, family) = (old.client_id.clone(), old.family.clone());
+                state.revoke_family(&family);
+                drop(state);
+                tracing::warn!(client_id = %client_id, "Refresh token reused; revoked grant");
+                self.persist().await;
+                return None;
+            }
+
+            old.rotated = true;
+            let (client_id, subject, scope, family, access, grant_expires_at) = (
+                old.client_id.clone(),
+                old.subject.clone(),
+                old.scope.clone(),
+                old.family.clone(),
+                old.access_token.clone(),
+                old.grant_expires_at,
+            );
+            state.access_tokens.remove(&access);
+            state.issue_pair(&client_id, subject.as_deref(), &scope, &family, grant_expires_at)
         };
+        self.persist().await;
+        Some(pair)
+    }
 
-        if old.is_expired() {
-            return None;
+    /// Revoke a token (RFC 7009).
+    ///
+    /// Revoking a refresh token revokes its whole grant. Tokens issued to a
+    /// different client than `client_id` (when given) are left alone.
+    /// Returns whether anything was revoked.
+    pub async fn revoke_token(&self, token: &str, client_id: Option<&str>) -> bool {
+        let revoked = {
+            let mut state = self.state.write().await;
+            let hash = hash_token(token);
+            let owned_by = |owner: &str| client_id.is_none_or(|id| id == owner);
+
+            if let Some(refresh) =
+                state.refresh_tokens.get(&hash).filter(|t| owned_by(&t.client_id))
+            {
+                let family = refresh.family.clone();
+                state.revoke_family(&family);
+                true
+            } else if state.access_tokens.get(&hash).is_some_and(|t| owned_by(&t.client_id)) {
+                state.access_tokens.remove(&hash);
+                true
+            } else {
+                false
+            }
+        };
+        if revoked {
+            self.persist().await;
         }
+        revoked
+    }
 
-        // Remove old access token
-        self.access_tokens.write().await.remove(&old.access_token);
-
-        // Issue new pair
-        Some(self.create_token_pair(&old.client_id, &old.scope).await)
+    /// Describe an active token (RFC 7662). Returns `None` for unknown,
+    /// expired, revoked or already-rotated tokens.
+    pub async fn introspect(&self, token: &str) -> Option<TokenIntrospection> {
+        let state = self.state.read().await;
+        let hash = hash_token(token);
+
+        if let Some(access) = state.access_tokens.get(&hash).filter(|t| !t.is_expired()) {
+            return Some(TokenIntrospection {
+                client_id: access.client_id.clone(),
+                scope: access.scope.clone(),
+                subject: access.subject.clone(),
+                token_type: "Bearer",
+                issued_at: access.created_at,
+                expires_at: access.created_at + access.expires_in,
+            });
+        }
+        let refresh = state.refresh_tokens.get(&hash).filter(|t| !t.is_expired() && !t.rotated)?;
+        Some(TokenIntrospection {
+            client_id: refresh.client_id.clone(),
+            scope: refresh.scope.clone(),
+            subject: refresh.subject.clone(),
+            token_type: "refresh_token",
+            issued_at: refresh.created_at,
+            expires_at: refresh.created_at + refresh.expires_in,
+        })
     }
 
     /// Start background cleanup task for expired tokens and codes.
@@ -200,47 +486,53 @@ impl OAuthStore {
     }
 
     async fn cleanup_expired(&self) {
-        let now = Instant::now();
+        let now = now();
+        let mut removed_any = false;
 
-        // Cleanup auth codes (expired or used and older than 1 minute)
         {
-            let mut codes = self.auth_codes.write().await;
-            codes.retain(|_, code| {
-                if code.is_expired() {
-                    return false;
-                }
-                // Keep used codes for a short while (for error messages), then remove
-                if code.used && now.duration_since(code.created_at).as_secs() > AUTH_CODE_LIFETIME {
-                    return false;
-                }
-                true
+            let mut state = self.state.write().await;
+
+            // Cleanup auth codes (expired or used and older than the code lifetime)
+            let before = state.auth_codes.len();
+            state.auth_codes.retain(|_, code| {
+                !(code.is_expired()
+                    || code.used && now.saturating_sub(code.created_at) > AUTH_CODE_LIFETIME)
             });
-        }
+            removed_any |= state.auth_codes.len() != before;
 
-        // Cleanup access tokens
-        {
-            let mut tokens = self.access_tokens.write().await;
-            let before = tokens.len();
-            tokens.retain(|_, token| !token.is_expired());
-            let removed = before - tokens.len();
+            // Cleanup access tokens
+            let before = state.access_tokens.len();
+            state.access_tokens.retain(|_, token| !token.is_expired());
+            let removed = before - state.access_tokens.len();
             if removed > 0 {
                 tracing::debug!(count = removed, "Cleaned up expired access tokens");
+                removed_any = true;
             }
-        }
 
-        // Cleanup refresh tokens
-        {
-            let mut tokens = self.refresh_tokens.write().await;
-            let before = tokens.len();
-            tokens.retain(|_, token| !token.is_expired());
-            let removed = before - tokens.len();
+            // Cleanup refresh tokens
+            let before = state.refresh_tokens.len();
+            state.refresh_tokens.retain(|_, token| !token.is_expired());
+            let removed = before - state.refresh_tokens.len();
             if removed > 0 {
                 tracing::debug!(count = removed, "Cleaned up expired refresh tokens");
+                removed_any = true;
             }
         }
+
+        if removed_any {
+            self.persist().await;
+        }
     }
 }
 
+/// Replace `path` with `contents` without leaving a torn file behind.
+async fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    tokio::fs::write(&tmp, contents).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
 impl Default for OAuthStore {
     fn default() -> Self {
         Self::new()
@@ -249,7 +541,7 @@ impl Default for OAuthStore {
 
 impl std::fmt::Debug for OAuthStore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("OAuthStore").finish()
+        f.debug_struct("OAuthStore").field("path", &self.path).finish_non_exhaustive()
     }
 }
 
@@ -266,6 +558,17 @@ pub struct AuthCodeInfo {
     pub redirect_uri: String,
     pub code_challenge: String,
     pub scope: String,
+    pub subject: Option<String>,
+    /// Seconds the resulting grant may be refreshed for, if limited.
+    pub grant_lifetime: Option<u64>,
+}
+
+/// What a valid access token grants.
+pub struct AccessGrant {
+    pub client_id: String,
+    /// Signed-in user, when accounts are configured.
+    pub subject: Option<String>,
+    pub scope: String,
 }
 
 /// A token pair returned from token creation/refresh.
@@ -276,6 +579,19 @@ pub struct TokenPair {
     pub scope: String,
 }
 
+/// What introspection reveals about an active token.
+pub struct TokenIntrospection {
+    pub client_id: String,
+    pub scope: String,
+    pub subject: Option<String>,
+    /// `Bearer` for access tokens, `refresh_token` for refresh tokens.
+    pub token_type: &'static str,
+    /// Unix seconds.
+    pub issued_at: u64,
+    /// Unix seconds.
+    pub expires_at: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -335,7 +651,7 @@ mod tests {
         let pair = store.create_token_pair("client1", "mcp").await;
 
         // Refresh
-        let new_pair = store.refresh_token_pair(&pair.refresh_token).await;
+        let new_pair = store.refresh_token_pair(&pair.refresh_token, "client1").await;
         assert!(new_pair.is_some());
         let new_pair = new_pair.unwrap();
 
@@ -345,7 +661,49 @@ mod tests {
         // New access token is valid
         assert!(store.validate_access_token(&new_pair.access_token).await.is_some());
 
+        // Other clients can't redeem it
+        let other = store.create_token_pair("client2", "mcp").await;
+        assert!(store.refresh_token_pair(&other.refresh_token, "client1").await.is_none());
+        assert!(store.refresh_token_pair(&other.refresh_token, "client2").await.is_some());
+
         // Old refresh token can't be reused
-        assert!(store.refresh_token_pair(&pair.refresh_token).await.is_none());
+        assert!(store.refresh_token_pair(&pair.refresh_token, "client1").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse_revokes_grant() {
+        let store = OAuthStore::new();
+        let first = store.create_token_pair("client1", "mcp").await;
+        let second = store.refresh_token_pair(&first.refresh_token, "client1").await.unwrap();
+
+        // Replaying the rotated token kills the descendants too.
+        assert!(store.refresh_token_pair(&first.refresh_token, "client1").await.is_none());
+        assert!(store.validate_access_token(&second.access_token).await.is_none());
+        assert!(store.refresh_token_pair(&second.refresh_token, "client1").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tokens_stored_hashed() {
+        let store = OAuthStore::new();
+        let pair = store.create_token_pair("client1", "mcp").await;
+        let state = store.state.read().await;
+        assert!(!state.access_tokens.contains_key(&pair.access_token));
+        assert!(state.access_tokens.contains_key(&hash_token(&pair.access_token)));
+        assert!(!state.refresh_tokens.contains_key(&pair.refresh_token));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_and_introspect() {
+        let store = OAuthStore::new();
+        let pair = store.create_token_pair("client1", "mcp").await;
+
+        let info = store.introspect(&pair.access_token).await.unwrap();
+        assert_eq!((info.client_id.as_str(), info.token_type), ("client1", "Bearer"));
+
+        // Another client can't revoke it.
+        assert!(!store.revoke_token(&pair.refresh_token, Some("client2")).await);
+        assert!(store.revoke_token(&pair.refresh_token, Some("client1")).await);
+        assert!(store.introspect(&pair.access_token).await.is_none());
+        assert!(store.introspect(&pair.refresh_token).await.is_none());
     }
 }
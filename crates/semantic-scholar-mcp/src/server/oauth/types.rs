@@ -1,58 +1,95 @@
 //! OAuth 2.0 types for MCP authentication.
+//!
+//! Timestamps are Unix seconds so records stay meaningful across restarts.
 
-use std::time::Instant;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Current time in Unix seconds.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
 
 /// A dynamically registered OAuth client.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OAuthClient {
     pub client_id: String,
     pub client_name: Option<String>,
     pub redirect_uris: Vec<String>,
-    pub created_at: Instant,
+    pub created_at: u64,
 }
 
 /// An authorization code issued after user approval.
+#[derive(Serialize, Deserialize)]
 pub struct AuthCode {
     pub client_id: String,
     pub redirect_uri: String,
     pub code_challenge: String,
     pub scope: String,
-    pub created_at: Instant,
+    /// Signed-in user, when accounts are configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Seconds the resulting grant may be refreshed for, if less than usual.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grant_lifetime: Option<u64>,
+    pub created_at: u64,
     pub used: bool,
 }
 
 /// An access token for API authentication.
+#[derive(Serialize, Deserialize)]
 pub struct AccessToken {
     pub client_id: String,
-    pub created_at: Instant,
+    pub scope: String,
+    /// Signed-in user, when accounts are configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Grant this token descends from (shared by every rotation).
+    pub family: String,
+    pub created_at: u64,
     pub expires_in: u64,
 }
 
 /// A refresh token for obtaining new access tokens.
+#[derive(Serialize, Deserialize)]
 pub struct RefreshToken {
     pub client_id: String,
+    /// Hash of the access token issued alongside.
     pub access_token: String,
     pub scope: String,
-    pub created_at: Instant,
+    /// Signed-in user, when accounts are configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Grant this token descends from (shared by every rotation).
+    pub family: String,
+    /// When the grant ends however often it is refreshed, if it does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grant_expires_at: Option<u64>,
+    pub created_at: u64,
     pub expires_in: u64,
+    /// Already exchanged; presenting it again means it leaked.
+    #[serde(default)]
+    pub rotated: bool,
 }
 
 impl AccessToken {
     /// Check if the token has expired.
     pub fn is_expired(&self) -> bool {
-        self.created_at.elapsed().as_secs() > self.expires_in
+        now().saturating_sub(self.created_at) > self.expires_in
     }
 }
 
 impl RefreshToken {
     /// Check if the token has expired.
     pub fn is_expired(&self) -> bool {
-        self.created_at.elapsed().as_secs() > self.expires_in
+        now().saturating_sub(self.created_at) > self.expires_in
     }
 }
 
 impl AuthCode {
     /// Check if the code has expired (10 minute lifetime).
     pub fn is_expired(&self) -> bool {
-        self.created_at.elapsed().as_secs() > 600
+        now().saturating_sub(self.created_at) > 600
     }
 }
@@ -0,0 +1,339 @@
+//! Per-deployment and per-OAuth-client tool policy.
+//!
+//! A policy file narrows which tools are exposed, both for the whole
+//! deployment and for individual OAuth clients:
+//!
+//! ```json
+//! {
+//!   "enable": [],
+//!   "disable": ["advanced"],
+//!   "clients": {
+//!     "Claude": { "enable": ["discovery", "enrichment", "export"] }
+//!   },
+//!   "quotas": {
+//!     "default": { "per_minute": 60, "per_day": 2000 },
+//!     "clients": { "Claude": { "per_minute": 120 } },
+//!     "users": { "alice": { "per_day": 5000 } }
+//!   }
+//! }
+//! ```
+//!
+//! Quotas count upstream Semantic Scholar requests per signed-in user, or per
+//! OAuth client for tokens without a user; a user or client with its own
+//! entry uses it instead of the default. Client keys match either the OAuth
+//! `client_id` or the `client_name` given at registration. The file is
+//! polled for changes; when the policy changes, connected HTTP sessions
+//! receive `notifications/tools/list_changed`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use tokio::sync::watch;
+
+use super::accounts::Principal;
+use crate::client::quota::QuotaLimits;
+use crate::tools::{ToolFilter, ToolGroup};
+
+/// Prefix of quota tenant keys that name a user rather than an OAuth client.
+pub const USER_TENANT_PREFIX: &str = "user:";
+
+/// How often the policy file is checked for modifications.
+pub const POLICY_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The OAuth client (and signed-in user, if any) behind a request,
+/// attached by the auth middleware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIdentity {
+    pub client_id: String,
+    pub client_name: Option<String>,
+    /// The user the token was issued to, when accounts are configured.
+    pub principal: Option<Principal>,
+}
+
+impl ClientIdentity {
+    /// Key upstream requests are counted under: the signed-in user, so that
+    /// one account cannot multiply its budget by registering more clients,
+    /// else the OAuth client.
+    #[must_use]
+    pub fn tenant(&self) -> String {
+        self.principal.as_ref().map_or_else(
+            || self.client_id.clone(),
+            |principal| format!("{USER_TENANT_PREFIX}{}", principal.user),
+        )
+    }
+}
+
+/// Deployment-wide filter plus per-client filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolPolicy {
+    /// Tools or groups to expose. Empty means all.
+    #[serde(default)]
+    pub enable: Vec<String>,
+    /// Tools or groups to hide from everyone.
+    #[serde(default)]
+    pub disable: Vec<String>,
+    /// Extra filters keyed by OAuth client ID or client name.
+    #[serde(default)]
+    pub clients: HashMap<String, ToolFilter>,
+    /// Upstream request quotas for OAuth clients.
+    #[serde(default)]
+    pub quotas: QuotaPolicy,
+}
+
+/// Default and per-client request quotas.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaPolicy {
+    /// Limits for clients without their own entry.
+    #[serde(default)]
+    pub default: QuotaLimits,
+    /// Limits keyed by OAuth client ID or client name.
+    #[serde(default)]
+    pub clients: HashMap<String, QuotaLimits>,
+    /// Limits keyed by account name, shared by all of a user's clients.
+    #[serde(default)]
+    pub users: HashMap<String, QuotaLimits>,
+}
+
+impl ToolPolicy {
+    /// Read a policy from a JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid policy.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("invalid tool policy {}: {e}", path.display()))
+    }
+
+    /// The deployment-wide part of the policy.
+    #[must_use]
+    pub fn deployment_filter(&self) -> ToolFilter {
+        ToolFilter::new(self.enable.iter().cloned(), self.disable.iter().cloned())
+    }
+
+    /// Whether `client` (if any) may see and call the tool.
+    #[must_use]
+    pub fn permits(&self, client: Option<&ClientIdentity>, name: &str, group: ToolGroup) -> bool {
+        if !self.deployment_filter().permits(name, group) {
+            return false;
+        }
+        client.and_then(|c| self.client_filter(c)).is_none_or(|f| f.permits(name, group))
+    }
+
+    fn client_filter(&self, client: &ClientIdentity) -> Option<&ToolFilter> {
+        client_entry(&self.clients, client)
+    }
+
+    /// Request quota for `client`: its user's, when signed in.
+    #[must_use]
+    pub fn quota_limits(&self, client: &ClientIdentity) -> QuotaLimits {
+        if let Some(principal) = &client.principal {
+            return self.user_quota_limits(&principal.user);
+        }
+        client_entry(&self.quotas.clients, client).copied().unwrap_or(self.quotas.default)
+    }
+
+    /// Request quota for the account `user`, whichever client it uses.
+    #[must_use]
+    pub fn user_quota_limits(&self, user: &str) -> QuotaLimits {
+        self.quotas.users.get(user).copied().unwrap_or(self.quotas.default)
+    }
+}
+
+/// Entry keyed by the client's ID, or failing that its name.
+fn client_entry<'a, T>(map: &'a HashMap<String, T>, client: &ClientIdentity) -> Option<&'a T> {
+    map.get(&client.client_id).or_else(|| client.client_name.as_ref().and_then(|n| map.get(n)))
+}
+
+/// Shared, replaceable [`ToolPolicy`].
+///
+/// Replacing the policy with a different one notifies subscribers; the HTTP
+/// transport turns that into `notifications/tools/list_changed`.
+#[derive(Debug, Clone)]
+pub struct ToolPolicyHandle {
+    tx: Arc<watch::Sender<ToolPolicy>>,
+}
+
+impl ToolPolicyHandle {
+    /// Create a handle holding `policy`.
+    #[must_use]
+    pub fn new(policy: ToolPolicy) -> Self {
+        Self { tx: Arc::new(watch::Sender::new(policy)) }
+    }
+
+    /// Evaluate `f` against the current policy.
+    pub fn with<R>(&self, f: impl FnOnce(&ToolPolicy) -> R) -> R {
+        f(&self.tx.borrow())
+    }
+
+    /// Replace the policy. Returns `true` (and notifies) if it changed.
+    #[allow(clippy::must_use_candidate)]
+    pub fn replace(&self, policy: ToolPolicy) -> bool {
+        self.tx.send_if_modified(|current| {
+            if *current == policy {
+                false
+            } else {
+                *current = policy;
+                true
+            }
+        })
+    }
+
+    /// Receive a notification whenever the policy changes.
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<ToolPolicy> {
+        self.tx.subscribe()
+    }
+
+    /// Reload the policy from `path` whenever its modification time changes.
+    ///
+    /// A file that fails to parse is logged and the previous policy is kept.
+    pub fn start_file_watch(&self, path: PathBuf, interval: Duration) {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            let mut last_modified = modified(&path);
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let current = modified(&path);
+                if current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+                match ToolPolicy::load(&path) {
+                    Ok(policy) => {
+                        if handle.replace(policy) {
+                            tracing::info!(path = %path.display(), "Tool policy reloaded");
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Keeping previous tool policy");
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl Default for ToolPolicyHandle {
+    fn default() -> Self {
+        Self::new(ToolPolicy::default())
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::accounts::Scopes;
+
+    fn client(id: &str, name: Option<&str>) -> ClientIdentity {
+        ClientIdentity {
+            client_id: id.to_string(),
+            client_name: name.map(str::to_string),
+            principal: None,
+        }
+    }
+
+    fn policy() -> ToolPolicy {
+        serde_json::from_value(serde_json::json!({
+            "disable": ["advanced"],
+            "clients": {
+                "Claude": {"enable": ["discovery"]},
+                "abc123": {"disable": ["export"]}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_deployment_disable_applies_to_everyone() {
+        let policy = policy();
+        assert!(!policy.permits(None, "pearl_growing", ToolGroup::Advanced));
+        assert!(!policy.permits(
+            Some(&client("abc123", None)),
+            "pearl_growing",
+            ToolGroup::Advanced
+        ));
+        assert!(policy.permits(None, "hot_papers", ToolGroup::Bibliometrics));
+    }
+
+    #[test]
+    fn test_client_matched_by_name_or_id() {
+        let policy = policy();
+        let named = client("zzz", Some("Claude"));
+        assert!(policy.permits(Some(&named), "exhaustive_search", ToolGroup::Discovery));
+        assert!(!policy.permits(Some(&named), "hot_papers", ToolGroup::Bibliometrics));
+
+        let by_id = client("abc123", Some("Other"));
+        assert!(!policy.permits(Some(&by_id), "reference_export", ToolGroup::Export));
+        assert!(policy.permits(Some(&by_id), "hot_papers", ToolGroup::Bibliometrics));
+    }
+
+    #[test]
+    fn test_quota_limits_fall_back_to_default() {
+        let policy: ToolPolicy = serde_json::from_value(serde_json::json!({
+            "quotas": {
+                "default": {"per_day": 100},
+                "clients": {"Claude": {"per_minute": 10}}
+            }
+        }))
+        .unwrap();
+        let claude = policy.quota_limits(&client("zzz", Some("Claude")));
+        assert_eq!(claude, QuotaLimits { per_minute: Some(10), per_day: None });
+        let other = policy.quota_limits(&client("abc", None));
+        assert_eq!(other, QuotaLimits { per_minute: None, per_day: Some(100) });
+    }
+
+    #[test]
+    fn test_signed_in_users_have_their_own_quota() {
+        let policy: ToolPolicy = serde_json::from_value(serde_json::json!({
+            "quotas": {
+                "default": {"per_day": 100},
+                "clients": {"Claude": {"per_minute": 10}},
+                "users": {"alice": {"per_day": 500}}
+            }
+        }))
+        .unwrap();
+        let principal =
+            |user: &str| Principal { user: user.to_string(), scopes: Scopes::default() };
+
+        let alice =
+            ClientIdentity { principal: Some(principal("alice")), ..client("zzz", Some("Claude")) };
+        assert_eq!(alice.tenant(), "user:alice");
+        assert_eq!(
+            policy.quota_limits(&alice),
+            QuotaLimits { per_minute: None, per_day: Some(500) }
+        );
+
+        let bob =
+            ClientIdentity { principal: Some(principal("bob")), ..client("zzz", Some("Claude")) };
+        assert_eq!(policy.quota_limits(&bob), QuotaLimits { per_minute: None, per_day: Some(100) });
+        assert_eq!(client("zzz", None).tenant(), "zzz");
+    }
+
+    #[test]
+    fn test_replace_notifies_only_on_change() {
+        let handle = ToolPolicyHandle::default();
+        let rx = handle.subscribe();
+        assert!(!handle.replace(ToolPolicy::default()));
+        assert!(!rx.has_changed().unwrap());
+        assert!(handle.replace(policy()));
+        assert!(rx.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_unknown_keys_rejected() {
+        let err = serde_json::from_str::<ToolPolicy>(r#"{"enabled": ["x"]}"#).unwrap_err();
+        assert!(err.to_string().contains("enabled"));
+    }
+}
@@ -1,20 +1,25 @@
 //! Session management for robust MCP connections.
 //!
 //! Implements the "Mailbox" pattern for never-failing connections:
-//! - In-memory session state with ring buffer for message replay
+//! - Session state and a ring buffer for message replay, kept in a
+//!   [`SessionStore`] (in memory, or on disk to survive restarts)
 //! - Last-Event-ID support for reconnection recovery
 //! - Broadcast channels for live event delivery
 //! - Background cleanup of stale sessions
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use std::fmt;
+use std::io;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use axum::http::HeaderValue;
 use axum::response::sse::Event;
-use tokio::sync::{RwLock, broadcast};
+use tokio::sync::{RwLock, broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+use super::logging::{LogLevelHandle, LogSink};
+use super::session_store::{MemorySessionStore, SessionStore};
 
 /// A type-safe session identifier.
 ///
@@ -66,13 +71,13 @@ impl AsRef<str> for SessionId {
 }
 
 /// Maximum number of events to keep in history per session.
-const HISTORY_SIZE: usize = 100;
+pub(crate) const HISTORY_SIZE: usize = 100;
 
 /// Session timeout after which sessions are cleaned up.
-const SESSION_TIMEOUT: Duration = Duration::from_secs(3600); // 1 hour
+const SESSION_TIMEOUT: Duration = Duration::from_hours(1);
 
 /// Cleanup interval for stale sessions.
-const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+const CLEANUP_INTERVAL: Duration = Duration::from_mins(1);
 
 /// A buffered SSE event with ID for replay support.
 #[derive(Clone, Debug)]
@@ -103,87 +108,136 @@ impl BufferedEvent {
     }
 }
 
-/// A single MCP session with message buffer and broadcast channel.
+/// A single MCP session: a broadcast channel for live events, backed by a
+/// [`SessionStore`] that keeps the replay buffer.
 pub struct Session {
     /// Unique session identifier.
     pub id: SessionId,
     /// Broadcast sender for live events.
     tx: broadcast::Sender<BufferedEvent>,
-    /// Ring buffer of recent events for replay.
-    history: RwLock<VecDeque<BufferedEvent>>,
-    /// Next event ID (monotonically increasing).
-    next_event_id: AtomicU64,
-    /// When the session was created.
+    /// Where buffered events are kept for replay.
+    store: Arc<dyn SessionStore>,
+    /// Tenant of the client that created the session (`None` without OAuth);
+    /// only that client may use it.
+    pub owner: Option<String>,
+    /// When this process first saw the session.
     pub created_at: Instant,
-    /// Last activity timestamp.
-    last_active: RwLock<Instant>,
+    /// Minimum level for `notifications/message` (set via `logging/setLevel`).
+    pub log_level: LogLevelHandle,
 }
 
 impl Session {
-    /// Create a new session with a random ID.
+    /// Create a new session with a random ID and its own in-memory buffer.
     #[must_use]
     pub fn new() -> Self {
+        let id = SessionId::new();
+        let store = MemorySessionStore::new();
+        store.insert(id.as_str());
+        Self::with_store(id, None, Arc::new(store))
+    }
+
+    /// Attach to a session whose buffer lives in `store`.
+    ///
+    /// The session must already exist in the store.
+    #[must_use]
+    pub fn with_store(id: SessionId, owner: Option<String>, store: Arc<dyn SessionStore>) -> Self {
         let (tx, _) = broadcast::channel(64);
         Self {
-            id: SessionId::new(),
+            id,
             tx,
-            history: RwLock::new(VecDeque::with_capacity(HISTORY_SIZE)),
-            next_event_id: AtomicU64::new(1),
+            store,
+            owner,
             created_at: Instant::now(),
-            last_active: RwLock::new(Instant::now()),
+            log_level: LogLevelHandle::default(),
         }
     }
 
+    /// Create a log sink whose notifications are pushed to this session.
+    ///
+    /// The returned task finishes once the sink (and every clone) is dropped;
+    /// await it to make sure log events precede whatever is pushed next.
+    #[must_use]
+    pub fn log_sink(self: &Arc<Self>) -> (LogSink, JoinHandle<()>) {
+        let (tx, forwarder) = self.notifier(None);
+        (LogSink::new(self.log_level.clone(), tx), forwarder)
+    }
+
+    /// Open a channel for notifications raised while handling one request.
+    ///
+    /// Each message sent is pushed to this session and, if `tap` is given,
+    /// also sent there (the request's own SSE response stream). The returned
+    /// task finishes once every sender clone is dropped.
+    #[must_use]
+    pub fn notifier(
+        self: &Arc<Self>,
+        tap: Option<mpsc::UnboundedSender<BufferedEvent>>,
+    ) -> (mpsc::UnboundedSender<serde_json::Value>, JoinHandle<()>) {
+        let (tx, mut rx) = mpsc::unbounded_channel::<serde_json::Value>();
+        let session = Arc::clone(self);
+        let forwarder = tokio::spawn(async move {
+            while let Some(notification) = rx.recv().await {
+                let event = session.push("message", notification.to_string()).await;
+                if let Some(tap) = &tap {
+                    // The client may have dropped the stream; the event stays replayable.
+                    let _ = tap.send(event);
+                }
+            }
+        });
+        (tx, forwarder)
+    }
+
     /// Push an event to the session (stores in history and broadcasts).
     pub async fn push_event(&self, event_type: impl Into<String>, data: impl Into<String>) -> u64 {
-        let id = self.next_event_id.fetch_add(1, Ordering::Relaxed);
-        let event = BufferedEvent::new(id, event_type, data);
+        self.push(event_type, data).await.id
+    }
 
-        // Store in history
-        {
-            let mut history = self.history.write().await;
-            if history.len() >= HISTORY_SIZE {
-                history.pop_front();
+    /// Like [`Session::push_event`], returning the buffered event.
+    ///
+    /// If the store cannot record the event it is still delivered live, with
+    /// ID 0, but cannot be replayed.
+    pub async fn push(
+        &self,
+        event_type: impl Into<String>,
+        data: impl Into<String>,
+    ) -> BufferedEvent {
+        let (event_type, data) = (event_type.into(), data.into());
+        let event = match self.store.append(self.id.as_str(), &event_type, &data).await {
+            Ok(event) => event,
+            Err(e) => {
+                if e.kind() == io::ErrorKind::NotFound {
+                    tracing::debug!(session_id = %self.id, "Event for a removed session");
+                } else {
+                    tracing::error!(session_id = %self.id, error = %e, "Failed to store session event");
+                }
+                BufferedEvent::new(0, event_type, data)
             }
-            history.push_back(event.clone());
-        }
+        };
 
         // Broadcast to active subscribers (ignore if no subscribers)
-        let _ = self.tx.send(event);
-
-        // Update activity timestamp
-        *self.last_active.write().await = Instant::now();
+        let _ = self.tx.send(event.clone());
 
-        id
+        event
     }
 
     /// Get events after a given ID (for replay on reconnection).
     pub async fn get_events_after(&self, last_event_id: u64) -> Vec<BufferedEvent> {
-        let history = self.history.read().await;
-        history.iter().filter(|e| e.id > last_event_id).cloned().collect()
+        self.store.events_after(self.id.as_str(), last_event_id).await.unwrap_or_else(|e| {
+            tracing::warn!(session_id = %self.id, error = %e, "Failed to read session events");
+            Vec::new()
+        })
     }
 
     /// Subscribe to live events.
     #[inline]
+    #[must_use]
     pub fn subscribe(&self) -> broadcast::Receiver<BufferedEvent> {
         self.tx.subscribe()
     }
 
-    /// Check if session is stale.
-    pub async fn is_stale(&self) -> bool {
-        let last_active = *self.last_active.read().await;
-        last_active.elapsed() > SESSION_TIMEOUT
-    }
-
-    /// Update last activity timestamp.
-    pub async fn touch(&self) {
-        *self.last_active.write().await = Instant::now();
-    }
-
-    /// Get current event ID (for debugging).
-    #[inline]
-    pub fn current_event_id(&self) -> u64 {
-        self.next_event_id.load(Ordering::Relaxed)
+    /// Number of live event streams.
+    #[must_use]
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
     }
 }
 
@@ -197,56 +251,117 @@ impl fmt::Debug for Session {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Session")
             .field("id", &self.id)
-            .field("current_event_id", &self.current_event_id())
             .field("created_at", &self.created_at)
-            .finish()
+            .finish_non_exhaustive()
     }
 }
 
 /// Global session manager.
+///
+/// Sessions live in a [`SessionStore`]; the manager caches the [`Session`]
+/// handles (broadcast channels, log levels) this process has handed out.
 #[derive(Clone)]
 pub struct SessionManager {
+    store: Arc<dyn SessionStore>,
     sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
 }
 
 impl SessionManager {
-    /// Create a new session manager.
+    /// Create a session manager backed by an in-memory store.
     #[must_use]
     pub fn new() -> Self {
-        Self { sessions: Arc::new(RwLock::new(HashMap::new())) }
+        Self::with_store(Arc::new(MemorySessionStore::new()))
     }
 
-    /// Create a new session and return it.
-    pub async fn create_session(&self) -> Arc<Session> {
-        let session = Arc::new(Session::new());
-        let id = session.id.as_str().to_owned();
+    /// Create a session manager backed by `store`.
+    #[must_use]
+    pub fn with_store(store: Arc<dyn SessionStore>) -> Self {
+        Self { store, sessions: Arc::new(RwLock::new(HashMap::new())) }
+    }
+
+    /// Create a new session owned by `owner` and return it.
+    pub async fn create_session(&self, owner: Option<String>) -> Arc<Session> {
+        let id = SessionId::new();
+        if let Err(e) = self.store.create(id.as_str(), owner.as_deref()).await {
+            tracing::error!(session_id = %id, error = %e, "Failed to store session");
+        }
+        let session = Arc::new(Session::with_store(id, owner, Arc::clone(&self.store)));
 
-        self.sessions.write().await.insert(id, session.clone());
+        self.sessions.write().await.insert(session.id.as_str().to_owned(), session.clone());
 
         tracing::info!(session_id = %session.id, "Created new session");
         session
     }
 
-    /// Get an existing session by ID.
+    /// Get an existing session by ID, marking it active.
+    ///
+    /// Sessions created by another process sharing the store (or before a
+    /// restart) are attached on first use.
     pub async fn get_session(&self, id: &str) -> Option<Arc<Session>> {
-        let sessions = self.sessions.read().await;
-        sessions.get(id).cloned()
-    }
+        match self.store.touch(id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                self.sessions.write().await.remove(id);
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!(session_id = %id, error = %e, "Session store unavailable");
+                return self.sessions.read().await.get(id).cloned();
+            }
+        }
 
-    /// Get or create a session.
-    pub async fn get_or_create_session(&self, id: Option<&str>) -> Arc<Session> {
-        if let Some(id) = id {
-            if let Some(session) = self.get_session(id).await {
-                session.touch().await;
-                return session;
+        if let Some(session) = self.sessions.read().await.get(id) {
+            return Some(session.clone());
+        }
+        let owner = match self.store.owner(id).await {
+            Ok(owner) => owner,
+            Err(e) => {
+                // Attaching it unowned would let any client use it.
+                tracing::warn!(session_id = %id, error = %e, "Session owner unavailable");
+                return None;
             }
+        };
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .entry(id.to_owned())
+            .or_insert_with(|| {
+                tracing::info!(session_id = %id, "Attached stored session");
+                Arc::new(Session::with_store(
+                    SessionId(id.to_owned()),
+                    owner,
+                    Arc::clone(&self.store),
+                ))
+            })
+            .clone();
+        Some(session)
+    }
+
+    /// Get a session `owner` may use, or create one for it.
+    pub async fn get_or_create_session(
+        &self,
+        id: Option<&str>,
+        owner: Option<String>,
+    ) -> Arc<Session> {
+        if let Some(session) = match id {
+            Some(id) => self.get_session(id).await,
+            None => None,
+        } && session.owner == owner
+        {
+            return session;
         }
-        self.create_session().await
+        self.create_session(owner).await
     }
 
     /// Remove a session.
     pub async fn remove_session(&self, id: &str) -> bool {
-        let removed = self.sessions.write().await.remove(id).is_some();
+        let cached = self.sessions.write().await.remove(id).is_some();
+        let removed = match self.store.remove(id).await {
+            Ok(removed) => removed,
+            Err(e) => {
+                tracing::error!(session_id = %id, error = %e, "Failed to remove stored session");
+                cached
+            }
+        };
         if removed {
             tracing::info!(session_id = %id, "Removed session");
         }
@@ -255,32 +370,56 @@ impl SessionManager {
 
     /// Clean up stale sessions.
     pub async fn cleanup_stale_sessions(&self) -> usize {
-        let mut to_remove = Vec::new();
-
-        {
-            let sessions = self.sessions.read().await;
-            for (id, session) in sessions.iter() {
-                if session.is_stale().await {
-                    to_remove.push(id.clone());
-                }
+        let removed = match self.store.remove_stale(SESSION_TIMEOUT).await {
+            Ok(removed) => removed,
+            Err(e) => {
+                tracing::warn!(error = %e, "Session cleanup failed");
+                return 0;
             }
-        }
+        };
 
-        let count = to_remove.len();
-        if count > 0 {
+        if !removed.is_empty() {
             let mut sessions = self.sessions.write().await;
-            for id in to_remove {
-                sessions.remove(&id);
+            for id in &removed {
+                sessions.remove(id);
                 tracing::info!(session_id = %id, "Cleaned up stale session");
             }
         }
 
-        count
+        removed.len()
+    }
+
+    /// Push an event to every session attached to this process. Returns the
+    /// number of sessions.
+    pub async fn broadcast_event(&self, event_type: &str, data: &str) -> usize {
+        let sessions: Vec<Arc<Session>> = self.sessions.read().await.values().cloned().collect();
+        for session in &sessions {
+            session.push_event(event_type, data).await;
+        }
+        sessions.len()
     }
 
     /// Get session count (for monitoring).
     pub async fn session_count(&self) -> usize {
-        self.sessions.read().await.len()
+        match self.store.count().await {
+            Ok(count) => count,
+            Err(_) => self.sessions.read().await.len(),
+        }
+    }
+
+    /// Count sessions in the store, failing if it is unavailable (for
+    /// readiness checks).
+    ///
+    /// # Errors
+    ///
+    /// Returns the store's error.
+    pub async fn check_store(&self) -> std::io::Result<usize> {
+        self.store.count().await
+    }
+
+    /// Open event streams across the sessions attached to this process.
+    pub async fn subscriber_count(&self) -> usize {
+        self.sessions.read().await.values().map(|s| s.subscriber_count()).sum()
     }
 
     /// Start background cleanup task.
@@ -306,7 +445,7 @@ impl Default for SessionManager {
 
 impl fmt::Debug for SessionManager {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("SessionManager").finish()
+        f.debug_struct("SessionManager").field("store", &self.store).finish_non_exhaustive()
     }
 }
 
@@ -317,7 +456,7 @@ mod tests {
     #[tokio::test]
     async fn test_session_creation() {
         let manager = SessionManager::new();
-        let session = manager.create_session().await;
+        let session = manager.create_session(None).await;
 
         assert!(!session.id.as_str().is_empty());
         assert_eq!(manager.session_count().await, 1);
@@ -346,7 +485,7 @@ mod tests {
     #[tokio::test]
     async fn test_session_lookup() {
         let manager = SessionManager::new();
-        let session = manager.create_session().await;
+        let session = manager.create_session(None).await;
         let id = session.id.clone();
 
         let found = manager.get_session(id.as_str()).await;
@@ -356,6 +495,18 @@ mod tests {
         assert!(not_found.is_none());
     }
 
+    #[tokio::test]
+    async fn test_owner_survives_attaching() {
+        let store: Arc<dyn SessionStore> = Arc::new(MemorySessionStore::new());
+        let session = SessionManager::with_store(Arc::clone(&store))
+            .create_session(Some("client-a".to_string()))
+            .await;
+
+        // Another process sharing the store learns the owner from it.
+        let attached = SessionManager::with_store(store).get_session(session.id.as_str()).await;
+        assert_eq!(attached.unwrap().owner.as_deref(), Some("client-a"));
+    }
+
     #[tokio::test]
     async fn test_ring_buffer_overflow() {
         let session = Session::new();
@@ -0,0 +1,579 @@
+//! Session persistence.
+//!
+//! A [`SessionStore`] holds what must outlive a process: which sessions
+//! exist, when they were last active, and each session's replay buffer of
+//! recent events (with their IDs), and who owns each session. Live delivery (broadcast channels, log
+//! levels) stays in the process that serves the connection.
+//!
+//! - [`MemorySessionStore`] keeps everything in memory (the default); a
+//!   restart loses every session.
+//! - [`FileSessionStore`] keeps one JSON-lines file per session in a
+//!   directory (and the owner, if any, in a `<session-id>.owner` file next
+//!   to it). Point several replicas at the same (shared) directory and a
+//!   client can reconnect to any of them, or after a restart, and replay from
+//!   `Last-Event-ID`. Files are locked while written, so event IDs stay
+//!   unique across processes; each process remembers where a file ended, so
+//!   an append only reads what other processes added since.
+//!
+//! Events pushed by one replica are only streamed live by that replica;
+//! other replicas serve them on replay.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::session::{BufferedEvent, HISTORY_SIZE};
+
+/// Storage backend for sessions and their replay buffers.
+#[async_trait]
+pub trait SessionStore: Send + Sync + fmt::Debug {
+    /// Register a new, empty session, owned by `owner` (a client's tenant)
+    /// if given.
+    async fn create(&self, id: &str, owner: Option<&str>) -> io::Result<()>;
+
+    /// Who owns a session (`None` for an unowned or unknown session).
+    async fn owner(&self, id: &str) -> io::Result<Option<String>>;
+
+    /// Mark a session active. Returns `false` if it does not exist.
+    async fn touch(&self, id: &str) -> io::Result<bool>;
+
+    /// Append an event, assigning the next event ID.
+    ///
+    /// Fails with [`io::ErrorKind::NotFound`] if the session was removed.
+    async fn append(&self, id: &str, event_type: &str, data: &str) -> io::Result<BufferedEvent>;
+
+    /// Buffered events with an ID greater than `last_event_id`.
+    async fn events_after(&self, id: &str, last_event_id: u64) -> io::Result<Vec<BufferedEvent>>;
+
+    /// Remove a session. Returns `false` if it did not exist.
+    async fn remove(&self, id: &str) -> io::Result<bool>;
+
+    /// Remove sessions idle for longer than `timeout`, returning their IDs.
+    async fn remove_stale(&self, timeout: Duration) -> io::Result<Vec<String>>;
+
+    /// Number of stored sessions.
+    async fn count(&self) -> io::Result<usize>;
+}
+
+fn not_found(id: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("session {id} not found"))
+}
+
+/// In-memory buffers for one session.
+#[derive(Debug)]
+struct MemoryEntry {
+    owner: Option<String>,
+    history: VecDeque<BufferedEvent>,
+    next_event_id: u64,
+    last_active: Instant,
+}
+
+impl MemoryEntry {
+    fn new(owner: Option<String>) -> Self {
+        Self {
+            owner,
+            history: VecDeque::with_capacity(HISTORY_SIZE),
+            next_event_id: 1,
+            last_active: Instant::now(),
+        }
+    }
+}
+
+/// Session store that lives and dies with the process.
+#[derive(Debug, Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<HashMap<String, MemoryEntry>>,
+}
+
+impl MemorySessionStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an unowned session without going through the async API.
+    pub fn insert(&self, id: &str) {
+        self.lock().insert(id.to_string(), MemoryEntry::new(None));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, MemoryEntry>> {
+        self.sessions.lock().expect("session store lock poisoned")
+    }
+}
+
+#[async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn create(&self, id: &str, owner: Option<&str>) -> io::Result<()> {
+        self.lock().insert(id.to_string(), MemoryEntry::new(owner.map(str::to_string)));
+        Ok(())
+    }
+
+    async fn owner(&self, id: &str) -> io::Result<Option<String>> {
+        Ok(self.lock().get(id).and_then(|entry| entry.owner.clone()))
+    }
+
+    async fn touch(&self, id: &str) -> io::Result<bool> {
+        Ok(self.lock().get_mut(id).map(|entry| entry.last_active = Instant::now()).is_some())
+    }
+
+    async fn append(&self, id: &str, event_type: &str, data: &str) -> io::Result<BufferedEvent> {
+        let mut sessions = self.lock();
+        let entry = sessions.get_mut(id).ok_or_else(|| not_found(id))?;
+        let event = BufferedEvent::new(entry.next_event_id, event_type, data);
+        entry.next_event_id += 1;
+        if entry.history.len() >= HISTORY_SIZE {
+            entry.history.pop_front();
+        }
+        entry.history.push_back(event.clone());
+        entry.last_active = Instant::now();
+        Ok(event)
+    }
+
+    async fn events_after(&self, id: &str, last_event_id: u64) -> io::Result<Vec<BufferedEvent>> {
+        let sessions = self.lock();
+        let entry = sessions.get(id).ok_or_else(|| not_found(id))?;
+        Ok(entry.history.iter().filter(|e| e.id > last_event_id).cloned().collect())
+    }
+
+    async fn remove(&self, id: &str) -> io::Result<bool> {
+        Ok(self.lock().remove(id).is_some())
+    }
+
+    async fn remove_stale(&self, timeout: Duration) -> io::Result<Vec<String>> {
+        let mut sessions = self.lock();
+        let stale: Vec<String> = sessions
+            .iter()
+            .filter(|(_, entry)| entry.last_active.elapsed() > timeout)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in &stale {
+            sessions.remove(id);
+        }
+        Ok(stale)
+    }
+
+    async fn count(&self) -> io::Result<usize> {
+        Ok(self.lock().len())
+    }
+}
+
+/// One line of a session file.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredEvent {
+    id: u64,
+    event: String,
+    data: String,
+}
+
+/// Where a session file ended when this process last saw it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Tail {
+    /// Which file it was (see [`file_id`]); compaction replaces the file.
+    file: u64,
+    /// File size after the last event.
+    len: u64,
+    /// ID of the last event (0: none yet).
+    last_id: u64,
+    /// Number of events in the file.
+    events: usize,
+}
+
+impl Tail {
+    /// Account for the events in `file` from the current position to the end.
+    fn scan(mut self, file: &mut File) -> io::Result<Self> {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        for event in parse_events(&contents) {
+            self.last_id = event.id;
+            self.events += 1;
+        }
+        self.len += contents.len() as u64;
+        Ok(self)
+    }
+}
+
+/// Session store keeping one `<session-id>.jsonl` file per session.
+///
+/// Each line is one buffered event; the file's modification time is the
+/// session's last activity. An owned session's owner is kept in a
+/// `<session-id>.owner` file, written before the session file appears. Files are compacted to the newest
+/// `HISTORY_SIZE` events once they grow to twice that.
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    dir: PathBuf,
+    /// Where each session file ended after this process last touched it.
+    tails: Arc<Mutex<HashMap<String, Tail>>>,
+}
+
+impl FileSessionStore {
+    /// Use `dir` (created if missing) for session files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, tails: Arc::default() })
+    }
+
+    /// Path of a session's file, or `None` for IDs that are not safe file
+    /// names (session IDs come from client headers).
+    fn path(&self, id: &str) -> Option<PathBuf> {
+        let valid = !id.is_empty()
+            && id.len() <= 64
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        valid.then(|| self.dir.join(format!("{id}.jsonl")))
+    }
+
+    /// Path of the file naming a session's owner.
+    fn owner_path(session_path: &Path) -> PathBuf {
+        session_path.with_extension("owner")
+    }
+
+    fn session_files(dir: &Path) -> io::Result<impl Iterator<Item = (String, fs::DirEntry)>> {
+        Ok(fs::read_dir(dir)?.filter_map(Result::ok).filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let id = name.strip_suffix(".jsonl")?.to_string();
+            Some((id, entry))
+        }))
+    }
+}
+
+/// Run blocking file I/O off the async runtime.
+async fn blocking<T, F>(f: F) -> io::Result<T>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(io::Error::other)?
+}
+
+fn parse_events(contents: &str) -> impl Iterator<Item = StoredEvent> + '_ {
+    contents.lines().filter_map(|line| match serde_json::from_str(line) {
+        Ok(event) => Some(event),
+        Err(e) => {
+            tracing::warn!(error = %e, "Skipping unreadable session event");
+            None
+        }
+    })
+}
+
+/// Remove a file, returning `false` if it did not exist.
+fn remove_if_exists(path: &Path) -> io::Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn open_existing(path: &Path, id: &str, options: &OpenOptions) -> io::Result<File> {
+    options
+        .open(path)
+        .map_err(|e| if e.kind() == io::ErrorKind::NotFound { not_found(id) } else { e })
+}
+
+/// Tells apart the files a path has named: the inode on Unix, elsewhere
+/// the creation time (a compacted file is always a new one).
+#[cfg(unix)]
+fn file_id(meta: &fs::Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::ino(meta)
+}
+
+#[cfg(not(unix))]
+fn file_id(meta: &fs::Metadata) -> u64 {
+    meta.created()
+        .ok()
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// Open and lock a session's file. Compaction replaces the file, so one
+/// that was replaced while waiting for the lock is opened again.
+fn lock_current(
+    path: &Path,
+    id: &str,
+    options: &OpenOptions,
+    lock: fn(&File) -> io::Result<()>,
+) -> io::Result<File> {
+    loop {
+        let file = open_existing(path, id, options)?;
+        lock(&file)?;
+        let current = fs::metadata(path)
+            .map_err(|e| if e.kind() == io::ErrorKind::NotFound { not_found(id) } else { e })?;
+        if file_id(&current) == file_id(&file.metadata()?) {
+            return Ok(file);
+        }
+    }
+}
+
+/// Replace the session file at `path` with its newest `HISTORY_SIZE`
+/// events, `event` last, returning the new file's tail.
+fn compact(path: &Path, file: &mut File, event: StoredEvent) -> io::Result<Tail> {
+    let mut contents = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_string(&mut contents)?;
+    let mut events: Vec<StoredEvent> = parse_events(&contents).collect();
+    events.push(event);
+    let keep = &events[events.len().saturating_sub(HISTORY_SIZE)..];
+    let mut compacted = String::new();
+    for e in keep {
+        compacted += &(serde_json::to_string(e).map_err(io::Error::other)? + "\n");
+    }
+
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let staged = path.with_file_name(format!(".{name}.compact"));
+    let mut replacement = File::create(&staged)?;
+    replacement.write_all(compacted.as_bytes())?;
+    fs::rename(&staged, path)?;
+    Ok(Tail {
+        file: file_id(&replacement.metadata()?),
+        len: compacted.len() as u64,
+        last_id: keep.last().map_or(0, |e| e.id),
+        events: keep.len(),
+    })
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn create(&self, id: &str, owner: Option<&str>) -> io::Result<()> {
+        let path = self.path(id).ok_or_else(|| not_found(id))?;
+        let owner = owner.map(str::to_string);
+        blocking(move || {
+            if let Some(owner) = owner {
+                fs::write(Self::owner_path(&path), owner)?;
+            }
+            OpenOptions::new().create(true).append(true).open(path).map(drop)
+        })
+        .await
+    }
+
+    async fn owner(&self, id: &str) -> io::Result<Option<String>> {
+        let Some(path) = self.path(id) else {
+            return Ok(None);
+        };
+        blocking(move || match fs::read_to_string(Self::owner_path(&path)) {
+            Ok(owner) => Ok(Some(owner)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        })
+        .await
+    }
+
+    async fn touch(&self, id: &str) -> io::Result<bool> {
+        let Some(path) = self.path(id) else {
+            return Ok(false);
+        };
+        blocking(move || match OpenOptions::new().write(true).open(path) {
+            Ok(file) => file.set_modified(SystemTime::now()).map(|()| true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        })
+        .await
+    }
+
+    async fn append(&self, id: &str, event_type: &str, data: &str) -> io::Result<BufferedEvent> {
+        let path = self.path(id).ok_or_else(|| not_found(id))?;
+        let (id, event_type, data) = (id.to_string(), event_type.to_string(), data.to_string());
+        let tails = Arc::clone(&self.tails);
+        blocking(move || {
+            let options = OpenOptions::new().read(true).write(true).clone();
+            let mut file = lock_current(&path, &id, &options, File::lock)?;
+            let meta = file.metadata()?;
+            let known = tails.lock().expect("session tails lock poisoned").get(&id).copied();
+            let tail = match known {
+                Some(tail) if tail.file == file_id(&meta) && tail.len == meta.len() => tail,
+                // Other processes appended: read only their events.
+                Some(tail) if tail.file == file_id(&meta) && tail.len < meta.len() => {
+                    file.seek(SeekFrom::Start(tail.len))?;
+                    tail.scan(&mut file)?
+                }
+                _ => {
+                    Tail { file: file_id(&meta), len: 0, last_id: 0, events: 0 }.scan(&mut file)?
+                }
+            };
+
+            let event = StoredEvent { id: tail.last_id + 1, event: event_type, data };
+            let buffered = BufferedEvent::new(event.id, event.event.clone(), event.data.clone());
+            let tail = if tail.events + 1 >= 2 * HISTORY_SIZE {
+                compact(&path, &mut file, event)?
+            } else {
+                let line = serde_json::to_string(&event).map_err(io::Error::other)? + "\n";
+                file.seek(SeekFrom::End(0))?;
+                file.write_all(line.as_bytes())?;
+                Tail {
+                    len: tail.len + line.len() as u64,
+                    last_id: event.id,
+                    events: tail.events + 1,
+                    ..tail
+                }
+            };
+            tails.lock().expect("session tails lock poisoned").insert(id, tail);
+            Ok(buffered)
+        })
+        .await
+    }
+
+    async fn events_after(&self, id: &str, last_event_id: u64) -> io::Result<Vec<BufferedEvent>> {
+        let path = self.path(id).ok_or_else(|| not_found(id))?;
+        let id = id.to_string();
+        blocking(move || {
+            let mut file =
+                lock_current(&path, &id, OpenOptions::new().read(true), File::lock_shared)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+            Ok(parse_events(&contents)
+                .filter(|e| e.id > last_event_id)
+                .map(|e| BufferedEvent::new(e.id, e.event, e.data))
+                .collect())
+        })
+        .await
+    }
+
+    async fn remove(&self, id: &str) -> io::Result<bool> {
+        let Some(path) = self.path(id) else {
+            return Ok(false);
+        };
+        self.tails.lock().expect("session tails lock poisoned").remove(id);
+        blocking(move || {
+            let removed = remove_if_exists(&path)?;
+            remove_if_exists(&Self::owner_path(&path))?;
+            Ok(removed)
+        })
+        .await
+    }
+
+    async fn remove_stale(&self, timeout: Duration) -> io::Result<Vec<String>> {
+        let dir = self.dir.clone();
+        let removed = blocking(move || {
+            let mut removed = Vec::new();
+            for (id, entry) in Self::session_files(&dir)? {
+                let idle = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.elapsed().ok())
+                    .unwrap_or_default();
+                // Another replica may have removed it already.
+                if idle > timeout && fs::remove_file(entry.path()).is_ok() {
+                    let _ = fs::remove_file(Self::owner_path(&entry.path()));
+                    removed.push(id);
+                }
+            }
+            Ok(removed)
+        })
+        .await?;
+        let mut tails = self.tails.lock().expect("session tails lock poisoned");
+        for id in &removed {
+            tails.remove(id);
+        }
+        Ok(removed)
+    }
+
+    async fn count(&self) -> io::Result<usize> {
+        let dir = self.dir.clone();
+        blocking(move || Ok(Self::session_files(&dir)?.count())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn exercise(store: &dyn SessionStore) {
+        store.create("s1", Some("client-a")).await.unwrap();
+        assert_eq!(store.owner("s1").await.unwrap().as_deref(), Some("client-a"));
+        assert_eq!(store.owner("missing").await.unwrap(), None);
+        assert!(store.touch("s1").await.unwrap());
+        assert!(!store.touch("missing").await.unwrap());
+
+        for n in 1..=3 {
+            let event = store.append("s1", "message", &format!("{{\"n\":{n}}}")).await.unwrap();
+            assert_eq!(event.id, n);
+        }
+        let events = store.events_after("s1", 1).await.unwrap();
+        assert_eq!(events.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(events[0].data, "{\"n\":2}");
+        assert_eq!(store.count().await.unwrap(), 1);
+
+        assert_eq!(
+            store.append("missing", "message", "{}").await.unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert!(store.remove("s1").await.unwrap());
+        assert!(!store.remove("s1").await.unwrap());
+        assert_eq!(store.owner("s1").await.unwrap(), None);
+        assert_eq!(store.count().await.unwrap(), 0);
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("s2-sessions-{name}-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        exercise(&MemorySessionStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let dir = temp_dir("basic");
+        exercise(&FileSessionStore::open(&dir).unwrap()).await;
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_store_compacts_and_keeps_ids() {
+        let dir = temp_dir("compact");
+        let store = FileSessionStore::open(&dir).unwrap();
+        store.create("s1", None).await.unwrap();
+        for _ in 0..(2 * HISTORY_SIZE + 5) {
+            store.append("s1", "message", "{}").await.unwrap();
+        }
+        let events = store.events_after("s1", 0).await.unwrap();
+        assert!(events.len() <= 2 * HISTORY_SIZE);
+        assert!(events.len() >= HISTORY_SIZE);
+        assert_eq!(events.last().unwrap().id, (2 * HISTORY_SIZE + 5) as u64);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_stores_share_a_directory() {
+        let dir = temp_dir("shared");
+        let (a, b) = (FileSessionStore::open(&dir).unwrap(), FileSessionStore::open(&dir).unwrap());
+        a.create("s1", None).await.unwrap();
+        let total = 2 * HISTORY_SIZE + 5;
+        for n in 1..=total {
+            // Runs of appends from each store, across compactions.
+            let store = if n % 7 < 4 { &a } else { &b };
+            assert_eq!(store.append("s1", "message", "{}").await.unwrap().id, n as u64);
+        }
+        let events = b.events_after("s1", 0).await.unwrap();
+        assert_eq!(events.last().unwrap().id, total as u64);
+        let ids: Vec<u64> = events.iter().map(|e| e.id).collect();
+        assert!(ids.windows(2).all(|w| w[1] == w[0] + 1), "{ids:?}");
+        // Compaction leaves no staging file behind.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_store_rejects_unsafe_ids() {
+        let dir = temp_dir("unsafe");
+        let store = FileSessionStore::open(&dir).unwrap();
+        assert!(!store.touch("../etc/passwd").await.unwrap());
+        assert!(store.create("a/b", None).await.is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,22 +1,66 @@
 //! Stdio transport for MCP protocol.
 //!
-//! Handles JSON-RPC 2.0 over stdin/stdout.
+//! Handles JSON-RPC 2.0 over stdin/stdout, one message (request or batch)
+//! per line.
 
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Instant;
 
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tracing::Instrument;
+
+use crate::telemetry;
 use crate::tools::{McpTool, ToolContext};
 
-use super::transport::{JsonRpcRequest, JsonRpcResponse, McpToolInfo};
+use super::audit::{AuditLog, AuditRecord};
+use super::completion;
+use super::logging::{self, LogLevelHandle, LogSink};
+use super::transport::{JsonRpcMessage, JsonRpcRequest, JsonRpcResponse, McpToolInfo, run_batch};
+
+/// Outgoing messages (responses and notifications), one JSON object per line.
+type Outbox = mpsc::UnboundedSender<serde_json::Value>;
 
 // Re-use types from transport module
 
-/// Handle MCP protocol over stdio.
-pub async fn run_stdio(tools: Vec<Box<dyn McpTool>>, ctx: ToolContext) -> anyhow::Result<()> {
-    let stdin = tokio::io::stdin();
-    let mut stdout = tokio::io::stdout();
-    let mut reader = BufReader::new(stdin);
+/// Handle MCP protocol over stdio, recording tool calls in `audit` if given.
+pub async fn run_stdio(
+    tools: Vec<Box<dyn McpTool>>,
+    ctx: ToolContext,
+    audit: Option<Arc<AuditLog>>,
+) -> anyhow::Result<()> {
+    serve(BufReader::new(tokio::io::stdin()), tokio::io::stdout(), tools, ctx, audit).await
+}
+
+/// Handle MCP protocol over any line-based stream: read messages from
+/// `input` until it ends and write responses and notifications to `output`.
+pub async fn serve<R, W>(
+    mut reader: R,
+    mut output: W,
+    tools: Vec<Box<dyn McpTool>>,
+    ctx: ToolContext,
+    audit: Option<Arc<AuditLog>>,
+) -> anyhow::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let audit = audit.as_deref();
     let mut line = String::new();
 
+    // A single writer keeps responses and log notifications from interleaving.
+    let (outbox, mut outgoing) = mpsc::unbounded_channel::<serde_json::Value>();
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outgoing.recv().await {
+            output.write_all(message.to_string().as_bytes()).await?;
+            output.write_all(b"\n").await?;
+            output.flush().await?;
+        }
+        Ok::<_, std::io::Error>(())
+    });
+    let log_level = LogLevelHandle::default();
+
     tracing::info!("MCP stdio server ready, waiting for requests...");
 
     loop {
@@ -25,7 +69,7 @@ pub async fn run_stdio(tools: Vec<Box<dyn McpTool>>, ctx: ToolContext) -> anyhow
 
         if bytes_read == 0 {
             // EOF
-            tracing::info!("Stdin closed, shutting down");
+            tracing::info!("Input closed, shutting down");
             break;
         }
 
@@ -34,32 +78,47 @@ pub async fn run_stdio(tools: Vec<Box<dyn McpTool>>, ctx: ToolContext) -> anyhow
             continue;
         }
 
-        // Parse JSON-RPC request
-        let request: JsonRpcRequest = match serde_json::from_str(trimmed) {
-            Ok(req) => req,
+        // Parse JSON-RPC request or batch
+        let message: JsonRpcMessage = match serde_json::from_str(trimmed) {
+            Ok(message) => message,
             Err(e) => {
                 let error_response =
                     JsonRpcResponse::error(None, -32700, format!("Parse error: {}", e));
-                let response_json = serde_json::to_string(&error_response)?;
-                stdout.write_all(response_json.as_bytes()).await?;
-                stdout.write_all(b"\n").await?;
-                stdout.flush().await?;
+                let _ = outbox.send(serde_json::to_value(&error_response)?);
                 continue;
             }
         };
 
-        tracing::debug!(method = %request.method, "Received request");
+        match message {
+            JsonRpcMessage::Single(request) => {
+                tracing::debug!(method = %request.method, "Received request");
+
+                // Handle request
+                let response =
+                    handle_request(&request, &tools, &ctx, audit, &outbox, &log_level).await;
 
-        // Handle request
-        let response = handle_request(&request, &tools, &ctx).await;
+                // Notifications (no id) are never answered
+                if request.id.is_some() {
+                    let _ = outbox.send(serde_json::to_value(&response)?);
+                }
+            }
+            JsonRpcMessage::Batch(items) => {
+                tracing::debug!(size = items.len(), "Received batch");
 
-        // Write response
-        let response_json = serde_json::to_string(&response)?;
-        stdout.write_all(response_json.as_bytes()).await?;
-        stdout.write_all(b"\n").await?;
-        stdout.flush().await?;
+                let (tools, ctx, outbox_ref, log_level) = (&tools, &ctx, &outbox, &log_level);
+                let reply = run_batch(items, |req| async move {
+                    Some(handle_request(&req, tools, ctx, audit, outbox_ref, log_level).await)
+                })
+                .await;
+                if let Some(reply) = reply {
+                    let _ = outbox.send(reply);
+                }
+            }
+        }
     }
 
+    drop(outbox);
+    writer.await??;
     Ok(())
 }
 
@@ -67,13 +126,47 @@ async fn handle_request(
     req: &JsonRpcRequest,
     tools: &[Box<dyn McpTool>],
     ctx: &ToolContext,
+    audit: Option<&AuditLog>,
+    outbox: &Outbox,
+    log_level: &LogLevelHandle,
+) -> JsonRpcResponse {
+    let span = telemetry::request_span(&req.method, req.id.as_ref(), &req.params);
+    dispatch(req, tools, ctx, audit, outbox, log_level).instrument(span).await
+}
+
+async fn dispatch(
+    req: &JsonRpcRequest,
+    tools: &[Box<dyn McpTool>],
+    ctx: &ToolContext,
+    audit: Option<&AuditLog>,
+    outbox: &Outbox,
+    log_level: &LogLevelHandle,
 ) -> JsonRpcResponse {
     match req.method.as_str() {
         "initialize" => handle_initialize(req.id.clone(), &req.params),
-        "initialized" => JsonRpcResponse::success(req.id.clone(), serde_json::json!({})),
-        "tools/list" => handle_tools_list(req.id.clone(), tools),
-        "tools/call" => handle_tools_call(req.id.clone(), &req.params, tools, ctx).await,
+        "notifications/initialized" | "initialized" | "notifications/cancelled" => {
+            JsonRpcResponse::success(req.id.clone(), serde_json::json!({}))
+        }
+        "tools/list" => handle_tools_list(req.id.clone(), tools, ctx),
+        "tools/call" => {
+            let sink = LogSink::new(log_level.clone(), outbox.clone());
+            sink.scope(handle_tools_call(req.id.clone(), &req.params, tools, ctx, audit)).await
+        }
         "ping" => JsonRpcResponse::success(req.id.clone(), serde_json::json!({})),
+        "completion/complete" => {
+            let input_schema =
+                |name: &str| tools.iter().find(|t| t.name() == name).map(|t| t.input_schema());
+            match completion::complete(ctx, &req.params, input_schema).await {
+                Ok(result) => JsonRpcResponse::success(req.id.clone(), result),
+                Err(e) => JsonRpcResponse::rejected(req.id.clone(), e),
+            }
+        }
+        "logging/setLevel" => match logging::set_level(log_level, &req.params) {
+            Ok(_) => JsonRpcResponse::success(req.id.clone(), serde_json::json!({})),
+            Err((field, message)) => {
+                JsonRpcResponse::invalid_params(req.id.clone(), &field, &message)
+            }
+        },
         _ => JsonRpcResponse::error(
             req.id.clone(),
             -32601,
@@ -93,7 +186,9 @@ fn handle_initialize(id: Option<serde_json::Value>, params: &serde_json::Value)
         serde_json::json!({
             "protocolVersion": protocol_version,
             "capabilities": {
-                "tools": {}
+                "tools": {},
+                "logging": {},
+                "completions": {}
             },
             "serverInfo": {
                 "name": "semantic-scholar-mcp",
@@ -103,14 +198,14 @@ fn handle_initialize(id: Option<serde_json::Value>, params: &serde_json::Value)
     )
 }
 
-fn handle_tools_list(id: Option<serde_json::Value>, tools: &[Box<dyn McpTool>]) -> JsonRpcResponse {
+fn handle_tools_list(
+    id: Option<serde_json::Value>,
+    tools: &[Box<dyn McpTool>],
+    ctx: &ToolContext,
+) -> JsonRpcResponse {
     let tool_list: Vec<McpToolInfo> = tools
         .iter()
-        .map(|t| McpToolInfo {
-            name: t.name().to_string(),
-            description: t.description().to_string(),
-            input_schema: t.input_schema(),
-        })
+        .map(|t| McpToolInfo::from_tool(t.as_ref()).with_defaults(ctx.tool_defaults()))
         .collect();
 
     JsonRpcResponse::success(
@@ -121,11 +216,39 @@ fn handle_tools_list(id: Option<serde_json::Value>, tools: &[Box<dyn McpTool>])
     )
 }
 
+/// Run a `tools/call` and record it in the audit log, if there is one.
 async fn handle_tools_call(
     id: Option<serde_json::Value>,
     params: &serde_json::Value,
     tools: &[Box<dyn McpTool>],
     ctx: &ToolContext,
+    audit: Option<&AuditLog>,
+) -> JsonRpcResponse {
+    let Some(audit) = audit else {
+        return run_tools_call(id, params, tools, ctx).await;
+    };
+    let started = Instant::now();
+    let requests = Arc::new(AtomicU32::new(0));
+    let mut ctx = ctx.clone();
+    ctx.client = Arc::new(ctx.client.with_request_counter(Arc::clone(&requests)));
+    let response = run_tools_call(id, params, tools, &ctx).await;
+
+    let tool = params.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+    let arguments = params.get("arguments").cloned().unwrap_or(serde_json::json!({}));
+    audit.record(AuditRecord {
+        principal: "stdio".to_owned(),
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        upstream_requests: requests.load(Ordering::Relaxed),
+        ..AuditRecord::new(tool, &arguments, &response)
+    });
+    response
+}
+
+async fn run_tools_call(
+    id: Option<serde_json::Value>,
+    params: &serde_json::Value,
+    tools: &[Box<dyn McpTool>],
+    ctx: &ToolContext,
 ) -> JsonRpcResponse {
     let tool_name = match params.get("name").and_then(|v| v.as_str()) {
         Some(name) => name,
@@ -143,21 +266,19 @@ async fn handle_tools_call(
         }
     };
 
+    let arguments = ctx.tool_defaults().apply(tool_name, arguments);
+    if let Err(e) = tool.validate_input(&arguments) {
+        tracing::debug!(tool = %tool_name, error = %e, "Rejected tool arguments");
+        return JsonRpcResponse::rejected(id, e);
+    }
+
     tracing::info!(tool = %tool_name, "Executing tool");
 
-    match tool.execute(ctx, arguments).await {
-        Ok(result) => JsonRpcResponse::success(
-            id,
-            serde_json::json!({
-                "content": [{
-                    "type": "text",
-                    "text": result
-                }]
-            }),
-        ),
+    match tool.execute(ctx, arguments).instrument(telemetry::tool_span(tool_name)).await {
+        Ok(output) => JsonRpcResponse::success(id, output.to_call_result()),
         Err(e) => {
-            tracing::error!(tool = %tool_name, error = %e, "Tool execution failed");
-            JsonRpcResponse::error(id, -32000, format!("Tool error: {}", e))
+            tracing::error!(tool = %tool_name, error = %e, code = e.code(), "Tool execution failed");
+            JsonRpcResponse::success(id, e.to_call_result())
         }
     }
 }
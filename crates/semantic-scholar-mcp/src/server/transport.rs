@@ -6,16 +6,23 @@
 //! - Last-Event-ID replay on reconnection
 //! - Broadcast channels for live event delivery
 //! - Async tool execution decoupled from HTTP handlers
+//! - JSON-RPC batches, run concurrently
+//! - Streamable HTTP: `tools/call` answered as an SSE stream when the client
+//!   accepts `text/event-stream`, `DELETE /mcp` to end a session
+//! - WebSocket at `/ws` (see [`super::websocket`])
+//! - Prometheus metrics at `/metrics`
 
 use std::borrow::Cow;
 use std::convert::Infallible;
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 
 use axum::{
     Json, Router,
-    extract::{Query, State},
-    http::{HeaderMap, StatusCode},
+    extract::{Extension, Query, State},
+    http::{HeaderMap, Method, StatusCode, header},
     response::{
         IntoResponse, Response,
         sse::{Event, KeepAlive, Sse},
@@ -24,13 +31,28 @@ use axum::{
 };
 use futures::stream::{self, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use tokio_stream::wrappers::BroadcastStream;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{BroadcastStream, UnboundedReceiverStream};
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
+use tracing::Instrument;
 
+use super::accounts::{Accounts, LoginThrottle, Principal, Scopes};
+use super::audit::{AuditLog, AuditOutcome, AuditQuery, AuditRecord};
+use super::completion;
+use super::jobs::{self, JobManager};
+use super::logging::{self, LogSink};
 use super::oauth::OAuthStore;
-use super::session::SessionManager;
-use crate::tools::{McpTool, ToolContext};
+use super::oauth::oidc::{CALLBACK_PATH, OidcProvider};
+use super::oauth::store::AccessGrant;
+use super::policy::{ClientIdentity, ToolPolicy, ToolPolicyHandle, USER_TENANT_PREFIX};
+use super::session::{BufferedEvent, Session, SessionManager};
+use super::session_store::SessionStore;
+use crate::config::ToolDefaults;
+use crate::error::ToolError;
+use crate::metrics::Metrics;
+use crate::telemetry;
+use crate::tools::{McpTool, ToolAnnotations, ToolContext, ToolFilter, ToolGroup};
 
 /// JSON-RPC 2.0 request.
 #[derive(Debug, Clone, Deserialize)]
@@ -43,6 +65,58 @@ pub struct JsonRpcRequest {
     pub id: Option<serde_json::Value>,
 }
 
+/// A JSON-RPC 2.0 message: one request or a batch.
+///
+/// Batch entries are kept as raw values so that a malformed entry only fails
+/// itself (see [`run_batch`]).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum JsonRpcMessage {
+    Single(JsonRpcRequest),
+    Batch(Vec<serde_json::Value>),
+}
+
+/// Run a batch concurrently and collect the responses in request order.
+///
+/// Returns the reply to send: an array of responses, a single error for an
+/// empty batch, or `None` when every entry was a notification. `handle`
+/// returns `None` for requests it acknowledges without a response. Tool calls
+/// in a batch share the client's rate limiter like any other requests.
+pub async fn run_batch<F, Fut>(
+    items: Vec<serde_json::Value>,
+    handle: F,
+) -> Option<serde_json::Value>
+where
+    F: Fn(JsonRpcRequest) -> Fut + Sync,
+    Fut: Future<Output = Option<JsonRpcResponse>> + Send,
+{
+    if items.is_empty() {
+        let error = JsonRpcResponse::error(None, -32600, "Invalid Request: empty batch");
+        return serde_json::to_value(error).ok();
+    }
+    let handle = &handle;
+    let responses = futures::future::join_all(items.into_iter().map(|item| async move {
+        match serde_json::from_value::<JsonRpcRequest>(item.clone()) {
+            Ok(req) if req.id.is_none() => {
+                handle(req).await;
+                None
+            }
+            Ok(req) => handle(req).await,
+            Err(e) => Some(JsonRpcResponse::error(
+                item.get("id").cloned(),
+                -32600,
+                format!("Invalid Request: {e}"),
+            )),
+        }
+    }))
+    .await;
+    let responses: Vec<JsonRpcResponse> = responses.into_iter().flatten().collect();
+    if responses.is_empty() {
+        return None;
+    }
+    serde_json::to_value(responses).ok()
+}
+
 /// JSON-RPC 2.0 response.
 #[derive(Debug, Clone, Serialize)]
 pub struct JsonRpcResponse {
@@ -51,7 +125,8 @@ pub struct JsonRpcResponse {
     pub result: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<JsonRpcError>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Written as `null` when the request's id could not be read; JSON-RPC
+    /// requires the member on every response.
     pub id: Option<serde_json::Value>,
 }
 
@@ -82,6 +157,32 @@ impl JsonRpcResponse {
             id,
         }
     }
+
+    /// `-32602 Invalid params` naming the offending argument in `data.field`.
+    #[must_use]
+    pub fn invalid_params(id: Option<serde_json::Value>, field: &str, message: &str) -> Self {
+        Self {
+            jsonrpc: Cow::Borrowed(Self::VERSION),
+            result: None,
+            error: Some(JsonRpcError {
+                code: -32602,
+                message: format!("Invalid params: '{field}' {message}"),
+                data: Some(serde_json::json!({ "field": field })),
+            }),
+            id,
+        }
+    }
+
+    /// `-32602` for arguments rejected before anything runs.
+    ///
+    /// Validation errors keep their field via [`invalid_params`](Self::invalid_params).
+    #[must_use]
+    pub fn rejected(id: Option<serde_json::Value>, error: ToolError) -> Self {
+        match error {
+            ToolError::Validation { field, message } => Self::invalid_params(id, &field, &message),
+            other => Self::error(id, -32602, other.to_string()),
+        }
+    }
 }
 
 /// MCP tool info for tools/list response.
@@ -91,6 +192,30 @@ pub struct McpToolInfo {
     pub description: String,
     #[serde(rename = "inputSchema")]
     pub input_schema: serde_json::Value,
+    #[serde(rename = "outputSchema")]
+    pub output_schema: serde_json::Value,
+    pub annotations: ToolAnnotations,
+}
+
+impl McpToolInfo {
+    /// Describe a registered tool.
+    #[must_use]
+    pub fn from_tool(tool: &dyn McpTool) -> Self {
+        Self {
+            name: tool.name().to_string(),
+            description: tool.description().to_string(),
+            input_schema: tool.input_schema(),
+            output_schema: tool.output_schema(),
+            annotations: tool.annotations(),
+        }
+    }
+
+    /// Advertise configured default arguments in the input schema.
+    #[must_use]
+    pub fn with_defaults(mut self, defaults: &ToolDefaults) -> Self {
+        defaults.annotate_schema(&self.name, &mut self.input_schema);
+        self
+    }
 }
 
 /// Query parameters for SSE endpoint.
@@ -104,9 +229,13 @@ pub struct SseQuery {
 #[derive(Debug, Deserialize)]
 pub struct MessageQuery {
     #[serde(rename = "sessionId")]
-    session_id: Option<String>,
+    pub(super) session_id: Option<String>,
 }
 
+/// Request extension set on requests authenticated with the static token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticToken;
+
 /// Shared state for HTTP handlers.
 pub struct HttpState {
     pub tools: Vec<Box<dyn McpTool>>,
@@ -118,6 +247,139 @@ pub struct HttpState {
     pub auth_token: Option<String>,
     /// OAuth store (active when auth_token is configured).
     pub oauth_store: Option<Arc<OAuthStore>>,
+    /// Background jobs started via `job_submit`.
+    pub jobs: Arc<JobManager>,
+    /// Fixed deployment filter (from the command line).
+    pub tool_filter: ToolFilter,
+    /// Reloadable deployment and per-client policy.
+    pub tool_policy: ToolPolicyHandle,
+    /// User accounts for the OAuth login (auto-approve when `None`).
+    pub accounts: Option<Arc<Accounts>>,
+    /// Backoff for failed password logins.
+    pub login_throttle: LoginThrottle,
+    /// Upstream OpenID Connect provider that handles the OAuth login.
+    pub oidc: Option<Arc<OidcProvider>>,
+    /// Audit log of tool calls.
+    pub audit: Option<Arc<AuditLog>>,
+    /// Browser origins, besides the server's own, that may open `/ws`.
+    pub allowed_origins: Vec<String>,
+    /// Tool call, session and OAuth metrics, served with the client's.
+    pub metrics: Arc<Metrics>,
+}
+
+impl HttpState {
+    /// Whether `client` may see and call the tool `name` in `group`.
+    #[must_use]
+    pub fn permits(&self, client: Option<&ClientIdentity>, name: &str, group: ToolGroup) -> bool {
+        self.tool_filter.permits(name, group)
+            && client
+                .and_then(|c| c.principal.as_ref())
+                .is_none_or(|p| p.scopes.permits_tool(group))
+            && self.tool_policy.with(|p| p.permits(client, name, group))
+    }
+
+    /// Whether the caller may use admin endpoints: one that presented the
+    /// static token, or a user holding the `admin` scope. Nobody is admin
+    /// with authentication off.
+    #[must_use]
+    pub fn is_admin(&self, token: Option<StaticToken>, client: Option<&ClientIdentity>) -> bool {
+        self.auth_token.is_some()
+            && (token.is_some()
+                || client.and_then(|c| c.principal.as_ref()).is_some_and(Principal::is_admin))
+    }
+
+    /// The response to send unless the caller is admin: 404 with
+    /// authentication off, 403 otherwise.
+    #[must_use]
+    pub fn admin_denial(
+        &self,
+        token: Option<StaticToken>,
+        client: Option<&ClientIdentity>,
+    ) -> Option<Response> {
+        if self.auth_token.is_none() {
+            let message = "Admin endpoints need authentication enabled";
+            return Some((StatusCode::NOT_FOUND, message).into_response());
+        }
+        (!self.is_admin(token, client))
+            .then(|| (StatusCode::FORBIDDEN, "Requires the admin scope").into_response())
+    }
+
+    /// Tool context for a call made by `client`.
+    ///
+    /// OAuth clients get an API client handle that charges their quota and
+    /// takes turns with other clients for rate-limit slots.
+    #[must_use]
+    pub fn tool_context(&self, client: Option<&ClientIdentity>) -> ToolContext {
+        let mut ctx = self.ctx.clone();
+        let Some(client) = client else {
+            return ctx;
+        };
+        let limits = self.tool_policy.with(|p| p.quota_limits(client));
+        ctx.client = Arc::new(ctx.client.for_tenant(client.tenant(), limits));
+        match client.principal.clone() {
+            Some(principal) => ctx.with_principal(principal),
+            None => ctx,
+        }
+    }
+
+    /// Audit record for a call of `tool` made by `client` in `session`,
+    /// without its duration and upstream request count.
+    pub(super) fn audit_record(
+        &self,
+        client: Option<&ClientIdentity>,
+        session: &Session,
+        tool: &str,
+        arguments: &serde_json::Value,
+        response: &JsonRpcResponse,
+    ) -> AuditRecord {
+        let principal = match (client, &self.auth_token) {
+            (Some(c), _) => c.client_id.clone(),
+            (None, Some(_)) => "token".to_owned(),
+            (None, None) => "anonymous".to_owned(),
+        };
+        AuditRecord {
+            principal,
+            client_name: client.and_then(|c| c.client_name.clone()),
+            user: client.and_then(|c| c.principal.as_ref()).map(|p| p.user.clone()),
+            session_id: Some(session.id.to_string()),
+            ..AuditRecord::new(tool, arguments, response)
+        }
+    }
+
+    /// Look up a registered tool visible to `client`.
+    #[must_use]
+    pub fn find_tool(&self, client: Option<&ClientIdentity>, name: &str) -> Option<&dyn McpTool> {
+        self.tools
+            .iter()
+            .find(|t| t.name() == name)
+            .filter(|t| self.permits(client, t.name(), t.group()))
+            .map(AsRef::as_ref)
+    }
+}
+
+/// Options for [`create_router_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    /// Base URL for endpoint announcements.
+    pub base_url: Option<String>,
+    /// Static Bearer token; also enables the OAuth server.
+    pub auth_token: Option<String>,
+    /// Tools or groups to enable/disable for the whole deployment.
+    pub tool_filter: ToolFilter,
+    /// Reloadable deployment and per-client policy.
+    pub tool_policy: ToolPolicyHandle,
+    /// Where sessions are kept (in memory when `None`).
+    pub session_store: Option<Arc<dyn SessionStore>>,
+    /// OAuth store to use when auth is enabled (in memory when `None`).
+    pub oauth_store: Option<Arc<OAuthStore>>,
+    /// User accounts; enables the OAuth login form and scoped tokens.
+    pub accounts: Option<Arc<Accounts>>,
+    /// Upstream OpenID Connect provider; delegates the OAuth login to it.
+    pub oidc: Option<Arc<OidcProvider>>,
+    /// Audit log to record every `tools/call` in.
+    pub audit: Option<Arc<AuditLog>>,
+    /// Browser origins, besides the server's own, that may open `/ws`.
+    pub allowed_origins: Vec<String>,
 }
 
 /// Create the HTTP router for MCP.
@@ -127,7 +389,33 @@ pub fn create_router(
     base_url: Option<String>,
     auth_token: Option<String>,
 ) -> Router {
-    let sessions = Arc::new(SessionManager::new());
+    create_router_with_options(
+        tools,
+        ctx,
+        HttpOptions { base_url, auth_token, ..HttpOptions::default() },
+    )
+}
+
+/// Create the HTTP router for MCP with tool filtering.
+pub fn create_router_with_options(
+    tools: Vec<Box<dyn McpTool>>,
+    ctx: ToolContext,
+    options: HttpOptions,
+) -> Router {
+    let HttpOptions {
+        base_url,
+        auth_token,
+        tool_filter,
+        tool_policy,
+        session_store,
+        oauth_store,
+        accounts,
+        oidc,
+        audit,
+        allowed_origins,
+    } = options;
+    let sessions =
+        Arc::new(session_store.map_or_else(SessionManager::new, SessionManager::with_store));
 
     // Start background cleanup task
     Arc::clone(&sessions).start_cleanup_task();
@@ -136,27 +424,58 @@ pub fn create_router(
 
     // Initialize OAuth store when auth is configured
     let oauth_store = auth_token.as_ref().map(|_| {
-        let store = Arc::new(OAuthStore::new());
+        let store = oauth_store.unwrap_or_else(|| Arc::new(OAuthStore::new()));
         Arc::clone(&store).start_cleanup_task();
         tracing::info!("OAuth 2.0 authorization server enabled");
         store
     });
 
-    let state = Arc::new(HttpState { tools, ctx, sessions, base_url, auth_token, oauth_store });
+    let jobs = Arc::new(JobManager::new());
+    Arc::clone(&jobs).start_cleanup_task();
+
+    let metrics = Arc::new(Metrics::new(Arc::clone(ctx.client.metrics())));
+
+    start_list_changed_task(&tool_policy, Arc::clone(&sessions));
+
+    let state = Arc::new(HttpState {
+        tools,
+        ctx,
+        sessions,
+        base_url,
+        auth_token,
+        oauth_store,
+        jobs,
+        tool_filter,
+        tool_policy,
+        accounts,
+        login_throttle: LoginThrottle::new(),
+        oidc,
+        audit,
+        allowed_origins: allowed_origins
+            .into_iter()
+            .map(|o| o.trim_end_matches('/').to_owned())
+            .collect(),
+        metrics,
+    });
 
     let mut router = Router::new()
         .route("/", get(health_check).post(handle_mcp_post))
         .route("/health", get(health_check))
         .route("/ready", get(readiness_check))
+        .route("/metrics", get(handle_metrics))
         // MCP discovery endpoint for Claude Connector
         .route("/.well-known/mcp.json", get(handle_mcp_discovery))
         // Streamable HTTP transport - single endpoint
-        .route("/mcp", post(handle_mcp_post).get(handle_mcp_get))
+        .route("/mcp", post(handle_mcp_post).get(handle_mcp_get).delete(handle_mcp_delete))
         // Legacy SSE transport for backward compatibility
         .route("/sse", get(handle_sse_legacy))
         .route("/message", post(handle_message_post))
+        // WebSocket transport for clients that cannot hold SSE + POST
+        .route("/ws", get(super::websocket::handle_ws))
         // Session management
-        .route("/sessions", get(handle_sessions_list));
+        .route("/sessions", get(handle_sessions_list))
+        .route("/admin/quotas", get(handle_admin_quotas))
+        .route("/admin/audit", get(handle_admin_audit));
 
     // OAuth endpoints (only when auth is configured)
     if state.oauth_store.is_some() {
@@ -170,8 +489,15 @@ pub fn create_router(
                 get(super::oauth::handlers::handle_auth_server_metadata),
             )
             .route("/register", post(super::oauth::handlers::handle_register))
-            .route("/authorize", get(super::oauth::handlers::handle_authorize_get))
-            .route("/token", post(super::oauth::handlers::handle_token));
+            .route(
+                "/authorize",
+                get(super::oauth::handlers::handle_authorize_get)
+                    .post(super::oauth::handlers::handle_authorize_post),
+            )
+            .route("/token", post(super::oauth::handlers::handle_token))
+            .route("/revoke", post(super::oauth::handlers::handle_revoke))
+            .route("/introspect", post(super::oauth::handlers::handle_introspect))
+            .route(CALLBACK_PATH, get(super::oauth::handlers::handle_oidc_callback));
     }
 
     router
@@ -181,6 +507,29 @@ pub fn create_router(
         .with_state(state)
 }
 
+/// Tell every connected session to re-fetch `tools/list` when the policy changes.
+fn start_list_changed_task(policy: &ToolPolicyHandle, sessions: Arc<SessionManager>) {
+    let visibility = |p: &ToolPolicy| (p.enable.clone(), p.disable.clone(), p.clients.clone());
+    let mut changes = policy.subscribe();
+    let mut last = visibility(&changes.borrow());
+    tokio::spawn(async move {
+        while changes.changed().await.is_ok() {
+            // Quota edits don't change what clients see.
+            let current = visibility(&changes.borrow_and_update());
+            if current == last {
+                continue;
+            }
+            last = current;
+            let notification = serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/tools/list_changed"
+            });
+            let notified = sessions.broadcast_event("message", &notification.to_string()).await;
+            tracing::info!(sessions = notified, "Tool list changed");
+        }
+    });
+}
+
 async fn health_check() -> impl IntoResponse {
     Json(serde_json::json!({
         "status": "ok",
@@ -247,15 +596,91 @@ async fn handle_mcp_discovery(
     }))
 }
 
-async fn readiness_check(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
-    let session_count = state.sessions.session_count().await;
-    Json(serde_json::json!({
-        "status": "ready",
+/// `"ok"` or `"degraded"`, for one readiness check.
+const fn check_status(ok: bool) -> &'static str {
+    if ok { "ok" } else { "degraded" }
+}
+
+/// Readiness with a breakdown per check; 503 when any check is degraded so
+/// load balancers stop routing here.
+async fn readiness_check(State(state): State<Arc<HttpState>>) -> Response {
+    let client = &state.ctx.client;
+    let (upstream, store, oauth) =
+        tokio::join!(client.check_upstream(), state.sessions.check_store(), async {
+            match state.oauth_store {
+                Some(ref store) => Some((store.is_persistent(), store.check().await)),
+                None => None,
+            }
+        });
+    let backoff = client.rate_limit_backoff();
+    let (normal, batch) = client.queued_requests();
+    let queued = |depths: &[(String, usize)]| depths.iter().map(|(_, n)| n).sum::<usize>();
+
+    let mut ready = upstream.reachable && backoff.is_none();
+    let mut checks = serde_json::Map::new();
+    let mut upstream_check = serde_json::to_value(&upstream).unwrap_or_default();
+    upstream_check["status"] = check_status(upstream.reachable).into();
+    checks.insert("upstream".into(), upstream_check);
+    checks.insert(
+        "rate_limit".into(),
+        serde_json::json!({
+            "status": check_status(backoff.is_none()),
+            "backoff_secs": backoff.map(|d| d.as_secs().max(1)),
+            "queued": {"normal": queued(&normal), "batch": queued(&batch)},
+        }),
+    );
+    let mut cache = serde_json::to_value(client.cache_stats()).unwrap_or_default();
+    cache["status"] = "ok".into();
+    checks.insert("cache".into(), cache);
+    let session_count = match store {
+        Ok(count) => {
+            checks.insert(
+                "session_store".into(),
+                serde_json::json!({"status": "ok", "sessions": count}),
+            );
+            count
+        }
+        Err(e) => {
+            ready = false;
+            checks.insert(
+                "session_store".into(),
+                serde_json::json!({"status": "degraded", "error": e.to_string()}),
+            );
+            state.sessions.session_count().await
+        }
+    };
+    if let Some((persistent, result)) = oauth {
+        ready &= result.is_ok();
+        checks.insert(
+            "oauth_store".into(),
+            serde_json::json!({
+                "status": check_status(result.is_ok()),
+                "persistent": persistent,
+                "error": result.err(),
+            }),
+        );
+    }
+
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    let body = Json(serde_json::json!({
+        "status": if ready { "ready" } else { "degraded" },
         "service": "semantic-scholar-mcp",
         "version": env!("CARGO_PKG_VERSION"),
         "sessions": session_count,
-        "tools": state.tools.len()
-    }))
+        "tools": state.tools.len(),
+        "checks": checks,
+    }));
+    (status, body).into_response()
+}
+
+/// Prometheus metrics; session gauges are sampled on each scrape.
+async fn handle_metrics(State(state): State<Arc<HttpState>>) -> Response {
+    let metrics = &state.metrics;
+    let sessions = state.sessions.session_count().await;
+    let subscribers = state.sessions.subscriber_count().await;
+    metrics.active_sessions.set(i64::try_from(sessions).unwrap_or(i64::MAX));
+    metrics.sse_subscribers.set(i64::try_from(subscribers).unwrap_or(i64::MAX));
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics.encode()).into_response()
 }
 
 /// List active sessions (for debugging).
@@ -266,38 +691,233 @@ async fn handle_sessions_list(State(state): State<Arc<HttpState>>) -> impl IntoR
     }))
 }
 
+/// Quota usage per user or OAuth client and queued upstream requests (admin
+/// only).
+async fn handle_admin_quotas(
+    State(state): State<Arc<HttpState>>,
+    token: Option<Extension<StaticToken>>,
+    client: Option<Extension<ClientIdentity>>,
+) -> Response {
+    if let Some(denial) =
+        state.admin_denial(token.map(|Extension(t)| t), client.as_ref().map(|Extension(c)| c))
+    {
+        return denial;
+    }
+
+    let window = |used, limit, resets_in| serde_json::json!({"used": used, "limit": limit, "resets_in": resets_in});
+    let mut clients = Vec::new();
+    let mut users = Vec::new();
+    for usage in state.ctx.client.quota_usage() {
+        let user = usage.tenant.strip_prefix(USER_TENANT_PREFIX);
+        let (mut entry, limits) = if let Some(user) = user {
+            let limits = state.tool_policy.with(|p| p.user_quota_limits(user));
+            (serde_json::json!({"user": user}), limits)
+        } else {
+            let client_name = match state.oauth_store {
+                Some(ref store) => {
+                    store.get_client(&usage.tenant).await.and_then(|c| c.client_name)
+                }
+                None => None,
+            };
+            let identity =
+                ClientIdentity { client_id: usage.tenant.clone(), client_name, principal: None };
+            let limits = state.tool_policy.with(|p| p.quota_limits(&identity));
+            let entry = serde_json::json!({
+                "client_id": identity.client_id,
+                "client_name": identity.client_name,
+            });
+            (entry, limits)
+        };
+        entry["minute"] = window(usage.minute_used, limits.per_minute, usage.minute_resets_in);
+        entry["day"] = window(usage.day_used, limits.per_day, usage.day_resets_in);
+        if user.is_some() { users.push(entry) } else { clients.push(entry) }
+    }
+
+    let (requests, batch) = state.ctx.client.queued_requests();
+    let queued = |waiting: Vec<(String, usize)>| -> serde_json::Map<String, serde_json::Value> {
+        waiting
+            .into_iter()
+            .map(|(tenant, n)| {
+                let tenant = if tenant.is_empty() { "server".to_owned() } else { tenant };
+                (tenant, serde_json::json!(n))
+            })
+            .collect()
+    };
+    Json(serde_json::json!({
+        "clients": clients,
+        "users": users,
+        "queued": { "requests": queued(requests), "batch": queued(batch) }
+    }))
+    .into_response()
+}
+
+/// Search the audit log (admin only); see [`AuditQuery`] for the filters.
+async fn handle_admin_audit(
+    State(state): State<Arc<HttpState>>,
+    token: Option<Extension<StaticToken>>,
+    client: Option<Extension<ClientIdentity>>,
+    Query(query): Query<AuditQuery>,
+) -> Response {
+    if let Some(denial) =
+        state.admin_denial(token.map(|Extension(t)| t), client.as_ref().map(|Extension(c)| c))
+    {
+        return denial;
+    }
+    let Some(audit) = state.audit.clone() else {
+        return (StatusCode::NOT_FOUND, "Audit log not enabled").into_response();
+    };
+    match tokio::task::spawn_blocking(move || audit.query(&query)).await {
+        Ok(Ok(records)) => {
+            Json(serde_json::json!({ "count": records.len(), "records": records })).into_response()
+        }
+        Ok(Err(e)) => {
+            tracing::error!(error = %e, "Failed to read audit log");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read audit log").into_response()
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Audit query panicked");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Session ID sent by the client: the `Mcp-Session-Id` header, or the
+/// `sessionId` query parameter used by the legacy transport.
+fn requested_session_id<'a>(headers: &'a HeaderMap, query: Option<&'a str>) -> Option<&'a str> {
+    headers.get("Mcp-Session-Id").and_then(|v| v.to_str().ok()).or(query)
+}
+
+/// Look up the session a request refers to.
+///
+/// Returns `Ok(None)` when the request names no session, and a 404 response
+/// when it names one that does not exist (expired or deleted), which tells
+/// the client to re-initialize. A session belonging to another client is
+/// reported the same way.
+pub(super) async fn resolve_session(
+    state: &HttpState,
+    headers: &HeaderMap,
+    query: Option<&str>,
+    client: Option<&ClientIdentity>,
+) -> Result<Option<Arc<Session>>, Response> {
+    let Some(id) = requested_session_id(headers, query) else {
+        return Ok(None);
+    };
+    let Some(session) = state.sessions.get_session(id).await else {
+        tracing::debug!(session_id = %id, "Unknown session");
+        return Err(session_not_found());
+    };
+    if session.owner != client.map(ClientIdentity::tenant) {
+        tracing::warn!(session_id = %id, "Refused another client's session");
+        return Err(session_not_found());
+    }
+    Ok(Some(session))
+}
+
+fn session_not_found() -> Response {
+    (StatusCode::NOT_FOUND, Json(JsonRpcResponse::error(None, -32001, "Session not found")))
+        .into_response()
+}
+
+/// Whether the client accepts an SSE stream as the response to a POST.
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|v| v.contains("text/event-stream"))
+}
+
 /// Handle POST requests to /mcp (Streamable HTTP transport)
 async fn handle_mcp_post(
     State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
     Query(query): Query<MessageQuery>,
-    Json(req): Json<JsonRpcRequest>,
+    client: Option<Extension<ClientIdentity>>,
+    Json(message): Json<JsonRpcMessage>,
 ) -> Response {
-    tracing::debug!(method = %req.method, "Handling MCP POST request");
+    let span = telemetry::http_span(&headers);
+    handle_mcp_message(state, headers, query, client.map(|Extension(c)| c), message)
+        .instrument(span)
+        .await
+}
 
+async fn handle_mcp_message(
+    state: Arc<HttpState>,
+    headers: HeaderMap,
+    query: MessageQuery,
+    client: Option<ClientIdentity>,
+    message: JsonRpcMessage,
+) -> Response {
     // Get or create session
-    let session = state.sessions.get_or_create_session(query.session_id.as_deref()).await;
+    let session =
+        match resolve_session(&state, &headers, query.session_id.as_deref(), client.as_ref()).await
+        {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                state.sessions.create_session(client.as_ref().map(ClientIdentity::tenant)).await
+            }
+            Err(response) => return response,
+        };
 
+    let mut res = match message {
+        JsonRpcMessage::Single(req) => {
+            tracing::debug!(method = %req.method, "Handling MCP POST request");
+
+            if req.method == "tools/call" && req.id.is_some() && accepts_event_stream(&headers) {
+                return stream_tool_call(state, session, client, req);
+            }
+            match dispatch(&state, &session, client.as_ref(), req).await {
+                Some(response) => Json(response).into_response(),
+                None => return StatusCode::ACCEPTED.into_response(),
+            }
+        }
+        JsonRpcMessage::Batch(items) => {
+            tracing::debug!(size = items.len(), "Handling MCP POST batch");
+
+            let reply =
+                run_batch(items, |req| dispatch(&state, &session, client.as_ref(), req)).await;
+            match reply {
+                Some(reply) => Json(reply).into_response(),
+                None => return StatusCode::ACCEPTED.into_response(),
+            }
+        }
+    };
+
+    res.headers_mut().insert("Mcp-Session-Id", session.id.to_header_value());
+    res
+}
+
+/// Handle one JSON-RPC request. Returns `None` for notifications that are
+/// acknowledged without a response.
+pub(super) async fn dispatch(
+    state: &Arc<HttpState>,
+    session: &Arc<Session>,
+    client: Option<&ClientIdentity>,
+    req: JsonRpcRequest,
+) -> Option<JsonRpcResponse> {
+    let span = telemetry::request_span(&req.method, req.id.as_ref(), &req.params);
+    dispatch_request(state, session, client, req).instrument(span).await
+}
+
+async fn dispatch_request(
+    state: &Arc<HttpState>,
+    session: &Arc<Session>,
+    client: Option<&ClientIdentity>,
+    req: JsonRpcRequest,
+) -> Option<JsonRpcResponse> {
     // Check if this is a notification (no id)
     let is_notification = req.id.is_none();
 
     let response = match req.method.as_str() {
-        "initialize" => {
-            let result = handle_initialize(&req.params);
-
-            let mut response = Json(JsonRpcResponse::success(req.id, result)).into_response();
-            response.headers_mut().insert("Mcp-Session-Id", session.id.to_header_value());
-            return response;
-        }
-        "notifications/initialized" | "initialized" => {
-            if is_notification {
-                return StatusCode::ACCEPTED.into_response();
-            }
+        "initialize" => JsonRpcResponse::success(req.id, handle_initialize(&req.params)),
+        "notifications/initialized" | "initialized" | "notifications/cancelled" => {
             JsonRpcResponse::success(req.id, serde_json::json!({}))
         }
-        "tools/list" => handle_tools_list(req.id, &state.tools),
+        "tools/list" => handle_tools_list(req.id, state, client),
         "tools/call" => {
             // Execute tool asynchronously and push result to session
-            let tool_response = handle_tools_call(req.id.clone(), &req.params, &state).await;
+            let tool_response =
+                handle_tools_call(req.id.clone(), &req.params, state, session, client, None).await;
 
             // For tool calls, also push result to session for replay
             if let Some(ref result) = tool_response.result {
@@ -312,33 +932,102 @@ async fn handle_mcp_post(
             tool_response
         }
         "ping" => JsonRpcResponse::success(req.id, serde_json::json!({})),
-        "notifications/cancelled" => {
-            if is_notification {
-                return StatusCode::ACCEPTED.into_response();
+        "completion/complete" => {
+            let input_schema = |name: &str| state.find_tool(client, name).map(|t| t.input_schema());
+            match completion::complete(&state.tool_context(client), &req.params, input_schema).await
+            {
+                Ok(result) => JsonRpcResponse::success(req.id, result),
+                Err(e) => JsonRpcResponse::rejected(req.id, e),
             }
-            JsonRpcResponse::success(req.id, serde_json::json!({}))
-        }
-        _ => {
-            if is_notification {
-                return StatusCode::ACCEPTED.into_response();
-            }
-            JsonRpcResponse::error(req.id, -32601, format!("Method not found: {}", req.method))
         }
+        "logging/setLevel" => match logging::set_level(&session.log_level, &req.params) {
+            Ok(_) => JsonRpcResponse::success(req.id, serde_json::json!({})),
+            Err((field, message)) => JsonRpcResponse::invalid_params(req.id, &field, &message),
+        },
+        _ => JsonRpcResponse::error(req.id, -32601, format!("Method not found: {}", req.method)),
     };
+    // A notification is handled but never answered, whatever its method.
+    (!is_notification).then_some(response)
+}
 
-    let mut res = Json(response).into_response();
-    res.headers_mut().insert("Mcp-Session-Id", session.id.to_header_value());
+/// Answer a `tools/call` with an SSE stream of its notifications and result.
+///
+/// The call runs in its own task, so it finishes even if the client drops the
+/// stream. Every event also lands in the session buffer, so the client can
+/// resume with `GET /mcp` and `Last-Event-ID`.
+fn stream_tool_call(
+    state: Arc<HttpState>,
+    session: Arc<Session>,
+    client: Option<ClientIdentity>,
+    req: JsonRpcRequest,
+) -> Response {
+    let session_header = session.id.to_header_value();
+    let (tap, events) = mpsc::unbounded_channel();
+
+    let span = telemetry::request_span(&req.method, req.id.as_ref(), &req.params);
+    tokio::spawn(
+        async move {
+            let response = handle_tools_call(
+                req.id,
+                &req.params,
+                &state,
+                &session,
+                client.as_ref(),
+                Some(tap.clone()),
+            )
+            .await;
+            let data = serde_json::to_string(&response).unwrap_or_default();
+            let _ = tap.send(session.push("message", data).await);
+        }
+        .instrument(span),
+    );
+
+    let stream = UnboundedReceiverStream::new(events)
+        .map(|event: BufferedEvent| Ok::<_, Infallible>(event.to_sse_event()));
+    let mut res = (
+        [("X-Accel-Buffering", "no"), ("Cache-Control", "no-cache, no-store, must-revalidate")],
+        Sse::new(stream)
+            .keep_alive(KeepAlive::new().interval(Duration::from_secs(15)).text("ping")),
+    )
+        .into_response();
+    res.headers_mut().insert("Mcp-Session-Id", session_header);
     res
 }
 
 /// Handle POST requests to /message (legacy transport)
 async fn handle_message_post(
     State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
     Query(query): Query<MessageQuery>,
-    Json(req): Json<JsonRpcRequest>,
+    client: Option<Extension<ClientIdentity>>,
+    Json(message): Json<JsonRpcMessage>,
 ) -> Response {
     // Delegate to the same handler
-    handle_mcp_post(State(state), Query(query), Json(req)).await
+    handle_mcp_post(State(state), headers, Query(query), client, Json(message)).await
+}
+
+/// Handle DELETE requests to /mcp (explicit session termination)
+async fn handle_mcp_delete(
+    State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
+    Query(query): Query<MessageQuery>,
+    client: Option<Extension<ClientIdentity>>,
+) -> Response {
+    let client = client.map(|Extension(c)| c);
+    let session =
+        match resolve_session(&state, &headers, query.session_id.as_deref(), client.as_ref()).await
+        {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                return (StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header").into_response();
+            }
+            Err(response) => return response,
+        };
+    if state.sessions.remove_session(session.id.as_str()).await {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        session_not_found()
+    }
 }
 
 /// Handle GET requests to /mcp (SSE stream for server-initiated messages)
@@ -346,7 +1035,8 @@ async fn handle_mcp_get(
     State(state): State<Arc<HttpState>>,
     headers: HeaderMap,
     Query(query): Query<SseQuery>,
-) -> impl IntoResponse {
+    client: Option<Extension<ClientIdentity>>,
+) -> Response {
     // Parse Last-Event-ID header for replay
     let last_event_id: u64 = headers
         .get("Last-Event-ID")
@@ -355,7 +1045,16 @@ async fn handle_mcp_get(
         .unwrap_or(0);
 
     // Get or create session
-    let session = state.sessions.get_or_create_session(query.session_id.as_deref()).await;
+    let client = client.map(|Extension(c)| c);
+    let session =
+        match resolve_session(&state, &headers, query.session_id.as_deref(), client.as_ref()).await
+        {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                state.sessions.create_session(client.as_ref().map(ClientIdentity::tenant)).await
+            }
+            Err(response) => return response,
+        };
 
     tracing::info!(
         session_id = %session.id,
@@ -371,6 +1070,7 @@ async fn handle_mcp_get(
         Sse::new(stream)
             .keep_alive(KeepAlive::new().interval(Duration::from_secs(15)).text("ping")),
     )
+        .into_response()
 }
 
 /// Build SSE stream with replay and live events.
@@ -407,6 +1107,7 @@ async fn build_sse_stream(
 async fn handle_sse_legacy(
     State(state): State<Arc<HttpState>>,
     headers: HeaderMap,
+    client: Option<Extension<ClientIdentity>>,
 ) -> impl IntoResponse {
     // Parse Last-Event-ID header for replay
     let last_event_id: u64 = headers
@@ -416,7 +1117,8 @@ async fn handle_sse_legacy(
         .unwrap_or(0);
 
     // Create new session for legacy connections
-    let session = state.sessions.create_session().await;
+    let owner = client.map(|Extension(c)| c.tenant());
+    let session = state.sessions.create_session(owner).await;
 
     tracing::info!(
         session_id = %session.id,
@@ -462,7 +1164,7 @@ async fn build_sse_stream_with_endpoint(
         None
     };
 
-    let initial_stream = stream::iter(initial_event.into_iter());
+    let initial_stream = stream::iter(initial_event);
 
     // Replay missed events (excluding endpoint if already sent)
     let missed_events = session.get_events_after(last_event_id.max(1)).await;
@@ -498,8 +1200,10 @@ fn handle_initialize(params: &serde_json::Value) -> serde_json::Value {
         "protocolVersion": protocol_version,
         "capabilities": {
             "tools": {
-                "listChanged": false
-            }
+                "listChanged": true
+            },
+            "logging": {},
+            "completions": {}
         },
         "serverInfo": {
             "name": "semantic-scholar-mcp",
@@ -508,14 +1212,22 @@ fn handle_initialize(params: &serde_json::Value) -> serde_json::Value {
     })
 }
 
-fn handle_tools_list(id: Option<serde_json::Value>, tools: &[Box<dyn McpTool>]) -> JsonRpcResponse {
-    let tool_list: Vec<McpToolInfo> = tools
+fn handle_tools_list(
+    id: Option<serde_json::Value>,
+    state: &HttpState,
+    client: Option<&ClientIdentity>,
+) -> JsonRpcResponse {
+    let job_tools = if state.permits(client, "job_submit", ToolGroup::Jobs) {
+        jobs::tool_infos()
+    } else {
+        Vec::new()
+    };
+    let tool_list: Vec<McpToolInfo> = state
+        .tools
         .iter()
-        .map(|t| McpToolInfo {
-            name: t.name().to_string(),
-            description: t.description().to_string(),
-            input_schema: t.input_schema(),
-        })
+        .filter(|t| state.permits(client, t.name(), t.group()))
+        .map(|t| McpToolInfo::from_tool(t.as_ref()).with_defaults(state.ctx.tool_defaults()))
+        .chain(job_tools)
         .collect();
 
     JsonRpcResponse::success(
@@ -526,10 +1238,56 @@ fn handle_tools_list(id: Option<serde_json::Value>, tools: &[Box<dyn McpTool>])
     )
 }
 
+/// Run a `tools/call`, record its metrics, and record it in the audit log
+/// if there is one.
+///
+/// Log notifications, and progress notifications when the request carries
+/// `_meta.progressToken`, are pushed to the session (and to `tap`, when the
+/// response is streamed) before the result is returned.
 async fn handle_tools_call(
     id: Option<serde_json::Value>,
     params: &serde_json::Value,
-    state: &HttpState,
+    state: &Arc<HttpState>,
+    session: &Arc<Session>,
+    client: Option<&ClientIdentity>,
+    tap: Option<mpsc::UnboundedSender<BufferedEvent>>,
+) -> JsonRpcResponse {
+    let started = Instant::now();
+    let requests = Arc::new(AtomicU32::new(0));
+    let response =
+        run_tools_call(id, params, state, session, client, tap, Arc::clone(&requests)).await;
+
+    let tool = params.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+    // Unknown names are lumped together to bound the metric's cardinality.
+    let known = jobs::is_job_tool(tool) || state.tools.iter().any(|t| t.name() == tool);
+    let (outcome, _) = AuditOutcome::of(&response);
+    state.metrics.observe_tool_call(
+        if known { tool } else { "unknown" },
+        outcome.as_str(),
+        started.elapsed(),
+    );
+
+    let Some(ref audit) = state.audit else {
+        return response;
+    };
+    let arguments = params.get("arguments").cloned().unwrap_or(serde_json::json!({}));
+    audit.record(AuditRecord {
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        upstream_requests: requests.load(Ordering::Relaxed),
+        ..state.audit_record(client, session, tool, &arguments, &response)
+    });
+    response
+}
+
+/// Run a `tools/call`, counting upstream requests in `requests`.
+async fn run_tools_call(
+    id: Option<serde_json::Value>,
+    params: &serde_json::Value,
+    state: &Arc<HttpState>,
+    session: &Arc<Session>,
+    client: Option<&ClientIdentity>,
+    tap: Option<mpsc::UnboundedSender<BufferedEvent>>,
+    requests: Arc<AtomicU32>,
 ) -> JsonRpcResponse {
     let tool_name = match params.get("name").and_then(|v| v.as_str()) {
         Some(name) => name,
@@ -540,36 +1298,108 @@ async fn handle_tools_call(
 
     let arguments = params.get("arguments").cloned().unwrap_or(serde_json::json!({}));
 
-    let tool = match state.tools.iter().find(|t| t.name() == tool_name) {
+    if jobs::is_job_tool(tool_name) && state.permits(client, tool_name, ToolGroup::Jobs) {
+        return match jobs::call_job_tool(tool_name, arguments, state, session, client).await {
+            Ok(result) => JsonRpcResponse::success(id, result),
+            Err(e) => JsonRpcResponse::success(id, e.to_call_result()),
+        };
+    }
+
+    let tool = match state.find_tool(client, tool_name) {
         Some(t) => t,
         None => {
             return JsonRpcResponse::error(id, -32602, format!("Tool not found: {}", tool_name));
         }
     };
 
-    tracing::info!(tool = %tool_name, "Executing tool");
+    let arguments = state.ctx.tool_defaults().apply(tool_name, arguments);
+    if let Err(e) = tool.validate_input(&arguments) {
+        tracing::debug!(tool = %tool_name, error = %e, "Rejected tool arguments");
+        return JsonRpcResponse::rejected(id, e);
+    }
+
+    let user = client.and_then(|c| c.principal.as_ref()).map(|p| p.user.as_str());
+    tracing::info!(tool = %tool_name, user, "Executing tool");
 
-    match tool.execute(&state.ctx, arguments).await {
-        Ok(result) => JsonRpcResponse::success(
-            id,
-            serde_json::json!({
-                "content": [{
-                    "type": "text",
-                    "text": result
-                }]
-            }),
-        ),
+    let (notify, forwarder) = session.notifier(tap);
+    let log_sink = LogSink::new(session.log_level.clone(), notify.clone());
+    let mut ctx = state.tool_context(client);
+    ctx.client = Arc::new(ctx.client.with_request_counter(requests));
+    if let Some(token) = params.get("_meta").and_then(|m| m.get("progressToken")) {
+        ctx = ctx.with_progress(progress_sender(token.clone(), notify.clone()));
+    }
+    drop(notify);
+    let outcome = log_sink
+        .scope(tool.execute(&ctx, arguments))
+        .instrument(telemetry::tool_span(tool_name))
+        .await;
+    drop(ctx);
+    // Deliver any log and progress notifications before the result.
+    let _ = forwarder.await;
+
+    match outcome {
+        Ok(output) => JsonRpcResponse::success(id, output.to_call_result()),
         Err(e) => {
-            tracing::error!(tool = %tool_name, error = %e, "Tool execution failed");
-            JsonRpcResponse::error(id, -32000, format!("Tool error: {}", e))
+            tracing::error!(tool = %tool_name, error = %e, code = e.code(), "Tool execution failed");
+            JsonRpcResponse::success(id, e.to_call_result())
         }
     }
 }
 
+/// Turn partial results reported by a tool into `notifications/progress`.
+fn progress_sender(
+    token: serde_json::Value,
+    notify: mpsc::UnboundedSender<serde_json::Value>,
+) -> crate::tools::ProgressSender {
+    let (tx, mut rx) = mpsc::unbounded_channel::<serde_json::Value>();
+    tokio::spawn(async move {
+        let mut progress = 0_u64;
+        while rx.recv().await.is_some() {
+            progress += 1;
+            let _ = notify.send(serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/progress",
+                "params": {"progressToken": token, "progress": progress}
+            }));
+        }
+    });
+    tx
+}
+
+/// Resolve the user behind an OAuth grant.
+///
+/// Returns `None` when the token must be refused: users sign in but the
+/// token names no user, or an account that no longer exists. With accounts
+/// the request's scopes are those on the token that the account still
+/// holds; with an OIDC provider they are the token's own.
+#[allow(clippy::option_option)]
+fn principal_for(state: &HttpState, grant: &AccessGrant) -> Option<Option<Principal>> {
+    if state.accounts.is_none() && state.oidc.is_none() {
+        return Some(None);
+    }
+    let user = grant.subject.as_deref()?;
+    let mut scopes = Scopes::parse(&grant.scope);
+    if let Some(ref accounts) = state.accounts {
+        scopes = scopes.allowed_by(&accounts.scopes(user)?);
+    }
+    Some(Some(Principal { user: user.to_owned(), scopes }))
+}
+
+/// The client an OAuth access token was issued to, if the token is valid
+/// and its user (when users sign in) may still use it.
+async fn oauth_identity(state: &HttpState, token: &str) -> Option<ClientIdentity> {
+    let oauth_store = state.oauth_store.as_ref()?;
+    let grant = oauth_store.access_grant(token).await?;
+    let principal = principal_for(state, &grant)?;
+    tracing::info!(client_id = %grant.client_id, "OAuth token validated");
+    let client_name = oauth_store.get_client(&grant.client_id).await.and_then(|c| c.client_name);
+    Some(ClientIdentity { client_id: grant.client_id, client_name, principal })
+}
+
 async fn auth_middleware(
     State(state): State<Arc<HttpState>>,
     headers: HeaderMap,
-    request: axum::extract::Request,
+    mut request: axum::extract::Request,
     next: axum::middleware::Next,
 ) -> Response {
     // If no token configured, skip auth
@@ -577,9 +1407,10 @@ async fn auth_middleware(
         return next.run(request).await;
     };
 
-    // Public endpoints that don't need auth
+    // Public endpoints that don't need auth. `POST /` runs MCP requests like
+    // `POST /mcp`, so only the health check at `/` is open.
     let path = request.uri().path();
-    if path == "/"
+    if (path == "/" && request.method() == Method::GET)
         || path == "/health"
         || path == "/ready"
         || path == "/.well-known/mcp.json"
@@ -590,35 +1421,32 @@ async fn auth_middleware(
         || path == "/register"
         || path == "/authorize"
         || path == "/token"
+        || path == "/revoke"
+        || path == CALLBACK_PATH
     {
         return next.run(request).await;
     }
 
-    // Check Authorization header - static token first
-    if let Some(auth_header) = headers.get("Authorization") {
-        if let Ok(auth_str) = auth_header.to_str() {
-            if auth_str.starts_with("Bearer ") {
-                let bearer = &auth_str[7..];
-
-                // Check static token
-                if bearer == expected_token {
-                    return next.run(request).await;
-                }
-
-                // Check OAuth access token
-                if let Some(ref oauth_store) = state.oauth_store {
-                    if let Some(client_id) = oauth_store.validate_access_token(bearer).await {
-                        tracing::info!(client_id = %client_id, "OAuth token validated");
-                        return next.run(request).await;
-                    }
-                }
-            }
+    // The Authorization header, else `?token=` for clients that cannot set
+    // headers (EventSource, browser WebSockets). Both accept the static token
+    // and OAuth access tokens.
+    let bearer = headers
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(str::to_owned);
+    let query_token = request.uri().query().and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+    });
+    for token in [bearer, query_token].into_iter().flatten() {
+        if token == *expected_token {
+            request.extensions_mut().insert(StaticToken);
+            return next.run(request).await;
         }
-    }
-
-    // Also check query param 'token' for SSE (EventSource doesn't support headers easily)
-    if let Some(query) = request.uri().query() {
-        if query.contains(&format!("token={}", expected_token)) {
+        if let Some(client) = oauth_identity(&state, &token).await {
+            request.extensions_mut().insert(client);
             return next.run(request).await;
         }
     }
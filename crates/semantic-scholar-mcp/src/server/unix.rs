@@ -0,0 +1,92 @@
+//! Unix domain socket transport.
+//!
+//! Serves the same router as the HTTP transport (Streamable HTTP, legacy
+//! SSE and WebSocket) on a socket file instead of a TCP port, so several
+//! local clients can share one server without it listening on the network.
+//! Who may connect is decided by the file's permissions ([`SOCKET_MODE`]).
+
+use std::fs::{DirBuilder, Permissions};
+use std::io;
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::path::Path;
+
+use tokio::net::{UnixListener, UnixStream};
+
+/// Permissions of the socket file: read and write for owner and group.
+pub const SOCKET_MODE: u32 = 0o660;
+
+/// Listen on `path`.
+///
+/// A socket file left behind by a server that did not shut down cleanly is
+/// replaced; one that a running server still answers on is not. The socket
+/// is created in a private directory next to `path` and only moved into
+/// place once it has [`SOCKET_MODE`], so nobody else can connect in between.
+///
+/// # Errors
+///
+/// Returns an error if another server is listening on `path`, if `path`
+/// exists and is not a socket, or if the socket cannot be created.
+pub async fn bind(path: &Path) -> io::Result<UnixListener> {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => {
+            if UnixStream::connect(path).await.is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("{} is in use by another server", path.display()),
+                ));
+            }
+            tracing::debug!(path = %path.display(), "Removing stale socket");
+            std::fs::remove_file(path)?;
+        }
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let parent = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    // Short names: socket paths are limited to about 100 bytes.
+    let private = parent.join(format!(".{}", &uuid::Uuid::new_v4().simple().to_string()[..8]));
+    DirBuilder::new().mode(0o700).create(&private)?;
+    let staged = private.join("s");
+    let bound = UnixListener::bind(&staged).and_then(|listener| {
+        std::fs::set_permissions(&staged, Permissions::from_mode(SOCKET_MODE))?;
+        std::fs::rename(&staged, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&private);
+    bound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bind_replaces_stale_socket_only() {
+        let dir = std::env::temp_dir().join(format!("s2-unix-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mcp.sock");
+
+        let listener = bind(&path).await.unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, SOCKET_MODE);
+        // The staging directory is gone.
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        let error = bind(&path).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AddrInUse);
+
+        drop(listener);
+        bind(&path).await.unwrap();
+
+        let file = dir.join("not-a-socket");
+        std::fs::write(&file, "").unwrap();
+        assert_eq!(bind(&file).await.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+//! WebSocket transport.
+//!
+//! `GET /ws` upgrades to a WebSocket carrying one JSON-RPC message (or
+//! batch) per text frame in each direction, for clients that cannot keep an
+//! SSE stream and POSTs paired up reliably (browsers behind proxies).
+//!
+//! A connection is bound to a session, exactly like a Streamable HTTP
+//! client: requests go through the same dispatch, and notifications pushed
+//! to the session (log and progress messages, `tools/list_changed`) are
+//! forwarded as they happen, before the response they belong to. The
+//! upgrade response carries `Mcp-Session-Id`; `?sessionId=` picks a session
+//! up again after a reconnect. Browsers that cannot set `Authorization`
+//! pass the token (static or OAuth) as `?token=`.
+//!
+//! Browsers send cookies and `?token=` links along with any page's
+//! upgrade request, so an upgrade with an `Origin` other than the server's
+//! own (or one allowed with `--allowed-origin`) is refused.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        Extension, Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use futures::{SinkExt, StreamExt};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+
+use super::policy::ClientIdentity;
+use super::session::{BufferedEvent, Session};
+use super::transport::{
+    HttpState, JsonRpcMessage, JsonRpcResponse, MessageQuery, dispatch, resolve_session, run_batch,
+};
+
+/// Interval between pings that keep idle connections open through proxies.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Handle `GET /ws`.
+pub(super) async fn handle_ws(
+    State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
+    Query(query): Query<MessageQuery>,
+    client: Option<Extension<ClientIdentity>>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    if let Some(origin) = headers.get(header::ORIGIN)
+        && !origin_allowed(&state, &headers, origin.to_str().unwrap_or_default())
+    {
+        tracing::warn!(origin = ?origin, "Refused cross-origin WebSocket upgrade");
+        return (StatusCode::FORBIDDEN, "Cross-origin WebSocket upgrade refused").into_response();
+    }
+    let client = client.map(|Extension(c)| c);
+    let session =
+        match resolve_session(&state, &headers, query.session_id.as_deref(), client.as_ref()).await
+        {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                state.sessions.create_session(client.as_ref().map(ClientIdentity::tenant)).await
+            }
+            Err(response) => return response,
+        };
+    let session_header = session.id.to_header_value();
+    let mut res = upgrade.on_upgrade(move |socket| serve(socket, state, session, client));
+    res.headers_mut().insert("Mcp-Session-Id", session_header);
+    res
+}
+
+/// Whether a page served from `origin` may connect: the server's own origin
+/// (its base URL, or the `Host` it was reached at) or an allowed one.
+fn origin_allowed(state: &HttpState, headers: &HeaderMap, origin: &str) -> bool {
+    let origin = origin.trim_end_matches('/');
+    let Ok(parsed) = url::Url::parse(origin) else {
+        return false;
+    };
+    if state.allowed_origins.iter().any(|allowed| allowed.eq_ignore_ascii_case(origin)) {
+        return true;
+    }
+    let own = url::Url::parse(&state.base_url).map(|base| base.origin());
+    if own.is_ok_and(|own| own == parsed.origin()) {
+        return true;
+    }
+    let authority = match (parsed.host_str(), parsed.port()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (Some(host), None) => host.to_owned(),
+        (None, _) => return false,
+    };
+    headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|host| host.eq_ignore_ascii_case(&authority))
+}
+
+/// Whether a session event is a notification (responses are sent directly).
+fn is_notification(event: &BufferedEvent) -> bool {
+    serde_json::from_str::<serde_json::Value>(&event.data)
+        .is_ok_and(|v| v.get("method").is_some() && v.get("id").is_none())
+}
+
+/// Run one connection until the client closes it and every request it made
+/// has been answered.
+async fn serve(
+    socket: WebSocket,
+    state: Arc<HttpState>,
+    session: Arc<Session>,
+    client: Option<ClientIdentity>,
+) {
+    tracing::info!(session_id = %session.id, "WebSocket connected");
+    let (mut sink, mut frames) = socket.split();
+    let (replies, mut outgoing) = mpsc::unbounded_channel::<String>();
+
+    let mut events = session.subscribe();
+    let writer_session = Arc::clone(&session);
+    let writer = tokio::spawn(async move {
+        let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+        keepalive.tick().await;
+        let mut last_event = 0;
+        loop {
+            // Session events first: a request's notifications are broadcast
+            // before its response is queued, so they go out ahead of it.
+            let messages = tokio::select! {
+                biased;
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        last_event = last_event.max(event.id);
+                        if !is_notification(&event) {
+                            continue;
+                        }
+                        vec![Message::Text(event.data.into())]
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "WebSocket lagged, replaying from the session buffer");
+                        let missed = writer_session.get_events_after(last_event).await;
+                        last_event = missed.last().map_or(last_event, |e| e.id);
+                        missed
+                            .into_iter()
+                            .filter(is_notification)
+                            .map(|e| Message::Text(e.data.into()))
+                            .collect()
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                reply = outgoing.recv() => match reply {
+                    Some(text) => vec![Message::Text(text.into())],
+                    None => break,
+                },
+                _ = keepalive.tick() => vec![Message::Ping(Vec::new().into())],
+            };
+            for message in messages {
+                if sink.send(message).await.is_err() {
+                    return;
+                }
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    while let Some(frame) = frames.next().await {
+        let text = match frame {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => break,
+            // Pongs need no answer and axum answers pings itself.
+            Ok(_) => continue,
+        };
+        let message = match serde_json::from_str::<JsonRpcMessage>(&text) {
+            Ok(message) => message,
+            Err(e) => {
+                let error = JsonRpcResponse::error(None, -32700, format!("Parse error: {e}"));
+                let _ = replies.send(serde_json::to_string(&error).unwrap_or_default());
+                continue;
+            }
+        };
+        // Requests run concurrently, so a long tool call does not hold up
+        // the rest of the connection.
+        let (state, session, client, replies) =
+            (Arc::clone(&state), Arc::clone(&session), client.clone(), replies.clone());
+        tokio::spawn(async move {
+            let reply = match message {
+                JsonRpcMessage::Single(req) => dispatch(&state, &session, client.as_ref(), req)
+                    .await
+                    .and_then(|r| serde_json::to_string(&r).ok()),
+                JsonRpcMessage::Batch(items) => {
+                    run_batch(items, |req| dispatch(&state, &session, client.as_ref(), req))
+                        .await
+                        .map(|reply| reply.to_string())
+                }
+            };
+            if let Some(reply) = reply {
+                let _ = replies.send(reply);
+            }
+        });
+    }
+
+    // The writer stops once the requests still running have replied.
+    drop(replies);
+    let _ = writer.await;
+    tracing::info!(session_id = %session.id, "WebSocket disconnected");
+}
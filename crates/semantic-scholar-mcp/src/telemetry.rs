@@ -0,0 +1,145 @@
+//! OpenTelemetry trace export.
+//!
+//! The server always creates these spans; they are only exported when an
+//! OTLP endpoint is configured (see [`tracer_provider`] and [`layer`]):
+//! - `mcp.request`: one per JSON-RPC request (method, id, tool name)
+//! - `tool.execute`: one per tool run
+//! - `s2.request`: one per Semantic Scholar call (endpoint, cache hit,
+//!   status, attempts including retries)
+//!
+//! On the HTTP transport, a W3C `traceparent` header on the incoming request
+//! makes its spans part of the caller's trace.
+
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use opentelemetry_otlp::{Protocol, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Service name reported when `OTEL_SERVICE_NAME` is not set.
+pub const DEFAULT_SERVICE_NAME: &str = "semantic-scholar-mcp";
+
+/// Export spans in batches to the OTLP/HTTP collector at `endpoint` (e.g.
+/// `http://localhost:4318`), as JSON.
+///
+/// Call [`SdkTracerProvider::shutdown`] before exiting to flush the last
+/// batch.
+///
+/// # Errors
+///
+/// Returns an error if `endpoint` is not a valid URL.
+pub fn tracer_provider(endpoint: &str, service_name: &str) -> anyhow::Result<SdkTracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_protocol(Protocol::HttpJson)
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+    let resource = Resource::builder().with_service_name(service_name.to_owned()).build();
+    Ok(SdkTracerProvider::builder().with_batch_exporter(exporter).with_resource(resource).build())
+}
+
+/// A tracing layer exporting this crate's and the API client's spans (and the
+/// info-level events inside them) through `provider`.
+#[must_use]
+pub fn layer<S>(provider: &SdkTracerProvider) -> impl Layer<S> + use<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let tracer = provider.tracer(DEFAULT_SERVICE_NAME);
+    tracing_opentelemetry::layer().with_tracer(tracer).with_filter(
+        Targets::new()
+            .with_target("semantic_scholar_mcp", tracing::Level::INFO)
+            .with_target("semantic_scholar_client", tracing::Level::INFO),
+    )
+}
+
+/// Reads `traceparent`/`tracestate` from HTTP headers.
+struct HeaderExtractor<'a>(&'a http::HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(http::HeaderName::as_str).collect()
+    }
+}
+
+/// Make `span` a child of the remote span named by `headers`, if any.
+///
+/// Does nothing when no exporter is installed or the header is missing or
+/// malformed.
+pub fn set_remote_parent(span: &tracing::Span, headers: &http::HeaderMap) {
+    let context = TraceContextPropagator::new().extract(&HeaderExtractor(headers));
+    if context.span().span_context().is_remote() {
+        let _ = span.set_parent(context);
+    }
+}
+
+/// Span for one JSON-RPC request.
+#[must_use]
+pub fn request_span(
+    method: &str,
+    id: Option<&serde_json::Value>,
+    params: &serde_json::Value,
+) -> tracing::Span {
+    let tool =
+        if method == "tools/call" { params.get("name").and_then(|n| n.as_str()) } else { None };
+    tracing::info_span!(
+        "mcp.request",
+        rpc.method = method,
+        rpc.id = id.map(ToString::to_string),
+        mcp.tool = tool,
+    )
+}
+
+/// Span for one HTTP request to the MCP endpoint, parented by its
+/// `traceparent` header.
+#[must_use]
+pub fn http_span(headers: &http::HeaderMap) -> tracing::Span {
+    let span = tracing::info_span!("mcp.http", otel.kind = "server");
+    set_remote_parent(&span, headers);
+    span
+}
+
+/// Span for one tool run.
+#[must_use]
+pub fn tool_span(tool: &str) -> tracing::Span {
+    tracing::info_span!("tool.execute", mcp.tool = tool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_traceparent_becomes_parent() {
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry().with(layer(&provider));
+        tracing::subscriber::with_default(subscriber, || {
+            let mut headers = http::HeaderMap::new();
+            headers.insert(
+                "traceparent",
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap(),
+            );
+            let span = request_span("ping", None, &serde_json::Value::Null);
+            set_remote_parent(&span, &headers);
+            let trace_id = span.context().span().span_context().trace_id();
+            assert_eq!(trace_id.to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+
+            // Without a header the span starts its own trace.
+            let span = request_span("ping", None, &serde_json::Value::Null);
+            set_remote_parent(&span, &http::HeaderMap::new());
+            let trace_id = span.context().span().span_context().trace_id();
+            assert_ne!(trace_id.to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        });
+    }
+}
@@ -5,7 +5,10 @@ use std::collections::{HashMap, HashSet};
 use regex::Regex;
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::formatters;
@@ -22,6 +25,14 @@ impl McpTool for PearlGrowingTool {
         "pearl_growing"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Advanced
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Expand literature search using pearl growing methodology. \
          Starts with seed papers and iteratively discovers related papers \
@@ -29,44 +40,36 @@ impl McpTool for PearlGrowingTool {
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<PearlGrowingInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "seedPaperIds": {
+                "seed_papers": {"type": "array", "items": {"type": "string"}},
+                "iterations": {"type": "integer"},
+                "strategy": {"type": "string"},
+                "total_papers": {"type": "integer"},
+                "growth_log": {
                     "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Initial seed paper IDs"
-                },
-                "iterations": {
-                    "type": "integer",
-                    "default": 2,
-                    "minimum": 1,
-                    "maximum": 3
-                },
-                "strategy": {
-                    "type": "string",
-                    "enum": ["keywords", "authors", "citations", "all"],
-                    "default": "all"
-                },
-                "max_papers_per_iteration": {
-                    "type": "integer",
-                    "default": 50
-                },
-                "deduplicate": {
-                    "type": "boolean",
-                    "default": true
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "iteration": {"type": "integer"},
+                            "strategy": {"type": "string"},
+                            "new": {"type": "integer"}
+                        }
+                    }
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "papers": formatters::compact_paper_list_schema(),
+                "error": {"type": "string"}
             },
-            "required": ["seedPaperIds"]
+            "required": ["seed_papers", "total_papers", "papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: PearlGrowingInput = serde_json::from_value(input)?;
 
         // Fetch seed papers
@@ -77,13 +80,18 @@ impl McpTool for PearlGrowingTool {
             .map_err(ToolError::from)?;
 
         if seed_results.is_empty() {
+            let structured = json!({
+                "error": "No valid seed papers found",
+                "seed_papers": params.seed_paper_ids,
+                "total_papers": 0,
+                "papers": []
+            });
             return match params.response_format {
-                ResponseFormat::Markdown => {
-                    Ok("# Pearl Growing\n\n**Error:** No valid seed papers found.".to_string())
-                }
-                ResponseFormat::Json => {
-                    Ok(json!({"error": "No valid seed papers found", "seeds": []}).to_string())
-                }
+                ResponseFormat::Markdown => Ok(ToolOutput::new(
+                    "# Pearl Growing\n\n**Error:** No valid seed papers found.",
+                    structured,
+                )),
+                ResponseFormat::Json => ToolOutput::json(structured),
             };
         }
 
@@ -222,6 +230,16 @@ impl McpTool for PearlGrowingTool {
         let mut final_papers: Vec<_> = all_papers.into_values().collect();
         final_papers.sort_by_key(|p| std::cmp::Reverse(p.citations()));
 
+        let compact: Vec<_> = final_papers.iter().map(formatters::compact_paper).collect();
+        let structured = json!({
+            "seed_papers": params.seed_paper_ids,
+            "iterations": params.iterations,
+            "strategy": format!("{:?}", params.strategy),
+            "total_papers": final_papers.len(),
+            "growth_log": growth_log,
+            "papers": compact
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -257,19 +275,9 @@ impl McpTool for PearlGrowingTool {
                         .push_str(&format!("\n*... and {} more papers*", final_papers.len() - 30));
                 }
 
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact: Vec<_> = final_papers.iter().map(formatters::compact_paper).collect();
-                Ok(serde_json::to_string(&json!({
-                    "seed_papers": params.seed_paper_ids,
-                    "iterations": params.iterations,
-                    "strategy": format!("{:?}", params.strategy),
-                    "total_papers": final_papers.len(),
-                    "growth_log": growth_log,
-                    "papers": compact
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -309,7 +317,7 @@ fn extract_keywords(papers: &[crate::models::Paper]) -> Vec<String> {
     }
 
     let mut sorted: Vec<_> = word_counts.into_iter().collect();
-    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    sorted.sort_by_key(|b| std::cmp::Reverse(b.1));
     sorted.into_iter().take(10).map(|(w, _)| w).collect()
 }
 
@@ -325,7 +333,7 @@ fn extract_author_ids(papers: &[crate::models::Paper]) -> Vec<String> {
     }
 
     let mut sorted: Vec<_> = author_counts.into_iter().collect();
-    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    sorted.sort_by_key(|b| std::cmp::Reverse(b.1));
     sorted.into_iter().take(5).map(|(id, _)| id).collect()
 }
 
@@ -338,38 +346,45 @@ impl McpTool for OrcidAuthorLookupTool {
         "orcid_author_lookup"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Advanced
+    }
+
     fn description(&self) -> &'static str {
         "Look up an author by their ORCID identifier. \
          ORCID provides persistent digital identifier for researchers."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<OrcidAuthorLookupInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "orcid": {
-                    "type": "string",
-                    "description": "ORCID iD (e.g., '0000-0002-1825-0097')"
-                },
-                "includePapers": {
-                    "type": "boolean",
-                    "default": false
-                },
-                "maxPapers": {
-                    "type": "integer",
-                    "default": 100
+                "orcid": {"type": "string"},
+                "found": {"type": "boolean"},
+                "author": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string"},
+                        "name": {"type": "string"},
+                        "affiliations": {"type": "array", "items": {"type": "string"}},
+                        "citation_count": {"type": "integer"},
+                        "h_index": {"type": "integer"},
+                        "paper_count": {"type": "integer"}
+                    },
+                    "required": ["id", "name"]
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "papers": {"anyOf": [formatters::compact_paper_list_schema(), {"type": "null"}]},
+                "paper_count": {"type": ["integer", "null"]}
             },
-            "required": ["orcid"]
+            "required": ["orcid", "found", "author"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: OrcidAuthorLookupInput = serde_json::from_value(input)?;
 
         // Semantic Scholar accepts ORCID with the ORCID: prefix
@@ -414,6 +429,25 @@ impl McpTool for OrcidAuthorLookupTool {
             }
         }
 
+        let structured = json!({
+            "orcid": params.orcid,
+            "found": true,
+            "author": {
+                "id": author.author_id,
+                "name": author.name_or_default(),
+                "affiliations": author.affiliations,
+                "citation_count": author.citations(),
+                "h_index": author.h_index_value(),
+                "paper_count": author.papers()
+            },
+            "papers": if params.include_papers {
+                Some(papers.iter().map(formatters::compact_paper).collect::<Vec<_>>())
+            } else {
+                None
+            },
+            "paper_count": if params.include_papers { Some(papers.len()) } else { None }
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -449,26 +483,9 @@ impl McpTool for OrcidAuthorLookupTool {
                     }
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "orcid": params.orcid,
-                "found": true,
-                "author": {
-                    "id": author.author_id,
-                    "name": author.name_or_default(),
-                    "affiliations": author.affiliations,
-                    "citation_count": author.citations(),
-                    "h_index": author.h_index_value(),
-                    "paper_count": author.papers()
-                },
-                "papers": if params.include_papers {
-                    Some(papers.iter().map(formatters::compact_paper).collect::<Vec<_>>())
-                } else {
-                    None
-                },
-                "paper_count": if params.include_papers { Some(papers.len()) } else { None }
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
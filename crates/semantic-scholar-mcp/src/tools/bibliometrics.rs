@@ -7,7 +7,10 @@ use chrono::{Datelike, Utc};
 use futures::stream::{FuturesUnordered, StreamExt};
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::formatters;
@@ -25,35 +28,52 @@ impl McpTool for FieldWeightedImpactTool {
         "field_weighted_impact"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Bibliometrics
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Calculate approximate Field-Weighted Citation Impact (FWCI). \
          FWCI normalizes citations by field and year. 1.0 = average."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<FieldWeightedImpactInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperIds": {
+                "methodology_note": {"type": "string"},
+                "results": {
                     "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Paper IDs to analyze"
-                },
-                "baselineSampleSize": {
-                    "type": "integer",
-                    "default": 100
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paperId": {"type": "string"},
+                            "title": {"type": "string"},
+                            "year": {"type": "integer"},
+                            "citations": {"type": "integer"},
+                            "fields": {"type": "array", "items": {"type": "string"}},
+                            "avg_baseline": {"type": "number"},
+                            "fwci": {"type": ["number", "null"]},
+                            "interpretation": {"type": "string"},
+                            "error": {"type": "string"}
+                        },
+                        "required": ["paperId", "title", "fwci"]
+                    }
                 }
             },
-            "required": ["paperIds"]
+            "required": ["methodology_note", "results"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: FieldWeightedImpactInput = serde_json::from_value(input)?;
 
         let papers = ctx
@@ -124,6 +144,11 @@ impl McpTool for FieldWeightedImpactTool {
             }));
         }
 
+        let structured = json!({
+            "methodology_note": "Baselines estimated from Semantic Scholar",
+            "results": results
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = String::from(
@@ -149,12 +174,9 @@ impl McpTool for FieldWeightedImpactTool {
                     }
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "methodology_note": "Baselines estimated from Semantic Scholar",
-                "results": results
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -182,12 +204,8 @@ async fn get_field_baseline(ctx: &ToolContext, field: &str, year: i32, sample_si
         return 1.0;
     };
 
-    let mut citations: Vec<i32> = search_result
-        .data
-        .iter()
-        .take(sample_size as usize)
-        .map(|p| p.citations())
-        .collect();
+    let mut citations: Vec<i32> =
+        search_result.data.iter().take(sample_size as usize).map(|p| p.citations()).collect();
 
     if citations.is_empty() {
         return 1.0;
@@ -213,36 +231,50 @@ impl McpTool for HighlyCitedPapersTool {
         "highly_cited_papers"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Bibliometrics
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Identify highly cited papers (top percentile by field/year). \
          Based on Essential Science Indicators methodology."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<HighlyCitedPapersInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperIds": {
+                "percentile_threshold": {"type": "number"},
+                "results": {
                     "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Paper IDs to evaluate"
-                },
-                "percentileThreshold": {
-                    "type": "number",
-                    "default": 1.0,
-                    "description": "Top X percentile (1.0 = top 1%)"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paperId": {"type": "string"},
+                            "title": {"type": "string"},
+                            "year": {"type": "integer"},
+                            "primary_field": {"type": "string"},
+                            "citations": {"type": "integer"},
+                            "threshold": {"type": "integer"},
+                            "is_highly_cited": {"type": "boolean"}
+                        },
+                        "required": ["paperId", "citations", "threshold", "is_highly_cited"]
+                    }
                 }
             },
-            "required": ["paperIds"]
+            "required": ["percentile_threshold", "results"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: HighlyCitedPapersInput = serde_json::from_value(input)?;
 
         let papers = ctx
@@ -295,6 +327,11 @@ impl McpTool for HighlyCitedPapersTool {
         let highly_count =
             results.iter().filter(|r| r["is_highly_cited"].as_bool().unwrap_or(false)).count();
 
+        let structured = json!({
+            "percentile_threshold": params.percentile_threshold,
+            "results": results
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -320,12 +357,9 @@ impl McpTool for HighlyCitedPapersTool {
                     ));
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "percentile_threshold": params.percentile_threshold,
-                "results": results
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -397,30 +431,44 @@ impl McpTool for CitationHalfLifeTool {
         "citation_half_life"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Bibliometrics
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Calculate citation half-life (median age of citations). \
          Low = rapidly obsoleting; high = enduring relevance."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<CitationHalfLifeInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperId": {
-                    "type": "string",
-                    "description": "Paper ID to analyze"
+                "paperId": {"type": "string"},
+                "title": {"type": "string"},
+                "year": {"type": "integer"},
+                "total_citations": {"type": "integer"},
+                "citations_analyzed": {"type": "integer"},
+                "citation_half_life_years": {"type": ["number", "null"]},
+                "age_distribution": {
+                    "type": "object",
+                    "additionalProperties": {"type": "integer"}
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "interpretation": {"type": ["string", "null"]}
             },
-            "required": ["paperId"]
+            "required": ["paperId", "year", "citations_analyzed", "citation_half_life_years"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: CitationHalfLifeInput = serde_json::from_value(input)?;
 
         let papers = ctx
@@ -491,6 +539,17 @@ impl McpTool for CitationHalfLifeTool {
             })
         };
 
+        let structured = json!({
+            "paperId": paper.paper_id,
+            "title": paper.title_or_default(),
+            "year": pub_year,
+            "total_citations": paper.citations(),
+            "citations_analyzed": ages.len(),
+            "citation_half_life_years": half_life.map(|h| (h * 10.0).round() / 10.0),
+            "age_distribution": age_distribution,
+            "interpretation": half_life.map(|h| format!("50% of citations within {:.1} years", h))
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -518,18 +577,9 @@ impl McpTool for CitationHalfLifeTool {
                     output.push_str(&format!("- {} years: {} ({:.1}%)\n", bucket, count, pct));
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "paperId": paper.paper_id,
-                "title": paper.title_or_default(),
-                "year": pub_year,
-                "total_citations": paper.citations(),
-                "citations_analyzed": ages.len(),
-                "citation_half_life_years": half_life.map(|h| (h * 10.0).round() / 10.0),
-                "age_distribution": age_distribution,
-                "interpretation": half_life.map(|h| format!("50% of citations within {:.1} years", h))
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -543,42 +593,52 @@ impl McpTool for CocitationAnalysisTool {
         "cocitation_analysis"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Bibliometrics
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Find conceptually related papers via co-citation analysis. \
          Papers frequently cited together are likely related."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<CocitationAnalysisInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperId": {
-                    "type": "string",
-                    "description": "Focal paper ID"
-                },
-                "minCocitations": {
-                    "type": "integer",
-                    "default": 5
-                },
-                "maxCitingPapers": {
-                    "type": "integer",
-                    "default": 100
-                },
-                "maxResults": {
-                    "type": "integer",
-                    "default": 50
+                "focal_paper": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string"},
+                        "title": {"type": "string"}
+                    }
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                "citing_papers_analyzed": {"type": "integer"},
+                "cocited_papers": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paper": formatters::compact_paper_schema(),
+                            "cocitation_count": {"type": "integer"},
+                            "cocitation_strength": {"type": "number"}
+                        }
+                    }
                 }
             },
-            "required": ["paperId"]
+            "required": ["focal_paper", "citing_papers_analyzed", "cocited_papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: CocitationAnalysisInput = serde_json::from_value(input)?;
 
         // Get focal paper
@@ -631,7 +691,7 @@ impl McpTool for CocitationAnalysisTool {
             .into_iter()
             .filter(|(_, count)| *count >= params.min_cocitations)
             .collect();
-        filtered.sort_by(|a, b| b.1.cmp(&a.1));
+        filtered.sort_by_key(|b| std::cmp::Reverse(b.1));
         filtered.truncate(params.max_results as usize);
 
         // Get details for top co-cited papers
@@ -659,6 +719,15 @@ impl McpTool for CocitationAnalysisTool {
             }
         }
 
+        let structured = json!({
+            "focal_paper": {
+                "id": focal_paper.paper_id,
+                "title": focal_paper.title_or_default()
+            },
+            "citing_papers_analyzed": citations.data.len(),
+            "cocited_papers": cocited_papers
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let title = focal_paper.title_or_default();
@@ -687,16 +756,9 @@ impl McpTool for CocitationAnalysisTool {
                     }
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "focal_paper": {
-                    "id": focal_paper.paper_id,
-                    "title": focal_paper.title_or_default()
-                },
-                "citing_papers_analyzed": citations.data.len(),
-                "cocited_papers": cocited_papers
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -710,42 +772,53 @@ impl McpTool for BibliographicCouplingTool {
         "bibliographic_coupling"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Bibliometrics
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Find methodologically similar papers via bibliographic coupling. \
          Papers sharing many references likely use similar methods."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<BibliographicCouplingInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperId": {
-                    "type": "string",
-                    "description": "Focal paper ID"
-                },
-                "minSharedRefs": {
-                    "type": "integer",
-                    "default": 3
-                },
-                "maxRefsToCheck": {
-                    "type": "integer",
-                    "default": 50
+                "focal_paper": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string"},
+                        "title": {"type": "string"},
+                        "reference_count": {"type": "integer"}
+                    }
                 },
-                "maxResults": {
-                    "type": "integer",
-                    "default": 50
+                "coupled_papers": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paper": formatters::compact_paper_schema(),
+                            "shared_references": {"type": "integer"},
+                            "coupling_strength": {"type": "number"}
+                        }
+                    }
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "error": {"type": "string"}
             },
-            "required": ["paperId"]
+            "required": ["focal_paper", "coupled_papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: BibliographicCouplingInput = serde_json::from_value(input)?;
 
         // Get focal paper
@@ -774,7 +847,19 @@ impl McpTool for BibliographicCouplingTool {
             .collect();
 
         if focal_ref_ids.is_empty() {
-            return Ok(json!({"error": "No references found for focal paper"}).to_string());
+            let structured = json!({
+                "error": "No references found for focal paper",
+                "focal_paper": {
+                    "id": focal_paper.paper_id,
+                    "title": focal_paper.title_or_default(),
+                    "reference_count": 0
+                },
+                "coupled_papers": []
+            });
+            return Ok(ToolOutput::new(
+                json!({"error": "No references found for focal paper"}).to_string(),
+                structured,
+            ));
         }
 
         // For each reference, find other papers that cite it
@@ -784,9 +869,7 @@ impl McpTool for BibliographicCouplingTool {
         for ref_id in focal_ref_ids.iter().take(params.max_refs_to_check as usize) {
             let client = Arc::clone(&ctx.client);
             let rid = ref_id.clone();
-            futures.push(async move {
-                client.get_citations(&rid, 0, 100, &["paperId"]).await
-            });
+            futures.push(async move { client.get_citations(&rid, 0, 100, &["paperId"]).await });
         }
 
         while let Some(result) = futures.next().await {
@@ -806,7 +889,7 @@ impl McpTool for BibliographicCouplingTool {
             .into_iter()
             .filter(|(_, count)| *count >= params.min_shared_refs)
             .collect();
-        filtered.sort_by(|a, b| b.1.cmp(&a.1));
+        filtered.sort_by_key(|b| std::cmp::Reverse(b.1));
         filtered.truncate(params.max_results as usize);
 
         // Get details
@@ -834,6 +917,15 @@ impl McpTool for BibliographicCouplingTool {
             }
         }
 
+        let structured = json!({
+            "focal_paper": {
+                "id": focal_paper.paper_id,
+                "title": focal_paper.title_or_default(),
+                "reference_count": focal_ref_ids.len()
+            },
+            "coupled_papers": coupled_papers
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let title = focal_paper.title_or_default();
@@ -862,16 +954,9 @@ impl McpTool for BibliographicCouplingTool {
                     }
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "focal_paper": {
-                    "id": focal_paper.paper_id,
-                    "title": focal_paper.title_or_default(),
-                    "reference_count": focal_ref_ids.len()
-                },
-                "coupled_papers": coupled_papers
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -885,46 +970,56 @@ impl McpTool for HotPapersTool {
         "hot_papers"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Bibliometrics
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Detect 'hot papers' with accelerating citation velocity. \
          Identifies trending research by analyzing citation patterns."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<HotPapersInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Search query for candidate papers"
-                },
-                "timeWindowMonths": {
-                    "type": "integer",
-                    "default": 24
-                },
-                "minRecentCitations": {
-                    "type": "integer",
-                    "default": 10
-                },
-                "maxPapers": {
-                    "type": "integer",
-                    "default": 50
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Minimum publication year"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                "query": {"type": "string"},
+                "timeWindowMonths": {"type": "integer"},
+                "papers_analyzed": {"type": "integer"},
+                "hot_threshold_rank": {"type": "integer"},
+                "results": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paper": formatters::compact_paper_schema(),
+                            "metrics": {
+                                "type": "object",
+                                "properties": {
+                                    "total_citations": {"type": "integer"},
+                                    "years_since_publication": {"type": "integer"},
+                                    "citation_velocity": {"type": "number"},
+                                    "is_hot": {"type": "boolean"},
+                                    "velocity_rank": {"type": "integer"}
+                                }
+                            }
+                        }
+                    }
                 }
             },
-            "required": ["query"]
+            "required": ["query", "papers_analyzed", "results"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: HotPapersInput = serde_json::from_value(input)?;
 
         let current_year = Utc::now().year();
@@ -994,6 +1089,14 @@ impl McpTool for HotPapersTool {
             item["metrics"]["velocity_rank"] = json!(i + 1);
         }
 
+        let structured = json!({
+            "query": params.query,
+            "timeWindowMonths": params.time_window_months,
+            "papers_analyzed": hot_papers_data.len(),
+            "hot_threshold_rank": hot_threshold,
+            "results": hot_papers_data
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -1024,15 +1127,9 @@ impl McpTool for HotPapersTool {
                     output.push_str(&format!("**Velocity:** {:.2} citations/year\n\n", velocity));
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "query": params.query,
-                "timeWindowMonths": params.time_window_months,
-                "papers_analyzed": hot_papers_data.len(),
-                "hot_threshold_rank": hot_threshold,
-                "results": hot_papers_data
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
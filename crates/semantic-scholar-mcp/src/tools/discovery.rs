@@ -2,7 +2,10 @@
 
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::formatters;
@@ -20,56 +23,35 @@ impl McpTool for ExhaustiveSearchTool {
         "exhaustive_search"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Discovery
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Search for papers with automatic pagination to retrieve ALL matching results. \
          Use for systematic reviews where comprehensive coverage is needed."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<ExhaustiveSearchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Search query (e.g., 'transformer attention mechanisms')"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Minimum publication year"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "Maximum publication year"
-                },
-                "fieldsOfStudy": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Filter by fields (e.g., ['Computer Science'])"
-                },
-                "minCitations": {
-                    "type": "integer",
-                    "description": "Minimum citation count"
-                },
-                "openAccessOnly": {
-                    "type": "boolean",
-                    "default": false
-                },
-                "maxResults": {
-                    "type": "integer",
-                    "default": 100,
-                    "description": "Maximum papers to return (-1 for unlimited)"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "total": {"type": "integer"},
+                "papers": formatters::compact_paper_list_schema()
             },
-            "required": ["query"]
+            "required": ["total", "papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: ExhaustiveSearchInput = serde_json::from_value(input)?;
 
         let field_list: Vec<&str> = if params.include_embeddings {
@@ -135,12 +117,19 @@ impl McpTool for ExhaustiveSearchTool {
             offset = result.next.unwrap_or(offset + limit);
         }
 
+        let compact = all_papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
+        let structured = json!({
+            "total": all_papers.len(),
+            "papers": compact
+        });
+
         // Format output
         match params.response_format {
-            ResponseFormat::Markdown => Ok(formatters::format_papers_markdown(&all_papers)),
+            ResponseFormat::Markdown => {
+                Ok(ToolOutput::new(formatters::format_papers_markdown(&all_papers), structured))
+            }
             ResponseFormat::Json => {
-                let compact = all_papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&compact)?)
+                Ok(ToolOutput::new(serde_json::to_string_pretty(&compact)?, structured))
             }
         }
     }
@@ -155,40 +144,31 @@ impl McpTool for RecommendationsTool {
         "recommendations"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Discovery
+    }
+
     fn description(&self) -> &'static str {
         "Get paper recommendations based on seed papers. Uses SPECTER2 embeddings \
          for semantic similarity."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<RecommendationsInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "positivePaperIds": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Paper IDs to use as positive examples"
-                },
-                "negativePaperIds": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Paper IDs to avoid"
-                },
-                "limit": {
-                    "type": "integer",
-                    "default": 100
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "total": {"type": "integer"},
+                "papers": formatters::compact_paper_list_schema()
             },
-            "required": ["positivePaperIds"]
+            "required": ["total", "papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: RecommendationsInput = serde_json::from_value(input)?;
 
         let papers = ctx
@@ -202,11 +182,18 @@ impl McpTool for RecommendationsTool {
             .await
             .map_err(ToolError::from)?;
 
+        let compact = papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
+        let structured = json!({
+            "total": papers.len(),
+            "papers": compact
+        });
+
         match params.response_format {
-            ResponseFormat::Markdown => Ok(formatters::format_papers_markdown(&papers)),
+            ResponseFormat::Markdown => {
+                Ok(ToolOutput::new(formatters::format_papers_markdown(&papers), structured))
+            }
             ResponseFormat::Json => {
-                let compact = papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&compact)?)
+                Ok(ToolOutput::new(serde_json::to_string_pretty(&compact)?, structured))
             }
         }
     }
@@ -221,56 +208,38 @@ impl McpTool for CitationSnowballTool {
         "citation_snowball"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Discovery
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Traverse the citation network from seed papers. Forward snowballing finds \
          papers that cite your seeds; backward finds papers your seeds cite."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<CitationSnowballInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "seedPaperIds": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Starting paper IDs"
-                },
-                "direction": {
-                    "type": "string",
-                    "enum": ["citations", "references", "both"],
-                    "default": "both"
-                },
-                "depth": {
-                    "type": "integer",
-                    "default": 1,
-                    "minimum": 1,
-                    "maximum": 3
-                },
-                "maxPerPaper": {
-                    "type": "integer",
-                    "default": 100
-                },
-                "minCitations": {
-                    "type": "integer",
-                    "default": 0,
-                    "minimum": 0,
-                    "description": "Minimum citations for included papers"
-                },
-                "deduplicate": {
-                    "type": "boolean",
-                    "default": true
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "seeds": {"type": "array", "items": {"type": "string"}},
+                "direction": {"type": "string", "enum": ["citations", "references", "both"]},
+                "depth": {"type": "integer"},
+                "count": {"type": "integer"},
+                "papers": formatters::compact_paper_list_schema()
             },
-            "required": ["seedPaperIds"]
+            "required": ["seeds", "direction", "depth", "count", "papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: CitationSnowballInput = serde_json::from_value(input)?;
 
         use crate::models::SearchDirection;
@@ -290,6 +259,7 @@ impl McpTool for CitationSnowballTool {
             if current_depth >= params.depth {
                 continue;
             }
+            let found_before = papers.len();
 
             // Get citations (forward)
             if matches!(params.direction, SearchDirection::Citations | SearchDirection::Both) {
@@ -346,8 +316,24 @@ impl McpTool for CitationSnowballTool {
                     }
                 }
             }
+
+            ctx.report_progress(json!({
+                "expanded": paper_id,
+                "depth": current_depth + 1,
+                "found": papers.len(),
+                "papers": papers[found_before..].iter().map(formatters::compact_paper).collect::<Vec<_>>()
+            }));
         }
 
+        let compact = papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
+        let structured = json!({
+            "seeds": params.seed_paper_ids,
+            "direction": params.direction,
+            "depth": params.depth,
+            "count": papers.len(),
+            "papers": compact
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -362,18 +348,9 @@ impl McpTool for CitationSnowballTool {
                     papers.len()
                 );
                 output.push_str(&formatters::format_papers_markdown(&papers));
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact = papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&json!({
-                    "seeds": params.seed_paper_ids,
-                    "direction": params.direction,
-                    "depth": params.depth,
-                    "count": papers.len(),
-                    "papers": compact
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -387,69 +364,36 @@ impl McpTool for BulkBooleanSearchTool {
         "bulk_boolean_search"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Discovery
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Search for papers using boolean query syntax. Supports +term (AND), -term (NOT), \
          |term (OR), \"phrase\", term*, term~N (fuzzy). Can retrieve up to 10M papers."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<BulkBooleanSearchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Boolean query: +term -term |term \"phrase\" term* term~2"
-                },
-                "fieldsOfStudy": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Filter by fields of study"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Minimum publication year"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "Maximum publication year"
-                },
-                "minCitations": {
-                    "type": "integer",
-                    "description": "Minimum citation count"
-                },
-                "venue": {
-                    "type": "string",
-                    "description": "Filter by venue name"
-                },
-                "publicationTypes": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "JournalArticle, Conference, Review, etc."
-                },
-                "openAccessOnly": {
-                    "type": "boolean",
-                    "default": false
-                },
-                "sort": {
-                    "type": "string",
-                    "description": "Sort: citationCount:desc, publicationDate:asc, paperId:asc"
-                },
-                "maxResults": {
-                    "type": "integer",
-                    "default": 1000,
-                    "description": "Maximum papers to return"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "query": {"type": "string"},
+                "total": {"type": "integer"},
+                "papers": formatters::compact_paper_list_schema()
             },
-            "required": ["query"]
+            "required": ["query", "total", "papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: BulkBooleanSearchInput = serde_json::from_value(input)?;
 
         // Build filter parameters
@@ -508,6 +452,10 @@ impl McpTool for BulkBooleanSearchTool {
 
             let has_more = result.has_more();
             token = result.token;
+            ctx.report_progress(json!({
+                "fetched": all_papers.len() + result.data.len(),
+                "papers": result.data.iter().map(formatters::compact_paper).collect::<Vec<_>>()
+            }));
             all_papers.extend(result.data);
 
             if !has_more {
@@ -518,6 +466,13 @@ impl McpTool for BulkBooleanSearchTool {
         // Truncate to max_results
         all_papers.truncate(params.max_results as usize);
 
+        let compact = all_papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
+        let structured = json!({
+            "query": params.query,
+            "total": all_papers.len(),
+            "papers": compact
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -529,16 +484,9 @@ impl McpTool for BulkBooleanSearchTool {
                     all_papers.len()
                 );
                 output.push_str(&formatters::format_papers_markdown(&all_papers));
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact = all_papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&json!({
-                    "query": params.query,
-                    "total": all_papers.len(),
-                    "papers": compact
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -552,61 +500,57 @@ impl McpTool for SnippetSearchTool {
         "snippet_search"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Discovery
+    }
+
     fn description(&self) -> &'static str {
         "Search for text snippets within paper titles, abstracts, and body text. \
          Returns highlighted excerpts matching your query - useful for finding specific claims or methods."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<SnippetSearchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Plain text search query"
-                },
-                "paperIds": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Filter to specific papers (up to ~100)"
-                },
-                "authors": {
+                "query": {"type": "string"},
+                "total": {"type": "integer"},
+                "snippets": {
                     "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Filter by author names (fuzzy match, max 10)"
-                },
-                "fieldsOfStudy": {
-                    "type": "array",
-                    "items": {"type": "string"}
-                },
-                "yearStart": {
-                    "type": "integer"
-                },
-                "yearEnd": {
-                    "type": "integer"
-                },
-                "minCitations": {
-                    "type": "integer"
-                },
-                "venue": {
-                    "type": "string"
-                },
-                "limit": {
-                    "type": "integer",
-                    "default": 100,
-                    "maximum": 1000
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paper": {
+                                "type": ["object", "null"],
+                                "properties": {
+                                    "paperId": {"type": ["string", "null"]},
+                                    "title": {"type": ["string", "null"]},
+                                    "year": {"type": ["integer", "null"]},
+                                    "authors": {"type": "array", "items": {"type": "string"}}
+                                }
+                            },
+                            "score": {"type": ["number", "null"]},
+                            "snippet": {
+                                "type": ["object", "null"],
+                                "properties": {
+                                    "text": {"type": ["string", "null"]},
+                                    "kind": {"type": ["string", "null"]},
+                                    "section": {"type": ["string", "null"]}
+                                }
+                            }
+                        }
+                    }
                 }
             },
-            "required": ["query"]
+            "required": ["query", "total", "snippets"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: SnippetSearchInput = serde_json::from_value(input)?;
 
         // Build filter parameters
@@ -648,6 +592,32 @@ impl McpTool for SnippetSearchTool {
             .await
             .map_err(ToolError::from)?;
 
+        let snippets: Vec<_> = result
+            .data
+            .iter()
+            .map(|s| {
+                json!({
+                    "paper": s.paper.as_ref().map(|p| json!({
+                        "paperId": p.paper_id,
+                        "title": p.title,
+                        "year": p.year,
+                        "authors": p.authors
+                    })),
+                    "score": s.score,
+                    "snippet": s.snippet.as_ref().map(|snip| json!({
+                        "text": snip.text,
+                        "kind": snip.snippet_kind,
+                        "section": snip.section
+                    }))
+                })
+            })
+            .collect();
+        let structured = json!({
+            "query": params.query,
+            "total": result.data.len(),
+            "snippets": snippets
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -698,36 +668,9 @@ impl McpTool for SnippetSearchTool {
                     output.push_str("*No snippets found matching the query.*");
                 }
 
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let snippets: Vec<_> = result
-                    .data
-                    .iter()
-                    .map(|s| {
-                        json!({
-                            "paper": s.paper.as_ref().map(|p| json!({
-                                "paperId": p.paper_id,
-                                "title": p.title,
-                                "year": p.year,
-                                "authors": p.authors
-                            })),
-                            "score": s.score,
-                            "snippet": s.snippet.as_ref().map(|snip| json!({
-                                "text": snip.text,
-                                "kind": snip.snippet_kind,
-                                "section": snip.section
-                            }))
-                        })
-                    })
-                    .collect();
-
-                Ok(serde_json::to_string_pretty(&json!({
-                    "query": params.query,
-                    "total": result.data.len(),
-                    "snippets": snippets
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
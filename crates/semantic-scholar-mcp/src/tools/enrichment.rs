@@ -3,7 +3,10 @@
 
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::formatters;
@@ -21,37 +24,35 @@ impl McpTool for BatchMetadataTool {
         "batch_metadata"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Enrichment
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Retrieve detailed metadata for multiple papers efficiently. \
          Accepts up to 500 paper IDs per call."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<BatchMetadataInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperIds": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "maxItems": 500,
-                    "description": "Paper IDs (S2, DOI:, ARXIV:, PMID:)"
-                },
-                "fields": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Fields to retrieve"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "found": formatters::compact_paper_list_schema(),
+                "not_found": {"type": "array", "items": {"type": "string"}}
             },
-            "required": ["paperIds"]
+            "required": ["found", "not_found"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: BatchMetadataInput = serde_json::from_value(input)?;
 
         let field_list: Vec<&str> = params
@@ -75,6 +76,12 @@ impl McpTool for BatchMetadataTool {
             }
         }
 
+        let compact = papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
+        let structured = json!({
+            "found": compact,
+            "not_found": not_found
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = formatters::format_papers_markdown(&papers);
@@ -85,15 +92,9 @@ impl McpTool for BatchMetadataTool {
                         not_found.join(", ")
                     ));
                 }
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact = papers.iter().map(formatters::compact_paper).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&json!({
-                    "found": compact,
-                    "not_found": not_found
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -107,34 +108,30 @@ impl McpTool for AuthorSearchTool {
         "author_search"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Enrichment
+    }
+
     fn description(&self) -> &'static str {
         "Search for authors by name."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<AuthorSearchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Author name to search for"
-                },
-                "limit": {
-                    "type": "integer",
-                    "default": 10,
-                    "maximum": 100
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "total": {"type": "integer"},
+                "authors": {"type": "array", "items": formatters::compact_author_schema()}
             },
-            "required": ["query"]
+            "required": ["total", "authors"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: AuthorSearchInput = serde_json::from_value(input)?;
 
         let result = ctx
@@ -143,12 +140,18 @@ impl McpTool for AuthorSearchTool {
             .await
             .map_err(ToolError::from)?;
 
+        let compact = result.data.iter().map(formatters::compact_author).collect::<Vec<_>>();
+        let structured = json!({
+            "total": result.data.len(),
+            "authors": compact
+        });
+
         match params.response_format {
-            ResponseFormat::Markdown => Ok(formatters::format_authors_markdown(&result.data)),
+            ResponseFormat::Markdown => {
+                Ok(ToolOutput::new(formatters::format_authors_markdown(&result.data), structured))
+            }
             ResponseFormat::Json => {
-                let compact =
-                    result.data.iter().map(formatters::compact_author).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&compact)?)
+                Ok(ToolOutput::new(serde_json::to_string_pretty(&compact)?, structured))
             }
         }
     }
@@ -163,42 +166,35 @@ impl McpTool for AuthorPapersTool {
         "author_papers"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Enrichment
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Get all papers by a specific author with optional year filtering."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<AuthorPapersInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "authorId": {
-                    "type": "string",
-                    "description": "Semantic Scholar author ID"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Minimum publication year"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "Maximum publication year"
-                },
-                "limit": {
-                    "type": "integer",
-                    "default": 100,
-                    "maximum": 1000
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "author": formatters::compact_author_schema(),
+                "papers": formatters::compact_paper_list_schema(),
+                "count": {"type": "integer"}
             },
-            "required": ["authorId"]
+            "required": ["author", "papers", "count"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: AuthorPapersInput = serde_json::from_value(input)?;
 
         // Get author info first
@@ -208,10 +204,10 @@ impl McpTool for AuthorPapersTool {
         let mut all_papers = Vec::new();
         let mut offset = 0;
         let limit = 100;
-        let max_results = params.limit;
+        let max_results = usize::try_from(params.limit).unwrap_or(0);
 
         loop {
-            if all_papers.len() as i32 >= max_results {
+            if all_papers.len() >= max_results {
                 break;
             }
 
@@ -236,7 +232,7 @@ impl McpTool for AuthorPapersTool {
 
                 all_papers.push(paper);
 
-                if all_papers.len() as i32 >= max_results {
+                if all_papers.len() >= max_results {
                     break;
                 }
             }
@@ -247,6 +243,13 @@ impl McpTool for AuthorPapersTool {
             offset = result.next.unwrap_or(offset + limit);
         }
 
+        let compact_papers: Vec<_> = all_papers.iter().map(formatters::compact_paper).collect();
+        let structured = json!({
+            "author": formatters::compact_author(&author),
+            "papers": compact_papers,
+            "count": all_papers.len()
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = formatters::format_author_markdown(&author);
@@ -264,17 +267,9 @@ impl McpTool for AuthorPapersTool {
                 }
 
                 output.push_str(&formatters::format_papers_markdown(&all_papers));
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact_papers: Vec<_> =
-                    all_papers.iter().map(formatters::compact_paper).collect();
-                Ok(serde_json::to_string_pretty(&json!({
-                    "author": formatters::compact_author(&author),
-                    "papers": compact_papers,
-                    "count": all_papers.len()
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -288,39 +283,55 @@ impl McpTool for PaperAutocompleteTool {
         "paper_autocomplete"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Enrichment
+    }
+
     fn description(&self) -> &'static str {
         "Get paper title suggestions for partial queries. \
          Useful for finding exact papers when you only remember part of the title."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<PaperAutocompleteInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Partial paper title to autocomplete"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                "query": {"type": "string"},
+                "suggestions": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": {"type": "string"},
+                            "title": {"type": ["string", "null"]},
+                            "year": {"type": ["integer", "null"]},
+                            "citations": {"type": ["integer", "null"]}
+                        },
+                        "required": ["id"]
+                    }
                 }
             },
-            "required": ["query"]
+            "required": ["query", "suggestions"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: PaperAutocompleteInput = serde_json::from_value(input)?;
 
         let matches =
             ctx.client.autocomplete_papers(&params.query).await.map_err(ToolError::from)?;
 
         if matches.is_empty() {
-            return Ok(format!(
-                "# Paper Autocomplete\n\n**Query:** `{}`\n\nNo suggestions found.",
-                params.query
+            return Ok(ToolOutput::new(
+                format!(
+                    "# Paper Autocomplete\n\n**Query:** `{}`\n\nNo suggestions found.",
+                    params.query
+                ),
+                json!({"query": params.query, "suggestions": []}),
             ));
         }
 
@@ -337,6 +348,19 @@ impl McpTool for PaperAutocompleteTool {
         let paper_map: std::collections::HashMap<&str, &crate::models::Paper> =
             papers.iter().map(|p| (p.paper_id.as_str(), p)).collect();
 
+        let structured = json!({
+            "query": params.query,
+            "suggestions": matches.iter().map(|m| {
+                let paper = paper_map.get(m.id.as_str());
+                json!({
+                    "id": m.id,
+                    "title": paper.and_then(|p| p.title.as_deref()).or(m.match_.as_deref()),
+                    "year": paper.and_then(|p| p.year),
+                    "citations": paper.map(|p| p.citations())
+                })
+            }).collect::<Vec<_>>()
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -363,20 +387,9 @@ impl McpTool for PaperAutocompleteTool {
                         m.id
                     ));
                 }
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string_pretty(&json!({
-                "query": params.query,
-                "suggestions": matches.iter().map(|m| {
-                    let paper = paper_map.get(m.id.as_str());
-                    json!({
-                        "id": m.id,
-                        "title": paper.and_then(|p| p.title.as_deref()).or(m.match_.as_deref()),
-                        "year": paper.and_then(|p| p.year),
-                        "citations": paper.map(|p| p.citations())
-                    })
-                }).collect::<Vec<_>>()
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -390,30 +403,32 @@ impl McpTool for PaperTitleMatchTool {
         "paper_title_match"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Enrichment
+    }
+
     fn description(&self) -> &'static str {
         "Find a paper by exact or near-exact title match. \
          Returns the best matching paper for the given title."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<PaperTitleMatchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "title": {
-                    "type": "string",
-                    "description": "Exact or near-exact paper title"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "query": {"type": "string"},
+                "matched": {"type": "boolean"},
+                "paper": {"anyOf": [formatters::compact_paper_schema(), {"type": "null"}]}
             },
-            "required": ["title"]
+            "required": ["query", "matched", "paper"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: PaperTitleMatchInput = serde_json::from_value(input)?;
 
         let paper = ctx
@@ -422,35 +437,28 @@ impl McpTool for PaperTitleMatchTool {
             .await
             .map_err(ToolError::from)?;
 
+        let structured = json!({
+            "query": params.title,
+            "matched": paper.is_some(),
+            "paper": paper.as_ref().map(formatters::compact_paper)
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
-                if let Some(p) = paper {
+                let output = if let Some(p) = paper {
                     let mut output =
                         format!("# Paper Title Match\n\n**Query:** `{}`\n\n---\n\n", params.title);
                     output.push_str(&formatters::format_paper_markdown(&p, 1));
-                    Ok(output)
+                    output
                 } else {
-                    Ok(format!(
+                    format!(
                         "# Paper Title Match\n\n**Query:** `{}`\n\n❌ No exact match found.",
                         params.title
-                    ))
-                }
-            }
-            ResponseFormat::Json => {
-                if let Some(p) = paper {
-                    Ok(serde_json::to_string_pretty(&json!({
-                        "query": params.title,
-                        "matched": true,
-                        "paper": formatters::compact_paper(&p)
-                    }))?)
-                } else {
-                    Ok(serde_json::to_string_pretty(&json!({
-                        "query": params.title,
-                        "matched": false,
-                        "paper": null
-                    }))?)
-                }
+                    )
+                };
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -464,35 +472,42 @@ impl McpTool for PaperAuthorsTool {
         "paper_authors"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Enrichment
+    }
+
     fn description(&self) -> &'static str {
         "Get detailed author information for a specific paper. \
          Returns full author profiles including affiliations, h-index, and citation counts."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<PaperAuthorsInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperId": {
-                    "type": "string",
-                    "description": "Paper ID (S2, DOI:, ARXIV:, PMID:)"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "paperId": {"type": "string"},
+                "authors": {"type": "array", "items": formatters::compact_author_schema()}
             },
-            "required": ["paperId"]
+            "required": ["paperId", "authors"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: PaperAuthorsInput = serde_json::from_value(input)?;
 
         let authors =
             ctx.client.get_paper_authors(&params.paper_id).await.map_err(ToolError::from)?;
 
+        let compact = authors.iter().map(formatters::compact_author).collect::<Vec<_>>();
+        let structured = json!({
+            "paperId": params.paper_id,
+            "authors": compact
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -501,15 +516,9 @@ impl McpTool for PaperAuthorsTool {
                     authors.len()
                 );
                 output.push_str(&formatters::format_authors_markdown(&authors));
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact = authors.iter().map(formatters::compact_author).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&json!({
-                    "paperId": params.paper_id,
-                    "authors": compact
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -523,37 +532,48 @@ impl McpTool for AuthorBatchTool {
         "author_batch"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Enrichment
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Get detailed metadata for multiple authors efficiently. \
          Accepts up to 1000 author IDs per call."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<AuthorBatchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "authorIds": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "maxItems": 1000,
-                    "description": "Semantic Scholar author IDs"
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "requested": {"type": "integer"},
+                "found": {"type": "integer"},
+                "authors": {"type": "array", "items": formatters::compact_author_schema()}
             },
-            "required": ["authorIds"]
+            "required": ["requested", "found", "authors"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: AuthorBatchInput = serde_json::from_value(input)?;
 
         let authors =
             ctx.client.get_authors_batch(&params.author_ids).await.map_err(ToolError::from)?;
 
+        let compact = authors.iter().map(formatters::compact_author).collect::<Vec<_>>();
+        let structured = json!({
+            "requested": params.author_ids.len(),
+            "found": authors.len(),
+            "authors": compact
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -562,16 +582,9 @@ impl McpTool for AuthorBatchTool {
                     authors.len()
                 );
                 output.push_str(&formatters::format_authors_markdown(&authors));
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact = authors.iter().map(formatters::compact_author).collect::<Vec<_>>();
-                Ok(serde_json::to_string_pretty(&json!({
-                    "requested": params.author_ids.len(),
-                    "found": authors.len(),
-                    "authors": compact
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
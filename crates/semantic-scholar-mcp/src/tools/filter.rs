@@ -0,0 +1,111 @@
+//! Enable/disable lists for tools and tool groups.
+
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+
+use super::ToolGroup;
+
+/// Which tools are exposed.
+///
+/// Entries are tool names (`citation_snowball`) or group names
+/// (`bibliometrics`). An empty `enable` list enables everything; `disable`
+/// always wins over `enable`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolFilter {
+    /// Tools or groups to expose. Empty means all.
+    #[serde(default)]
+    pub enable: BTreeSet<String>,
+    /// Tools or groups to hide.
+    #[serde(default)]
+    pub disable: BTreeSet<String>,
+}
+
+impl ToolFilter {
+    /// Create a filter from enable and disable lists.
+    #[must_use]
+    pub fn new<I, J>(enable: I, disable: J) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        J: IntoIterator,
+        J::Item: Into<String>,
+    {
+        Self {
+            enable: enable.into_iter().map(Into::into).collect(),
+            disable: disable.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Whether the filter lets every tool through.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.enable.is_empty() && self.disable.is_empty()
+    }
+
+    /// Whether the tool `name` in `group` is exposed.
+    #[must_use]
+    pub fn permits(&self, name: &str, group: ToolGroup) -> bool {
+        let listed = |set: &BTreeSet<String>| set.contains(name) || set.contains(group.as_str());
+        if listed(&self.disable) {
+            return false;
+        }
+        self.enable.is_empty() || listed(&self.enable)
+    }
+
+    /// Entries that name neither a built-in group nor one of `known` (tool
+    /// names and custom group names).
+    ///
+    /// Used to warn about typos in configuration.
+    #[must_use]
+    pub fn unknown_entries<'a>(&'a self, known: &[&str]) -> Vec<&'a str> {
+        self.enable
+            .iter()
+            .chain(&self.disable)
+            .map(String::as_str)
+            .filter(|e| e.parse::<ToolGroup>().is_err() && !known.contains(e))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_filter_permits_everything() {
+        let filter = ToolFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.permits("citation_snowball", ToolGroup::Discovery));
+    }
+
+    #[test]
+    fn test_enable_by_group_and_name() {
+        let filter = ToolFilter::new(["bibliometrics", "author_search"], Vec::<String>::new());
+        assert!(filter.permits("hot_papers", ToolGroup::Bibliometrics));
+        assert!(filter.permits("author_search", ToolGroup::Enrichment));
+        assert!(!filter.permits("batch_metadata", ToolGroup::Enrichment));
+    }
+
+    #[test]
+    fn test_disable_wins() {
+        let filter = ToolFilter::new(["discovery"], ["bulk_boolean_search"]);
+        assert!(filter.permits("exhaustive_search", ToolGroup::Discovery));
+        assert!(!filter.permits("bulk_boolean_search", ToolGroup::Discovery));
+    }
+
+    #[test]
+    fn test_custom_groups_match_by_name() {
+        let filter = ToolFilter::new(Vec::<String>::new(), ["internal"]);
+        assert!(!filter.permits("lookup", ToolGroup::Custom("internal")));
+        assert!(filter.permits("lookup", ToolGroup::Custom("public")));
+        assert!(filter.unknown_entries(&["lookup", "internal"]).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_entries() {
+        let filter = ToolFilter::new(["trends", "hot_paper"], ["jobs"]);
+        assert_eq!(filter.unknown_entries(&["hot_papers"]), vec!["hot_paper"]);
+    }
+}
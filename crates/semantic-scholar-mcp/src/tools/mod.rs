@@ -3,14 +3,20 @@
 //! Each tool module provides functions that:
 //! 1. Parse and validate input parameters
 //! 2. Call the Semantic Scholar API client
-//! 3. Format results as Markdown or JSON
+//! 3. Build a structured result and render it as Markdown or JSON text
+//!
+//! Applications embedding the server pick tools, add their own and wrap
+//! calls in middleware with a [`ToolRegistry`].
 
 mod advanced;
 mod bibliometrics;
 mod discovery;
 mod enrichment;
 mod export;
+mod filter;
 mod networks;
+mod registry;
+mod schema;
 mod semantic;
 mod systematic;
 mod trends;
@@ -20,27 +26,275 @@ pub use bibliometrics::*;
 pub use discovery::*;
 pub use enrichment::*;
 pub use export::*;
+pub use filter::ToolFilter;
 pub use networks::*;
+pub use registry::{Next, ToolMiddleware, ToolRegistry};
+pub use schema::{input_schema_for, validate_arguments};
 pub use semantic::*;
 pub use systematic::*;
 pub use trends::*;
 
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 
+use serde::Serialize;
+use tokio::sync::mpsc;
+
 use crate::client::SemanticScholarClient;
+use crate::config::ToolDefaults;
 use crate::error::ToolResult;
+use crate::server::accounts::Principal;
+
+/// Sink for partial results emitted by long-running tools.
+pub type ProgressSender = mpsc::UnboundedSender<serde_json::Value>;
 
 /// Tool execution context.
+#[derive(Clone)]
 pub struct ToolContext {
     /// API client.
     pub client: Arc<SemanticScholarClient>,
+    /// Where partial results go (background jobs, calls with a progress token).
+    progress: Option<ProgressSender>,
+    /// Signed-in user making the call (HTTP with user accounts).
+    principal: Option<Principal>,
+    /// Configured default arguments, applied by the transports.
+    tool_defaults: Arc<ToolDefaults>,
 }
 
 impl ToolContext {
     /// Create a new tool context.
     #[must_use]
     pub fn new(client: Arc<SemanticScholarClient>) -> Self {
-        Self { client }
+        Self { client, progress: None, principal: None, tool_defaults: Arc::default() }
+    }
+
+    /// Fill in configured default arguments before validation.
+    #[must_use]
+    pub fn with_tool_defaults(mut self, defaults: ToolDefaults) -> Self {
+        self.tool_defaults = Arc::new(defaults);
+        self
+    }
+
+    /// Configured default arguments per tool.
+    #[must_use]
+    pub fn tool_defaults(&self) -> &ToolDefaults {
+        &self.tool_defaults
+    }
+
+    /// Attach a progress sink (used by the job runner and for progress tokens).
+    #[must_use]
+    pub fn with_progress(mut self, sender: ProgressSender) -> Self {
+        self.progress = Some(sender);
+        self
+    }
+
+    /// Attach the user making the call.
+    #[must_use]
+    pub fn with_principal(mut self, principal: Principal) -> Self {
+        self.principal = Some(principal);
+        self
+    }
+
+    /// The user making the call, if known.
+    #[must_use]
+    pub fn principal(&self) -> Option<&Principal> {
+        self.principal.as_ref()
+    }
+
+    /// Report a partial result. No-op unless a progress sink is attached.
+    pub fn report_progress(&self, partial: serde_json::Value) {
+        if let Some(sender) = &self.progress {
+            // Receiver gone means the job was cancelled; nothing to do.
+            let _ = sender.send(partial);
+        }
+    }
+}
+
+/// Result of a tool invocation.
+///
+/// `structured` is the machine-readable payload described by
+/// [`McpTool::output_schema`] and is sent as `structuredContent`;
+/// `text` is its rendering for the text content block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolOutput {
+    /// Text rendering (Markdown or JSON, per `responseFormat`).
+    pub text: String,
+    /// Structured result conforming to the tool's output schema.
+    pub structured: serde_json::Value,
+}
+
+impl ToolOutput {
+    /// Create an output from a text rendering and its structured payload.
+    #[must_use]
+    pub fn new(text: impl Into<String>, structured: serde_json::Value) -> Self {
+        Self { text: text.into(), structured }
+    }
+
+    /// Create an output whose text is the pretty-printed structured payload.
+    pub fn json(structured: serde_json::Value) -> ToolResult<Self> {
+        let text = serde_json::to_string_pretty(&structured)?;
+        Ok(Self { text, structured })
+    }
+
+    /// Build the MCP `CallToolResult` payload.
+    #[must_use]
+    pub fn to_call_result(&self) -> serde_json::Value {
+        serde_json::json!({
+            "content": [{
+                "type": "text",
+                "text": self.text
+            }],
+            "structuredContent": self.structured
+        })
+    }
+}
+
+/// Functional area a tool belongs to, used to enable or disable tools in bulk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolGroup {
+    Discovery,
+    Enrichment,
+    Export,
+    Systematic,
+    Semantic,
+    Networks,
+    Trends,
+    Bibliometrics,
+    Advanced,
+    /// Background job companion tools (HTTP transport only).
+    Jobs,
+    /// A group of tools added by an embedding application. Filters match it
+    /// by name like the built-in groups; OAuth scopes only let admins call
+    /// its tools.
+    #[serde(untagged)]
+    Custom(&'static str),
+}
+
+impl ToolGroup {
+    /// All built-in groups, in `tools/list` order.
+    pub const ALL: [Self; 10] = [
+        Self::Discovery,
+        Self::Enrichment,
+        Self::Export,
+        Self::Systematic,
+        Self::Semantic,
+        Self::Networks,
+        Self::Trends,
+        Self::Bibliometrics,
+        Self::Advanced,
+        Self::Jobs,
+    ];
+
+    /// Group name as used in configuration.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Discovery => "discovery",
+            Self::Enrichment => "enrichment",
+            Self::Export => "export",
+            Self::Systematic => "systematic",
+            Self::Semantic => "semantic",
+            Self::Networks => "networks",
+            Self::Trends => "trends",
+            Self::Bibliometrics => "bibliometrics",
+            Self::Advanced => "advanced",
+            Self::Jobs => "jobs",
+            Self::Custom(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for ToolGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ToolGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|g| g.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown tool group '{s}'"))
+    }
+}
+
+/// Rough number of upstream API requests a call makes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolCost {
+    /// One or two requests.
+    Low,
+    /// A handful of requests or a paginated fetch.
+    Medium,
+    /// Many requests (graph expansion, multi-query pipelines).
+    High,
+}
+
+/// Typical wall-clock time of a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolLatency {
+    /// Usually under a couple of seconds.
+    Fast,
+    /// Several seconds.
+    Moderate,
+    /// Can take a minute or more; consider `job_submit`.
+    Slow,
+}
+
+/// MCP tool annotations (`annotations` in `tools/list`).
+///
+/// The four `*Hint` fields are the standard MCP hints; `costHint` and
+/// `latencyHint` are extensions that let clients budget expensive calls.
+// The hint fields mirror the MCP spec, hence the bools.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    /// The tool does not modify any state.
+    pub read_only_hint: bool,
+    /// The tool may irreversibly change state.
+    pub destructive_hint: bool,
+    /// Repeating a call with the same arguments has no additional effect.
+    pub idempotent_hint: bool,
+    /// The tool talks to an external system (the Semantic Scholar API).
+    pub open_world_hint: bool,
+    /// Expected upstream request volume.
+    pub cost_hint: ToolCost,
+    /// Expected latency.
+    pub latency_hint: ToolLatency,
+}
+
+impl ToolAnnotations {
+    /// A read-only, idempotent tool backed by the Semantic Scholar API.
+    #[must_use]
+    pub const fn read_only(cost_hint: ToolCost, latency_hint: ToolLatency) -> Self {
+        Self {
+            read_only_hint: true,
+            destructive_hint: false,
+            idempotent_hint: true,
+            open_world_hint: true,
+            cost_hint,
+            latency_hint,
+        }
+    }
+
+    /// Mark the tool as purely local (no upstream requests).
+    #[must_use]
+    pub const fn local(mut self) -> Self {
+        self.open_world_hint = false;
+        self
+    }
+}
+
+impl Default for ToolAnnotations {
+    fn default() -> Self {
+        Self::read_only(ToolCost::Low, ToolLatency::Fast)
     }
 }
 
@@ -53,11 +307,31 @@ pub trait McpTool: Send + Sync {
     /// Tool description for LLM.
     fn description(&self) -> &'static str;
 
+    /// Group used by deployment and per-client tool filters.
+    fn group(&self) -> ToolGroup;
+
+    /// Behavioural hints advertised in `tools/list`.
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
     /// JSON Schema for input parameters.
+    ///
+    /// Tools derive this from their input struct via [`input_schema_for`].
     fn input_schema(&self) -> serde_json::Value;
 
+    /// Validate arguments against [`input_schema`](Self::input_schema).
+    ///
+    /// Called by the transports before [`execute`](Self::execute).
+    fn validate_input(&self, input: &serde_json::Value) -> ToolResult<()> {
+        validate_arguments(&self.input_schema(), input)
+    }
+
+    /// JSON Schema for the structured result (`structuredContent`).
+    fn output_schema(&self) -> serde_json::Value;
+
     /// Execute the tool with given input.
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String>;
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput>;
 }
 
 /// Register all tools.
@@ -4,7 +4,10 @@ use std::collections::HashMap;
 
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::models::{AuthorNetworkInput, ResponseFormat};
@@ -18,47 +21,56 @@ impl McpTool for AuthorNetworkTool {
         "author_network"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Networks
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Discover author collaboration networks. Analyzes papers to find \
          frequent collaborators and build a collaboration graph."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<AuthorNetworkInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "authorId": {
-                    "type": "string",
-                    "description": "Semantic Scholar author ID"
-                },
-                "depth": {
-                    "type": "integer",
-                    "default": 1,
-                    "minimum": 1,
-                    "maximum": 2,
-                    "description": "Collaboration depth (1 = direct, 2 = collaborators of collaborators)"
-                },
-                "minSharedPapers": {
-                    "type": "integer",
-                    "default": 2,
-                    "description": "Minimum shared papers to include a collaborator"
-                },
-                "maxCollaborators": {
-                    "type": "integer",
-                    "default": 50,
-                    "description": "Maximum collaborators to return"
+                "author": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string"},
+                        "name": {"type": "string"}
+                    }
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                "total_papers": {"type": "integer"},
+                "total_collaborators": {"type": "integer"},
+                "minSharedPapers": {"type": "integer"},
+                "collaborators": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "id": {"type": "string"},
+                            "name": {"type": "string"},
+                            "shared_papers": {"type": "integer"},
+                            "paperIds": {"type": "array", "items": {"type": "string"}}
+                        },
+                        "required": ["id", "name", "shared_papers"]
+                    }
                 }
             },
-            "required": ["authorId"]
+            "required": ["author", "total_papers", "total_collaborators", "collaborators"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: AuthorNetworkInput = serde_json::from_value(input)?;
 
         // Get author info
@@ -146,6 +158,17 @@ impl McpTool for AuthorNetworkTool {
         // Limit to max_collaborators
         collaborators.truncate(params.max_collaborators as usize);
 
+        let structured = json!({
+            "author": {
+                "id": params.author_id,
+                "name": author_name
+            },
+            "total_papers": papers.len(),
+            "total_collaborators": collaborators.len(),
+            "minSharedPapers": params.min_shared_papers,
+            "collaborators": collaborators
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -177,18 +200,9 @@ impl McpTool for AuthorNetworkTool {
                     output.push_str("*No collaborators found matching the criteria.*");
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "author": {
-                    "id": params.author_id,
-                    "name": author_name
-                },
-                "total_papers": papers.len(),
-                "total_collaborators": collaborators.len(),
-                "minSharedPapers": params.min_shared_papers,
-                "collaborators": collaborators
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
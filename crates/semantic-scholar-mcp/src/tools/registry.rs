@@ -0,0 +1,214 @@
+//! Tool registry for applications embedding the server.
+
+use std::sync::Arc;
+
+use super::{McpTool, ToolAnnotations, ToolContext, ToolFilter, ToolGroup, ToolOutput};
+use crate::error::ToolResult;
+
+/// Code run around every [`McpTool::execute`] call of a [`ToolRegistry`]'s
+/// tools (auth checks, caching, logging, ...).
+///
+/// A middleware either answers the call itself or hands it on with
+/// [`Next::run`], possibly with changed arguments or context.
+///
+/// ```
+/// use semantic_scholar_mcp::error::{ToolError, ToolResult};
+/// use semantic_scholar_mcp::tools::{Next, ToolContext, ToolMiddleware, ToolOutput};
+///
+/// /// Only signed-in users may call tools.
+/// struct RequireUser;
+///
+/// #[async_trait::async_trait]
+/// impl ToolMiddleware for RequireUser {
+///     async fn handle(
+///         &self,
+///         ctx: &ToolContext,
+///         input: serde_json::Value,
+///         next: Next<'_>,
+///     ) -> ToolResult<ToolOutput> {
+///         if ctx.principal().is_none() {
+///             return Err(ToolError::forbidden(format!("{} requires a user", next.tool().name())));
+///         }
+///         next.run(ctx, input).await
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait ToolMiddleware: Send + Sync {
+    /// Handle a call of `next.tool()` with `input`.
+    async fn handle(
+        &self,
+        ctx: &ToolContext,
+        input: serde_json::Value,
+        next: Next<'_>,
+    ) -> ToolResult<ToolOutput>;
+}
+
+/// The rest of the middleware chain, ending in the tool itself.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    tool: &'a dyn McpTool,
+    middleware: &'a [Arc<dyn ToolMiddleware>],
+}
+
+impl<'a> Next<'a> {
+    /// The tool being called.
+    #[must_use]
+    pub fn tool(&self) -> &'a dyn McpTool {
+        self.tool
+    }
+
+    /// Run the remaining middleware and then the tool.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever the middleware or the tool fails with.
+    pub async fn run(self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
+        match self.middleware.split_first() {
+            Some((first, rest)) => {
+                first.handle(ctx, input, Next { tool: self.tool, middleware: rest }).await
+            }
+            None => self.tool.execute(ctx, input).await,
+        }
+    }
+}
+
+/// The tools an embedding application serves: a selection of the built-in
+/// tools, its own [`McpTool`] implementations, and middleware around them.
+///
+/// ```no_run
+/// # fn main() -> anyhow::Result<()> {
+/// use semantic_scholar_mcp::client::SemanticScholarClient;
+/// use semantic_scholar_mcp::server::McpServer;
+/// use semantic_scholar_mcp::tools::{ToolFilter, ToolRegistry};
+///
+/// let registry = ToolRegistry::builtin()
+///     .with_filter(ToolFilter::new(["discovery", "export"], Vec::<String>::new()));
+/// let server = McpServer::new(SemanticScholarClient::builder().build()?).with_tools(registry);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+#[must_use]
+pub struct ToolRegistry {
+    tools: Vec<Box<dyn McpTool>>,
+    filter: ToolFilter,
+    middleware: Vec<Arc<dyn ToolMiddleware>>,
+}
+
+impl ToolRegistry {
+    /// A registry without tools.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry of every built-in tool (see [`register_all_tools`](super::register_all_tools)).
+    pub fn builtin() -> Self {
+        Self { tools: super::register_all_tools(), ..Self::default() }
+    }
+
+    /// Add `tool`, replacing any tool of the same name.
+    pub fn with_tool(self, tool: impl McpTool + 'static) -> Self {
+        self.with_boxed_tool(Box::new(tool))
+    }
+
+    /// Add `tool`, replacing any tool of the same name.
+    pub fn with_boxed_tool(mut self, tool: Box<dyn McpTool>) -> Self {
+        match self.tools.iter_mut().find(|t| t.name() == tool.name()) {
+            Some(existing) => *existing = tool,
+            None => self.tools.push(tool),
+        }
+        self
+    }
+
+    /// Serve only the tools `filter` permits, custom tools included.
+    pub fn with_filter(mut self, filter: ToolFilter) -> Self {
+        let known: Vec<&str> =
+            self.tools.iter().flat_map(|t| [t.name(), t.group().as_str()]).collect();
+        for entry in filter.unknown_entries(&known) {
+            tracing::warn!(entry, "Tool filter entry matches no tool or group");
+        }
+        self.filter = filter;
+        self
+    }
+
+    /// Run `middleware` around every tool call. Middleware added first runs
+    /// outermost.
+    pub fn with_middleware(mut self, middleware: impl ToolMiddleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Names of the registered tools, filtered out ones included.
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        self.tools.iter().map(|t| t.name()).collect()
+    }
+
+    /// The tools to serve, each wrapped in the middleware.
+    #[must_use]
+    pub fn into_tools(self) -> Vec<Box<dyn McpTool>> {
+        let Self { tools, filter, middleware } = self;
+        let tools = tools.into_iter().filter(|t| filter.permits(t.name(), t.group()));
+        if middleware.is_empty() {
+            return tools.collect();
+        }
+        let middleware: Arc<[_]> = middleware.into();
+        tools
+            .map(|tool| {
+                Box::new(Layered { tool, middleware: Arc::clone(&middleware) }) as Box<dyn McpTool>
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.names())
+            .field("filter", &self.filter)
+            .field("middleware", &self.middleware.len())
+            .finish()
+    }
+}
+
+/// A tool whose calls go through the registry's middleware.
+struct Layered {
+    tool: Box<dyn McpTool>,
+    middleware: Arc<[Arc<dyn ToolMiddleware>]>,
+}
+
+#[async_trait::async_trait]
+impl McpTool for Layered {
+    fn name(&self) -> &'static str {
+        self.tool.name()
+    }
+
+    fn description(&self) -> &'static str {
+        self.tool.description()
+    }
+
+    fn group(&self) -> ToolGroup {
+        self.tool.group()
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        self.tool.annotations()
+    }
+
+    fn input_schema(&self) -> serde_json::Value {
+        self.tool.input_schema()
+    }
+
+    fn validate_input(&self, input: &serde_json::Value) -> ToolResult<()> {
+        self.tool.validate_input(input)
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        self.tool.output_schema()
+    }
+
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
+        Next { tool: self.tool.as_ref(), middleware: &self.middleware }.run(ctx, input).await
+    }
+}
@@ -0,0 +1,360 @@
+//! Input schema generation and argument validation.
+//!
+//! Tool input schemas are derived from the serde input structs in
+//! [`crate::models`] so the advertised schema, defaults, and constraints
+//! cannot drift from what `execute` actually parses. Arguments are checked
+//! against the same schema before a tool runs.
+
+use schemars::JsonSchema;
+use schemars::generate::SchemaSettings;
+use serde_json::{Map, Value};
+
+use crate::error::{ToolError, ToolResult};
+
+/// Generate the MCP `inputSchema` for an input type.
+///
+/// Subschemas are inlined (no `$defs`/`$ref`) since several MCP clients do
+/// not resolve references. The root `title`/`description` are dropped; the
+/// tool description already covers them.
+#[must_use]
+pub fn input_schema_for<T: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft2020_12()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.meta_schema = None;
+        })
+        .into_generator();
+
+    let mut schema = generator.into_root_schema_for::<T>().to_value();
+    if let Some(obj) = schema.as_object_mut() {
+        obj.remove("title");
+        obj.remove("description");
+    }
+    schema
+}
+
+/// Validate tool arguments against an input schema.
+///
+/// Supports the subset of JSON Schema produced by [`input_schema_for`]:
+/// `type`, `const`, `enum`, `oneOf`/`anyOf`, `properties`/`required`/
+/// `additionalProperties`, `items`, length/size bounds, numeric bounds and
+/// the `int32`/`uint32` formats. The returned error names the offending field
+/// as a dotted path (e.g. `identification.databases[0].results`).
+pub fn validate_arguments(schema: &Value, args: &Value) -> ToolResult<()> {
+    validate_at(schema, args, "")
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str) -> ToolResult<()> {
+    let Some(schema) = schema.as_object() else {
+        // `true` accepts anything; `false` accepts nothing.
+        return if schema == &Value::Bool(false) {
+            Err(fail(path, "is not allowed"))
+        } else {
+            Ok(())
+        };
+    };
+
+    if let Some(expected) = schema.get("type") {
+        if !matches_type(expected, value) {
+            return Err(fail(
+                path,
+                format!("expected {}, got {}", describe_type(expected), kind(value)),
+            ));
+        }
+    }
+
+    if let Some(constant) = schema.get("const") {
+        if value != constant {
+            return Err(fail(path, format!("must be {constant}")));
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            return Err(fail(path, format!("must be one of {}", join_values(allowed))));
+        }
+    }
+
+    for key in ["oneOf", "anyOf"] {
+        if let Some(Value::Array(variants)) = schema.get(key) {
+            if !variants.iter().any(|v| validate_at(v, value, path).is_ok()) {
+                return Err(fail(path, variant_message(variants)));
+            }
+        }
+    }
+
+    match value {
+        Value::Object(obj) => validate_object(schema, obj, path)?,
+        Value::Array(items) => validate_array(schema, items, path)?,
+        Value::String(s) => validate_string(schema, s, path)?,
+        Value::Number(_) => validate_number(schema, value, path)?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    obj: &Map<String, Value>,
+    path: &str,
+) -> ToolResult<()> {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !obj.contains_key(key) {
+                return Err(fail(&join_path(path, key), "is required"));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (key, value) in obj {
+        let field = join_path(path, key);
+        match properties.and_then(|p| p.get(key)) {
+            Some(prop) => validate_at(prop, value, &field)?,
+            None => {
+                if let Some(additional) = schema.get("additionalProperties") {
+                    validate_at(additional, value, &field)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_array(schema: &Map<String, Value>, items: &[Value], path: &str) -> ToolResult<()> {
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if (items.len() as u64) < min {
+            return Err(fail(path, format!("must contain at least {min} item(s)")));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if items.len() as u64 > max {
+            return Err(fail(
+                path,
+                format!("must contain at most {max} items, got {}", items.len()),
+            ));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate_at(item_schema, item, &format!("{path}[{i}]"))?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_string(schema: &Map<String, Value>, s: &str, path: &str) -> ToolResult<()> {
+    let len = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if len < min {
+            return Err(fail(path, format!("must be at least {min} character(s)")));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if len > max {
+            return Err(fail(path, format!("must be at most {max} characters")));
+        }
+    }
+    Ok(())
+}
+
+fn validate_number(schema: &Map<String, Value>, value: &Value, path: &str) -> ToolResult<()> {
+    let Some(n) = value.as_f64() else {
+        return Ok(());
+    };
+
+    let format_range = match schema.get("format").and_then(Value::as_str) {
+        Some("int32") => Some((f64::from(i32::MIN), f64::from(i32::MAX))),
+        Some("uint32") => Some((0.0, f64::from(u32::MAX))),
+        _ => None,
+    };
+    if let Some((lo, hi)) = format_range {
+        if n < lo || n > hi {
+            return Err(fail(path, format!("must be between {lo} and {hi}")));
+        }
+    }
+
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+        if n < min {
+            return Err(fail(path, format!("must be >= {}", number(min))));
+        }
+    }
+    if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+        if n > max {
+            return Err(fail(path, format!("must be <= {}", number(max))));
+        }
+    }
+    if let Some(min) = schema.get("exclusiveMinimum").and_then(Value::as_f64) {
+        if n <= min {
+            return Err(fail(path, format!("must be > {}", number(min))));
+        }
+    }
+    if let Some(max) = schema.get("exclusiveMaximum").and_then(Value::as_f64) {
+        if n >= max {
+            return Err(fail(path, format!("must be < {}", number(max))));
+        }
+    }
+    Ok(())
+}
+
+fn matches_type(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(t) => matches_single_type(t, value),
+        Value::Array(types) => {
+            types.iter().filter_map(Value::as_str).any(|t| matches_single_type(t, value))
+        }
+        _ => true,
+    }
+}
+
+fn matches_single_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(types) => {
+            types.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(" or ")
+        }
+        other => other.as_str().unwrap_or("value").to_string(),
+    }
+}
+
+const fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Error message for a failed `oneOf`/`anyOf`, listing allowed constants when
+/// the variants are string enums (the shape schemars emits for unit enums).
+fn variant_message(variants: &[Value]) -> String {
+    let constants: Vec<Value> = variants.iter().filter_map(|v| v.get("const").cloned()).collect();
+    if constants.len() == variants.len() {
+        format!("must be one of {}", join_values(&constants))
+    } else {
+        "does not match any allowed form".to_string()
+    }
+}
+
+fn join_values(values: &[Value]) -> String {
+    values.iter().map(Value::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn number(n: f64) -> String {
+    if n.fract() == 0.0 { format!("{n:.0}") } else { n.to_string() }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() { key.to_string() } else { format!("{path}.{key}") }
+}
+
+fn fail(path: &str, message: impl Into<String>) -> ToolError {
+    let field = if path.is_empty() { "arguments" } else { path };
+    ToolError::validation(field, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::{BatchMetadataInput, CitationSnowballInput, PrismaFlowDiagramInput};
+
+    fn field_of(err: ToolError) -> String {
+        match err {
+            ToolError::Validation { field, .. } => field,
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_schema_uses_serde_names_and_defaults() {
+        let schema = input_schema_for::<CitationSnowballInput>();
+
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["seedPaperIds"]));
+        assert_eq!(schema["properties"]["depth"]["default"], 1);
+        assert_eq!(schema["properties"]["deduplicate"]["default"], true);
+        assert_eq!(schema["properties"]["responseFormat"]["default"], "markdown");
+        assert!(schema.get("title").is_none());
+        assert!(schema.get("$defs").is_none());
+    }
+
+    #[test]
+    fn test_valid_arguments_pass() {
+        let schema = input_schema_for::<CitationSnowballInput>();
+        let args = json!({"seedPaperIds": ["a"], "direction": "citations", "depth": 2});
+        assert!(validate_arguments(&schema, &args).is_ok());
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let schema = input_schema_for::<CitationSnowballInput>();
+        let err = validate_arguments(&schema, &json!({})).unwrap_err();
+        assert_eq!(field_of(err), "seedPaperIds");
+    }
+
+    #[test]
+    fn test_wrong_type_and_enum() {
+        let schema = input_schema_for::<CitationSnowballInput>();
+
+        let err = validate_arguments(&schema, &json!({"seedPaperIds": "a"})).unwrap_err();
+        assert_eq!(field_of(err), "seedPaperIds");
+
+        let err = validate_arguments(&schema, &json!({"seedPaperIds": ["a"], "direction": "up"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("\"citations\""));
+        assert_eq!(field_of(err), "direction");
+    }
+
+    #[test]
+    fn test_range_enforced() {
+        let schema = input_schema_for::<CitationSnowballInput>();
+        let err =
+            validate_arguments(&schema, &json!({"seedPaperIds": ["a"], "depth": 5})).unwrap_err();
+        assert_eq!(field_of(err), "depth");
+    }
+
+    #[test]
+    fn test_max_items_enforced() {
+        let schema = input_schema_for::<BatchMetadataInput>();
+        let ids: Vec<String> = (0..501).map(|i| format!("p{i}")).collect();
+        let err = validate_arguments(&schema, &json!({"paperIds": ids})).unwrap_err();
+        assert_eq!(field_of(err), "paperIds");
+    }
+
+    #[test]
+    fn test_nested_path() {
+        let schema = input_schema_for::<PrismaFlowDiagramInput>();
+        let args = json!({
+            "identification": {"databases": [{"name": "S2", "results": "many"}]},
+            "screening": {"recordsAfterDedup": 1, "recordsScreened": 1, "recordsExcluded": 0}
+        });
+        let err = validate_arguments(&schema, &args).unwrap_err();
+        assert_eq!(field_of(err), "identification.databases[0].results");
+    }
+
+    #[test]
+    fn test_non_object_arguments() {
+        let schema = input_schema_for::<CitationSnowballInput>();
+        let err = validate_arguments(&schema, &json!([1, 2])).unwrap_err();
+        assert_eq!(field_of(err), "arguments");
+    }
+}
@@ -4,7 +4,10 @@ use std::collections::HashMap;
 
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::formatters;
@@ -19,47 +22,47 @@ impl McpTool for SemanticSearchTool {
         "semantic_search"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Semantic
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Find semantically similar papers using SPECTER2 embeddings. \
          Uses the recommendations API for embedding-based similarity."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<SemanticSearchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "seedPaperId": {
-                    "type": "string",
-                    "description": "Paper ID to find similar papers for"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Minimum publication year"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "Maximum publication year"
-                },
-                "fieldsOfStudy": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Filter by fields of study"
-                },
-                "limit": {
-                    "type": "integer",
-                    "default": 100
+                "seed_paper_id": {"type": "string"},
+                "total_found": {"type": "integer"},
+                "filters": {
+                    "type": "object",
+                    "properties": {
+                        "yearStart": {"type": ["integer", "null"]},
+                        "yearEnd": {"type": ["integer", "null"]},
+                        "fieldsOfStudy": {
+                            "type": ["array", "null"],
+                            "items": {"type": "string"}
+                        }
+                    }
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "similar_papers": formatters::compact_paper_list_schema()
             },
-            "required": ["seedPaperId"]
+            "required": ["seed_paper_id", "total_found", "similar_papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: SemanticSearchInput = serde_json::from_value(input)?;
 
         // Get recommendations using the seed paper
@@ -92,6 +95,18 @@ impl McpTool for SemanticSearchTool {
             })
             .collect();
 
+        let compact: Vec<_> = filtered.iter().map(formatters::compact_paper).collect();
+        let structured = json!({
+            "seed_paper_id": params.seed_paper_id,
+            "total_found": filtered.len(),
+            "filters": {
+                "yearStart": params.year_start,
+                "yearEnd": params.year_end,
+                "fieldsOfStudy": params.fields_of_study
+            },
+            "similar_papers": compact
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -125,21 +140,9 @@ impl McpTool for SemanticSearchTool {
                     output.push_str("*No similar papers found matching the criteria.*");
                 }
 
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact: Vec<_> = filtered.iter().map(formatters::compact_paper).collect();
-                Ok(serde_json::to_string(&json!({
-                    "seed_paper_id": params.seed_paper_id,
-                    "total_found": filtered.len(),
-                    "filters": {
-                        "yearStart": params.year_start,
-                        "yearEnd": params.year_end,
-                        "fieldsOfStudy": params.fields_of_study
-                    },
-                    "similar_papers": compact
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -153,54 +156,53 @@ impl McpTool for LiteratureReviewPipelineTool {
         "literature_review_pipeline"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Semantic
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Automated literature review combining search, recommendations, and citations. \
          Performs deduplication and ranks by citation count."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<LiteratureReviewInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Initial search query"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Minimum publication year"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "Maximum publication year"
-                },
-                "minCitations": {
-                    "type": "integer",
-                    "description": "Minimum citation count"
-                },
-                "includeRecommendations": {
-                    "type": "boolean",
-                    "default": true
-                },
-                "includeCitations": {
-                    "type": "boolean",
-                    "default": true
+                "query": {"type": "string"},
+                "total_unique": {"type": "integer"},
+                "duplicates_removed": {"type": "integer"},
+                "sources": {
+                    "type": "object",
+                    "properties": {
+                        "search": {"type": "integer"},
+                        "recommendations": {"type": "integer"},
+                        "citations": {"type": "integer"}
+                    }
                 },
-                "maxPapers": {
-                    "type": "integer",
-                    "default": 200
+                "filters": {
+                    "type": "object",
+                    "properties": {
+                        "yearStart": {"type": ["integer", "null"]},
+                        "yearEnd": {"type": ["integer", "null"]},
+                        "minCitations": {"type": ["integer", "null"]}
+                    }
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "papers": formatters::compact_paper_list_schema()
             },
-            "required": ["query"]
+            "required": ["query", "total_unique", "duplicates_removed", "sources", "papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: LiteratureReviewInput = serde_json::from_value(input)?;
 
         let mut all_papers: HashMap<String, crate::models::Paper> = HashMap::new();
@@ -366,6 +368,20 @@ impl McpTool for LiteratureReviewPipelineTool {
         // Limit to max_papers
         paper_list.truncate(params.max_papers as usize);
 
+        let compact: Vec<_> = paper_list.iter().map(formatters::compact_paper).collect();
+        let structured = json!({
+            "query": params.query,
+            "total_unique": total_unique,
+            "duplicates_removed": duplicates_removed,
+            "sources": sources,
+            "filters": {
+                "yearStart": params.year_start,
+                "yearEnd": params.year_end,
+                "minCitations": params.min_citations
+            },
+            "papers": compact
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -396,23 +412,9 @@ impl McpTool for LiteratureReviewPipelineTool {
                     output.push_str(&format!("\n*... and {} more papers*", paper_list.len() - 50));
                 }
 
-                Ok(output)
-            }
-            ResponseFormat::Json => {
-                let compact: Vec<_> = paper_list.iter().map(formatters::compact_paper).collect();
-                Ok(serde_json::to_string(&json!({
-                    "query": params.query,
-                    "total_unique": total_unique,
-                    "duplicates_removed": duplicates_removed,
-                    "sources": sources,
-                    "filters": {
-                        "yearStart": params.year_start,
-                        "yearEnd": params.year_end,
-                        "minCitations": params.min_citations
-                    },
-                    "papers": compact
-                }))?)
+                Ok(ToolOutput::new(output, structured))
             }
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
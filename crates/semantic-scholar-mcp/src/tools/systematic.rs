@@ -7,7 +7,10 @@ use chrono::Utc;
 use futures::stream::{FuturesUnordered, StreamExt};
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::formatters;
@@ -24,47 +27,62 @@ impl McpTool for PrismaSearchTool {
         "prisma_search"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Systematic
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Run multiple search queries with deduplication and logging. \
          Designed for systematic reviews following PRISMA guidelines."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<PrismaSearchInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "queries": {
-                    "type": "array",
-                    "items": {"type": "string"},
-                    "description": "List of search queries to run"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Minimum publication year"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "Maximum publication year"
-                },
-                "minCitations": {
-                    "type": "integer",
-                    "description": "Minimum citation count"
+                "queries": {"type": "array", "items": {"type": "string"}},
+                "results_per_query": {
+                    "type": "object",
+                    "additionalProperties": {"type": "integer"}
                 },
-                "maxResultsPerQuery": {
-                    "type": "integer",
-                    "default": 500
+                "total_before_dedup": {"type": "integer"},
+                "duplicates_removed": {"type": "integer"},
+                "total_unique": {"type": "integer"},
+                "search_log": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "query": {"type": "string"},
+                            "retrieved": {"type": "integer"},
+                            "new_unique": {"type": "integer"},
+                            "duplicates": {"type": "integer"},
+                            "timestamp": {"type": "string", "format": "date-time"}
+                        }
+                    }
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "papers": formatters::compact_paper_list_schema()
             },
-            "required": ["queries"]
+            "required": [
+                "queries",
+                "total_before_dedup",
+                "duplicates_removed",
+                "total_unique",
+                "search_log",
+                "papers"
+            ]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: PrismaSearchInput = serde_json::from_value(input)?;
 
         let mut all_papers: HashMap<String, crate::models::Paper> = HashMap::new();
@@ -109,9 +127,8 @@ impl McpTool for PrismaSearchTool {
                         break;
                     }
 
-                    let result = client
-                        .search_papers(&q, offset, limit, fields::DEFAULT, &filters)
-                        .await;
+                    let result =
+                        client.search_papers(&q, offset, limit, fields::DEFAULT, &filters).await;
 
                     match result {
                         Ok(res) => {
@@ -162,6 +179,16 @@ impl McpTool for PrismaSearchTool {
         let total_before_dedup: usize = results_per_query.values().sum();
         let duplicates_removed = total_before_dedup - paper_list.len();
 
+        let structured = json!({
+            "queries": params.queries,
+            "results_per_query": results_per_query,
+            "total_before_dedup": total_before_dedup,
+            "duplicates_removed": duplicates_removed,
+            "total_unique": paper_list.len(),
+            "search_log": search_log,
+            "papers": paper_list.iter().map(formatters::compact_paper).collect::<Vec<_>>()
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -192,17 +219,9 @@ impl McpTool for PrismaSearchTool {
                     output.push_str(&format!("\n*... and {} more papers*", paper_list.len() - 100));
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "queries": params.queries,
-                "results_per_query": results_per_query,
-                "total_before_dedup": total_before_dedup,
-                "duplicates_removed": duplicates_removed,
-                "total_unique": paper_list.len(),
-                "search_log": search_log,
-                "papers": paper_list.iter().map(formatters::compact_paper).collect::<Vec<_>>()
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -216,38 +235,53 @@ impl McpTool for ScreeningExportTool {
         "screening_export"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Systematic
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Export papers in a format optimized for title/abstract screening."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<ScreeningExportInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "paperIds": {
+                "total": {"type": "integer"},
+                "export_date": {"type": "string", "format": "date-time"},
+                "papers": {
                     "type": "array",
-                    "items": {"type": "string"},
-                    "description": "Paper IDs to export"
-                },
-                "includeAbstract": {
-                    "type": "boolean",
-                    "default": true
-                },
-                "includeTldr": {
-                    "type": "boolean",
-                    "default": false
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "json"
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paperId": {"type": "string"},
+                            "title": {"type": "string"},
+                            "authors": {"type": "string"},
+                            "year": {"type": ["integer", "null"]},
+                            "venue": {"type": ["string", "null"]},
+                            "citations": {"type": "integer"},
+                            "doi": {"type": ["string", "null"]},
+                            "arxiv": {"type": ["string", "null"]},
+                            "abstract": {"type": "string"},
+                            "tldr": {"type": "string"}
+                        },
+                        "required": ["paperId", "title"]
+                    }
                 }
             },
-            "required": ["paperIds"]
+            "required": ["total", "export_date", "papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: ScreeningExportInput = serde_json::from_value(input)?;
 
         let mut field_list =
@@ -265,43 +299,9 @@ impl McpTool for ScreeningExportTool {
             .await
             .map_err(ToolError::from)?;
 
-        if matches!(params.response_format, ResponseFormat::Markdown) {
-            let mut output = String::from("# Screening Export\n\n");
-            for (i, paper) in papers.iter().enumerate() {
-                output.push_str(&format!("### {}. {}\n", i + 1, paper.title_or_default()));
-                output.push_str(&format!(
-                    "**ID:** `{}` | **Year:** {} | **Citations:** {}\n",
-                    paper.paper_id,
-                    paper.year.unwrap_or(0),
-                    paper.citations()
-                ));
-                output.push_str(&format!("**Authors:** {}\n", paper.author_names()));
-                if let Some(venue) = &paper.venue {
-                    output.push_str(&format!("**Venue:** {}\n", venue));
-                }
-                if let Some(doi) = paper.doi() {
-                    output.push_str(&format!("**DOI:** {}\n", doi));
-                }
-
-                if params.include_tldr {
-                    if let Some(tldr) = paper.tldr_text() {
-                        output.push_str(&format!("\n> **TLDR:** {}\n", tldr));
-                    }
-                }
-
-                if params.include_abstract {
-                    if let Some(abs) = &paper.r#abstract {
-                        output.push_str(&format!("\n**Abstract:**\n{}\n", abs));
-                    }
-                }
-                output.push_str("\n---\n\n");
-            }
-            return Ok(output);
-        }
-
         let mut export_data = Vec::new();
 
-        for paper in papers {
+        for paper in &papers {
             let authors: Vec<_> =
                 paper.authors.iter().take(5).map(|a| a.name_or_default().to_string()).collect();
             let mut author_str = authors.join("; ");
@@ -331,11 +331,47 @@ impl McpTool for ScreeningExportTool {
             export_data.push(row);
         }
 
-        Ok(serde_json::to_string(&json!({
+        let structured = json!({
             "total": export_data.len(),
             "export_date": Utc::now().to_rfc3339(),
             "papers": export_data
-        }))?)
+        });
+
+        if matches!(params.response_format, ResponseFormat::Markdown) {
+            let mut output = String::from("# Screening Export\n\n");
+            for (i, paper) in papers.iter().enumerate() {
+                output.push_str(&format!("### {}. {}\n", i + 1, paper.title_or_default()));
+                output.push_str(&format!(
+                    "**ID:** `{}` | **Year:** {} | **Citations:** {}\n",
+                    paper.paper_id,
+                    paper.year.unwrap_or(0),
+                    paper.citations()
+                ));
+                output.push_str(&format!("**Authors:** {}\n", paper.author_names()));
+                if let Some(venue) = &paper.venue {
+                    output.push_str(&format!("**Venue:** {}\n", venue));
+                }
+                if let Some(doi) = paper.doi() {
+                    output.push_str(&format!("**DOI:** {}\n", doi));
+                }
+
+                if params.include_tldr {
+                    if let Some(tldr) = paper.tldr_text() {
+                        output.push_str(&format!("\n> **TLDR:** {}\n", tldr));
+                    }
+                }
+
+                if params.include_abstract {
+                    if let Some(abs) = &paper.r#abstract {
+                        output.push_str(&format!("\n**Abstract:**\n{}\n", abs));
+                    }
+                }
+                output.push_str("\n---\n\n");
+            }
+            return Ok(ToolOutput::new(output, structured));
+        }
+
+        ToolOutput::json(structured)
     }
 }
 
@@ -348,65 +384,42 @@ impl McpTool for PrismaFlowDiagramTool {
         "prisma_flow_diagram"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Systematic
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default().local()
+    }
+
     fn description(&self) -> &'static str {
         "Generate a PRISMA 2020 flow diagram data structure for systematic reviews."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<PrismaFlowDiagramInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "identification": {
-                    "type": "object",
-                    "properties": {
-                        "databases": {
-                            "type": "array",
-                            "items": {
-                                "type": "object",
-                                "properties": {
-                                    "name": {"type": "string"},
-                                    "results": {"type": "integer"}
-                                }
-                            }
-                        }
-                    },
-                    "required": ["databases"]
-                },
-                "screening": {
-                    "type": "object",
-                    "properties": {
-                        "recordsAfterDedup": {"type": "integer"},
-                        "recordsScreened": {"type": "integer"},
-                        "recordsExcluded": {"type": "integer"}
-                    },
-                    "required": ["recordsAfterDedup", "recordsScreened", "recordsExcluded"]
-                },
-                "eligibility": {
-                    "type": "object",
-                    "properties": {
-                        "reportsSought": {"type": "integer"},
-                        "reportsAssessed": {"type": "integer"},
-                        "reportsExcluded": {"type": "integer"}
-                    }
-                },
-                "included": {
-                    "type": "object",
-                    "properties": {
-                        "studiesIncluded": {"type": "integer"},
-                        "reportsIncluded": {"type": "integer"}
-                    }
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
-                }
+                "version": {"type": "string"},
+                "identification": {"type": "object"},
+                "screening": {"type": "object"},
+                "eligibility": {"type": "object"},
+                "included": {"type": "object"},
+                "summary": {"type": "object"}
             },
-            "required": ["identification", "screening"]
+            "required": ["version", "identification", "screening", "summary"]
         })
     }
 
-    async fn execute(&self, _ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(
+        &self,
+        _ctx: &ToolContext,
+        input: serde_json::Value,
+    ) -> ToolResult<ToolOutput> {
         let params: PrismaFlowDiagramInput = serde_json::from_value(input)?;
 
         // Calculate identification totals
@@ -490,9 +503,9 @@ impl McpTool for PrismaFlowDiagramTool {
                     }
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, data))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&data)?),
+            ResponseFormat::Json => ToolOutput::json(data),
         }
     }
 }
@@ -4,7 +4,10 @@ use std::collections::HashMap;
 
 use serde_json::json;
 
-use super::{McpTool, ToolContext};
+use super::{
+    McpTool, ToolAnnotations, ToolContext, ToolCost, ToolGroup, ToolLatency, ToolOutput,
+    input_schema_for,
+};
 use crate::config::fields;
 use crate::error::{ToolError, ToolResult};
 use crate::models::{ResponseFormat, TrendAnalysisInput, VenueAnalyticsInput};
@@ -18,47 +21,61 @@ impl McpTool for ResearchTrendsTool {
         "research_trends"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Trends
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::High, ToolLatency::Slow)
+    }
+
     fn description(&self) -> &'static str {
         "Analyze publication trends for a research topic over time. \
          Groups papers by year with statistics and top papers per period."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<TrendAnalysisInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "query": {
-                    "type": "string",
-                    "description": "Research topic to analyze"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Start year for analysis"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "End year for analysis"
-                },
-                "granularity": {
-                    "type": "string",
-                    "enum": ["year", "quarter"],
-                    "default": "year"
-                },
-                "maxPapersPerPeriod": {
-                    "type": "integer",
-                    "default": 100
-                },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                "query": {"type": "string"},
+                "yearStart": {"type": "integer"},
+                "yearEnd": {"type": "integer"},
+                "total_papers": {"type": "integer"},
+                "trends": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "year": {"type": "integer"},
+                            "paper_count": {"type": "integer"},
+                            "total_citations": {"type": "integer"},
+                            "avg_citations": {"type": "number"},
+                            "top_papers": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "paperId": {"type": "string"},
+                                        "title": {"type": "string"},
+                                        "citations": {"type": "integer"}
+                                    }
+                                }
+                            }
+                        },
+                        "required": ["year", "paper_count"]
+                    }
                 }
             },
-            "required": ["query", "yearStart", "yearEnd"]
+            "required": ["query", "yearStart", "yearEnd", "total_papers", "trends"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: TrendAnalysisInput = serde_json::from_value(input)?;
 
         // Search for papers in the time range
@@ -134,6 +151,14 @@ impl McpTool for ResearchTrendsTool {
             }));
         }
 
+        let structured = json!({
+            "query": params.query,
+            "yearStart": params.year_start,
+            "yearEnd": params.year_end,
+            "total_papers": all_papers.len(),
+            "trends": trends
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -170,15 +195,9 @@ impl McpTool for ResearchTrendsTool {
                     output.push('\n');
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "query": params.query,
-                "yearStart": params.year_start,
-                "yearEnd": params.year_end,
-                "total_papers": all_papers.len(),
-                "trends": trends
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
@@ -192,42 +211,60 @@ impl McpTool for VenueAnalyticsTool {
         "venue_analytics"
     }
 
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Trends
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only(ToolCost::Medium, ToolLatency::Moderate)
+    }
+
     fn description(&self) -> &'static str {
         "Analyze publication statistics for a venue/conference. \
          Calculates metrics and identifies top papers."
     }
 
     fn input_schema(&self) -> serde_json::Value {
+        input_schema_for::<VenueAnalyticsInput>()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
         json!({
             "type": "object",
             "properties": {
-                "venueQuery": {
-                    "type": "string",
-                    "description": "Venue name (e.g., 'NeurIPS', 'Nature Medicine')"
-                },
-                "yearStart": {
-                    "type": "integer",
-                    "description": "Start year for analysis"
-                },
-                "yearEnd": {
-                    "type": "integer",
-                    "description": "End year for analysis"
+                "venueQuery": {"type": "string"},
+                "yearStart": {"type": ["integer", "null"]},
+                "yearEnd": {"type": ["integer", "null"]},
+                "statistics": {
+                    "type": "object",
+                    "properties": {
+                        "total_papers": {"type": "integer"},
+                        "total_citations": {"type": "integer"},
+                        "avg_citations": {"type": "number"}
+                    }
                 },
-                "maxPapers": {
-                    "type": "integer",
-                    "default": 500
+                "papers_by_year": {
+                    "type": "object",
+                    "additionalProperties": {"type": "integer"}
                 },
-                "responseFormat": {
-                    "type": "string",
-                    "enum": ["markdown", "json"],
-                    "default": "markdown"
+                "top_papers": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "paperId": {"type": "string"},
+                            "title": {"type": "string"},
+                            "year": {"type": ["integer", "null"]},
+                            "citations": {"type": "integer"}
+                        }
+                    }
                 }
             },
-            "required": ["venueQuery"]
+            "required": ["venueQuery", "statistics", "papers_by_year", "top_papers"]
         })
     }
 
-    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<String> {
+    async fn execute(&self, ctx: &ToolContext, input: serde_json::Value) -> ToolResult<ToolOutput> {
         let params: VenueAnalyticsInput = serde_json::from_value(input)?;
 
         // Search for papers in this venue
@@ -298,6 +335,19 @@ impl McpTool for VenueAnalyticsTool {
             })
             .collect();
 
+        let structured = json!({
+            "venueQuery": params.venue_query,
+            "yearStart": params.year_start,
+            "yearEnd": params.year_end,
+            "statistics": {
+                "total_papers": total_papers,
+                "total_citations": total_citations,
+                "avg_citations": (avg_citations * 10.0).round() / 10.0
+            },
+            "papers_by_year": years,
+            "top_papers": top_papers
+        });
+
         match params.response_format {
             ResponseFormat::Markdown => {
                 let mut output = format!(
@@ -327,7 +377,7 @@ impl McpTool for VenueAnalyticsTool {
                 ));
 
                 let mut year_list: Vec<_> = years.into_iter().collect();
-                year_list.sort_by(|a, b| b.0.cmp(&a.0));
+                year_list.sort_by_key(|b| std::cmp::Reverse(b.0));
                 for (year, count) in year_list {
                     output.push_str(&format!("- {}: {} papers\n", year, count));
                 }
@@ -343,20 +393,9 @@ impl McpTool for VenueAnalyticsTool {
                     ));
                 }
 
-                Ok(output)
+                Ok(ToolOutput::new(output, structured))
             }
-            ResponseFormat::Json => Ok(serde_json::to_string(&json!({
-                "venueQuery": params.venue_query,
-                "yearStart": params.year_start,
-                "yearEnd": params.year_end,
-                "statistics": {
-                    "total_papers": total_papers,
-                    "total_citations": total_citations,
-                    "avg_citations": (avg_citations * 10.0).round() / 10.0
-                },
-                "papers_by_year": years,
-                "top_papers": top_papers
-            }))?),
+            ResponseFormat::Json => ToolOutput::json(structured),
         }
     }
 }
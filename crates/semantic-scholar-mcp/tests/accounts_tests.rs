@@ -0,0 +1,304 @@
+//! Tests for user accounts: the login form, scope-bound tokens and
+//! scope-based tool access.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::server::accounts::{Account, Accounts, Scope, hash_password};
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::transport::{HttpOptions, create_router_with_options};
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+const AUTH_TOKEN: &str = "test-secret-token-12345";
+const REDIRECT_URI: &str = "https://client.example.com/cb";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+fn accounts() -> Accounts {
+    let mut users = HashMap::new();
+    users.insert(
+        "reader".to_string(),
+        Account { password_hash: hash_password("reader-pw"), scopes: vec![Scope::Read] },
+    );
+    users.insert(
+        "ops".to_string(),
+        Account { password_hash: hash_password("ops-pw"), scopes: vec![Scope::Admin] },
+    );
+    Accounts { users }
+}
+
+fn build_router(store: Arc<OAuthStore>) -> axum::Router {
+    let client =
+        SemanticScholarClient::new(ClientConfig::for_testing("http://unused.localhost")).unwrap();
+    let options = HttpOptions {
+        base_url: Some("https://example.com".into()),
+        auth_token: Some(AUTH_TOKEN.into()),
+        oauth_store: Some(store),
+        accounts: Some(Arc::new(accounts())),
+        ..HttpOptions::default()
+    };
+    create_router_with_options(
+        tools::register_all_tools(),
+        ToolContext::new(Arc::new(client)),
+        options,
+    )
+}
+
+async fn post_form(
+    app: &axum::Router,
+    uri: &str,
+    params: &[(&str, &str)],
+) -> axum::response::Response {
+    let body = serde_urlencoded::to_string(params).unwrap();
+    let request = Request::post(uri)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(body))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null)
+}
+
+/// Sign in through the login form and exchange the code for an access token.
+async fn login(app: &axum::Router, client_id: &str, user: &str, password: &str) -> String {
+    login_with_scope(app, client_id, user, password, "mcp").await
+}
+
+async fn login_with_scope(
+    app: &axum::Router,
+    client_id: &str,
+    user: &str,
+    password: &str,
+    scope: &str,
+) -> String {
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(CODE_VERIFIER.as_bytes()));
+    let response = post_form(
+        app,
+        "/authorize",
+        &[
+            ("client_id", client_id),
+            ("redirect_uri", REDIRECT_URI),
+            ("state", "xyz"),
+            ("code_challenge", &challenge),
+            ("scope", scope),
+            ("username", user),
+            ("password", password),
+        ],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let location = response.headers()["Location"].to_str().unwrap();
+    let url = url::Url::parse(location).unwrap();
+    let code = url.query_pairs().find(|(k, _)| k == "code").unwrap().1.to_string();
+
+    let response = post_form(
+        app,
+        "/token",
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_verifier", CODE_VERIFIER),
+            ("client_id", client_id),
+        ],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    body_json(response).await["access_token"].as_str().unwrap().to_string()
+}
+
+async fn mcp(app: &axum::Router, token: &str, body: serde_json::Value) -> serde_json::Value {
+    let request = Request::post("/mcp")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    body_json(response).await
+}
+
+async fn tool_names(app: &axum::Router, token: &str) -> Vec<String> {
+    let list = mcp(app, token, json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"})).await;
+    list["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_authorize_shows_login_form_and_rejects_bad_password() {
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(Some("Claude".into()), vec![REDIRECT_URI.into()]).await;
+    let app = build_router(Arc::clone(&store));
+
+    let uri = format!(
+        "/authorize?client_id={}&redirect_uri={}&response_type=code&code_challenge=abc&code_challenge_method=S256",
+        client.client_id,
+        url::form_urlencoded::byte_serialize(REDIRECT_URI.as_bytes()).collect::<String>(),
+    );
+    let response =
+        app.clone().oneshot(Request::get(&uri).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "accounts replace auto-approval with a form");
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(String::from_utf8_lossy(&bytes).contains(r#"name="username""#));
+
+    for (user, password) in [("reader", "wrong"), ("nobody", "reader-pw")] {
+        let response = post_form(
+            &app,
+            "/authorize",
+            &[
+                ("client_id", client.client_id.as_str()),
+                ("redirect_uri", REDIRECT_URI),
+                ("code_challenge", "abc"),
+                ("username", user),
+                ("password", password),
+            ],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.headers().get("Location").is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_read_scope_hides_and_blocks_export_tools() {
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec![REDIRECT_URI.into()]).await;
+    let app = build_router(Arc::clone(&store));
+
+    let reader = login(&app, &client.client_id, "reader", "reader-pw").await;
+    let names = tool_names(&app, &reader).await;
+    assert!(names.iter().any(|n| n == "exhaustive_search"));
+    assert!(!names.iter().any(|n| n == "reference_export"));
+
+    let call = json!({
+        "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+        "params": {"name": "reference_export", "arguments": {"paper_ids": ["abc"]}}
+    });
+    let response = mcp(&app, &reader, call).await;
+    assert!(response["error"].is_object(), "export call must be refused: {response}");
+
+    let admin = login(&app, &client.client_id, "ops", "ops-pw").await;
+    assert!(tool_names(&app, &admin).await.iter().any(|n| n == "reference_export"));
+}
+
+#[tokio::test]
+async fn test_tokens_carry_user_and_require_one() {
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec![REDIRECT_URI.into()]).await;
+    let app = build_router(Arc::clone(&store));
+
+    let reader = login(&app, &client.client_id, "reader", "reader-pw").await;
+    let info = body_json(
+        app.clone()
+            .oneshot(
+                Request::post("/introspect")
+                    .header("Authorization", format!("Bearer {AUTH_TOKEN}"))
+                    .header("Content-Type", "application/x-www-form-urlencoded")
+                    .body(Body::from(format!("token={reader}")))
+                    .unwrap(),
+            )
+            .await
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(info["active"], true);
+    assert_eq!(info["sub"], "reader");
+    assert_eq!(info["scope"], "read");
+
+    // Only admins may introspect.
+    let request = Request::post("/introspect")
+        .header("Authorization", format!("Bearer {reader}"))
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(format!("token={reader}")))
+        .unwrap();
+    assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::FORBIDDEN);
+
+    // A token issued without a user is refused once accounts are enabled.
+    let anonymous = store.create_token_pair(&client.client_id, "mcp").await;
+    let request = Request::post("/mcp")
+        .header("Authorization", format!("Bearer {}", anonymous.access_token))
+        .header("Content-Type", "application/json")
+        .body(Body::from(json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}).to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+async fn introspect_status(app: &axum::Router, token: &str) -> StatusCode {
+    let request = Request::post("/introspect")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(format!("token={token}")))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_admin_scope_only_when_requested() {
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec![REDIRECT_URI.into()]).await;
+    let app = build_router(Arc::clone(&store));
+
+    let implicit = login(&app, &client.client_id, "ops", "ops-pw").await;
+    assert!(tool_names(&app, &implicit).await.iter().any(|n| n == "reference_export"));
+    assert_eq!(introspect_status(&app, &implicit).await, StatusCode::FORBIDDEN);
+
+    let explicit = login_with_scope(&app, &client.client_id, "ops", "ops-pw", "mcp admin").await;
+    assert_eq!(introspect_status(&app, &explicit).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_repeated_failed_logins_are_throttled() {
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec![REDIRECT_URI.into()]).await;
+    let app = build_router(Arc::clone(&store));
+
+    let attempt = |from: [u8; 4], password: &'static str| {
+        let app = app.clone();
+        let body = serde_urlencoded::to_string([
+            ("client_id", client.client_id.as_str()),
+            ("redirect_uri", REDIRECT_URI),
+            ("code_challenge", "abc"),
+            ("username", "reader"),
+            ("password", password),
+        ])
+        .unwrap();
+        let request = Request::post("/authorize")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .extension(ConnectInfo(SocketAddr::from((from, 40000))))
+            .body(Body::from(body))
+            .unwrap();
+        async move { app.oneshot(request).await.unwrap() }
+    };
+    let attacker = [203, 0, 113, 7];
+    for _ in 0..5 {
+        assert_eq!(attempt(attacker, "wrong").await.status(), StatusCode::UNAUTHORIZED);
+    }
+    // Even the right password is refused until the backoff has passed.
+    let response = attempt(attacker, "reader-pw").await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key("Retry-After"));
+
+    // The user can still sign in from elsewhere, and other users are
+    // unaffected.
+    assert_eq!(attempt([198, 51, 100, 1], "reader-pw").await.status(), StatusCode::FOUND);
+    login(&app, &client.client_id, "ops", "ops-pw").await;
+}
@@ -8,11 +8,11 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{McpTool, OrcidAuthorLookupTool, PearlGrowingTool, ToolContext};
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -114,7 +114,7 @@ async fn test_pearl_growing_basic() {
     let tool = PearlGrowingTool;
 
     let result =
-        tool.execute(&ctx, json!({"seedPaperIds": ["seed1"], "iterations": 1})).await.unwrap();
+        tool.execute(&ctx, json!({"seedPaperIds": ["seed1"], "iterations": 1})).await.unwrap().text;
 
     assert!(result.contains("Pearl Growing") || result.contains("pearl"));
     assert!(result.contains("Deep Learning Survey") || result.contains("seed"));
@@ -159,7 +159,8 @@ async fn test_pearl_growing_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("seed_papers").is_some() || parsed.get("total_papers").is_some());
@@ -178,7 +179,7 @@ async fn test_pearl_growing_no_valid_seeds() {
     let ctx = setup_test_context(&mock_server);
     let tool = PearlGrowingTool;
 
-    let result = tool.execute(&ctx, json!({"seedPaperIds": ["invalid"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"seedPaperIds": ["invalid"]})).await.unwrap().text;
 
     assert!(result.contains("No valid seed") || result.contains("error"));
 }
@@ -220,7 +221,8 @@ async fn test_pearl_growing_keywords_strategy() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("keywords") || result.contains("Paper"));
 }
@@ -262,7 +264,8 @@ async fn test_pearl_growing_authors_strategy() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("authors") || result.contains("Paper"));
 }
@@ -305,7 +308,8 @@ async fn test_pearl_growing_citations_strategy() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("citations") || result.contains("Paper"));
 }
@@ -355,7 +359,8 @@ async fn test_pearl_growing_multiple_iterations() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(
         result.contains("Iteration") || result.contains("iteration") || result.contains("Paper")
@@ -379,7 +384,7 @@ async fn test_orcid_lookup_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = OrcidAuthorLookupTool;
 
-    let result = tool.execute(&ctx, json!({"orcid": "0000-0002-1825-0097"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"orcid": "0000-0002-1825-0097"})).await.unwrap().text;
 
     assert!(result.contains("John Smith") || result.contains("ORCID"));
 }
@@ -406,7 +411,8 @@ async fn test_orcid_lookup_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("orcid").is_some());
@@ -450,7 +456,8 @@ async fn test_orcid_lookup_with_papers() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Research Prof") || result.contains("Paper"));
 }
@@ -487,7 +494,8 @@ async fn test_orcid_lookup_with_papers_json() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("papers").is_some());
@@ -531,7 +539,7 @@ async fn test_orcid_lookup_affiliations() {
     let ctx = setup_test_context(&mock_server);
     let tool = OrcidAuthorLookupTool;
 
-    let result = tool.execute(&ctx, json!({"orcid": "0000-0001-1111-2222"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"orcid": "0000-0001-1111-2222"})).await.unwrap().text;
 
     assert!(
         result.contains("Harvard") || result.contains("Affiliated") || result.contains("DeepMind")
@@ -0,0 +1,183 @@
+//! Tests for the tool call audit log and its admin endpoint.
+
+mod common;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router_with, json_body, post_as};
+use semantic_scholar_mcp::server::audit::{AuditConfig, AuditLog};
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::transport::HttpOptions;
+
+const AUTH_TOKEN: &str = "audit-test-token";
+
+fn temp_log() -> PathBuf {
+    std::env::temp_dir().join(format!("s2-audit-{}", uuid::Uuid::new_v4())).join("audit.jsonl")
+}
+
+async fn mock_api() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"matches": [{"id": "p1"}]})))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn build_router(api: &MockServer, store: Arc<OAuthStore>, config: AuditConfig) -> axum::Router {
+    let options = HttpOptions {
+        auth_token: Some(AUTH_TOKEN.into()),
+        oauth_store: Some(store),
+        audit: Some(Arc::new(AuditLog::open(config).unwrap())),
+        ..HttpOptions::default()
+    };
+    build_router_with(&api.uri(), options)
+}
+
+async fn call_tool(app: &axum::Router, token: &str, name: &str, arguments: serde_json::Value) {
+    let body = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": {"name": name, "arguments": arguments}
+    });
+    assert_eq!(post_as(app, token, &body).await.status(), StatusCode::OK);
+}
+
+async fn admin_audit(
+    app: &axum::Router,
+    token: &str,
+    query: &str,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::get(format!("/admin/audit{query}"))
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    (response.status(), json_body(response).await)
+}
+
+#[tokio::test]
+async fn test_tool_calls_are_audited() {
+    let api = mock_api().await;
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(Some("Review".into()), vec!["https://cb".into()]).await;
+    let token = store.create_token_pair(&client.client_id, "mcp").await.access_token;
+    let log = temp_log();
+    let app = build_router(&api, Arc::clone(&store), AuditConfig::new(&log));
+
+    call_tool(&app, &token, "paper_autocomplete", json!({"query": "graph"})).await;
+    call_tool(&app, &token, "paper_autocomplete", json!({"query": "graph"})).await;
+    call_tool(&app, AUTH_TOKEN, "no_such_tool", json!({})).await;
+
+    let (status, _) = admin_audit(&app, &token, "").await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "OAuth clients are not admins");
+
+    let (status, report) = admin_audit(&app, AUTH_TOKEN, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["count"], 3);
+    let records = report["records"].as_array().unwrap();
+
+    let rejected = &records[0];
+    assert_eq!(rejected["principal"], "token");
+    assert_eq!(rejected["tool"], "no_such_tool");
+    assert_eq!(rejected["outcome"], "rejected");
+
+    let (second, first) = (&records[1], &records[2]);
+    assert_eq!(first["principal"], client.client_id.as_str());
+    assert_eq!(first["client_name"], "Review");
+    assert_eq!(first["outcome"], "ok");
+    let upstream = api.received_requests().await.unwrap().len();
+    let counted = first["upstream_requests"].as_u64().unwrap()
+        + second["upstream_requests"].as_u64().unwrap();
+    assert_eq!(counted, upstream as u64);
+    assert!(first["upstream_requests"].as_u64().unwrap() >= 1);
+    assert_eq!(first["arguments_sha256"], second["arguments_sha256"]);
+    assert!(first["session_id"].is_string());
+    assert!(first["duration_ms"].is_u64());
+    assert!(first.get("arguments").is_none(), "arguments are only hashed by default");
+
+    // Filters.
+    let (_, report) =
+        admin_audit(&app, AUTH_TOKEN, &format!("?principal={}", client.client_id)).await;
+    assert_eq!(report["count"], 2);
+    let (_, report) = admin_audit(&app, AUTH_TOKEN, "?outcome=rejected&limit=10").await;
+    assert_eq!(report["count"], 1);
+    let (status, _) = admin_audit(&app, AUTH_TOKEN, "?bogus=1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // One JSON object per line on disk.
+    let lines = std::fs::read_to_string(&log).unwrap();
+    assert_eq!(lines.lines().count(), 3);
+    for line in lines.lines() {
+        serde_json::from_str::<serde_json::Value>(line).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_background_job_runs_are_audited() {
+    let api = mock_api().await;
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(Some("Review".into()), vec!["https://cb".into()]).await;
+    let token = store.create_token_pair(&client.client_id, "mcp").await.access_token;
+    let app = build_router(&api, Arc::clone(&store), AuditConfig::new(temp_log()));
+
+    let job = json!({"tool": "paper_autocomplete", "arguments": {"query": "graph"}});
+    call_tool(&app, &token, "job_submit", job).await;
+
+    // The run is recorded when the job finishes, next to the submission.
+    let mut report = serde_json::Value::Null;
+    for _ in 0..50 {
+        report = admin_audit(&app, AUTH_TOKEN, "?tool=paper_autocomplete").await.1;
+        if report["count"] == 1 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let run = &report["records"][0];
+    assert_eq!(run["principal"], client.client_id.as_str());
+    assert_eq!(run["outcome"], "ok");
+    let upstream = api.received_requests().await.unwrap().len();
+    assert_eq!(run["upstream_requests"], upstream);
+    assert!(upstream >= 1);
+
+    let (_, report) = admin_audit(&app, AUTH_TOKEN, "?tool=job_submit").await;
+    assert_eq!(report["count"], 1);
+    assert_eq!(report["records"][0]["upstream_requests"], 0);
+}
+
+#[tokio::test]
+async fn test_arguments_recorded_when_enabled() {
+    let api = mock_api().await;
+    let store = Arc::new(OAuthStore::new());
+    let config = AuditConfig { record_arguments: true, ..AuditConfig::new(temp_log()) };
+    let app = build_router(&api, store, config);
+
+    call_tool(&app, AUTH_TOKEN, "paper_autocomplete", json!({"query": "graph"})).await;
+
+    let (_, report) = admin_audit(&app, AUTH_TOKEN, "?tool=paper_autocomplete").await;
+    assert_eq!(report["records"][0]["arguments"], json!({"query": "graph"}));
+}
+
+#[tokio::test]
+async fn test_admin_endpoints_closed_without_auth() {
+    let api = mock_api().await;
+    let options = HttpOptions {
+        audit: Some(Arc::new(AuditLog::open(AuditConfig::new(temp_log())).unwrap())),
+        ..HttpOptions::default()
+    };
+    let app = build_router_with(&api.uri(), options);
+
+    for path in ["/admin/audit", "/admin/quotas"] {
+        let request = Request::get(path).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{path} is open without auth");
+    }
+}
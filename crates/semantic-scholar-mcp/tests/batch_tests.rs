@@ -0,0 +1,141 @@
+//! Tests for JSON-RPC batch requests.
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use axum::http::StatusCode;
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router, json_body};
+use semantic_scholar_mcp::server::transport::{JsonRpcResponse, run_batch};
+
+async fn post(app: &axum::Router, body: &serde_json::Value) -> (StatusCode, serde_json::Value) {
+    let response = common::post(app, None, body).await;
+    (response.status(), json_body(response).await)
+}
+
+fn autocomplete_call(id: i64, query: &str) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": {
+            "name": "paper_autocomplete",
+            "arguments": {"query": query, "responseFormat": "json"}
+        }
+    })
+}
+
+async fn mount_autocomplete(mock_server: &MockServer, query: &str, id: &str, delay: Duration) {
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .and(query_param("query", query))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"matches": [{"id": id, "title": query}]}))
+                .set_delay(delay),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_batch_runs_concurrently_and_keeps_order() {
+    let mock_server = MockServer::start().await;
+    mount_autocomplete(&mock_server, "slow", "p-slow", Duration::from_millis(600)).await;
+    mount_autocomplete(&mock_server, "fast", "p-fast", Duration::from_millis(300)).await;
+    let app = build_router(&mock_server.uri());
+
+    let batch = json!([
+        autocomplete_call(1, "slow"),
+        {"jsonrpc": "2.0", "method": "notifications/initialized"},
+        autocomplete_call(2, "fast"),
+        {"jsonrpc": "2.0", "id": 3, "method": "ping"}
+    ]);
+    let started = Instant::now();
+    let (status, response) = post(&app, &batch).await;
+    let elapsed = started.elapsed();
+
+    assert_eq!(status, StatusCode::OK);
+    let responses = response.as_array().expect("batch reply is an array");
+    let ids: Vec<i64> = responses.iter().map(|r| r["id"].as_i64().unwrap()).collect();
+    assert_eq!(ids, vec![1, 2, 3], "notification omitted, order preserved");
+    assert!(responses[0]["result"]["content"][0]["text"].as_str().unwrap().contains("p-slow"));
+    assert!(responses[1]["result"]["content"][0]["text"].as_str().unwrap().contains("p-fast"));
+    assert!(elapsed < Duration::from_millis(850), "calls ran sequentially: {elapsed:?}");
+}
+
+#[tokio::test]
+async fn test_batch_invalid_entries_fail_individually() {
+    let app = build_router("http://unused.localhost");
+
+    let batch = json!([
+        {"jsonrpc": "2.0", "id": "a", "method": "ping"},
+        {"jsonrpc": "2.0", "id": "b"},
+        42
+    ]);
+    let (_, response) = post(&app, &batch).await;
+    let responses = response.as_array().unwrap();
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0]["result"], json!({}));
+    assert_eq!(responses[1]["id"], "b");
+    assert_eq!(responses[1]["error"]["code"], -32600);
+    assert_eq!(responses[2]["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_empty_and_notification_only_batches() {
+    let app = build_router("http://unused.localhost");
+
+    let (status, response) = post(&app, &json!([])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(response.is_object(), "empty batch gets a single error object");
+    assert_eq!(response["error"]["code"], -32600);
+
+    let batch = json!([{"jsonrpc": "2.0", "method": "notifications/initialized"}]);
+    let (status, _) = post(&app, &batch).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+}
+
+#[tokio::test]
+async fn test_unreadable_request_is_answered_with_null_id() {
+    let app = build_router("http://unused.localhost");
+
+    let (_, response) = post(&app, &json!([42])).await;
+    let reply = &response[0];
+    assert_eq!(reply.get("id"), Some(&serde_json::Value::Null), "{response}");
+    assert_eq!(reply["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_notifications_get_no_reply_whatever_their_method() {
+    let app = build_router("http://unused.localhost");
+
+    let (status, _) = post(&app, &json!({"jsonrpc": "2.0", "method": "ping"})).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    let batch = json!([
+        {"jsonrpc": "2.0", "method": "tools/list"},
+        {"jsonrpc": "2.0", "id": 1, "method": "ping"}
+    ]);
+    let (status, response) = post(&app, &batch).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response, json!([{"jsonrpc": "2.0", "id": 1, "result": {}}]));
+}
+
+#[tokio::test]
+async fn test_run_batch_omits_acknowledged_requests() {
+    let items = vec![
+        json!({"jsonrpc": "2.0", "id": 1, "method": "keep"}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "drop"}),
+    ];
+    let reply = run_batch(items, |req| async move {
+        (req.method == "keep").then(|| JsonRpcResponse::success(req.id, json!("ok")))
+    })
+    .await
+    .unwrap();
+    assert_eq!(reply, json!([{"jsonrpc": "2.0", "id": 1, "result": "ok"}]));
+}
@@ -10,14 +10,14 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     BibliographicCouplingTool, CitationHalfLifeTool, CocitationAnalysisTool,
     FieldWeightedImpactTool, HighlyCitedPapersTool, HotPapersTool, McpTool, ToolContext,
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -94,8 +94,11 @@ async fn test_fwci_json_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = FieldWeightedImpactTool;
 
-    let result =
-        tool.execute(&ctx, json!({"paperIds": ["p1"], "responseFormat": "json"})).await.unwrap();
+    let result = tool
+        .execute(&ctx, json!({"paperIds": ["p1"], "responseFormat": "json"}))
+        .await
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("methodology_note").is_some());
@@ -126,8 +129,11 @@ async fn test_fwci_missing_year() {
     let ctx = setup_test_context(&mock_server);
     let tool = FieldWeightedImpactTool;
 
-    let result =
-        tool.execute(&ctx, json!({"paperIds": ["p1"], "responseFormat": "json"})).await.unwrap();
+    let result = tool
+        .execute(&ctx, json!({"paperIds": ["p1"], "responseFormat": "json"}))
+        .await
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed["results"][0]["error"].is_string());
@@ -155,7 +161,7 @@ async fn test_fwci_missing_field() {
     let ctx = setup_test_context(&mock_server);
     let tool = FieldWeightedImpactTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["p1"]})).await.unwrap().text;
 
     assert!(result.contains("Missing year or field data") || result.contains("FWCI"));
 }
@@ -192,8 +198,11 @@ async fn test_highly_cited_json_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = HighlyCitedPapersTool;
 
-    let result =
-        tool.execute(&ctx, json!({"paperIds": ["p1"], "responseFormat": "json"})).await.unwrap();
+    let result = tool
+        .execute(&ctx, json!({"paperIds": ["p1"], "responseFormat": "json"}))
+        .await
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("percentile_threshold").is_some());
@@ -229,8 +238,11 @@ async fn test_highly_cited_custom_percentile() {
     let ctx = setup_test_context(&mock_server);
     let tool = HighlyCitedPapersTool;
 
-    let result =
-        tool.execute(&ctx, json!({"paperIds": ["p1"], "percentileThreshold": 5.0})).await.unwrap();
+    let result = tool
+        .execute(&ctx, json!({"paperIds": ["p1"], "percentileThreshold": 5.0}))
+        .await
+        .unwrap()
+        .text;
 
     assert!(result.contains("Highly Cited") || result.contains("Top"));
 }
@@ -273,7 +285,7 @@ async fn test_citation_half_life_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = CitationHalfLifeTool;
 
-    let result = tool.execute(&ctx, json!({"paperId": "p1"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperId": "p1"})).await.unwrap().text;
 
     assert!(result.contains("Half-life") || result.contains("half_life"));
     assert!(result.contains("Classic Paper"));
@@ -311,7 +323,7 @@ async fn test_citation_half_life_json_format() {
     let tool = CitationHalfLifeTool;
 
     let result =
-        tool.execute(&ctx, json!({"paperId": "p1", "responseFormat": "json"})).await.unwrap();
+        tool.execute(&ctx, json!({"paperId": "p1", "responseFormat": "json"})).await.unwrap().text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("paperId").is_some());
@@ -346,7 +358,7 @@ async fn test_citation_half_life_no_citations() {
     let ctx = setup_test_context(&mock_server);
     let tool = CitationHalfLifeTool;
 
-    let result = tool.execute(&ctx, json!({"paperId": "p1"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperId": "p1"})).await.unwrap().text;
 
     assert!(result.contains("N/A") || result.contains("null"));
 }
@@ -414,7 +426,7 @@ async fn test_cocitation_basic() {
     let tool = CocitationAnalysisTool;
 
     let result =
-        tool.execute(&ctx, json!({"paperId": "focal", "minCocitations": 1})).await.unwrap();
+        tool.execute(&ctx, json!({"paperId": "focal", "minCocitations": 1})).await.unwrap().text;
 
     assert!(result.contains("Co-citation") || result.contains("cocitation"));
 }
@@ -447,8 +459,11 @@ async fn test_cocitation_json_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = CocitationAnalysisTool;
 
-    let result =
-        tool.execute(&ctx, json!({"paperId": "focal", "responseFormat": "json"})).await.unwrap();
+    let result = tool
+        .execute(&ctx, json!({"paperId": "focal", "responseFormat": "json"}))
+        .await
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("focal_paper").is_some());
@@ -515,7 +530,8 @@ async fn test_bibliographic_coupling_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = BibliographicCouplingTool;
 
-    let result = tool.execute(&ctx, json!({"paperId": "focal", "minSharedRefs": 1})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"paperId": "focal", "minSharedRefs": 1})).await.unwrap().text;
 
     assert!(
         result.contains("Bibliographic")
@@ -552,7 +568,7 @@ async fn test_bibliographic_coupling_no_refs() {
     let ctx = setup_test_context(&mock_server);
     let tool = BibliographicCouplingTool;
 
-    let result = tool.execute(&ctx, json!({"paperId": "focal"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperId": "focal"})).await.unwrap().text;
 
     assert!(result.contains("error") || result.contains("No references"));
 }
@@ -581,7 +597,8 @@ async fn test_hot_papers_basic() {
     let result = tool
         .execute(&ctx, json!({"query": "artificial intelligence", "minRecentCitations": 10}))
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Hot Papers") || result.contains("hot"));
     assert!(result.contains("velocity") || result.contains("Velocity"));
@@ -616,7 +633,8 @@ async fn test_hot_papers_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("query").is_some());
@@ -649,7 +667,8 @@ async fn test_hot_papers_year_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should only include papers from 2023+
     assert!(result.contains("Recent") || result.contains("2024"));
@@ -668,7 +687,7 @@ async fn test_hot_papers_empty_results() {
     let ctx = setup_test_context(&mock_server);
     let tool = HotPapersTool;
 
-    let result = tool.execute(&ctx, json!({"query": "nonexistent topic xyz"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "nonexistent topic xyz"})).await.unwrap().text;
 
     assert!(result.contains('0') || result.contains("Hot Papers"));
 }
@@ -0,0 +1,102 @@
+//! Tests for calling tools without an MCP client (the CLI subcommands).
+
+use std::sync::Arc;
+
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use semantic_scholar_mcp::cli::{self, BatchSummary};
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::{ClientConfig, ToolDefaults};
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+fn context(server: &MockServer) -> ToolContext {
+    let client = SemanticScholarClient::new(ClientConfig::for_testing(&server.uri())).unwrap();
+    ToolContext::new(Arc::new(client))
+}
+
+fn paper(id: &str, title: &str) -> serde_json::Value {
+    json!({
+        "paperId": id,
+        "title": title,
+        "year": 2023,
+        "citationCount": 10,
+        "authors": [{"authorId": "a1", "name": "Ada Lovelace"}]
+    })
+}
+
+#[tokio::test]
+async fn test_call_tool_applies_defaults_and_validates() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/author/search"))
+        .and(query_param("limit", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"total": 0, "data": []})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut defaults = ToolDefaults::default();
+    defaults.insert("author_search", "limit", json!(3));
+    let ctx = context(&server).with_tool_defaults(defaults);
+    let tools = tools::register_all_tools();
+
+    cli::call_tool(&tools, &ctx, "author_search", json!({"query": "lovelace"})).await.unwrap();
+
+    let error = cli::call_tool(&tools, &ctx, "author_search", json!({})).await.unwrap_err();
+    assert_eq!(error.code(), "validation");
+    assert_eq!(error.field().as_deref(), Some("query"));
+
+    let error = cli::call_tool(&tools, &ctx, "no_such_tool", json!({})).await.unwrap_err();
+    assert_eq!(error.code(), "not_found");
+}
+
+#[tokio::test]
+async fn test_batch_writes_one_result_per_call_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/graph/v1/paper/batch"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!([paper("p1", "Analytical Engine")])),
+        )
+        .mount(&server)
+        .await;
+    let ctx = context(&server);
+    let tools = tools::register_all_tools();
+
+    let input = [
+        r#"{"id": "export", "tool": "reference_export", "arguments": {"paperIds": ["p1"], "format": "bibtex"}}"#,
+        "",
+        r#"{"id": 2, "tool": "reference_export", "arguments": {"paperIds": "p1"}}"#,
+        "not json",
+        r#"{"tool": "no_such_tool"}"#,
+    ]
+    .join("\n");
+    let mut output = Vec::new();
+    let summary = cli::run_batch(&tools, &ctx, input.as_bytes(), &mut output).await.unwrap();
+    assert_eq!(summary, BatchSummary { calls: 4, failed: 3 });
+
+    let results: Vec<serde_json::Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(results.len(), 4);
+
+    assert_eq!(results[0]["id"], "export");
+    assert_eq!(results[0]["tool"], "reference_export");
+    assert_eq!(results[0]["result"]["format"], "bibtex");
+    assert!(results[0]["result"]["content"].as_str().unwrap().contains("Analytical Engine"));
+    assert!(results[0].get("error").is_none());
+
+    assert_eq!(results[1]["id"], 2);
+    assert_eq!(results[1]["error"]["code"], "validation");
+    assert_eq!(results[1]["error"]["field"], "paperIds");
+
+    assert_eq!(results[2]["line"], 4);
+    assert_eq!(results[2]["error"]["code"], "validation");
+
+    assert_eq!(results[3]["id"], serde_json::Value::Null);
+    assert_eq!(results[3]["error"]["code"], "not_found");
+}
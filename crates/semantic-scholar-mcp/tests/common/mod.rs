@@ -0,0 +1,76 @@
+//! Helpers shared by the HTTP transport tests.
+//!
+//! Every test binary compiles its own copy and uses only some of them.
+
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::request::Builder;
+use axum::response::Response;
+use serde_json::json;
+use tower::ServiceExt;
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::server::transport::{HttpOptions, create_router_with_options};
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+/// A server with every tool, calling the API at `base_url`.
+pub fn build_router(base_url: &str) -> axum::Router {
+    build_router_with(base_url, HttpOptions::default())
+}
+
+/// Like [`build_router`], with the given options.
+pub fn build_router_with(base_url: &str, options: HttpOptions) -> axum::Router {
+    let client = SemanticScholarClient::new(ClientConfig::for_testing(base_url)).unwrap();
+    create_router_with_options(
+        tools::register_all_tools(),
+        ToolContext::new(Arc::new(client)),
+        options,
+    )
+}
+
+/// A `POST /mcp` request naming `session`, if any.
+pub fn mcp_request(session: Option<&str>) -> Builder {
+    let builder = Request::post("/mcp").header("Content-Type", "application/json");
+    match session {
+        Some(id) => builder.header("Mcp-Session-Id", id),
+        None => builder,
+    }
+}
+
+/// POST a JSON-RPC message to `/mcp` in `session`, if any.
+pub async fn post(app: &axum::Router, session: Option<&str>, body: &serde_json::Value) -> Response {
+    send(app, mcp_request(session), body).await
+}
+
+/// POST a JSON-RPC message to `/mcp` with a bearer token.
+pub async fn post_as(app: &axum::Router, token: &str, body: &serde_json::Value) -> Response {
+    send(app, mcp_request(None).header("Authorization", format!("Bearer {token}")), body).await
+}
+
+async fn send(app: &axum::Router, request: Builder, body: &serde_json::Value) -> Response {
+    app.clone().oneshot(request.body(Body::from(body.to_string())).unwrap()).await.unwrap()
+}
+
+/// The JSON body of a response (`null` if it is not JSON).
+pub async fn json_body(response: Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null)
+}
+
+/// The session a response was answered in.
+pub fn session_id(response: &Response) -> String {
+    response.headers()["Mcp-Session-Id"].to_str().unwrap().to_string()
+}
+
+/// Open a session and return its ID.
+pub async fn initialize(app: &axum::Router) -> String {
+    let body = json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}});
+    let response = post(app, None, &body).await;
+    assert_eq!(response.status(), axum::http::StatusCode::OK);
+    session_id(&response)
+}
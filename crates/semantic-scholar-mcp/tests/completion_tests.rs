@@ -0,0 +1,123 @@
+//! Tests for `completion/complete`.
+
+mod common;
+
+use serde_json::json;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router, json_body, post};
+
+async fn complete(
+    app: &axum::Router,
+    reference: serde_json::Value,
+    argument: &str,
+    value: &str,
+) -> serde_json::Value {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "completion/complete",
+        "params": {"ref": reference, "argument": {"name": argument, "value": value}}
+    });
+    json_body(post(app, None, &body).await).await
+}
+
+fn tool_ref(name: &str) -> serde_json::Value {
+    json!({"type": "ref/tool", "name": name})
+}
+
+fn values(response: &serde_json::Value) -> Vec<String> {
+    response["result"]["completion"]["values"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_paper_id_completes_from_title_autocomplete() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .and(query_param("query", "attention is"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "matches": [
+                {"id": "204e3073", "title": "Attention Is All You Need"},
+                {"id": "abc123", "title": "Attention Is Not Explanation"}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+    let app = build_router(&mock_server.uri());
+
+    let response = complete(&app, tool_ref("citation_half_life"), "paperId", "attention is").await;
+    assert_eq!(values(&response), vec!["204e3073", "abc123"]);
+    assert_eq!(response["result"]["completion"]["hasMore"], false);
+}
+
+#[tokio::test]
+async fn test_author_id_completes_from_author_search() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/author/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total": 1,
+            "offset": 0,
+            "data": [{"authorId": "1741101", "name": "Geoffrey Hinton"}]
+        })))
+        .mount(&mock_server)
+        .await;
+    let app = build_router(&mock_server.uri());
+
+    let response = complete(&app, tool_ref("author_papers"), "authorId", "hinton").await;
+    assert_eq!(values(&response), vec!["1741101"]);
+}
+
+#[tokio::test]
+async fn test_short_input_skips_upstream_lookup() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"matches": []})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    let app = build_router(&mock_server.uri());
+
+    let response = complete(&app, tool_ref("citation_half_life"), "paperId", "at").await;
+    assert!(values(&response).is_empty());
+}
+
+#[tokio::test]
+async fn test_static_and_enum_completions() {
+    let app = build_router("http://unused.localhost");
+
+    let response = complete(&app, tool_ref("exhaustive_search"), "fieldsOfStudy", "science").await;
+    let fields = values(&response);
+    assert!(fields.contains(&"Computer Science".to_string()));
+    assert!(fields.contains(&"Political Science".to_string()));
+    assert!(!fields.contains(&"Medicine".to_string()));
+
+    let response = complete(&app, tool_ref("reference_export"), "format", "b").await;
+    assert_eq!(values(&response), vec!["bibtex"]);
+
+    let response = complete(&app, tool_ref("citation_snowball"), "direction", "").await;
+    assert_eq!(values(&response), vec!["citations", "references", "both"]);
+
+    let prompt = json!({"type": "ref/prompt", "name": "anything"});
+    let response = complete(&app, prompt, "format", "").await;
+    assert_eq!(values(&response).len(), 4);
+}
+
+#[tokio::test]
+async fn test_unknown_tool_is_invalid_params() {
+    let app = build_router("http://unused.localhost");
+
+    let response = complete(&app, tool_ref("no_such_tool"), "paperId", "x").await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["field"], "ref.name");
+
+    let response = complete(&app, json!({"type": "ref/resource"}), "paperId", "x").await;
+    assert_eq!(response["error"]["data"]["field"], "ref.type");
+}
@@ -19,7 +19,7 @@ fn test_config_default_has_no_api_key() {
 fn test_config_with_api_key() {
     let config = Config::new(Some("test-key".to_string()), None);
     assert!(config.has_api_key());
-    assert_eq!(config.api_key.as_deref(), Some("test-key"));
+    assert_eq!(config.client.api_key.as_deref(), Some("test-key"));
 }
 
 #[test]
@@ -33,7 +33,7 @@ fn test_config_with_auth_token() {
 fn test_config_with_both_keys() {
     let config = Config::new(Some("api-key".to_string()), Some("auth-token".to_string()));
     assert!(config.has_api_key());
-    assert_eq!(config.api_key.as_deref(), Some("api-key"));
+    assert_eq!(config.client.api_key.as_deref(), Some("api-key"));
     assert_eq!(config.auth_token.as_deref(), Some("auth-token"));
 }
 
@@ -41,7 +41,7 @@ fn test_config_with_both_keys() {
 fn test_config_clone_preserves_api_key() {
     let config = Config::new(Some("secret".to_string()), None);
     let cloned = config.clone();
-    assert_eq!(config.api_key, cloned.api_key);
+    assert_eq!(config.client.api_key, cloned.client.api_key);
 }
 
 // =============================================================================
@@ -51,32 +51,32 @@ fn test_config_clone_preserves_api_key() {
 #[test]
 fn test_client_creation_succeeds() {
     let config = Config::default();
-    let client = SemanticScholarClient::new(config);
+    let client = SemanticScholarClient::new(config.client);
     assert!(client.is_ok());
 }
 
 #[test]
 fn test_client_with_api_key_succeeds() {
     let config = Config::new(Some("test-key".to_string()), None);
-    let client = SemanticScholarClient::new(config);
+    let client = SemanticScholarClient::new(config.client);
     assert!(client.is_ok());
 }
 
 #[test]
 fn test_client_reports_api_key_status() {
     let config = Config::new(Some("key".to_string()), None);
-    let client = SemanticScholarClient::new(config).unwrap();
+    let client = SemanticScholarClient::new(config.client).unwrap();
     assert!(client.has_api_key());
 
     let config_no_key = Config::default();
-    let client_no_key = SemanticScholarClient::new(config_no_key).unwrap();
+    let client_no_key = SemanticScholarClient::new(config_no_key.client).unwrap();
     assert!(!client_no_key.has_api_key());
 }
 
 #[test]
 fn test_client_debug_hides_api_key() {
     let config = Config::new(Some("super-secret-key".to_string()), None);
-    let client = SemanticScholarClient::new(config).unwrap();
+    let client = SemanticScholarClient::new(config.client).unwrap();
     let debug = format!("{client:?}");
     // API key should NOT appear in debug output
     assert!(!debug.contains("super-secret-key"));
@@ -86,7 +86,7 @@ fn test_client_debug_hides_api_key() {
 #[test]
 fn test_client_is_cloneable() {
     let config = Config::default();
-    let client = SemanticScholarClient::new(config).unwrap();
+    let client = SemanticScholarClient::new(config.client).unwrap();
     let _cloned = client;
     // Should compile and work
 }
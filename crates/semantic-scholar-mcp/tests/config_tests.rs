@@ -0,0 +1,101 @@
+//! Tests for the TOML configuration file and per-tool default arguments.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::{ConfigFile, Settings};
+use semantic_scholar_mcp::server::transport::create_router;
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+const EXAMPLE: &str = include_str!("../../../config.example.toml");
+
+#[test]
+fn test_example_config_resolves_every_profile() {
+    let file = ConfigFile::parse(EXAMPLE).unwrap();
+    let tools = tools::register_all_tools();
+    for profile in file.profiles.keys() {
+        let settings = Settings::resolve(&file, Some(profile), &[], &[], &tools).unwrap();
+        assert_eq!(settings.profile.as_deref(), Some(profile.as_str()));
+    }
+    let settings = Settings::resolve(&file, Some("offline"), &[], &[], &tools).unwrap();
+    let config = settings.config(None, None);
+    assert_eq!(config.client.graph_api_url, "http://localhost:8900/graph/v1");
+    assert!(config.client.rate_limit_delay.is_zero());
+}
+
+#[test]
+fn test_load_reports_file_and_key() {
+    let dir = std::env::temp_dir().join(format!("s2-config-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    std::fs::write(&path, "[profiles.server.tools.exhaustive_search]\nmaxResults = \"many\"\n")
+        .unwrap();
+
+    let tools = tools::register_all_tools();
+    Settings::load(Some(&path), None, &[], &tools).unwrap();
+    let error = Settings::load(Some(&path), Some("server"), &[], &tools).unwrap_err().to_string();
+    assert!(error.starts_with(&path.display().to_string()), "{error}");
+    assert!(error.contains("profiles.server.tools.exhaustive_search.maxResults: "), "{error}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+async fn post(app: &axum::Router, body: serde_json::Value) -> serde_json::Value {
+    let request = Request::post("/mcp")
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_tool_defaults_fill_in_arguments() {
+    let api = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search"))
+        .and(query_param("fieldsOfStudy", "Computer Science"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"total": 0, "data": []})))
+        .expect(1..)
+        .mount(&api)
+        .await;
+
+    let file = ConfigFile::parse(&format!(
+        "[client]\ngraph_api_url = \"{}/graph/v1\"\nrate_limit_ms = 0\ncache_max_size = 0\n\n\
+         [tools.exhaustive_search]\nfieldsOfStudy = [\"Computer Science\"]\nmaxResults = 5\n",
+        api.uri()
+    ))
+    .unwrap();
+    let settings = Settings::resolve(&file, None, &[], &[], &tools::register_all_tools()).unwrap();
+    let client = SemanticScholarClient::new(settings.config(None, None).client).unwrap();
+    let ctx = ToolContext::new(Arc::new(client)).with_tool_defaults(settings.tool_defaults);
+    let app = create_router(tools::register_all_tools(), ctx, None, None);
+
+    let listed = post(&app, json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"})).await;
+    let tool = listed["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == "exhaustive_search")
+        .unwrap();
+    assert_eq!(tool["inputSchema"]["properties"]["maxResults"]["default"], 5);
+
+    let called = post(
+        &app,
+        json!({
+            "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+            "params": {"name": "exhaustive_search", "arguments": {"query": "graphs"}}
+        }),
+    )
+    .await;
+    assert_eq!(called["result"]["isError"], json!(null), "{called}");
+}
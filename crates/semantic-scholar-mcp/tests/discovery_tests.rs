@@ -8,14 +8,14 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     BulkBooleanSearchTool, CitationSnowballTool, ExhaustiveSearchTool, McpTool,
     RecommendationsTool, SnippetSearchTool, ToolContext,
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -90,7 +90,8 @@ async fn test_citation_snowball_forward() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(
         result.contains("Citing Paper")
@@ -140,7 +141,8 @@ async fn test_citation_snowball_backward() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Reference Paper") || result.contains("snowball"));
 }
@@ -191,7 +193,8 @@ async fn test_citation_snowball_both_directions() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(
         result.contains("Citer") || result.contains("Referenced") || result.contains("snowball")
@@ -243,7 +246,8 @@ async fn test_citation_snowball_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(
@@ -299,7 +303,8 @@ async fn test_citation_snowball_depth_2() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should have both levels
     assert!(result.contains("Level") || result.contains("snowball"));
@@ -345,7 +350,8 @@ async fn test_citation_snowball_deduplication() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Duplicate") || result.contains("snowball"));
 }
@@ -390,7 +396,8 @@ async fn test_citation_snowball_min_citations_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // MUST include high citation paper AND exclude low citation paper
     assert!(result.contains("High Citations"), "Should include paper with 500 citations");
@@ -428,7 +435,8 @@ async fn test_exhaustive_search_fields_of_study_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("CS Paper") || result.contains("machine"));
 }
@@ -458,7 +466,8 @@ async fn test_exhaustive_search_open_access_only() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Open Access Paper") || result.contains("Found") || result.len() > 10);
 }
@@ -493,7 +502,8 @@ async fn test_recommendations_with_negative_seeds() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Positive Rec") || result.contains("Recommended"));
 }
@@ -525,7 +535,8 @@ async fn test_recommendations_with_limit() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Rec") || result.contains("Recommended"));
 }
@@ -556,7 +567,8 @@ async fn test_recommendations_fields_of_study_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Physics") || result.contains("Recommended"));
 }
@@ -662,7 +674,8 @@ async fn test_bulk_boolean_search_basic() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Transformer") || result.contains("Boolean"));
 }
@@ -695,7 +708,8 @@ async fn test_bulk_boolean_search_with_filters() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Filtered Paper") || result.contains("Boolean"));
 }
@@ -725,7 +739,8 @@ async fn test_bulk_boolean_search_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("papers").is_some() || parsed.get("query").is_some());
@@ -759,7 +774,8 @@ async fn test_bulk_boolean_search_pagination() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Page 1") || result.contains("Boolean"));
 }
@@ -789,7 +805,8 @@ async fn test_bulk_boolean_search_with_sort() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Sorted Paper") || result.contains("Boolean"));
 }
@@ -880,7 +897,8 @@ async fn test_snippet_search_basic() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Method Paper") || result.contains("Snippet"));
 }
@@ -912,7 +930,8 @@ async fn test_snippet_search_with_filters() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Filtered Snippet") || result.contains("Snippet"));
 }
@@ -941,7 +960,8 @@ async fn test_snippet_search_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("snippets").is_some() || parsed.get("query").is_some());
@@ -971,7 +991,8 @@ async fn test_snippet_search_with_limit() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Limited Snippet") || result.contains("Snippet"));
 }
@@ -997,7 +1018,8 @@ async fn test_snippet_search_empty_results() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains('0') || result.contains("no") || result.contains("Snippet"));
 }
@@ -8,13 +8,13 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     ExhaustiveSearchTool, McpTool, ReferenceExportTool, ResearchTrendsTool, ToolContext,
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -102,7 +102,8 @@ async fn test_exhaustive_search_with_embeddings() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Embeddings Paper") || result.len() > 10);
 }
@@ -135,7 +136,8 @@ async fn test_exhaustive_search_unlimited_results() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Unlimited Paper") || result.len() > 10);
 }
@@ -165,7 +167,8 @@ async fn test_exhaustive_search_year_end_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should filter out 2024 paper
     assert!(result.contains("Old Paper") || result.len() > 10);
@@ -203,7 +206,8 @@ async fn test_research_trends_single_year() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("2023") || result.contains("Single Year") || result.len() > 10);
 }
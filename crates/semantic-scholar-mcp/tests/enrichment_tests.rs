@@ -8,14 +8,14 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     AuthorBatchTool, AuthorPapersTool, AuthorSearchTool, BatchMetadataTool, McpTool,
     PaperAuthorsTool, PaperAutocompleteTool, PaperTitleMatchTool, ToolContext,
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -66,7 +66,7 @@ async fn test_batch_metadata_markdown_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = BatchMetadataTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1", "p2"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["p1", "p2"]})).await.unwrap().text;
 
     assert!(result.contains("Paper One"));
     assert!(result.contains("Paper Two"));
@@ -99,7 +99,8 @@ async fn test_batch_metadata_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.is_object());
@@ -131,7 +132,8 @@ async fn test_batch_metadata_custom_fields() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Custom Fields") || result.contains("2023"));
 }
@@ -149,7 +151,8 @@ async fn test_batch_metadata_empty_results() {
     let ctx = setup_test_context(&mock_server);
     let tool = BatchMetadataTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["invalid1", "invalid2"]})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"paperIds": ["invalid1", "invalid2"]})).await.unwrap().text;
 
     // Empty but not error
     assert!(result.is_empty() || result.contains("[]") || result.len() < 50);
@@ -195,7 +198,7 @@ async fn test_author_search_markdown_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorSearchTool;
 
-    let result = tool.execute(&ctx, json!({"query": "Smith"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "Smith"})).await.unwrap().text;
 
     assert!(result.contains("John Smith") || result.contains("Jane Smith"));
 }
@@ -225,7 +228,8 @@ async fn test_author_search_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.is_array());
@@ -256,7 +260,8 @@ async fn test_author_search_with_limit() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Limited") || !result.is_empty());
 }
@@ -277,7 +282,7 @@ async fn test_author_search_no_results() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorSearchTool;
 
-    let result = tool.execute(&ctx, json!({"query": "nonexistent xyz"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "nonexistent xyz"})).await.unwrap().text;
 
     // Empty but not error
     assert!(result.is_empty() || result.contains("[]") || result.len() < 50);
@@ -317,7 +322,7 @@ async fn test_author_papers_markdown_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorPapersTool;
 
-    let result = tool.execute(&ctx, json!({"authorId": "author123"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"authorId": "author123"})).await.unwrap().text;
 
     assert!(result.contains("Prolific Writer"));
     assert!(result.contains("Paper One"));
@@ -359,7 +364,8 @@ async fn test_author_papers_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.is_object());
@@ -424,7 +430,8 @@ async fn test_author_papers_with_year_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Filtered Author"));
     assert!(result.contains("Target Paper"));
@@ -520,7 +527,7 @@ async fn test_paper_title_match_data_wrapper() {
     let ctx = setup_test_context(&mock_server);
     let tool = PaperTitleMatchTool;
 
-    let result = tool.execute(&ctx, json!({"title": "Wrapped Paper"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"title": "Wrapped Paper"})).await.unwrap().text;
 
     assert!(result.contains("Wrapped Paper"));
 }
@@ -555,7 +562,8 @@ async fn test_batch_metadata_partial_not_found() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Found Paper One"));
     assert!(result.contains("Found Paper Three"));
@@ -595,7 +603,7 @@ async fn test_paper_autocomplete_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = PaperAutocompleteTool;
 
-    let result = tool.execute(&ctx, json!({"query": "attention"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "attention"})).await.unwrap().text;
 
     assert!(result.contains("Attention") || result.contains("autocomplete"));
 }
@@ -632,7 +640,8 @@ async fn test_paper_autocomplete_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("suggestions").is_some());
@@ -653,7 +662,7 @@ async fn test_paper_autocomplete_empty() {
     let ctx = setup_test_context(&mock_server);
     let tool = PaperAutocompleteTool;
 
-    let result = tool.execute(&ctx, json!({"query": "xyz123nonexistent"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "xyz123nonexistent"})).await.unwrap().text;
 
     assert!(result.contains("No suggestions") || result.contains("suggestions"));
 }
@@ -700,7 +709,8 @@ async fn test_paper_title_match_found() {
     let ctx = setup_test_context(&mock_server);
     let tool = PaperTitleMatchTool;
 
-    let result = tool.execute(&ctx, json!({"title": "Attention Is All You Need"})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"title": "Attention Is All You Need"})).await.unwrap().text;
 
     assert!(result.contains("Attention") || result.contains("Match"));
 }
@@ -718,7 +728,7 @@ async fn test_paper_title_match_not_found() {
     let ctx = setup_test_context(&mock_server);
     let tool = PaperTitleMatchTool;
 
-    let result = tool.execute(&ctx, json!({"title": "Nonexistent Paper XYZ"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"title": "Nonexistent Paper XYZ"})).await.unwrap().text;
 
     assert!(result.contains("No exact match") || result.contains("Match"));
 }
@@ -750,7 +760,8 @@ async fn test_paper_title_match_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("matched").is_some());
@@ -798,7 +809,7 @@ async fn test_paper_authors_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = PaperAuthorsTool;
 
-    let result = tool.execute(&ctx, json!({"paperId": "p123"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperId": "p123"})).await.unwrap().text;
 
     assert!(result.contains("First Author") || result.contains("Authors"));
 }
@@ -827,7 +838,8 @@ async fn test_paper_authors_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("authors").is_some());
@@ -873,7 +885,7 @@ async fn test_author_batch_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorBatchTool;
 
-    let result = tool.execute(&ctx, json!({"authorIds": ["a1", "a2"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"authorIds": ["a1", "a2"]})).await.unwrap().text;
 
     assert!(result.contains("Batch Author") || result.contains("Author"));
 }
@@ -905,7 +917,8 @@ async fn test_author_batch_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("authors").is_some());
@@ -929,7 +942,8 @@ async fn test_author_batch_partial_results() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorBatchTool;
 
-    let result = tool.execute(&ctx, json!({"authorIds": ["a1", "invalid", "a3"]})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"authorIds": ["a1", "invalid", "a3"]})).await.unwrap().text;
 
     assert!(result.contains("Valid Author") || result.contains("Found"));
 }
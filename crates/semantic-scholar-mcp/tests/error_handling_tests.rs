@@ -9,13 +9,13 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     BatchMetadataTool, CitationSnowballTool, ExhaustiveSearchTool, McpTool, ToolContext,
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -168,7 +168,7 @@ async fn test_batch_with_null_entries() {
     assert!(result.is_ok() || result.is_err());
     if let Ok(output) = result {
         // If it succeeds, should have filtered the null
-        assert!(output.contains("Valid Paper"));
+        assert!(output.text.contains("Valid Paper"));
     }
 }
 
@@ -226,7 +226,7 @@ async fn test_citation_graph_cycle() {
 
     // Should complete without hanging/panicking
     assert!(result.is_ok(), "Should handle citation cycles gracefully");
-    let output = result.unwrap();
+    let output = result.unwrap().text;
     // Should have both papers but not duplicate them infinitely
     assert!(output.contains("Paper A") || output.contains("Paper B"));
 }
@@ -276,7 +276,8 @@ async fn test_exhaustive_search_pagination() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should contain papers from BOTH pages
     assert!(result.contains("Page 1 Paper"), "Should have first page results");
@@ -14,7 +14,7 @@ use semantic_scholar_mcp::error::{ClientError, ToolError};
 fn test_rate_limited_error_construction() {
     let err = ClientError::rate_limited(60);
     assert!(matches!(err, ClientError::RateLimited { .. }));
-    assert_eq!(err.retry_after(), Some(Duration::from_secs(60)));
+    assert_eq!(err.retry_after(), Some(Duration::from_mins(1)));
 }
 
 #[test]
@@ -26,7 +26,7 @@ fn test_rate_limited_zero_seconds() {
 #[test]
 fn test_rate_limited_large_value() {
     let err = ClientError::rate_limited(86400); // 24 hours
-    assert_eq!(err.retry_after(), Some(Duration::from_secs(86400)));
+    assert_eq!(err.retry_after(), Some(Duration::from_hours(24)));
 }
 
 #[test]
@@ -160,7 +160,7 @@ fn test_not_retryable_unexpected_status() {
 #[test]
 fn test_retry_after_rate_limited() {
     let err = ClientError::rate_limited(120);
-    assert_eq!(err.retry_after(), Some(Duration::from_secs(120)));
+    assert_eq!(err.retry_after(), Some(Duration::from_mins(2)));
 }
 
 #[test]
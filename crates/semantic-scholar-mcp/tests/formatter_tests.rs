@@ -632,3 +632,47 @@ fn test_compact_author_serialization() {
     let json_str = serde_json::to_string(&compact).unwrap();
     assert!(json_str.contains("John"));
 }
+
+// =============================================================================
+// Reference Export Formats
+// =============================================================================
+
+#[test]
+fn test_bibtex_escaping_keeps_braces_balanced() {
+    let mut paper = make_paper("abc123", r"Sets {x \in X} and a stray } brace", 2024, 10);
+    paper.authors =
+        vec![AuthorRef { author_id: None, name: Some("Ada {Byron}, Lovelace".to_string()) }];
+    let output = formatters::format_bibtex(&[paper], false);
+
+    assert!(output.starts_with("@article{Lovelace2024,\n"), "{output}");
+    assert!(output.contains(
+        r"title = {Sets \textbraceleft{}x \textbackslash{}in X\textbraceright{} and a stray \textbraceright{} brace},"
+    ));
+    // BibTeX counts escaped braces too.
+    let depth = output.chars().try_fold(0i32, |depth, c| {
+        let depth = depth + i32::from(c == '{') - i32::from(c == '}');
+        (depth >= 0).then_some(depth)
+    });
+    assert_eq!(depth, Some(0), "{output}");
+}
+
+#[test]
+fn test_line_based_exports_keep_values_on_one_line() {
+    let mut paper = make_paper("abc123", "Line one\r\nline two", 2024, 10);
+    paper.venue = Some("Venue\nER  - ".to_string());
+
+    let ris = formatters::format_ris(std::slice::from_ref(&paper), false);
+    assert!(ris.contains("TI  - Line one line two\n"));
+    assert_eq!(ris.matches("\nER  - ").count(), 1, "{ris}");
+
+    let endnote = formatters::format_endnote(&[paper], false);
+    assert!(endnote.contains("%T Line one line two\n"));
+    assert!(endnote.lines().all(|line| line.is_empty() || line.starts_with('%')), "{endnote}");
+}
+
+#[test]
+fn test_csv_neutralises_formulas() {
+    assert_eq!(formatters::csv_escape("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+    assert_eq!(formatters::csv_escape("+1"), "'+1");
+    assert_eq!(formatters::csv_escape("plain"), "plain");
+}
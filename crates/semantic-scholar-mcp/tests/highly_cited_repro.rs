@@ -1,10 +1,10 @@
-use wiremock::{Mock, MockServer, ResponseTemplate};
-use wiremock::matchers::{method, path};
-use serde_json::json;
-use semantic_scholar_mcp::tools::{McpTool, ToolContext, HighlyCitedPapersTool};
 use semantic_scholar_mcp::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::tools::{HighlyCitedPapersTool, McpTool, ToolContext};
+use serde_json::json;
 use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
 async fn test_highly_cited_threshold_bug() {
@@ -61,7 +61,7 @@ async fn test_highly_cited_threshold_bug() {
         .mount(&mock_server)
         .await;
 
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     let ctx = ToolContext::new(Arc::new(client));
     let tool = HighlyCitedPapersTool;
@@ -72,12 +72,15 @@ async fn test_highly_cited_threshold_bug() {
         "responseFormat": "json"
     });
 
-    let result = tool.execute(&ctx, input).await.unwrap();
+    let result = tool.execute(&ctx, input).await.unwrap().text;
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
-    
+
     let paper_result = &parsed["results"][0];
     // Threshold should be cites[149] = 1000 - 149 = 851.
-    assert!(paper_result["threshold"].as_i64().unwrap() > 0, "Threshold should be determined via pagination");
+    assert!(
+        paper_result["threshold"].as_i64().unwrap() > 0,
+        "Threshold should be determined via pagination"
+    );
     assert_eq!(paper_result["threshold"], 851);
     assert_eq!(paper_result["is_highly_cited"], false); // 850 < 851
 }
@@ -7,7 +7,7 @@
 #![allow(dead_code)]
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::{Config, fields};
+use semantic_scholar_mcp::config::{ClientConfig, fields};
 use std::sync::Arc;
 
 /// Well-known paper IDs for testing.
@@ -31,8 +31,10 @@ mod author_ids {
 }
 
 fn create_client() -> Arc<SemanticScholarClient> {
-    let config =
-        Config { api_key: std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok(), ..Config::default() };
+    let config = ClientConfig {
+        api_key: std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok(),
+        ..ClientConfig::default()
+    };
     Arc::new(SemanticScholarClient::new(config).expect("Failed to create client"))
 }
 
@@ -203,8 +205,10 @@ async fn test_get_recommendations_multiple_seeds() {
 #[tokio::test]
 async fn test_search_authors() {
     let client = create_client();
-    let result =
-        client.search_authors("Geoffrey Hinton", 0, 5, fields::AUTHOR).await.expect("Author search should succeed");
+    let result = client
+        .search_authors("Geoffrey Hinton", 0, 5, fields::AUTHOR)
+        .await
+        .expect("Author search should succeed");
 
     assert!(!result.data.is_empty(), "Should find Geoffrey Hinton");
 }
@@ -246,9 +250,8 @@ async fn test_zero_citation_paper() {
     match result {
         Ok(search_result) => {
             // Check that papers with 0 citations are handled
-            let zero_cite_papers: Vec<_> =
-                search_result.data.iter().filter(|p| p.citations() == 0).collect();
-            println!("Found {} papers with 0 citations", zero_cite_papers.len());
+            let zero_cite_papers = search_result.data.iter().filter(|p| p.citations() == 0).count();
+            println!("Found {} papers with 0 citations", zero_cite_papers);
         }
         Err(e) => {
             // Rate limiting is acceptable for this test
@@ -0,0 +1,135 @@
+//! End-to-end tests for background job mode over the HTTP transport.
+
+mod common;
+
+use std::time::Duration;
+
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router, initialize, json_body, post};
+
+async fn call_tool(
+    app: &axum::Router,
+    session: &str,
+    name: &str,
+    arguments: serde_json::Value,
+) -> serde_json::Value {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {"name": name, "arguments": arguments}
+    });
+    json_body(post(app, Some(session), &body).await).await["result"].clone()
+}
+
+async fn wait_for_finish(app: &axum::Router, session: &str, job_id: &str) -> serde_json::Value {
+    for _ in 0..100 {
+        let status = call_tool(app, session, "job_status", json!({"jobId": job_id})).await;
+        if status["structuredContent"]["status"] != "running" {
+            return status;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("job {job_id} did not finish");
+}
+
+#[tokio::test]
+async fn test_job_submit_poll_and_fetch_result() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search/bulk"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total": 2,
+            "data": [
+                {"paperId": "b1", "title": "Bulk One", "citationCount": 3},
+                {"paperId": "b2", "title": "Bulk Two", "citationCount": 1}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+    let app = build_router(&mock_server.uri());
+    let session = initialize(&app).await;
+
+    let submitted = call_tool(
+        &app,
+        &session,
+        "job_submit",
+        json!({"tool": "bulk_boolean_search", "arguments": {"query": "+graph"}}),
+    )
+    .await;
+    let job_id = submitted["structuredContent"]["jobId"].as_str().unwrap().to_string();
+    assert!(submitted.get("isError").is_none());
+
+    let status = wait_for_finish(&app, &session, &job_id).await;
+    assert_eq!(status["structuredContent"]["status"], "completed");
+    assert_eq!(status["structuredContent"]["partialCount"], 1);
+    assert_eq!(status["structuredContent"]["partials"][0]["papers"][1]["id"], "b2");
+
+    let result = call_tool(&app, &session, "job_result", json!({"jobId": job_id})).await;
+    assert_eq!(result["structuredContent"]["total"], 2);
+    assert!(result["content"][0]["text"].as_str().unwrap().contains("Bulk One"));
+
+    // Another session can neither see nor cancel the job.
+    let other = initialize(&app).await;
+    for tool in ["job_status", "job_result", "job_cancel"] {
+        let result = call_tool(&app, &other, tool, json!({"jobId": job_id})).await;
+        assert_eq!(result["isError"], true, "{tool}");
+    }
+}
+
+#[tokio::test]
+async fn test_job_submit_rejects_bad_arguments() {
+    let app = build_router("http://unused.localhost");
+    let session = initialize(&app).await;
+
+    let result = call_tool(&app, &session, "job_submit", json!({"tool": "batch_metadata"})).await;
+    assert_eq!(result["isError"], true);
+    assert_eq!(result["_meta"]["error"]["field"], "arguments.paperIds");
+
+    let result = call_tool(&app, &session, "job_submit", json!({"tool": "no_such_tool"})).await;
+    assert_eq!(result["_meta"]["error"]["field"], "tool");
+}
+
+#[tokio::test]
+async fn test_job_unknown_id_is_not_found() {
+    let app = build_router("http://unused.localhost");
+    let session = initialize(&app).await;
+
+    let result = call_tool(&app, &session, "job_status", json!({"jobId": "nope"})).await;
+    assert_eq!(result["isError"], true);
+    assert_eq!(result["_meta"]["error"]["code"], "not_found");
+}
+
+#[tokio::test]
+async fn test_job_cancel_running_job() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search/bulk"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"total": 0, "data": []}))
+                .set_delay(Duration::from_secs(3)),
+        )
+        .mount(&mock_server)
+        .await;
+    let app = build_router(&mock_server.uri());
+    let session = initialize(&app).await;
+
+    let submitted = call_tool(
+        &app,
+        &session,
+        "job_submit",
+        json!({"tool": "bulk_boolean_search", "arguments": {"query": "slow"}}),
+    )
+    .await;
+    let job_id = submitted["structuredContent"]["jobId"].as_str().unwrap().to_string();
+
+    let cancelled = call_tool(&app, &session, "job_cancel", json!({"jobId": job_id})).await;
+    assert_eq!(cancelled["structuredContent"]["status"], "cancelled");
+
+    let again = call_tool(&app, &session, "job_cancel", json!({"jobId": job_id})).await;
+    assert_eq!(again["isError"], true);
+}
@@ -0,0 +1,110 @@
+//! Tests for the MCP logging capability over the HTTP transport.
+
+mod common;
+
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::Request;
+use futures::StreamExt;
+use serde_json::json;
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router, initialize, json_body, post};
+use semantic_scholar_mcp::server::logging::McpLogLayer;
+
+#[tokio::test]
+async fn test_initialize_advertises_logging() {
+    let app = build_router("http://unused.localhost");
+    let body = json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}});
+    let response = json_body(post(&app, None, &body).await).await;
+    assert!(response["result"]["capabilities"]["logging"].is_object());
+}
+
+#[tokio::test]
+async fn test_set_level_rejects_unknown_level() {
+    let app = build_router("http://unused.localhost");
+    let body = json!({"jsonrpc": "2.0", "id": 1, "method": "logging/setLevel", "params": {"level": "loud"}});
+    let response = json_body(post(&app, None, &body).await).await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["field"], "level");
+}
+
+#[tokio::test]
+async fn test_dropped_prisma_query_is_reported_to_client() {
+    let subscriber = tracing_subscriber::registry().with(McpLogLayer::filtered());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search"))
+        .and(query_param("query", "broken"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("nope"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total": 1,
+            "offset": 0,
+            "data": [{"paperId": "p1", "title": "Working"}]
+        })))
+        .mount(&mock_server)
+        .await;
+    let app = build_router(&mock_server.uri());
+
+    let session = initialize(&app).await;
+    let response = post(
+        &app,
+        Some(&session),
+        &json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "logging/setLevel",
+            "params": {"level": "warning"}
+        }),
+    )
+    .await;
+    let response = json_body(response).await;
+    assert_eq!(response["result"], json!({}));
+
+    let response = post(
+        &app,
+        Some(&session),
+        &json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {"name": "prisma_search", "arguments": {"queries": ["works", "broken"]}}
+        }),
+    )
+    .await;
+    let response = json_body(response).await;
+    assert!(response["result"]["isError"].is_null());
+
+    // Replay the session's SSE buffer: the warning precedes the tool result.
+    let response = app
+        .clone()
+        .oneshot(Request::get(format!("/mcp?sessionId={session}")).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let mut stream = response.into_body().into_data_stream();
+    let mut text = String::new();
+    while !text.contains("\"id\":3") {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("SSE stream stalled")
+            .unwrap()
+            .unwrap();
+        text.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+
+    let log_at = text.find("notifications/message").expect("no log notification");
+    assert!(log_at < text.find("\"id\":3").unwrap());
+    assert!(text.contains("\"logger\":\"tools::systematic\""));
+    assert!(text.contains("\"query\":\"broken\""));
+    assert!(text.contains("Search query failed"));
+}
@@ -133,23 +133,50 @@ fn test_jsonrpc_error_structure() {
     assert!(error_response.get("result").is_none());
 }
 
-/// Test tool execution error
-#[test]
-fn test_tool_error_response() {
-    let error_response = json!({
+/// Test tool execution error (reported as an `isError` result, not a JSON-RPC error)
+#[tokio::test]
+async fn test_tool_error_response() {
+    use std::sync::Arc;
+
+    use semantic_scholar_mcp::client::SemanticScholarClient;
+    use semantic_scholar_mcp::config::ClientConfig;
+    use semantic_scholar_mcp::server::stdio;
+    use semantic_scholar_mcp::tools::{self, ToolContext};
+    use tokio::io::AsyncReadExt;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/missing/authors"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Paper not found"))
+        .mount(&mock_server)
+        .await;
+    let client = SemanticScholarClient::new(ClientConfig::for_testing(&mock_server.uri())).unwrap();
+    let ctx = ToolContext::new(Arc::new(client));
+    let input = json!({
         "jsonrpc": "2.0",
         "id": 1,
-        "error": {
-            "code": -32000,
-            "message": "Tool execution failed",
-            "data": {
-                "tool": "exhaustive_search",
-                "reason": "Rate limited"
-            }
-        }
-    });
-
-    assert!(error_response["error"]["data"]["reason"].as_str().unwrap().contains("Rate"));
+        "method": "tools/call",
+        "params": {"name": "paper_authors", "arguments": {"paperId": "missing"}}
+    })
+    .to_string();
+
+    let (output, mut replies) = tokio::io::duplex(64 * 1024);
+    let serve = stdio::serve(input.as_bytes(), output, tools::register_all_tools(), ctx, None);
+    let mut text = String::new();
+    let (served, read) = tokio::join!(serve, replies.read_to_string(&mut text));
+    served.unwrap();
+    read.unwrap();
+
+    let error_response: serde_json::Value = serde_json::from_str(text.trim()).unwrap();
+    assert!(error_response.get("error").is_none());
+    let result = &error_response["result"];
+    assert_eq!(result["isError"], true);
+    assert!(result["content"][0]["text"].as_str().unwrap().contains("Not found"));
+    assert_eq!(result["_meta"]["error"]["code"], "not_found");
+    // The error must not pose as output that breaks the tool's outputSchema.
+    assert!(result.get("structuredContent").is_none());
 }
 
 // =============================================================================
@@ -313,3 +340,49 @@ fn test_initialize_response() {
 
     assert_eq!(response["result"]["serverInfo"]["name"], "semantic-scholar-mcp");
 }
+
+// =============================================================================
+// Stdio Transport
+// =============================================================================
+
+/// Test that the stdio transport answers every line in order
+#[tokio::test]
+async fn test_stdio_serve_answers_each_line() {
+    use std::sync::Arc;
+
+    use semantic_scholar_mcp::client::SemanticScholarClient;
+    use semantic_scholar_mcp::config::ClientConfig;
+    use semantic_scholar_mcp::server::stdio;
+    use semantic_scholar_mcp::tools::{self, ToolContext};
+    use tokio::io::AsyncReadExt;
+
+    let client =
+        SemanticScholarClient::new(ClientConfig::for_testing("http://unused.localhost")).unwrap();
+    let ctx = ToolContext::new(Arc::new(client));
+    let input = [
+        r#"{"jsonrpc": "2.0", "id": 1, "method": "ping"}"#,
+        "",
+        "{not json",
+        r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#,
+        r#"{"jsonrpc": "2.0", "method": "ping"}"#,
+        r#"[{"jsonrpc": "2.0", "id": 2, "method": "ping"}, {"jsonrpc": "2.0", "id": 3, "method": "nope"}]"#,
+    ]
+    .join("\n");
+
+    let (output, mut replies) = tokio::io::duplex(64 * 1024);
+    let serve = stdio::serve(input.as_bytes(), output, tools::register_all_tools(), ctx, None);
+    let mut text = String::new();
+    let (served, read) = tokio::join!(serve, replies.read_to_string(&mut text));
+    served.unwrap();
+    read.unwrap();
+
+    let replies: Vec<serde_json::Value> =
+        text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(replies[0], json!({"jsonrpc": "2.0", "id": 1, "result": {}}));
+    assert_eq!(replies[1]["error"]["code"], -32700);
+    assert_eq!(replies[1].get("id"), Some(&serde_json::Value::Null));
+    // Notifications get no reply, whatever their method.
+    assert_eq!(replies.len(), 3, "{text}");
+    assert_eq!(replies[2][0]["id"], 2);
+    assert_eq!(replies[2][1]["error"]["code"], -32601);
+}
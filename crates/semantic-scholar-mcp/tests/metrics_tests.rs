@@ -0,0 +1,111 @@
+//! Tests for the Prometheus `/metrics` endpoint.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router_with, post_as};
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::transport::HttpOptions;
+
+const AUTH_TOKEN: &str = "metrics-test-token";
+
+async fn build_router(api: &MockServer, store: Arc<OAuthStore>) -> axum::Router {
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"matches": []})))
+        .mount(api)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/649def34f8be52c8b66281af98ae884c09aef38b/authors"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Paper not found"))
+        .mount(api)
+        .await;
+    let options = HttpOptions {
+        auth_token: Some(AUTH_TOKEN.into()),
+        oauth_store: Some(store),
+        ..HttpOptions::default()
+    };
+    build_router_with(&api.uri(), options)
+}
+
+async fn call_tool(app: &axum::Router, name: &str, arguments: serde_json::Value) {
+    let body = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": {"name": name, "arguments": arguments}
+    });
+    assert_eq!(post_as(app, AUTH_TOKEN, &body).await.status(), StatusCode::OK);
+}
+
+async fn scrape(app: &axum::Router) -> String {
+    let request = Request::get("/metrics")
+        .header("Authorization", format!("Bearer {AUTH_TOKEN}"))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["Content-Type"].to_str().unwrap().starts_with("text/plain"));
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_metrics_count_tool_calls_and_upstream_requests() {
+    let api = MockServer::start().await;
+    let app = build_router(&api, Arc::new(OAuthStore::new())).await;
+
+    call_tool(&app, "paper_autocomplete", json!({"query": "graph"})).await;
+    call_tool(
+        &app,
+        "paper_authors",
+        json!({"paperId": "649def34f8be52c8b66281af98ae884c09aef38b"}),
+    )
+    .await;
+    call_tool(&app, "no_such_tool", json!({})).await;
+
+    let text = scrape(&app).await;
+    for expected in [
+        r#"mcp_tool_calls_total{outcome="ok",tool="paper_autocomplete"} 1"#,
+        r#"mcp_tool_calls_total{outcome="error",tool="paper_authors"} 1"#,
+        r#"mcp_tool_calls_total{outcome="rejected",tool="unknown"} 1"#,
+        r#"s2_upstream_requests_total{endpoint="/paper/autocomplete",status="200"} 1"#,
+        r#"s2_upstream_requests_total{endpoint="/paper/{id}/authors",status="404"} 1"#,
+        r#"s2_cache_lookups_total{result="miss"} 2"#,
+        r#"s2_rate_limit_wait_seconds_count{queue="normal"} 2"#,
+        "s2_upstream_retries_total 0",
+        "mcp_active_sessions 3",
+    ] {
+        assert!(text.contains(expected), "missing {expected} in:\n{text}");
+    }
+    assert!(text.contains(r#"mcp_tool_call_duration_seconds_bucket{tool="paper_autocomplete""#));
+}
+
+#[tokio::test]
+async fn test_metrics_count_token_issuance() {
+    let api = MockServer::start().await;
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec!["https://cb".into()]).await;
+    let pair = store.create_token_pair(&client.client_id, "mcp").await;
+    let app = build_router(&api, Arc::clone(&store)).await;
+
+    let form = format!(
+        "grant_type=refresh_token&refresh_token={}&client_id={}",
+        pair.refresh_token, client.client_id
+    );
+    let request = Request::post("/token")
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(form))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let text = scrape(&app).await;
+    assert!(text.contains(r#"mcp_oauth_tokens_issued_total{grant_type="refresh_token"} 1"#));
+}
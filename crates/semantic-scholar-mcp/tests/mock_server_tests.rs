@@ -0,0 +1,212 @@
+//! Tests for the mock Semantic Scholar API, driven through the real client.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde_json::json;
+
+use semantic_scholar_mcp::cli;
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::{ClientConfig, fields};
+use semantic_scholar_mcp::error::ClientError;
+use semantic_scholar_mcp::mock::{self, Dataset, FaultStats, Faults, MockOptions};
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+const ATTENTION: &str = "ARXIV:1706.03762";
+const BERT: &str = "DOI:10.18653/v1/N19-1423";
+
+async fn start(options: MockOptions) -> (String, SemanticScholarClient) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let router = mock::router(Dataset::sample(), options);
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    let client = SemanticScholarClient::new(ClientConfig::for_testing(&base)).unwrap();
+    (base, client)
+}
+
+#[tokio::test]
+async fn test_search_paginates_filters_and_enforces_limits() {
+    let (_, client) = start(MockOptions::default()).await;
+
+    let first = client.search_papers("transformer", 0, 2, fields::DEFAULT, &[]).await.unwrap();
+    assert!(first.total > 2);
+    assert_eq!(first.next, Some(2));
+    let second = client.search_papers("transformer", 2, 2, fields::DEFAULT, &[]).await.unwrap();
+    assert_eq!(second.offset, 2);
+    assert!(first.data.iter().all(|p| second.data.iter().all(|q| q.paper_id != p.paper_id)));
+
+    let filters = [("year".to_owned(), "2020-".to_owned())];
+    let recent = client.search_papers("language models", 0, 100, fields::DEFAULT, &filters).await;
+    let recent = recent.unwrap();
+    assert!(!recent.data.is_empty());
+    assert!(recent.data.iter().all(|p| p.year.unwrap() >= 2020));
+    assert_eq!(recent.next, None);
+
+    let error = client.search_papers("transformer", 0, 101, fields::DEFAULT, &[]).await;
+    assert!(matches!(error, Err(ClientError::BadRequest { .. })), "{error:?}");
+    let error = client.search_papers("transformer", 950, 100, fields::DEFAULT, &[]).await;
+    assert!(matches!(error, Err(ClientError::BadRequest { .. })), "{error:?}");
+    let error = client.search_papers("transformer", 0, 10, &["title", "colour"], &[]).await;
+    assert!(matches!(error, Err(ClientError::BadRequest { .. })), "{error:?}");
+}
+
+#[tokio::test]
+async fn test_lookups_resolve_aliases_and_report_missing_papers() {
+    let (_, client) = start(MockOptions::default()).await;
+
+    let bert = client.get_paper(BERT, fields::DEFAULT).await.unwrap();
+    assert!(bert.title.unwrap().starts_with("BERT"));
+    assert_eq!(bert.reference_count, Some(4));
+
+    let batch = client
+        .get_papers_batch_with_nulls(
+            &[ATTENTION.to_owned(), "no-such-paper".to_owned(), bert.paper_id.clone()],
+            fields::MINIMAL,
+        )
+        .await
+        .unwrap();
+    assert_eq!(batch.len(), 3);
+    assert!(batch[1].is_none());
+    assert_eq!(batch[2].as_ref().unwrap().paper_id, bert.paper_id);
+
+    let missing = client.get_paper("no-such-paper", fields::DEFAULT).await;
+    assert!(matches!(missing, Err(ClientError::NotFound { .. })), "{missing:?}");
+    let title = client.search_paper_by_title("attention is all you need", &["title"]).await;
+    assert!(title.unwrap().is_some());
+    assert!(client.search_paper_by_title("unrelated words", &["title"]).await.unwrap().is_none());
+
+    // Nulls in the fixtures come back as nulls, lists as empty lists.
+    let survey = client.search_papers("survey", 0, 1, fields::DEFAULT, &[]).await.unwrap();
+    let survey = &survey.data[0];
+    assert_eq!(survey.year, None);
+    assert_eq!(survey.authors[0].author_id, None);
+}
+
+#[tokio::test]
+async fn test_citation_graph_authors_and_recommendations() {
+    let (_, client) = start(MockOptions::default()).await;
+    let attention = client.get_paper(ATTENTION, &["title"]).await.unwrap().paper_id;
+
+    let citations = client.get_citations(&attention, 0, 3, &["title", "year"]).await.unwrap();
+    assert_eq!(citations.data.len(), 3);
+    assert_eq!(citations.next, Some(3));
+    assert!(citations.data.iter().all(|c| c.paper.as_ref().unwrap().year.unwrap() >= 2018));
+    let references = client.get_references(&attention, 0, 100, &["title"]).await.unwrap();
+    assert_eq!(references.data.len(), 4);
+    assert_eq!(references.next, None);
+
+    let authors = client.get_paper_authors(&attention).await.unwrap();
+    assert_eq!(authors[0].name.as_deref(), Some("Ashish Vaswani"));
+    assert_eq!(authors[0].affiliations, ["Google Brain"]);
+    assert_eq!(authors[0].paper_count, Some(1));
+
+    let found = client.search_authors("sutskever", 0, 10, fields::AUTHOR).await.unwrap();
+    assert_eq!(found.total, 1);
+    let sutskever = &found.data[0].author_id;
+    let papers = client.get_author_papers(sutskever, 0, 100, fields::MINIMAL).await.unwrap();
+    assert!(papers.data.len() >= 4);
+    assert!(papers.data.windows(2).all(|w| w[0].year >= w[1].year));
+    let batch = client.get_authors_batch(&[sutskever.clone(), "0".to_owned()]).await.unwrap();
+    assert_eq!(batch.len(), 1);
+
+    let recommended =
+        client.get_recommendations(std::slice::from_ref(&attention), None, 5, &["title"]).await;
+    let recommended = recommended.unwrap();
+    assert_eq!(recommended.len(), 5);
+    assert!(recommended.iter().all(|p| p.paper_id != attention));
+    let bert = client.get_paper(BERT, &["title"]).await.unwrap().paper_id;
+    let recommended = client
+        .get_recommendations(
+            &[attention, bert.clone()],
+            Some(std::slice::from_ref(&bert)),
+            5,
+            &["title"],
+        )
+        .await;
+    assert!(matches!(recommended, Err(ClientError::BadRequest { .. })), "{recommended:?}");
+
+    let snippets = client.search_snippets("bidirectional", 5, &[]).await.unwrap();
+    assert_eq!(snippets.data[0].paper.as_ref().unwrap().paper_id.as_deref(), Some(bert.as_str()));
+    assert!(
+        snippets.data[0].snippet.as_ref().unwrap().text.as_ref().unwrap().contains("bidirectional")
+    );
+}
+
+#[tokio::test]
+async fn test_bulk_search_pages_with_tokens() {
+    let (_, client) = start(MockOptions { bulk_page_size: 3, ..MockOptions::default() }).await;
+
+    let sort = Some("citationCount:desc");
+    let mut token = None;
+    let mut seen = Vec::new();
+    let total = loop {
+        let page = client
+            .search_papers_bulk("", token.as_deref(), &["citationCount"], sort, &[])
+            .await
+            .unwrap();
+        assert!(page.data.len() <= 3);
+        seen.extend(page.data);
+        token = page.token;
+        if token.is_none() {
+            break page.total;
+        }
+    };
+    assert_eq!(usize::try_from(total).unwrap(), Dataset::sample().paper_count());
+    assert_eq!(seen.len(), Dataset::sample().paper_count());
+    assert_eq!(seen.iter().map(|p| &p.paper_id).collect::<HashSet<_>>().len(), seen.len());
+    assert!(seen.windows(2).all(|w| w[0].citation_count >= w[1].citation_count));
+
+    let matched = client
+        .search_papers_bulk(r#""language model" -bert"#, None, &["title"], None, &[])
+        .await
+        .unwrap();
+    assert!(matched.total > 0);
+    assert!(matched.data.iter().all(|p| !p.title.as_ref().unwrap().contains("BERT")));
+
+    let error = client.search_papers_bulk("", Some("bogus"), &["title"], None, &[]).await;
+    assert!(matches!(error, Err(ClientError::BadRequest { .. })), "{error:?}");
+}
+
+#[tokio::test]
+async fn test_injected_faults_are_retried_and_counted() {
+    let faults =
+        Faults { rate_limit_every: Some(2), retry_after_secs: Some(0), ..Faults::default() };
+    let (base, client) = start(MockOptions { faults, ..MockOptions::default() }).await;
+    let http = reqwest::Client::new();
+
+    client.get_paper(ATTENTION, &["title"]).await.unwrap();
+    // The second request is answered with 429 and retried by the client.
+    client.get_paper(ATTENTION, &["title"]).await.unwrap();
+    let stats: FaultStats =
+        http.get(format!("{base}/_mock/stats")).send().await.unwrap().json().await.unwrap();
+    assert_eq!(stats, FaultStats { requests: 3, rate_limited: 1, errors: 0 });
+
+    let faults = json!({"errorEvery": 1, "errorStatus": 503, "pathPrefix": "/graph/v1/author"});
+    let response = http.put(format!("{base}/_mock/faults")).json(&faults).send().await.unwrap();
+    assert!(response.status().is_success());
+    let response = http.get(format!("{base}/graph/v1/author/1701686")).send().await.unwrap();
+    assert_eq!(response.status(), 503);
+    let response = http.get(format!("{base}/graph/v1/paper/{ATTENTION}")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = http.put(format!("{base}/_mock/faults")).json(&json!({"typo": 1})).send().await;
+    assert!(response.unwrap().status().is_client_error());
+}
+
+#[tokio::test]
+async fn test_tools_run_against_the_mock() {
+    let (_, client) = start(MockOptions::default()).await;
+    let ctx = ToolContext::new(Arc::new(client));
+    let tools = tools::register_all_tools();
+
+    let output = cli::call_tool(
+        &tools,
+        &ctx,
+        "citation_snowball",
+        json!({"seedPaperIds": [ATTENTION], "direction": "both", "depth": 1}),
+    )
+    .await
+    .unwrap();
+    assert!(output.text.contains("BERT"), "{}", output.text);
+    assert!(output.structured.is_object());
+}
@@ -8,11 +8,11 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{AuthorNetworkTool, McpTool, ToolContext};
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -103,7 +103,7 @@ async fn test_author_network_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorNetworkTool;
 
-    let result = tool.execute(&ctx, json!({"authorId": "main_author"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"authorId": "main_author"})).await.unwrap().text;
 
     assert!(
         result.contains("Collaboration") || result.contains("Author") || result.contains("network")
@@ -147,7 +147,8 @@ async fn test_author_network_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("author").is_some() || parsed.get("collaborators").is_some());
@@ -205,7 +206,8 @@ async fn test_author_network_min_shared_papers() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should include frequent collaborator (3 papers) but may filter rare (1 paper)
     assert!(result.contains("Frequent") || result.contains("Collaboration"));
@@ -255,7 +257,8 @@ async fn test_author_network_max_collaborators() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should limit to 3 collaborators
     assert!(result.contains("Collab") || result.contains("Collaboration"));
@@ -285,7 +288,7 @@ async fn test_author_network_no_collaborators() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorNetworkTool;
 
-    let result = tool.execute(&ctx, json!({"authorId": "solo_author"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"authorId": "solo_author"})).await.unwrap().text;
 
     assert!(result.contains("No collaborators") || result.contains('0') || result.contains("Solo"));
 }
@@ -313,7 +316,7 @@ async fn test_author_network_no_papers() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorNetworkTool;
 
-    let result = tool.execute(&ctx, json!({"authorId": "no_papers"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"authorId": "no_papers"})).await.unwrap().text;
 
     assert!(
         result.contains('0')
@@ -372,7 +375,7 @@ async fn test_author_network_pagination() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorNetworkTool;
 
-    let result = tool.execute(&ctx, json!({"authorId": "paginated"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"authorId": "paginated"})).await.unwrap().text;
 
     assert!(
         result.contains("Friend") || result.contains("Collaboration") || result.contains("Author")
@@ -14,7 +14,7 @@ use sha2::{Digest, Sha256};
 use tower::ServiceExt;
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::server::transport::create_router;
 use semantic_scholar_mcp::tools::{self, ToolContext};
 
@@ -22,7 +22,7 @@ const AUTH_TOKEN: &str = "test-secret-token-12345";
 const BASE_URL: &str = "https://example.com";
 
 fn build_test_router() -> axum::Router {
-    let config = Config::for_testing("http://unused.localhost");
+    let config = ClientConfig::for_testing("http://unused.localhost");
     let client = SemanticScholarClient::new(config).unwrap();
     let ctx = ToolContext::new(Arc::new(client));
     let tools = tools::register_all_tools();
@@ -142,21 +142,24 @@ async fn test_full_oauth_http_flow() {
     let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
     assert!(result.get("result").is_some());
 
-    // 7. Refresh Token
-    let params = [("grant_type", "refresh_token"), ("refresh_token", refresh_token)];
-    let body_str = serde_urlencoded::to_string(params).unwrap();
-
-    let response = app
-        .clone()
-        .oneshot(
-            Request::post("/token")
-                .header("Content-Type", "application/x-www-form-urlencoded")
-                .body(Body::from(body_str))
-                .unwrap(),
-        )
-        .await
-        .unwrap();
+    // 7. Refresh Token, which only the client it was issued to can redeem
+    let refresh = |client_id: Option<&str>| {
+        let mut params = vec![("grant_type", "refresh_token"), ("refresh_token", refresh_token)];
+        params.extend(client_id.map(|id| ("client_id", id)));
+        Request::post("/token")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(serde_urlencoded::to_string(params).unwrap()))
+            .unwrap()
+    };
+    for client_id in [None, Some("someone-else")] {
+        let response = app.clone().oneshot(refresh(client_id)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["error"], "invalid_grant");
+    }
 
+    let response = app.clone().oneshot(refresh(Some(&client_id))).await.unwrap();
     assert_eq!(response.status(), StatusCode::OK);
     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
     let new_token_info: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -217,11 +220,8 @@ async fn test_authorize_rejects_wrong_redirect_uri() {
         url_encode("https://evil.com/steal"),
     );
 
-    let response = app
-        .clone()
-        .oneshot(Request::get(&uri).body(Body::empty()).unwrap())
-        .await
-        .unwrap();
+    let response =
+        app.clone().oneshot(Request::get(&uri).body(Body::empty()).unwrap()).await.unwrap();
 
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
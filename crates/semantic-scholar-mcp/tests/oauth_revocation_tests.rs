@@ -0,0 +1,154 @@
+//! Tests for persistent OAuth state, refresh-token rotation, revocation
+//! (RFC 7009) and introspection (RFC 7662).
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::transport::{HttpOptions, create_router_with_options};
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+const AUTH_TOKEN: &str = "test-secret-token-12345";
+
+fn build_router(store: Arc<OAuthStore>) -> axum::Router {
+    let client =
+        SemanticScholarClient::new(ClientConfig::for_testing("http://unused.localhost")).unwrap();
+    let options = HttpOptions {
+        base_url: Some("https://example.com".into()),
+        auth_token: Some(AUTH_TOKEN.into()),
+        oauth_store: Some(store),
+        ..HttpOptions::default()
+    };
+    create_router_with_options(
+        tools::register_all_tools(),
+        ToolContext::new(Arc::new(client)),
+        options,
+    )
+}
+
+fn temp_file() -> PathBuf {
+    std::env::temp_dir().join(format!("s2-oauth-{}.json", uuid::Uuid::new_v4()))
+}
+
+async fn post_form(
+    app: &axum::Router,
+    uri: &str,
+    bearer: Option<&str>,
+    params: &[(&str, &str)],
+) -> (StatusCode, serde_json::Value) {
+    let mut builder =
+        Request::post(uri).header("Content-Type", "application/x-www-form-urlencoded");
+    if let Some(token) = bearer {
+        builder = builder.header("Authorization", format!("Bearer {token}"));
+    }
+    let body = serde_urlencoded::to_string(params).unwrap();
+    let response = app.clone().oneshot(builder.body(Body::from(body)).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+}
+
+/// Status of a `tools/list` call made with `token`.
+async fn mcp_status(app: &axum::Router, token: &str) -> StatusCode {
+    let request = Request::post("/mcp")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}).to_string()))
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_grants_survive_restart_and_are_stored_hashed() {
+    let path = temp_file();
+    let store = OAuthStore::open(&path).unwrap();
+    let client = store.register_client(Some("Claude".into()), vec!["https://cb".into()]).await;
+    let pair = store.create_token_pair(&client.client_id, "mcp").await;
+    drop(store);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains(&pair.access_token));
+    assert!(!contents.contains(&pair.refresh_token));
+
+    let reopened = OAuthStore::open(&path).unwrap();
+    assert!(reopened.get_client(&client.client_id).await.is_some());
+    assert_eq!(
+        reopened.validate_access_token(&pair.access_token).await.as_deref(),
+        Some(client.client_id.as_str())
+    );
+    assert!(reopened.refresh_token_pair(&pair.refresh_token, &client.client_id).await.is_some());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_refresh_token_reuse_revokes_grant() {
+    let store = Arc::new(OAuthStore::new());
+    let pair = store.create_token_pair("client1", "mcp").await;
+    let app = build_router(Arc::clone(&store));
+
+    let refresh = [
+        ("grant_type", "refresh_token"),
+        ("refresh_token", pair.refresh_token.as_str()),
+        ("client_id", "client1"),
+    ];
+    let (status, rotated) = post_form(&app, "/token", None, &refresh).await;
+    assert_eq!(status, StatusCode::OK);
+    let new_access = rotated["access_token"].as_str().unwrap();
+    assert_eq!(mcp_status(&app, new_access).await, StatusCode::OK);
+
+    // Replaying the old refresh token is rejected and kills the new pair.
+    let (status, error) = post_form(&app, "/token", None, &refresh).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_grant");
+    assert_eq!(mcp_status(&app, new_access).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_revoke_endpoint() {
+    let store = Arc::new(OAuthStore::new());
+    let pair = store.create_token_pair("client1", "mcp").await;
+    let app = build_router(Arc::clone(&store));
+
+    // Revoking the refresh token ends the whole grant.
+    let (status, _) =
+        post_form(&app, "/revoke", None, &[("token", pair.refresh_token.as_str())]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(mcp_status(&app, &pair.access_token).await, StatusCode::UNAUTHORIZED);
+
+    // Unknown tokens are not an error; a missing token is.
+    let (status, _) = post_form(&app, "/revoke", None, &[("token", "bogus")]).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, error) = post_form(&app, "/revoke", None, &[]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_request");
+}
+
+#[tokio::test]
+async fn test_introspect_endpoint() {
+    let store = Arc::new(OAuthStore::new());
+    let pair = store.create_token_pair("client1", "mcp").await;
+    let app = build_router(Arc::clone(&store));
+    let query = [("token", pair.access_token.as_str())];
+
+    let (status, _) = post_form(&app, "/introspect", None, &query).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "introspection requires authentication");
+
+    let (status, info) = post_form(&app, "/introspect", Some(AUTH_TOKEN), &query).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(info["active"], true);
+    assert_eq!(info["client_id"], "client1");
+    assert_eq!(info["scope"], "mcp");
+    assert_eq!(info["token_type"], "Bearer");
+    assert!(info["exp"].as_u64().unwrap() > info["iat"].as_u64().unwrap());
+
+    let (_, info) = post_form(&app, "/introspect", Some(AUTH_TOKEN), &[("token", "bogus")]).await;
+    assert_eq!(info, json!({"active": false}));
+}
@@ -10,7 +10,7 @@ use serde_json::json;
 use tower::ServiceExt;
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::server::transport::create_router;
 use semantic_scholar_mcp::tools::{self, ToolContext};
 
@@ -18,7 +18,7 @@ const AUTH_TOKEN: &str = "test-secret-token-12345";
 const BASE_URL: &str = "https://example.com";
 
 fn build_test_router() -> axum::Router {
-    let config = Config::for_testing("http://unused.localhost");
+    let config = ClientConfig::for_testing("http://unused.localhost");
     let client = SemanticScholarClient::new(config).unwrap();
     let ctx = ToolContext::new(Arc::new(client));
     let tools = tools::register_all_tools();
@@ -88,6 +88,33 @@ async fn test_401_includes_www_authenticate() {
     assert!(www_auth.contains("oauth-protected-resource"));
 }
 
+#[tokio::test]
+async fn test_root_post_requires_auth() {
+    let app = build_test_router();
+
+    let call = json!({
+        "jsonrpc": "2.0",
+        "method": "tools/call",
+        "params": {"name": "exhaustive_search", "arguments": {"query": "attention"}},
+        "id": 1
+    });
+    let response = app
+        .clone()
+        .oneshot(
+            Request::post("/")
+                .header("Content-Type", "application/json")
+                .body(Body::from(call.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // The health check at `/` stays public.
+    let response = app.oneshot(Request::get("/").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 // ─── Dynamic Client Registration ─────────────────────────────────────────────
 
 #[tokio::test]
@@ -143,7 +170,7 @@ async fn test_legacy_token_auth_still_works() {
 
     let response = app
         .oneshot(
-            Request::post(&format!("/mcp?token={AUTH_TOKEN}"))
+            Request::post(format!("/mcp?token={AUTH_TOKEN}"))
                 .header("Content-Type", "application/json")
                 .body(Body::from(json!({"jsonrpc":"2.0","method":"tools/list","id":1}).to_string()))
                 .unwrap(),
@@ -252,7 +279,8 @@ async fn test_full_oauth_flow_via_store() {
     assert_eq!(validated.as_deref(), Some(client.client_id.as_str()));
 
     // 7. Refresh
-    let new_tokens = store.refresh_token_pair(&tokens.refresh_token).await.unwrap();
+    let new_tokens =
+        store.refresh_token_pair(&tokens.refresh_token, &client.client_id).await.unwrap();
     assert_ne!(new_tokens.access_token, tokens.access_token);
 
     // Old token is invalid
@@ -0,0 +1,267 @@
+//! Tests for delegating the OAuth login to an upstream OpenID Connect
+//! provider, against a wiremock IdP.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::server::accounts::Scope;
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::oauth::oidc::{ClaimRule, OidcConfig, OidcProvider};
+use semantic_scholar_mcp::server::transport::{HttpOptions, create_router_with_options};
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+const AUTH_TOKEN: &str = "test-secret-token-12345";
+const BASE_URL: &str = "https://mcp.example.com";
+const REDIRECT_URI: &str = "https://client.example.com/cb";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+/// A mock identity provider serving discovery, token and userinfo.
+struct MockIdp {
+    server: MockServer,
+}
+
+impl MockIdp {
+    async fn start() -> Self {
+        let server = MockServer::start().await;
+        let issuer = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "issuer": issuer,
+                "authorization_endpoint": format!("{issuer}/authorize"),
+                "token_endpoint": format!("{issuer}/token"),
+                "userinfo_endpoint": format!("{issuer}/userinfo"),
+            })))
+            .mount(&server)
+            .await;
+        Self { server }
+    }
+
+    fn config(&self) -> OidcConfig {
+        OidcConfig {
+            issuer: self.server.uri(),
+            client_id: "scholar-mcp".into(),
+            client_secret: Some("idp-secret".into()),
+            scopes: "openid email".into(),
+            groups_claim: "groups".into(),
+            grant_lifetime_secs: 3600,
+            rules: vec![
+                ClaimRule {
+                    email_domain: Some("uni.edu".into()),
+                    group: None,
+                    scopes: vec![Scope::Read],
+                },
+                ClaimRule {
+                    email_domain: None,
+                    group: Some("library".into()),
+                    scopes: vec![Scope::Export],
+                },
+            ],
+        }
+    }
+
+    /// Answer the next code exchange with an ID token for `claims`.
+    async fn issue(&self, code: &str, nonce: &str, claims: serde_json::Value) {
+        let mut payload = json!({
+            "iss": self.server.uri(),
+            "aud": "scholar-mcp",
+            "sub": "user-1",
+            "exp": 4_102_444_800_u64,
+            "nonce": nonce,
+        });
+        payload.as_object_mut().unwrap().extend(claims.as_object().unwrap().clone());
+        let id_token = format!(
+            "{}.{}.sig",
+            URL_SAFE_NO_PAD.encode(br#"{"alg":"RS256"}"#),
+            URL_SAFE_NO_PAD.encode(payload.to_string())
+        );
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains(format!("code={code}")))
+            .and(body_string_contains("code_verifier="))
+            .and(body_string_contains("client_secret=idp-secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "upstream-access",
+                "token_type": "Bearer",
+                "id_token": id_token,
+            })))
+            .mount(&self.server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/userinfo"))
+            .and(header("Authorization", "Bearer upstream-access"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"sub": "user-1"})))
+            .mount(&self.server)
+            .await;
+    }
+}
+
+async fn build_router(idp: &MockIdp, store: Arc<OAuthStore>) -> axum::Router {
+    let client =
+        SemanticScholarClient::new(ClientConfig::for_testing("http://unused.localhost")).unwrap();
+    let provider = OidcProvider::discover(idp.config()).await.unwrap();
+    let options = HttpOptions {
+        base_url: Some(BASE_URL.into()),
+        auth_token: Some(AUTH_TOKEN.into()),
+        oauth_store: Some(store),
+        oidc: Some(Arc::new(provider)),
+        ..HttpOptions::default()
+    };
+    create_router_with_options(
+        tools::register_all_tools(),
+        ToolContext::new(Arc::new(client)),
+        options,
+    )
+}
+
+async fn get(app: &axum::Router, uri: &str) -> axum::response::Response {
+    app.clone().oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap()
+}
+
+fn query_of(response: &axum::response::Response) -> HashMap<String, String> {
+    let location = response.headers()["Location"].to_str().unwrap();
+    url::Url::parse(location).unwrap().query_pairs().into_owned().collect()
+}
+
+/// Start the MCP client's authorization and return the IdP redirect's query.
+async fn authorize(app: &axum::Router, client_id: &str) -> HashMap<String, String> {
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(CODE_VERIFIER.as_bytes()));
+    let uri = format!(
+        "/authorize?client_id={client_id}&redirect_uri={}&response_type=code&state=client-state&code_challenge={challenge}&code_challenge_method=S256&scope=mcp",
+        url::form_urlencoded::byte_serialize(REDIRECT_URI.as_bytes()).collect::<String>(),
+    );
+    let response = get(app, &uri).await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    query_of(&response)
+}
+
+#[tokio::test]
+async fn test_login_delegated_to_idp_maps_claims_to_scopes() {
+    let idp = MockIdp::start().await;
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec![REDIRECT_URI.into()]).await;
+    let app = build_router(&idp, Arc::clone(&store)).await;
+
+    let upstream = authorize(&app, &client.client_id).await;
+    assert_eq!(upstream["client_id"], "scholar-mcp");
+    assert_eq!(upstream["redirect_uri"], format!("{BASE_URL}/oauth/callback"));
+    assert_eq!(upstream["scope"], "openid email");
+    assert_eq!(upstream["code_challenge_method"], "S256");
+
+    idp.issue(
+        "idp-code",
+        &upstream["nonce"],
+        json!({"email": "ada@uni.edu", "email_verified": true, "groups": ["library"]}),
+    )
+    .await;
+    let response =
+        get(&app, &format!("/oauth/callback?code=idp-code&state={}", upstream["state"])).await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let back = query_of(&response);
+    assert_eq!(back["state"], "client-state");
+
+    let exchange = serde_urlencoded::to_string([
+        ("grant_type", "authorization_code"),
+        ("code", back["code"].as_str()),
+        ("redirect_uri", REDIRECT_URI),
+        ("code_verifier", CODE_VERIFIER),
+        ("client_id", client.client_id.as_str()),
+    ])
+    .unwrap();
+    let response = app
+        .clone()
+        .oneshot(
+            Request::post("/token")
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(Body::from(exchange))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let token: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    let access_token = token["access_token"].as_str().unwrap();
+
+    let info = store.introspect(access_token).await.unwrap();
+    assert_eq!(info.subject.as_deref(), Some("ada@uni.edu"));
+    assert_eq!(info.scope, "read export");
+
+    // Refreshing cannot stretch the grant past the configured lifetime.
+    let refresh = store.introspect(token["refresh_token"].as_str().unwrap()).await.unwrap();
+    assert!(refresh.expires_at - refresh.issued_at <= 3600);
+    let refreshed = store
+        .refresh_token_pair(token["refresh_token"].as_str().unwrap(), &client.client_id)
+        .await
+        .unwrap();
+    let refresh = store.introspect(&refreshed.refresh_token).await.unwrap();
+    assert!(refresh.expires_at <= info.issued_at + 3600);
+
+    // The state is single-use.
+    let replay =
+        get(&app, &format!("/oauth/callback?code=idp-code&state={}", upstream["state"])).await;
+    assert_eq!(replay.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_user_matching_no_rule_is_denied() {
+    let idp = MockIdp::start().await;
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec![REDIRECT_URI.into()]).await;
+    let app = build_router(&idp, Arc::clone(&store)).await;
+
+    let upstream = authorize(&app, &client.client_id).await;
+    idp.issue(
+        "idp-code",
+        &upstream["nonce"],
+        json!({"email": "eve@elsewhere.com", "email_verified": true}),
+    )
+    .await;
+    let response =
+        get(&app, &format!("/oauth/callback?code=idp-code&state={}", upstream["state"])).await;
+    assert_eq!(response.status(), StatusCode::FOUND);
+    let back = query_of(&response);
+    assert_eq!(back["error"], "access_denied");
+    assert_eq!(back["state"], "client-state");
+    assert!(!back.contains_key("code"));
+}
+
+#[tokio::test]
+async fn test_id_token_with_wrong_nonce_is_rejected() {
+    let idp = MockIdp::start().await;
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec![REDIRECT_URI.into()]).await;
+    let app = build_router(&idp, Arc::clone(&store)).await;
+
+    let upstream = authorize(&app, &client.client_id).await;
+    idp.issue(
+        "idp-code",
+        "replayed-nonce",
+        json!({"email": "ada@uni.edu", "email_verified": true}),
+    )
+    .await;
+    let response =
+        get(&app, &format!("/oauth/callback?code=idp-code&state={}", upstream["state"])).await;
+    assert_eq!(query_of(&response)["error"], "access_denied");
+
+    // Provider-side errors and unknown states are handled too.
+    let upstream = authorize(&app, &client.client_id).await;
+    let response =
+        get(&app, &format!("/oauth/callback?error=access_denied&state={}", upstream["state"]))
+            .await;
+    assert_eq!(query_of(&response)["error"], "access_denied");
+    let response = get(&app, "/oauth/callback?code=x&state=unknown").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
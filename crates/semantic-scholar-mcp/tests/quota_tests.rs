@@ -0,0 +1,150 @@
+//! Tests for per-client request quotas and the quota admin endpoint.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router_with, json_body, post_as};
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::policy::{ToolPolicy, ToolPolicyHandle};
+use semantic_scholar_mcp::server::transport::HttpOptions;
+
+const AUTH_TOKEN: &str = "quota-test-token";
+
+async fn mock_api() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"matches": [{"id": "p1"}]})))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn build_router(api: &MockServer, store: Arc<OAuthStore>) -> axum::Router {
+    let policy: ToolPolicy = serde_json::from_value(json!({
+        "quotas": {
+            "default": {"per_day": 100},
+            "clients": {"Limited": {"per_minute": 4}}
+        }
+    }))
+    .unwrap();
+    let options = HttpOptions {
+        auth_token: Some(AUTH_TOKEN.into()),
+        oauth_store: Some(store),
+        tool_policy: ToolPolicyHandle::new(policy),
+        ..HttpOptions::default()
+    };
+    build_router_with(&api.uri(), options)
+}
+
+/// Call `paper_autocomplete`, which makes two upstream requests
+/// (autocomplete, then a batch lookup of the matches).
+async fn call_autocomplete(app: &axum::Router, token: &str, query: &str) -> serde_json::Value {
+    let body = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": {"name": "paper_autocomplete", "arguments": {"query": query}}
+    });
+    let response = post_as(app, token, &body).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    json_body(response).await["result"].clone()
+}
+
+async fn admin_quotas(app: &axum::Router, token: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::get("/admin/quotas")
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    (response.status(), json_body(response).await)
+}
+
+#[tokio::test]
+async fn test_client_over_quota_gets_structured_error() {
+    let api = mock_api().await;
+    let store = Arc::new(OAuthStore::new());
+    let limited = store.register_client(Some("Limited".into()), vec!["https://cb".into()]).await;
+    let other = store.register_client(Some("Other".into()), vec!["https://cb".into()]).await;
+    let limited_token = store.create_token_pair(&limited.client_id, "mcp").await.access_token;
+    let other_token = store.create_token_pair(&other.client_id, "mcp").await.access_token;
+    let app = build_router(&api, Arc::clone(&store));
+
+    for query in ["a", "b"] {
+        let result = call_autocomplete(&app, &limited_token, query).await;
+        assert_ne!(result["isError"], true, "{result}");
+    }
+    let result = call_autocomplete(&app, &limited_token, "c").await;
+    assert_eq!(result["isError"], true);
+    let error = &result["_meta"]["error"];
+    assert_eq!(error["code"], "quota_exceeded");
+    assert_eq!(error["window"], "minute");
+    assert_eq!(error["limit"], 4);
+    assert!(error["retry_after"].as_u64().unwrap() <= 60);
+
+    // The refused call never reached Semantic Scholar, and other clients
+    // have their own budget.
+    assert_eq!(api.received_requests().await.unwrap().len(), 4);
+    let result = call_autocomplete(&app, &other_token, "d").await;
+    assert_ne!(result["isError"], true, "{result}");
+}
+
+#[tokio::test]
+async fn test_admin_endpoint_reports_usage() {
+    let api = mock_api().await;
+    let store = Arc::new(OAuthStore::new());
+    let limited = store.register_client(Some("Limited".into()), vec!["https://cb".into()]).await;
+    let token = store.create_token_pair(&limited.client_id, "mcp").await.access_token;
+    let app = build_router(&api, Arc::clone(&store));
+
+    call_autocomplete(&app, &token, "a").await;
+
+    let (status, _) = admin_quotas(&app, &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "OAuth clients are not admins");
+
+    let (status, report) = admin_quotas(&app, AUTH_TOKEN).await;
+    assert_eq!(status, StatusCode::OK);
+    let entry = &report["clients"][0];
+    assert_eq!(entry["client_id"], limited.client_id.as_str());
+    assert_eq!(entry["client_name"], "Limited");
+    assert_eq!(entry["minute"]["used"], 2);
+    assert_eq!(entry["minute"]["limit"], 4);
+    assert_eq!(entry["day"]["used"], 2);
+    assert_eq!(entry["day"]["limit"], serde_json::Value::Null);
+    assert_eq!(report["queued"]["requests"], json!({}));
+}
+
+#[tokio::test]
+async fn test_completion_lookups_count_against_the_quota() {
+    let api = mock_api().await;
+    let store = Arc::new(OAuthStore::new());
+    let limited = store.register_client(Some("Limited".into()), vec!["https://cb".into()]).await;
+    let token = store.create_token_pair(&limited.client_id, "mcp").await.access_token;
+    let app = build_router(&api, Arc::clone(&store));
+
+    let body = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "completion/complete",
+        "params": {
+            "ref": {"type": "ref/tool", "name": "paper_authors"},
+            "argument": {"name": "paperId", "value": "attention"}
+        }
+    });
+    let request = Request::post("/mcp")
+        .header("Authorization", format!("Bearer {token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let (_, report) = admin_quotas(&app, AUTH_TOKEN).await;
+    let entry = &report["clients"][0];
+    assert_eq!(entry["client_name"], "Limited");
+    assert_eq!(entry["minute"]["used"], 1);
+}
@@ -1,8 +1,8 @@
+use semantic_scholar_mcp::{SemanticScholarClient, config::ClientConfig};
 use std::time::{Duration, Instant};
-use wiremock::{Mock, MockServer, ResponseTemplate};
-use wiremock::matchers::{method, path};
-use semantic_scholar_mcp::{SemanticScholarClient, config::Config};
 use tokio::sync::mpsc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
 async fn test_concurrent_rate_limiting_bug() {
@@ -21,10 +21,10 @@ async fn test_concurrent_rate_limiting_bug() {
         .await;
 
     // Create client with 200ms delay (5 req/s)
-    let mut config = Config::new(None, None);
+    let mut config = ClientConfig::new(None);
     config.graph_api_url = format!("{}/graph/v1", mock_server.uri());
     config.rate_limit_delay = Duration::from_millis(200);
-    
+
     let client = SemanticScholarClient::new(config).unwrap();
 
     // Fire 10 concurrent requests
@@ -43,15 +43,55 @@ async fn test_concurrent_rate_limiting_bug() {
         }));
     }
 
-    let mut results = vec![];
     for _ in 0..num_requests {
-        results.push(rx.recv().await.unwrap());
+        rx.recv().await.unwrap();
+    }
+    for handle in handles {
+        handle.await.unwrap();
     }
 
     let total_duration = start.elapsed();
     println!("Total duration for {} concurrent requests: {:?}", num_requests, total_duration);
-    
+
     // If rate limiting is global and working, 10 requests with 200ms delay should take at least 800ms.
     // (First few might be burst, but subsequent ones must wait).
-    assert!(total_duration >= Duration::from_millis(800), "Rate limiting IS NOT global (Still buggy!)");
+    assert!(
+        total_duration >= Duration::from_millis(800),
+        "Rate limiting IS NOT global (Still buggy!)"
+    );
+}
+
+#[tokio::test]
+async fn test_max_connections_caps_requests_in_flight() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/test"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"paperId": "test", "title": "Test Paper"}))
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = SemanticScholarClient::builder()
+        .base_url(&mock_server.uri())
+        .rate_limits(Duration::ZERO, Duration::ZERO)
+        .no_cache()
+        .max_connections(2)
+        .build()
+        .unwrap();
+
+    // Six 200ms requests, two at a time: three rounds.
+    let start = Instant::now();
+    let handles: Vec<_> = (0..6)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.get_paper("test", &[]).await })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap().unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(600), "{:?}", start.elapsed());
 }
@@ -0,0 +1,114 @@
+//! Tests for the `/ready` endpoint's checks.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router_with, json_body};
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::session_store::FileSessionStore;
+use semantic_scholar_mcp::server::transport::HttpOptions;
+
+async fn mock_api(response: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+    server
+}
+
+async fn ready(app: &axum::Router) -> (StatusCode, serde_json::Value) {
+    let request = Request::get("/ready").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    (response.status(), json_body(response).await)
+}
+
+#[tokio::test]
+async fn test_ready_when_upstream_answers() {
+    let api = mock_api(ResponseTemplate::new(200).set_body_json(json!({"matches": []}))).await;
+    let app = build_router_with(&api.uri(), HttpOptions::default());
+
+    let (status, report) = ready(&app).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["status"], "ready");
+    let checks = &report["checks"];
+    assert_eq!(checks["upstream"]["status"], "ok");
+    assert_eq!(checks["upstream"]["reachable"], true);
+    assert_eq!(checks["rate_limit"]["status"], "ok");
+    assert_eq!(checks["rate_limit"]["queued"], json!({"normal": 0, "batch": 0}));
+    assert_eq!(checks["cache"]["status"], "ok");
+    assert_eq!(checks["session_store"], json!({"status": "ok", "sessions": 0}));
+    assert!(checks.get("oauth_store").is_none(), "no OAuth without auth");
+
+    // The answer is recent, so the next check does not probe again.
+    let (status, _) = ready(&app).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(api.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_not_ready_when_upstream_fails() {
+    let api = mock_api(ResponseTemplate::new(503)).await;
+    let app = build_router_with(&api.uri(), HttpOptions::default());
+
+    let (status, report) = ready(&app).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(report["status"], "degraded");
+    assert_eq!(report["checks"]["upstream"]["status"], "degraded");
+    assert_eq!(report["checks"]["upstream"]["error"], "HTTP 503");
+}
+
+#[tokio::test]
+async fn test_not_ready_during_rate_limit_backoff() {
+    let api = mock_api(ResponseTemplate::new(429).insert_header("Retry-After", "120")).await;
+    let app = build_router_with(&api.uri(), HttpOptions::default());
+
+    let (status, report) = ready(&app).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let checks = &report["checks"];
+    assert_eq!(checks["upstream"]["status"], "ok", "a 429 is still an answer");
+    assert_eq!(checks["rate_limit"]["status"], "degraded");
+    let backoff = checks["rate_limit"]["backoff_secs"].as_u64().unwrap();
+    assert!(backoff > 100 && backoff <= 120, "backoff {backoff}");
+}
+
+#[tokio::test]
+async fn test_not_ready_when_stores_are_unavailable() {
+    let api = mock_api(ResponseTemplate::new(200).set_body_json(json!({"matches": []}))).await;
+    let dir = std::env::temp_dir().join(format!("s2-ready-{}", uuid::Uuid::new_v4()));
+    let store = FileSessionStore::open(dir.join("sessions")).unwrap();
+    let options = HttpOptions {
+        auth_token: Some("ready-test-token".into()),
+        session_store: Some(Arc::new(store)),
+        oauth_store: Some(Arc::new(OAuthStore::open(dir.join("oauth/store.json")).unwrap())),
+        ..HttpOptions::default()
+    };
+    let app = build_router_with(&api.uri(), options);
+
+    let (status, report) = ready(&app).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE, "{report}");
+    assert_eq!(report["checks"]["session_store"]["status"], "ok");
+    assert_eq!(report["checks"]["oauth_store"]["status"], "degraded");
+    assert_eq!(report["checks"]["oauth_store"]["persistent"], true);
+
+    std::fs::create_dir_all(dir.join("oauth")).unwrap();
+    let (status, _) = ready(&app).await;
+    assert_eq!(status, StatusCode::OK);
+
+    std::fs::remove_dir_all(dir.join("sessions")).unwrap();
+    let (status, report) = ready(&app).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(report["checks"]["session_store"]["status"], "degraded");
+    assert!(report["checks"]["session_store"]["error"].is_string());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,93 @@
+//! Tests for derived input schemas and server-side argument validation.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::Request;
+use serde_json::json;
+use tower::ServiceExt;
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::models::{BulkBooleanSearchInput, ExhaustiveSearchInput};
+use semantic_scholar_mcp::server::transport::create_router;
+use semantic_scholar_mcp::tools::{self, McpTool, ToolContext};
+
+fn build_router() -> axum::Router {
+    let config = ClientConfig::for_testing("http://unused.localhost");
+    let client = SemanticScholarClient::new(config).unwrap();
+    create_router(tools::register_all_tools(), ToolContext::new(Arc::new(client)), None, None)
+}
+
+async fn call_tool(name: &str, arguments: serde_json::Value) -> serde_json::Value {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {"name": name, "arguments": arguments}
+    });
+    let response = build_router()
+        .oneshot(
+            Request::post("/mcp")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[test]
+fn test_input_schemas_are_self_contained() {
+    for tool in tools::register_all_tools() {
+        let schema = tool.input_schema();
+        let text = schema.to_string();
+        assert_eq!(schema["type"], "object", "{}", tool.name());
+        assert!(!text.contains("$ref"), "{} schema contains $ref", tool.name());
+        assert!(!text.contains("$defs"), "{} schema contains $defs", tool.name());
+    }
+}
+
+#[test]
+fn test_schema_defaults_match_serde_defaults() {
+    let tool = tools::ExhaustiveSearchTool;
+    let props = &tool.input_schema()["properties"];
+    let parsed: ExhaustiveSearchInput = serde_json::from_value(json!({"query": "q"})).unwrap();
+    assert_eq!(props["maxResults"]["default"], parsed.max_results);
+    assert_eq!(props["openAccessOnly"]["default"], parsed.open_access_only);
+
+    let tool = tools::BulkBooleanSearchTool;
+    let props = &tool.input_schema()["properties"];
+    let parsed: BulkBooleanSearchInput = serde_json::from_value(json!({"query": "q"})).unwrap();
+    assert_eq!(props["maxResults"]["default"], parsed.max_results);
+}
+
+#[tokio::test]
+async fn test_missing_required_argument_is_invalid_params() {
+    let response = call_tool("exhaustive_search", json!({})).await;
+
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["field"], "query");
+    assert!(response["error"]["message"].as_str().unwrap().contains("query"));
+}
+
+#[tokio::test]
+async fn test_max_items_enforced_before_execute() {
+    let ids: Vec<String> = (0..501).map(|i| format!("p{i}")).collect();
+    let response = call_tool("batch_metadata", json!({"paperIds": ids})).await;
+
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["field"], "paperIds");
+}
+
+#[tokio::test]
+async fn test_wrong_enum_value_names_field() {
+    let response =
+        call_tool("citation_snowball", json!({"seedPaperIds": ["p1"], "direction": "sideways"}))
+            .await;
+
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(response["error"]["data"]["field"], "direction");
+}
@@ -8,13 +8,13 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     LiteratureReviewPipelineTool, McpTool, SemanticSearchTool, ToolContext,
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -65,7 +65,7 @@ async fn test_semantic_search_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = SemanticSearchTool;
 
-    let result = tool.execute(&ctx, json!({"seedPaperId": "seed123"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"seedPaperId": "seed123"})).await.unwrap().text;
 
     assert!(result.contains("Semantic Search") || result.contains("Similar"));
     assert!(result.contains("Similar Paper 1") || result.contains("sim1"));
@@ -97,7 +97,8 @@ async fn test_semantic_search_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("seed_paper_id").is_some());
@@ -133,7 +134,8 @@ async fn test_semantic_search_year_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should filter out 2018 paper
     assert!(result.contains("New Paper") || result.contains("Mid Paper"));
@@ -154,7 +156,7 @@ async fn test_semantic_search_empty_results() {
     let ctx = setup_test_context(&mock_server);
     let tool = SemanticSearchTool;
 
-    let result = tool.execute(&ctx, json!({"seedPaperId": "empty_seed"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"seedPaperId": "empty_seed"})).await.unwrap().text;
 
     assert!(result.contains("No similar") || result.contains('0') || result.contains("found"));
 }
@@ -186,7 +188,8 @@ async fn test_semantic_search_with_limit() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Limited") || result.contains("Paper"));
 }
@@ -264,7 +267,7 @@ async fn test_literature_review_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = LiteratureReviewPipelineTool;
 
-    let result = tool.execute(&ctx, json!({"query": "machine learning"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "machine learning"})).await.unwrap().text;
 
     assert!(result.contains("Literature Review") || result.contains("literature"));
 }
@@ -314,7 +317,8 @@ async fn test_literature_review_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("query").is_some());
@@ -371,7 +375,8 @@ async fn test_literature_review_with_filters() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should filter out old/unpopular papers
     assert!(result.contains("Recent Popular") || result.contains("Literature"));
@@ -403,7 +408,8 @@ async fn test_literature_review_no_recommendations() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("No Rec Paper") || result.contains("Literature"));
 }
@@ -448,7 +454,7 @@ async fn test_literature_review_deduplication() {
     let ctx = setup_test_context(&mock_server);
     let tool = LiteratureReviewPipelineTool;
 
-    let result = tool.execute(&ctx, json!({"query": "dedupe test"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "dedupe test"})).await.unwrap().text;
 
     // Should show deduplication
     assert!(
@@ -529,7 +535,8 @@ async fn test_literature_review_max_papers() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Literature") || result.contains("Paper"));
 }
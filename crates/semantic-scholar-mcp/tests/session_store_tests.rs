@@ -0,0 +1,133 @@
+//! Tests for persistent sessions shared between server instances.
+
+mod common;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use futures::StreamExt;
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router_with, initialize, post};
+use semantic_scholar_mcp::server::session_store::FileSessionStore;
+use semantic_scholar_mcp::server::transport::HttpOptions;
+
+/// A server instance keeping its sessions in `dir`.
+fn build_router(base_url: &str, dir: &PathBuf) -> axum::Router {
+    let options = HttpOptions {
+        session_store: Some(Arc::new(FileSessionStore::open(dir).unwrap())),
+        ..HttpOptions::default()
+    };
+    build_router_with(base_url, options)
+}
+
+async fn mock_api() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"matches": [{"id": "p1"}]})))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+/// A tool call whose result is buffered in the session.
+fn tool_call(id: i64) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": {"name": "paper_autocomplete", "arguments": {"query": "attention"}}
+    })
+}
+
+fn temp_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("s2-session-store-{}", uuid::Uuid::new_v4()))
+}
+
+/// Read SSE frames from `GET /mcp` until `want` events with IDs have arrived.
+async fn replay(app: &axum::Router, session: &str, last_event_id: u64, want: usize) -> Vec<u64> {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/mcp")
+                .header("Mcp-Session-Id", session)
+                .header("Last-Event-ID", last_event_id.to_string())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut stream = response.into_body().into_data_stream();
+    let mut text = String::new();
+    loop {
+        let ids: Vec<u64> = text
+            .lines()
+            .filter_map(|l| l.strip_prefix("id: "))
+            .filter_map(|id| id.parse().ok())
+            .collect();
+        if ids.len() >= want {
+            return ids;
+        }
+        let chunk = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("SSE stream stalled")
+            .unwrap()
+            .unwrap();
+        text.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_session_survives_restart_and_replays() {
+    let mock_server = mock_api().await;
+    let dir = temp_dir();
+    let first = build_router(&mock_server.uri(), &dir);
+    let session = initialize(&first).await;
+
+    // Tool calls push their results to the session buffer.
+    for id in 2..=4 {
+        assert_eq!(post(&first, Some(&session), &tool_call(id)).await.status(), StatusCode::OK);
+    }
+    drop(first);
+
+    // A fresh instance over the same directory knows the session.
+    let restarted = build_router(&mock_server.uri(), &dir);
+    let ping = json!({"jsonrpc": "2.0", "id": 5, "method": "ping"});
+    assert_eq!(post(&restarted, Some(&session), &ping).await.status(), StatusCode::OK);
+    assert_eq!(replay(&restarted, &session, 1, 2).await, vec![2, 3]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_replicas_share_sessions_and_event_ids() {
+    let mock_server = mock_api().await;
+    let dir = temp_dir();
+    let (a, b) = (build_router(&mock_server.uri(), &dir), build_router(&mock_server.uri(), &dir));
+    let session = initialize(&a).await;
+
+    assert_eq!(post(&a, Some(&session), &tool_call(2)).await.status(), StatusCode::OK);
+    assert_eq!(post(&b, Some(&session), &tool_call(3)).await.status(), StatusCode::OK);
+
+    // Events pushed on either replica replay from both, without ID clashes.
+    assert_eq!(replay(&a, &session, 0, 2).await, vec![1, 2]);
+    assert_eq!(replay(&b, &session, 0, 2).await, vec![1, 2]);
+
+    // Ending the session on one replica ends it everywhere.
+    let delete = Request::delete("/mcp").header("Mcp-Session-Id", &session).body(Body::empty());
+    let response = b.clone().oneshot(delete.unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let ping = json!({"jsonrpc": "2.0", "id": 4, "method": "ping"});
+    assert_eq!(post(&a, Some(&session), &ping).await.status(), StatusCode::NOT_FOUND);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,191 @@
+//! Tests for Streamable HTTP: SSE responses to POST, resumption and session lifecycle.
+
+mod common;
+
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use futures::StreamExt;
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use common::{build_router, initialize, json_body, mcp_request};
+
+const ACCEPT_BOTH: &str = "application/json, text/event-stream";
+
+/// A `POST /mcp` of `body` accepting `accept`.
+fn request(session_id: Option<&str>, accept: &str, body: &serde_json::Value) -> Request<Body> {
+    mcp_request(session_id).header("Accept", accept).body(Body::from(body.to_string())).unwrap()
+}
+
+/// Parse an SSE body into `(id, data)` pairs.
+fn parse_events(text: &str) -> Vec<(u64, serde_json::Value)> {
+    text.split("\n\n")
+        .filter_map(|block| {
+            let id = block.lines().find_map(|l| l.strip_prefix("id: "))?.parse().ok()?;
+            let data = block.lines().find_map(|l| l.strip_prefix("data: "))?;
+            Some((id, serde_json::from_str(data).ok()?))
+        })
+        .collect()
+}
+
+async fn mount_citations(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/graph/v1/paper/s\d/citations$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "offset": 0,
+            "data": [{"citingPaper": {"paperId": "c1", "title": "Citing"}}]
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+fn snowball_call() -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {
+            "name": "citation_snowball",
+            "arguments": {"seedPaperIds": ["s1", "s2"], "direction": "citations", "depth": 1},
+            "_meta": {"progressToken": "tok"}
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_tool_call_streams_progress_then_result() {
+    let mock_server = MockServer::start().await;
+    mount_citations(&mock_server).await;
+    let app = build_router(&mock_server.uri());
+    let session = initialize(&app).await;
+
+    let response =
+        app.clone().oneshot(request(Some(&session), ACCEPT_BOTH, &snowball_call())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["Content-Type"].to_str().unwrap().starts_with("text/event-stream"));
+    assert_eq!(response.headers()["Mcp-Session-Id"], session.as_str());
+
+    // The stream closes after the result.
+    let bytes = tokio::time::timeout(
+        Duration::from_secs(10),
+        axum::body::to_bytes(response.into_body(), usize::MAX),
+    )
+    .await
+    .expect("SSE response did not finish")
+    .unwrap();
+    let events = parse_events(std::str::from_utf8(&bytes).unwrap());
+
+    let progress: Vec<_> =
+        events.iter().filter(|(_, e)| e["method"] == "notifications/progress").collect();
+    assert_eq!(progress.len(), 2);
+    assert_eq!(progress[0].1["params"]["progressToken"], "tok");
+    assert_eq!(progress[1].1["params"]["progress"], 2);
+
+    let (last_id, last) = events.last().unwrap();
+    assert_eq!(last["id"], 7);
+    assert!(last["result"]["structuredContent"].is_object());
+    assert!(events.windows(2).all(|w| w[0].0 < w[1].0), "event IDs must increase");
+    assert!(progress.iter().all(|(id, _)| id < last_id));
+}
+
+#[tokio::test]
+async fn test_stream_resumes_from_last_event_id() {
+    let mock_server = MockServer::start().await;
+    mount_citations(&mock_server).await;
+    let app = build_router(&mock_server.uri());
+    let session = initialize(&app).await;
+
+    let response =
+        app.clone().oneshot(request(Some(&session), ACCEPT_BOTH, &snowball_call())).await.unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let events = parse_events(std::str::from_utf8(&bytes).unwrap());
+    let (first_id, _) = events[0];
+
+    // Pretend the connection dropped after the first event.
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/mcp")
+                .header("Mcp-Session-Id", &session)
+                .header("Last-Event-ID", first_id.to_string())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut stream = response.into_body().into_data_stream();
+    let mut text = String::new();
+    while !text.contains("\"id\":7") {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("SSE stream stalled")
+            .unwrap()
+            .unwrap();
+        text.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    let replayed: Vec<u64> = parse_events(&text).into_iter().map(|(id, _)| id).collect();
+    let expected: Vec<u64> = events[1..].iter().map(|(id, _)| *id).collect();
+    assert_eq!(replayed, expected);
+}
+
+#[tokio::test]
+async fn test_json_response_without_event_stream_accept() {
+    let app = build_router("http://unused.localhost");
+    let session = initialize(&app).await;
+
+    let body = json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {}});
+    let response =
+        app.clone().oneshot(request(Some(&session), "application/json", &body)).await.unwrap();
+    assert!(response.headers()["Content-Type"].to_str().unwrap().starts_with("application/json"));
+    assert_eq!(json_body(response).await["error"]["code"], -32602);
+}
+
+#[tokio::test]
+async fn test_delete_terminates_session() {
+    let app = build_router("http://unused.localhost");
+    let session = initialize(&app).await;
+    let delete =
+        || Request::delete("/mcp").header("Mcp-Session-Id", &session).body(Body::empty()).unwrap();
+
+    let response = app.clone().oneshot(delete()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let ping = json!({"jsonrpc": "2.0", "id": 3, "method": "ping"});
+    let response = app.clone().oneshot(request(Some(&session), ACCEPT_BOTH, &ping)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app.clone().oneshot(delete()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response =
+        app.clone().oneshot(Request::delete("/mcp").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_unknown_session_id_is_not_found() {
+    let app = build_router("http://unused.localhost");
+
+    let ping = json!({"jsonrpc": "2.0", "id": 1, "method": "ping"});
+    let response =
+        app.clone().oneshot(request(Some("no-such-session"), ACCEPT_BOTH, &ping)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/mcp")
+                .header("Mcp-Session-Id", "no-such-session")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,172 @@
+//! Tests for structured tool output (`outputSchema` + `structuredContent`).
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::server::transport::create_router;
+use semantic_scholar_mcp::tools::{self, ExhaustiveSearchTool, McpTool, ToolContext};
+
+fn setup_test_context(base_url: &str) -> ToolContext {
+    let config = ClientConfig::for_testing(base_url);
+    let client = SemanticScholarClient::new(config).unwrap();
+    ToolContext::new(Arc::new(client))
+}
+
+async fn mount_search(mock_server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total": 1,
+            "offset": 0,
+            "data": [{
+                "paperId": "p1",
+                "title": "Structured Paper",
+                "year": 2023,
+                "citationCount": 7,
+                "authors": [{"authorId": "a1", "name": "Ada Lovelace"}]
+            }]
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+async fn post_json(app: &axum::Router, body: serde_json::Value) -> serde_json::Value {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::post("/mcp")
+                .header("Content-Type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[test]
+fn test_all_tools_declare_object_output_schema() {
+    for tool in tools::register_all_tools() {
+        let schema = tool.output_schema();
+        assert_eq!(schema["type"], "object", "{} outputSchema must be an object", tool.name());
+        assert!(schema["properties"].is_object(), "{} outputSchema has no properties", tool.name());
+        for key in schema["required"].as_array().into_iter().flatten() {
+            let key = key.as_str().unwrap();
+            assert!(
+                schema["properties"].get(key).is_some(),
+                "{}: required key '{}' not in properties",
+                tool.name(),
+                key
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_structured_output_independent_of_response_format() {
+    let mock_server = MockServer::start().await;
+    mount_search(&mock_server).await;
+    let ctx = setup_test_context(&mock_server.uri());
+
+    let markdown = ExhaustiveSearchTool.execute(&ctx, json!({"query": "test"})).await.unwrap();
+    let json_out = ExhaustiveSearchTool
+        .execute(&ctx, json!({"query": "test", "responseFormat": "json"}))
+        .await
+        .unwrap();
+
+    assert!(markdown.text.contains("Structured Paper"));
+    assert_eq!(markdown.structured, json_out.structured);
+    assert_eq!(markdown.structured["total"], 1);
+    assert_eq!(markdown.structured["papers"][0]["id"], "p1");
+    assert_eq!(markdown.structured["papers"][0]["authors"], json!(["Ada Lovelace"]));
+}
+
+#[tokio::test]
+async fn test_http_tools_list_includes_output_schema() {
+    let app = create_router(
+        tools::register_all_tools(),
+        setup_test_context("http://unused.localhost"),
+        None,
+        None,
+    );
+
+    let response =
+        post_json(&app, json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"})).await;
+    let tools = response["result"]["tools"].as_array().unwrap();
+    assert!(!tools.is_empty());
+    for tool in tools {
+        assert_eq!(tool["outputSchema"]["type"], "object", "tool {}", tool["name"]);
+    }
+}
+
+#[tokio::test]
+async fn test_http_tools_call_returns_structured_content() {
+    let mock_server = MockServer::start().await;
+    mount_search(&mock_server).await;
+    let app = create_router(
+        tools::register_all_tools(),
+        setup_test_context(&mock_server.uri()),
+        None,
+        None,
+    );
+
+    let response = post_json(
+        &app,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "exhaustive_search", "arguments": {"query": "test"}}
+        }),
+    )
+    .await;
+
+    let result = &response["result"];
+    assert_eq!(result["content"][0]["type"], "text");
+    assert!(result["content"][0]["text"].as_str().unwrap().contains("Structured Paper"));
+    assert_eq!(result["structuredContent"]["total"], 1);
+    assert_eq!(result["structuredContent"]["papers"][0]["title"], "Structured Paper");
+}
+
+#[tokio::test]
+async fn test_http_tool_failure_is_error_result() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/missing/authors"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Paper not found"))
+        .mount(&mock_server)
+        .await;
+    let app = create_router(
+        tools::register_all_tools(),
+        setup_test_context(&mock_server.uri()),
+        None,
+        None,
+    );
+
+    let response = post_json(
+        &app,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {"name": "paper_authors", "arguments": {"paperId": "missing"}}
+        }),
+    )
+    .await;
+
+    assert!(response.get("error").is_none(), "tool failures must not be JSON-RPC errors");
+    let result = &response["result"];
+    assert_eq!(result["isError"], true);
+    assert_eq!(result["_meta"]["error"]["code"], "not_found");
+    assert!(result["content"][0]["text"].as_str().unwrap().contains("Not found"));
+}
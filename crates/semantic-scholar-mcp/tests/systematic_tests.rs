@@ -8,13 +8,13 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     McpTool, PrismaFlowDiagramTool, PrismaSearchTool, ScreeningExportTool, ToolContext,
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -66,7 +66,7 @@ async fn test_prisma_search_single_query() {
     let ctx = setup_test_context(&mock_server);
     let tool = PrismaSearchTool;
 
-    let result = tool.execute(&ctx, json!({"queries": ["machine learning"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"queries": ["machine learning"]})).await.unwrap().text;
 
     assert!(result.contains("PRISMA") || result.contains("Search"));
     assert!(result.contains("PRISMA Paper 1") || result.contains("machine"));
@@ -96,7 +96,8 @@ async fn test_prisma_search_multiple_queries() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("PRISMA") || result.contains("queries"));
 }
@@ -126,7 +127,8 @@ async fn test_prisma_search_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("queries").is_some() || parsed.get("search_log").is_some());
@@ -161,7 +163,8 @@ async fn test_prisma_search_with_year_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should filter to only recent papers
     assert!(result.contains("New Paper") || result.contains("PRISMA"));
@@ -195,7 +198,8 @@ async fn test_prisma_search_with_citation_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("High Cite") || result.contains("PRISMA"));
 }
@@ -225,7 +229,8 @@ async fn test_prisma_search_deduplication_across_queries() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should show deduplication stats
     assert!(result.contains("Duplicate") || result.contains("PRISMA") || result.contains("unique"));
@@ -251,7 +256,7 @@ async fn test_screening_export_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = ScreeningExportTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["s1", "s2"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["s1", "s2"]})).await.unwrap().text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("papers").is_some() || parsed.get("total").is_some());
@@ -284,7 +289,8 @@ async fn test_screening_export_with_abstract() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed["papers"][0].get("abstract").is_some() || parsed.get("papers").is_some());
@@ -321,7 +327,8 @@ async fn test_screening_export_with_tldr() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("papers").is_some());
@@ -340,7 +347,7 @@ async fn test_screening_export_empty_papers() {
     let ctx = setup_test_context(&mock_server);
     let tool = ScreeningExportTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["invalid"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["invalid"]})).await.unwrap().text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert_eq!(parsed["total"], 0);
@@ -374,7 +381,8 @@ async fn test_prisma_flow_diagram_basic() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(
         result.contains("PRISMA")
@@ -405,7 +413,8 @@ async fn test_prisma_flow_diagram_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("version").is_some() || parsed.get("identification").is_some());
@@ -437,7 +446,8 @@ async fn test_prisma_flow_diagram_with_eligibility() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(
         result.contains("ELIGIBILITY")
@@ -471,7 +481,8 @@ async fn test_prisma_flow_diagram_with_included() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(
         result.contains("INCLUDED") || result.contains("included") || result.contains("studies")
@@ -526,7 +537,8 @@ async fn test_prisma_flow_diagram_full() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.get("summary").is_some());
@@ -557,7 +569,8 @@ async fn test_prisma_flow_diagram_with_other_sources() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(
         result.contains("other") || result.contains("Manual") || result.contains("IDENTIFICATION")
@@ -0,0 +1,114 @@
+//! Tests for OpenTelemetry trace export, against a mock OTLP/HTTP collector.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::server::transport::create_router;
+use semantic_scholar_mcp::telemetry;
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PAPER_ID: &str = "649def34f8be52c8b66281af98ae884c09aef38b";
+
+/// Every span in the OTLP JSON export requests the collector received.
+async fn exported_spans(collector: &MockServer) -> Vec<serde_json::Value> {
+    let mut spans = Vec::new();
+    for request in collector.received_requests().await.unwrap() {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        for resource in body["resourceSpans"].as_array().unwrap() {
+            for scope in resource["scopeSpans"].as_array().unwrap() {
+                spans.extend(scope["spans"].as_array().unwrap().iter().cloned());
+            }
+        }
+    }
+    spans
+}
+
+fn find<'a>(spans: &'a [serde_json::Value], name: &str) -> &'a serde_json::Value {
+    spans
+        .iter()
+        .find(|s| s["name"] == name)
+        .unwrap_or_else(|| panic!("no {name} span in {spans:#?}"))
+}
+
+fn attribute<'a>(span: &'a serde_json::Value, key: &str) -> &'a serde_json::Value {
+    let attributes = span["attributes"].as_array().unwrap();
+    let attribute = attributes.iter().find(|a| a["key"] == key);
+    &attribute.unwrap_or_else(|| panic!("no {key} attribute in {span:#?}"))["value"]
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tool_call_exports_spans_in_callers_trace() {
+    let collector = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/traces"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&collector)
+        .await;
+    let api = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/graph/v1/paper/{PAPER_ID}/authors")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"data": []})))
+        .mount(&api)
+        .await;
+
+    let provider = telemetry::tracer_provider(&collector.uri(), "telemetry-test").unwrap();
+    let subscriber = tracing_subscriber::registry().with(telemetry::layer(&provider));
+    let guard = tracing::subscriber::set_default(subscriber);
+
+    let client = SemanticScholarClient::new(ClientConfig::for_testing(&api.uri())).unwrap();
+    let app =
+        create_router(tools::register_all_tools(), ToolContext::new(Arc::new(client)), None, None);
+    let body = json!({
+        "jsonrpc": "2.0", "id": 7, "method": "tools/call",
+        "params": {"name": "paper_authors", "arguments": {"paperId": PAPER_ID}}
+    });
+    let request = Request::post("/mcp")
+        .header("Content-Type", "application/json")
+        .header("traceparent", format!("00-{TRACE_ID}-00f067aa0ba902b7-01"))
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    drop(guard);
+    // The exporter's blocking HTTP client must not run on the test's runtime
+    // thread.
+    let provider = tokio::task::spawn_blocking(move || {
+        provider.force_flush().unwrap();
+        provider
+    })
+    .await
+    .unwrap();
+
+    let spans = exported_spans(&collector).await;
+    let http = find(&spans, "mcp.http");
+    let request = find(&spans, "mcp.request");
+    let tool = find(&spans, "tool.execute");
+    let upstream = find(&spans, "s2.request");
+    for span in [http, request, tool, upstream] {
+        assert_eq!(span["traceId"].as_str().unwrap().to_lowercase(), TRACE_ID);
+    }
+    assert_eq!(http["parentSpanId"].as_str().unwrap().to_lowercase(), "00f067aa0ba902b7");
+    assert_eq!(request["parentSpanId"], http["spanId"]);
+    assert_eq!(tool["parentSpanId"], request["spanId"]);
+    assert_eq!(upstream["parentSpanId"], tool["spanId"]);
+
+    assert_eq!(attribute(request, "rpc.method")["stringValue"], "tools/call");
+    assert_eq!(attribute(request, "mcp.tool")["stringValue"], "paper_authors");
+    assert_eq!(attribute(upstream, "s2.endpoint")["stringValue"], "/paper/{id}/authors");
+    assert_eq!(attribute(upstream, "cache_hit")["boolValue"], false);
+    assert_eq!(attribute(upstream, "attempts")["intValue"], "1");
+    assert_eq!(attribute(upstream, "http.response.status_code")["intValue"], "200");
+
+    tokio::task::spawn_blocking(move || provider.shutdown().unwrap()).await.unwrap();
+}
@@ -6,7 +6,7 @@
 #![allow(dead_code)]
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     AuthorNetworkTool, ExhaustiveSearchTool, FieldWeightedImpactTool, McpTool, ToolContext,
 };
@@ -14,8 +14,10 @@ use serde_json::json;
 use std::sync::Arc;
 
 fn create_context() -> ToolContext {
-    let config =
-        Config { api_key: std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok(), ..Config::default() };
+    let config = ClientConfig {
+        api_key: std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok(),
+        ..ClientConfig::default()
+    };
     let client = Arc::new(SemanticScholarClient::new(config).expect("Failed to create client"));
     ToolContext::new(client)
 }
@@ -39,7 +41,7 @@ async fn test_tool_exhaustive_search_real() {
     match result {
         Ok(output) => {
             let json: serde_json::Value =
-                serde_json::from_str(&output).expect("Should be valid JSON");
+                serde_json::from_str(&output.text).expect("Should be valid JSON");
 
             // ExhaustiveSearchTool returns a JSON array of papers directly
             if let Some(papers) = json.as_array() {
@@ -75,7 +77,7 @@ async fn test_tool_author_network_real() {
     match result {
         Ok(output) => {
             let json: serde_json::Value =
-                serde_json::from_str(&output).expect("Should be valid JSON");
+                serde_json::from_str(&output.text).expect("Should be valid JSON");
             if let Some(collaborators) = json["collaborators"].as_array() {
                 if collaborators.is_empty() {
                     println!(
@@ -110,7 +112,7 @@ async fn test_tool_fwci_real() {
     match result {
         Ok(output) => {
             let json: serde_json::Value =
-                serde_json::from_str(&output).expect("Should be valid JSON");
+                serde_json::from_str(&output.text).expect("Should be valid JSON");
             let results = json["results"].as_array().expect("Should have results array");
             if let Some(first) = results.first() {
                 let fwci = first["fwci"].as_f64().unwrap_or(0.0);
@@ -10,7 +10,7 @@ use wiremock::matchers::{method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     AuthorSearchTool, BatchMetadataTool, ExhaustiveSearchTool, McpTool, RecommendationsTool,
     ToolContext,
@@ -18,7 +18,7 @@ use semantic_scholar_mcp::tools::{
 
 /// Create a test context with a mock server.
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -73,7 +73,7 @@ async fn test_exhaustive_search_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = ExhaustiveSearchTool;
 
-    let result = tool.execute(&ctx, json!({"query": "machine learning"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "machine learning"})).await.unwrap().text;
 
     assert!(result.contains("ML Paper One"));
     assert!(result.contains("ML Paper Two"));
@@ -98,7 +98,8 @@ async fn test_exhaustive_search_with_year_filter() {
     let tool = ExhaustiveSearchTool;
 
     // Filter to only 2024
-    let result = tool.execute(&ctx, json!({"query": "test", "yearStart": 2024})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"query": "test", "yearStart": 2024})).await.unwrap().text;
 
     // Should only include the 2024 paper
     assert!(result.contains("New Paper"));
@@ -122,7 +123,8 @@ async fn test_exhaustive_search_with_citation_filter() {
     let ctx = setup_test_context(&mock_server);
     let tool = ExhaustiveSearchTool;
 
-    let result = tool.execute(&ctx, json!({"query": "test", "minCitations": 100})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"query": "test", "minCitations": 100})).await.unwrap().text;
 
     assert!(result.contains("Popular Paper"));
     assert!(!result.contains("Unpopular Paper"));
@@ -145,7 +147,7 @@ async fn test_exhaustive_search_json_format() {
     let tool = ExhaustiveSearchTool;
 
     let result =
-        tool.execute(&ctx, json!({"query": "test", "responseFormat": "json"})).await.unwrap();
+        tool.execute(&ctx, json!({"query": "test", "responseFormat": "json"})).await.unwrap().text;
 
     // Should be valid JSON
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
@@ -182,7 +184,8 @@ async fn test_exhaustive_search_pagination() {
     let ctx = setup_test_context(&mock_server);
     let tool = ExhaustiveSearchTool;
 
-    let result = tool.execute(&ctx, json!({"query": "test", "maxResults": 200})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"query": "test", "maxResults": 200})).await.unwrap().text;
 
     // Should include papers from both pages
     assert!(result.contains("Page 1 Paper"));
@@ -212,7 +215,8 @@ async fn test_exhaustive_search_max_results_limit() {
     let result = tool
         .execute(&ctx, json!({"query": "test", "maxResults": 2, "responseFormat": "json"}))
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
     assert_eq!(parsed.len(), 2);
@@ -240,7 +244,7 @@ async fn test_recommendations_single_seed() {
     let ctx = setup_test_context(&mock_server);
     let tool = RecommendationsTool;
 
-    let result = tool.execute(&ctx, json!({"positivePaperIds": ["seed123"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"positivePaperIds": ["seed123"]})).await.unwrap().text;
 
     assert!(result.contains("Recommended Paper 1"));
     assert!(result.contains("Recommended Paper 2"));
@@ -263,7 +267,8 @@ async fn test_recommendations_multiple_seeds() {
     let ctx = setup_test_context(&mock_server);
     let tool = RecommendationsTool;
 
-    let result = tool.execute(&ctx, json!({"positivePaperIds": ["seed1", "seed2"]})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"positivePaperIds": ["seed1", "seed2"]})).await.unwrap().text;
 
     assert!(result.contains("Multi-Seed Rec"));
 }
@@ -294,7 +299,8 @@ async fn test_recommendations_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(parsed.is_array());
@@ -320,7 +326,7 @@ async fn test_batch_metadata_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = BatchMetadataTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1", "p2"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["p1", "p2"]})).await.unwrap().text;
 
     assert!(result.contains("Batch Paper 1"));
     assert!(result.contains("Batch Paper 2"));
@@ -344,7 +350,8 @@ async fn test_batch_metadata_with_nulls() {
     let ctx = setup_test_context(&mock_server);
     let tool = BatchMetadataTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1", "invalid", "p3"]})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"paperIds": ["p1", "invalid", "p3"]})).await.unwrap().text;
 
     // Should skip null and include valid papers
     assert!(result.contains("Valid Paper"));
@@ -389,7 +396,7 @@ async fn test_author_search_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorSearchTool;
 
-    let result = tool.execute(&ctx, json!({"query": "John Smith"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"query": "John Smith"})).await.unwrap().text;
 
     assert!(result.contains("John Smith"));
     assert!(result.contains("MIT") || result.contains("Stanford"));
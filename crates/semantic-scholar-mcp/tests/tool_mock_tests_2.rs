@@ -9,7 +9,7 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{
     // Networks
     AuthorNetworkTool,
@@ -31,7 +31,7 @@ use semantic_scholar_mcp::tools::{
 };
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -83,7 +83,8 @@ async fn test_export_ris_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = ReferenceExportTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "ris"})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "ris"})).await.unwrap().text;
 
     assert!(result.contains("TY  - JOUR"));
     assert!(result.contains("TI  - Test Paper"));
@@ -110,7 +111,8 @@ async fn test_export_bibtex_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = ReferenceExportTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "bibtex"})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "bibtex"})).await.unwrap().text;
 
     assert!(result.contains("@article{"));
     assert!(result.contains("title = {Machine Learning Paper}"));
@@ -134,7 +136,8 @@ async fn test_export_csv_format() {
     let ctx = setup_test_context(&mock_server);
     let tool = ReferenceExportTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "csv"})).await.unwrap();
+    let result =
+        tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "csv"})).await.unwrap().text;
 
     // Check CSV header
     assert!(result.contains("paper_id,title,authors,year,venue,citations,doi"));
@@ -171,7 +174,8 @@ async fn test_export_csv_without_abstract() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Header should not have abstract column
     let header_line = result.lines().next().unwrap();
@@ -197,7 +201,7 @@ async fn test_export_endnote_format() {
     let tool = ReferenceExportTool;
 
     let result =
-        tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "endnote"})).await.unwrap();
+        tool.execute(&ctx, json!({"paperIds": ["p1"], "format": "endnote"})).await.unwrap().text;
 
     assert!(result.contains("%0 Journal Article"));
     assert!(result.contains("%T EndNote Test"));
@@ -247,8 +251,11 @@ async fn test_export_multiple_papers() {
     let ctx = setup_test_context(&mock_server);
     let tool = ReferenceExportTool;
 
-    let result =
-        tool.execute(&ctx, json!({"paperIds": ["p1", "p2"], "format": "bibtex"})).await.unwrap();
+    let result = tool
+        .execute(&ctx, json!({"paperIds": ["p1", "p2"], "format": "bibtex"}))
+        .await
+        .unwrap()
+        .text;
 
     assert!(result.contains("Paper One"));
     assert!(result.contains("Paper Two"));
@@ -280,7 +287,7 @@ async fn test_prisma_search_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = PrismaSearchTool;
 
-    let result = tool.execute(&ctx, json!({"queries": ["machine learning"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"queries": ["machine learning"]})).await.unwrap().text;
 
     assert!(result.contains("PRISMA Paper 1"));
 }
@@ -312,7 +319,8 @@ async fn test_prisma_search_deduplication() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Parse JSON and check deduplication
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
@@ -345,7 +353,7 @@ async fn test_screening_export_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = ScreeningExportTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["p1"]})).await.unwrap().text;
 
     assert!(result.contains("Screening Paper"));
 }
@@ -374,7 +382,7 @@ async fn test_screening_export_with_tldr() {
     let tool = ScreeningExportTool;
 
     let result =
-        tool.execute(&ctx, json!({"paperIds": ["p1"], "includeTldr": true})).await.unwrap();
+        tool.execute(&ctx, json!({"paperIds": ["p1"], "includeTldr": true})).await.unwrap().text;
 
     assert!(result.contains("TLDR Paper"));
 }
@@ -430,7 +438,7 @@ async fn test_author_network_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = AuthorNetworkTool;
 
-    let result = tool.execute(&ctx, json!({"authorId": "123"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"authorId": "123"})).await.unwrap().text;
 
     // Should contain collaborator information
     assert!(
@@ -475,7 +483,8 @@ async fn test_research_trends_basic() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should contain year groupings
     assert!(result.contains("2022") || result.contains("2023"));
@@ -505,7 +514,7 @@ async fn test_venue_analytics_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = VenueAnalyticsTool;
 
-    let result = tool.execute(&ctx, json!({"venueQuery": "Nature"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"venueQuery": "Nature"})).await.unwrap().text;
 
     assert!(result.contains("Nature") || result.contains("papers") || result.contains("venue"));
 }
@@ -532,7 +541,7 @@ async fn test_semantic_search_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = SemanticSearchTool;
 
-    let result = tool.execute(&ctx, json!({"seedPaperId": "seed123"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"seedPaperId": "seed123"})).await.unwrap().text;
 
     assert!(result.contains("Similar Paper"));
 }
@@ -564,7 +573,8 @@ async fn test_semantic_search_with_year_filter() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should filter to only 2024 paper
     assert!(result.contains("New Paper"));
@@ -614,7 +624,7 @@ async fn test_fwci_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = FieldWeightedImpactTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["p1"]})).await.unwrap().text;
 
     // Should contain FWCI calculation results
     assert!(result.contains("p1") || result.contains("FWCI") || result.contains("impact"));
@@ -672,7 +682,7 @@ async fn test_highly_cited_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = HighlyCitedPapersTool;
 
-    let result = tool.execute(&ctx, json!({"paperIds": ["p1", "p2"]})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"paperIds": ["p1", "p2"]})).await.unwrap().text;
 
     // Should identify the highly cited paper
     assert!(
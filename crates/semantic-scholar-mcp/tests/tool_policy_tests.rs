@@ -0,0 +1,245 @@
+//! Tests for tool annotations and per-deployment / per-client tool filtering.
+
+mod common;
+
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use futures::StreamExt;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+
+use common::{build_router_with, json_body, post, post_as};
+use semantic_scholar_mcp::server::policy::{ToolPolicy, ToolPolicyHandle};
+use semantic_scholar_mcp::server::transport::HttpOptions;
+use semantic_scholar_mcp::tools::{self, ToolFilter};
+
+const AUTH_TOKEN: &str = "policy-test-token";
+
+fn build_router(options: HttpOptions) -> axum::Router {
+    build_router_with("http://unused.localhost", options)
+}
+
+async fn post_mcp(
+    app: &axum::Router,
+    bearer: Option<&str>,
+    body: serde_json::Value,
+) -> (Option<String>, serde_json::Value) {
+    let response = match bearer {
+        Some(token) => post_as(app, token, &body).await,
+        None => post(app, None, &body).await,
+    };
+    assert_eq!(response.status(), StatusCode::OK);
+    let session = response.headers().get("Mcp-Session-Id").map(|v| v.to_str().unwrap().to_string());
+    (session, json_body(response).await)
+}
+
+async fn list_tool_names(app: &axum::Router, bearer: Option<&str>) -> Vec<String> {
+    let (_, response) =
+        post_mcp(app, bearer, json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"})).await;
+    response["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// Run the OAuth authorization-code flow for a client registered as `name`.
+async fn oauth_access_token(app: &axum::Router, name: &str) -> String {
+    let redirect = "https://client.example.com/cb";
+    let response = app
+        .clone()
+        .oneshot(
+            Request::post("/register")
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({"client_name": name, "redirect_uris": [redirect]}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let client: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    let client_id = client["client_id"].as_str().unwrap().to_string();
+
+    let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    let authorize = format!(
+        "/authorize?client_id={client_id}&redirect_uri={}&response_type=code&state=s\
+         &code_challenge={challenge}&code_challenge_method=S256&scope=mcp",
+        url::form_urlencoded::byte_serialize(redirect.as_bytes()).collect::<String>()
+    );
+    let response =
+        app.clone().oneshot(Request::get(authorize).body(Body::empty()).unwrap()).await.unwrap();
+    let location = response.headers().get("Location").unwrap().to_str().unwrap();
+    let url = url::Url::parse(location).unwrap();
+    let code = url.query_pairs().find(|(k, _)| k == "code").unwrap().1.to_string();
+
+    let form = serde_urlencoded::to_string([
+        ("grant_type", "authorization_code"),
+        ("code", code.as_str()),
+        ("redirect_uri", redirect),
+        ("code_verifier", verifier),
+        ("client_id", client_id.as_str()),
+    ])
+    .unwrap();
+    let response = app
+        .clone()
+        .oneshot(
+            Request::post("/token")
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(Body::from(form))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let token: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    token["access_token"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_every_tool_has_a_group_and_annotations() {
+    for tool in tools::register_all_tools() {
+        let annotations = serde_json::to_value(tool.annotations()).unwrap();
+        assert_eq!(annotations["readOnlyHint"], true, "{}", tool.name());
+        assert_eq!(annotations["destructiveHint"], false, "{}", tool.name());
+        assert!(annotations["costHint"].is_string(), "{}", tool.name());
+        assert!(!tool.group().as_str().is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_tools_list_includes_annotations() {
+    let app = build_router(HttpOptions::default());
+    let (_, response) =
+        post_mcp(&app, None, json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"})).await;
+    let tools = response["result"]["tools"].as_array().unwrap();
+
+    let snowball = tools.iter().find(|t| t["name"] == "citation_snowball").unwrap();
+    assert_eq!(snowball["annotations"]["openWorldHint"], true);
+    assert_eq!(snowball["annotations"]["costHint"], "high");
+    assert_eq!(snowball["annotations"]["latencyHint"], "slow");
+
+    let flow = tools.iter().find(|t| t["name"] == "prisma_flow_diagram").unwrap();
+    assert_eq!(flow["annotations"]["openWorldHint"], false);
+
+    let submit = tools.iter().find(|t| t["name"] == "job_submit").unwrap();
+    assert_eq!(submit["annotations"]["readOnlyHint"], false);
+}
+
+#[tokio::test]
+async fn test_deployment_filter_hides_and_blocks_tools() {
+    let app = build_router(HttpOptions {
+        tool_filter: ToolFilter::new(Vec::<String>::new(), ["bibliometrics", "jobs"]),
+        ..HttpOptions::default()
+    });
+
+    let names = list_tool_names(&app, None).await;
+    assert!(names.contains(&"exhaustive_search".to_string()));
+    assert!(!names.contains(&"hot_papers".to_string()));
+    assert!(!names.contains(&"job_submit".to_string()));
+
+    let (_, response) = post_mcp(
+        &app,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "hot_papers", "arguments": {"query": "x"}}
+        }),
+    )
+    .await;
+    assert_eq!(response["error"]["code"], -32602);
+    assert!(response["error"]["message"].as_str().unwrap().contains("Tool not found"));
+}
+
+#[tokio::test]
+async fn test_job_submit_respects_filter() {
+    let app = build_router(HttpOptions {
+        tool_filter: ToolFilter::new(Vec::<String>::new(), ["bulk_boolean_search"]),
+        ..HttpOptions::default()
+    });
+
+    let (_, response) = post_mcp(
+        &app,
+        None,
+        json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "tools/call",
+            "params": {
+                "name": "job_submit",
+                "arguments": {"tool": "bulk_boolean_search", "arguments": {"query": "x"}}
+            }
+        }),
+    )
+    .await;
+    assert_eq!(response["result"]["isError"], true);
+    assert_eq!(response["result"]["_meta"]["error"]["field"], "tool");
+}
+
+#[tokio::test]
+async fn test_per_client_policy() {
+    let policy: ToolPolicy = serde_json::from_value(json!({
+        "clients": {"Restricted Client": {"enable": ["discovery"]}}
+    }))
+    .unwrap();
+    let app = build_router(HttpOptions {
+        auth_token: Some(AUTH_TOKEN.to_string()),
+        tool_policy: ToolPolicyHandle::new(policy),
+        ..HttpOptions::default()
+    });
+
+    let all = list_tool_names(&app, Some(AUTH_TOKEN)).await;
+    assert!(all.contains(&"hot_papers".to_string()));
+
+    let restricted = oauth_access_token(&app, "Restricted Client").await;
+    let names = list_tool_names(&app, Some(&restricted)).await;
+    assert_eq!(names.len(), 5, "only discovery tools: {names:?}");
+    assert!(names.contains(&"citation_snowball".to_string()));
+
+    let other = oauth_access_token(&app, "Other Client").await;
+    assert_eq!(list_tool_names(&app, Some(&other)).await.len(), all.len());
+}
+
+#[tokio::test]
+async fn test_policy_change_sends_list_changed() {
+    let handle = ToolPolicyHandle::default();
+    let app = build_router(HttpOptions { tool_policy: handle.clone(), ..HttpOptions::default() });
+
+    let (session_id, response) = post_mcp(
+        &app,
+        None,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+    )
+    .await;
+    assert_eq!(response["result"]["capabilities"]["tools"]["listChanged"], true);
+    let session_id = session_id.unwrap();
+
+    assert!(handle.replace(ToolPolicy { disable: vec!["trends".into()], ..ToolPolicy::default() }));
+
+    let response = app
+        .clone()
+        .oneshot(Request::get(format!("/mcp?sessionId={session_id}")).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let mut stream = response.into_body().into_data_stream();
+    let chunk = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("no SSE event")
+        .unwrap()
+        .unwrap();
+    let text = String::from_utf8(chunk.to_vec()).unwrap();
+    assert!(text.contains("notifications/tools/list_changed"), "{text}");
+
+    let names = list_tool_names(&app, None).await;
+    assert!(!names.contains(&"research_trends".to_string()));
+}
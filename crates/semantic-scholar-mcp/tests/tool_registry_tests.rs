@@ -0,0 +1,256 @@
+//! Tests for embedding: tool selection, custom tools, middleware and mounting
+//! the router inside another application.
+
+use std::sync::{Arc, Mutex};
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::error::{ToolError, ToolResult, codes};
+use semantic_scholar_mcp::server::McpServer;
+use semantic_scholar_mcp::tools::{
+    self, McpTool, Next, ToolAnnotations, ToolContext, ToolFilter, ToolGroup, ToolMiddleware,
+    ToolOutput, ToolRegistry,
+};
+
+/// Echoes its `text` argument. Its group is the application's own.
+struct EchoTool(&'static str);
+
+#[async_trait::async_trait]
+impl McpTool for EchoTool {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+
+    fn description(&self) -> &'static str {
+        "Echo the text back"
+    }
+
+    fn group(&self) -> ToolGroup {
+        ToolGroup::Custom("local")
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default().local()
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {"text": {"type": "string"}},
+            "required": ["text"]
+        })
+    }
+
+    fn output_schema(&self) -> Value {
+        json!({"type": "object", "properties": {"echo": {"type": "string"}}})
+    }
+
+    async fn execute(&self, _ctx: &ToolContext, input: Value) -> ToolResult<ToolOutput> {
+        ToolOutput::json(json!({"echo": input["text"]}))
+    }
+}
+
+/// Records entry and exit, and tags the text on the way in.
+struct Trace {
+    label: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl ToolMiddleware for Trace {
+    async fn handle(
+        &self,
+        ctx: &ToolContext,
+        mut input: Value,
+        next: Next<'_>,
+    ) -> ToolResult<ToolOutput> {
+        self.log.lock().unwrap().push(format!("{} > {}", self.label, next.tool().name()));
+        input["text"] = json!(format!("{}{}", input["text"].as_str().unwrap_or(""), self.label));
+        let output = next.run(ctx, input).await;
+        self.log.lock().unwrap().push(format!("{} <", self.label));
+        output
+    }
+}
+
+/// Rejects calls of one tool.
+struct Deny(&'static str);
+
+#[async_trait::async_trait]
+impl ToolMiddleware for Deny {
+    async fn handle(
+        &self,
+        ctx: &ToolContext,
+        input: Value,
+        next: Next<'_>,
+    ) -> ToolResult<ToolOutput> {
+        if next.tool().name() == self.0 {
+            return Err(ToolError::forbidden(format!("{} is disabled here", self.0)));
+        }
+        next.run(ctx, input).await
+    }
+}
+
+fn client() -> SemanticScholarClient {
+    // Nothing listens here; these tests never reach the API.
+    SemanticScholarClient::new(ClientConfig::for_testing("http://127.0.0.1:9")).unwrap()
+}
+
+fn context() -> ToolContext {
+    ToolContext::new(Arc::new(client()))
+}
+
+fn find<'a>(tools: &'a [Box<dyn McpTool>], name: &str) -> &'a dyn McpTool {
+    tools.iter().find(|t| t.name() == name).unwrap_or_else(|| panic!("no {name}")).as_ref()
+}
+
+#[test]
+fn test_registry_selects_builtin_and_custom_tools() {
+    let tools = ToolRegistry::builtin()
+        .with_tool(EchoTool("echo"))
+        .with_filter(ToolFilter::new(["export", "echo"], Vec::<String>::new()))
+        .into_tools();
+
+    let mut expected: Vec<&str> = tools::register_all_tools()
+        .iter()
+        .filter(|t| t.group() == ToolGroup::Export)
+        .map(|t| t.name())
+        .collect();
+    expected.push("echo");
+    let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+    assert_eq!(names, expected);
+
+    // Custom groups are filtered by name like the built-in ones.
+    let tools = ToolRegistry::builtin()
+        .with_tool(EchoTool("echo"))
+        .with_tool(EchoTool("shout"))
+        .with_filter(ToolFilter::new(["local"], ["shout"]))
+        .into_tools();
+    let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+    assert_eq!(names, ["echo"]);
+}
+
+#[test]
+fn test_custom_tool_replaces_builtin_of_same_name() {
+    let builtin = ToolRegistry::builtin().names().len();
+    let registry = ToolRegistry::builtin().with_tool(EchoTool("paper_title_match"));
+    assert_eq!(registry.names().len(), builtin);
+
+    let tools = registry.into_tools();
+    assert_eq!(find(&tools, "paper_title_match").description(), "Echo the text back");
+    assert!(ToolRegistry::new().into_tools().is_empty());
+}
+
+#[tokio::test]
+async fn test_middleware_wraps_execute_in_order() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let tools = ToolRegistry::new()
+        .with_tool(EchoTool("echo"))
+        .with_middleware(Trace { label: "a", log: Arc::clone(&log) })
+        .with_middleware(Trace { label: "b", log: Arc::clone(&log) })
+        .into_tools();
+
+    let tool = find(&tools, "echo");
+    assert_eq!(tool.group(), ToolGroup::Custom("local"));
+    assert_eq!(tool.input_schema()["required"], json!(["text"]));
+    assert!(tool.validate_input(&json!({})).is_err());
+
+    let output = tool.execute(&context(), json!({"text": "x"})).await.unwrap();
+    assert_eq!(output.structured, json!({"echo": "xab"}));
+    assert_eq!(*log.lock().unwrap(), ["a > echo", "b > echo", "b <", "a <"]);
+}
+
+#[tokio::test]
+async fn test_middleware_can_reject_calls() {
+    let tools = ToolRegistry::new()
+        .with_tool(EchoTool("echo"))
+        .with_tool(EchoTool("shout"))
+        .with_middleware(Deny("shout"))
+        .into_tools();
+
+    let error = find(&tools, "shout").execute(&context(), json!({"text": "x"})).await.unwrap_err();
+    assert_eq!(error.code(), codes::FORBIDDEN);
+    assert_eq!(error.to_call_result()["isError"], true);
+    assert!(find(&tools, "echo").execute(&context(), json!({"text": "x"})).await.is_ok());
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (StatusCode, Vec<u8>) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, body.to_vec())
+}
+
+fn rpc(body: &Value) -> Request<Body> {
+    Request::post("/scholar/mcp")
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_router_mounts_inside_existing_app() {
+    let registry = ToolRegistry::builtin()
+        .with_filter(ToolFilter::new(["echo", "paper_title_match"], Vec::<String>::new()))
+        .with_tool(EchoTool("echo"))
+        .with_middleware(Deny("paper_title_match"));
+    let app = axum::Router::new().route("/", get(|| async { "host app" }));
+    let app = McpServer::new(client())
+        .with_tools(registry)
+        .mount(app, "/scholar/", "https://example.org/", None)
+        .await
+        .unwrap();
+
+    let (status, body) = send(&app, Request::get("/").body(Body::empty()).unwrap()).await;
+    assert_eq!((status, body.as_slice()), (StatusCode::OK, b"host app".as_slice()));
+
+    let request = Request::get("/scholar/.well-known/mcp.json").body(Body::empty()).unwrap();
+    let (status, body) = send(&app, request).await;
+    assert_eq!(status, StatusCode::OK);
+    let discovery: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(discovery["endpoints"]["mcp"], "https://example.org/scholar/mcp");
+    assert_eq!(discovery["tools_count"], 2);
+
+    let list = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"});
+    let (status, body) = send(&app, rpc(&list)).await;
+    assert_eq!(status, StatusCode::OK);
+    let reply: Value = serde_json::from_slice(&body).unwrap();
+    let names: Vec<&str> = reply["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|t| t["name"].as_str())
+        .collect();
+    // The HTTP transport adds its job_* tools after the registry's.
+    assert_eq!(names[..2], ["paper_title_match", "echo"]);
+
+    let call = json!({
+        "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+        "params": {"name": "echo", "arguments": {"text": "hello"}}
+    });
+    let (_, body) = send(&app, rpc(&call)).await;
+    let reply: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(reply["result"]["structuredContent"], json!({"echo": "hello"}));
+
+    let call = json!({
+        "jsonrpc": "2.0", "id": 3, "method": "tools/call",
+        "params": {"name": "paper_title_match", "arguments": {"title": "attention"}}
+    });
+    let (_, body) = send(&app, rpc(&call)).await;
+    let reply: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(reply["result"]["isError"], true);
+    assert_eq!(reply["result"]["_meta"]["error"]["code"], "forbidden");
+}
+
+#[tokio::test]
+async fn test_mount_rejects_root_path() {
+    let app: axum::Router = axum::Router::new();
+    let error = McpServer::new(client()).mount(app, "/", "https://example.org", None).await;
+    assert!(error.is_err());
+}
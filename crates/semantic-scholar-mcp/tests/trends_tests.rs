@@ -8,11 +8,11 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use semantic_scholar_mcp::client::SemanticScholarClient;
-use semantic_scholar_mcp::config::Config;
+use semantic_scholar_mcp::config::ClientConfig;
 use semantic_scholar_mcp::tools::{McpTool, ResearchTrendsTool, ToolContext, VenueAnalyticsTool};
 
 fn setup_test_context(mock_server: &MockServer) -> ToolContext {
-    let config = Config::for_testing(&mock_server.uri());
+    let config = ClientConfig::for_testing(&mock_server.uri());
     let client = SemanticScholarClient::new(config).unwrap();
     ToolContext::new(Arc::new(client))
 }
@@ -73,7 +73,8 @@ async fn test_research_trends_basic() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Trend") || result.contains("trend") || result.contains("2020"));
 }
@@ -107,7 +108,8 @@ async fn test_research_trends_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(
@@ -144,7 +146,8 @@ async fn test_research_trends_quarterly_granularity() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains('Q') || result.contains("quarter") || result.contains("Trend"));
 }
@@ -172,7 +175,8 @@ async fn test_research_trends_no_results() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     // Should handle empty gracefully
     assert!(result.contains('0') || result.contains("Trend") || !result.is_empty());
@@ -204,7 +208,8 @@ async fn test_research_trends_wide_range() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("2010") || result.contains("2023") || result.contains("Trend"));
 }
@@ -236,7 +241,8 @@ async fn test_research_trends_max_papers_per_period() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("Paper") || result.contains("Trend"));
 }
@@ -275,7 +281,7 @@ async fn test_venue_analytics_basic() {
     let ctx = setup_test_context(&mock_server);
     let tool = VenueAnalyticsTool;
 
-    let result = tool.execute(&ctx, json!({"venueQuery": "NeurIPS"})).await.unwrap();
+    let result = tool.execute(&ctx, json!({"venueQuery": "NeurIPS"})).await.unwrap().text;
 
     assert!(result.contains("NeurIPS") || result.contains("Venue") || result.contains("venue"));
 }
@@ -309,7 +315,8 @@ async fn test_venue_analytics_json_format() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     assert!(
@@ -349,7 +356,8 @@ async fn test_venue_analytics_with_year_range() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("ICML") || result.contains("Venue") || result.contains("2023"));
 }
@@ -393,7 +401,8 @@ async fn test_venue_analytics_max_papers() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     assert!(result.contains("CVPR") || result.contains("Venue") || result.contains("Max"));
 }
@@ -412,7 +421,7 @@ async fn test_venue_analytics_empty_results() {
     let tool = VenueAnalyticsTool;
 
     let result =
-        tool.execute(&ctx, json!({"venueQuery": "Nonexistent Conference XYZ"})).await.unwrap();
+        tool.execute(&ctx, json!({"venueQuery": "Nonexistent Conference XYZ"})).await.unwrap().text;
 
     assert!(result.contains('0') || result.contains("Venue") || !result.is_empty());
 }
@@ -443,7 +452,8 @@ async fn test_venue_analytics_statistics() {
             }),
         )
         .await
-        .unwrap();
+        .unwrap()
+        .text;
 
     let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
     // Should have some statistics
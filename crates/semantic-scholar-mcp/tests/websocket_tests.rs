@@ -0,0 +1,275 @@
+//! Tests for the WebSocket and Unix domain socket transports.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use wiremock::matchers::{method, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::server::oauth::OAuthStore;
+use semantic_scholar_mcp::server::transport::{
+    HttpOptions, create_router, create_router_with_options,
+};
+use semantic_scholar_mcp::server::unix;
+use semantic_scholar_mcp::tools::{self, ToolContext};
+
+fn build_router(base_url: &str, auth_token: Option<&str>) -> axum::Router {
+    let client = SemanticScholarClient::new(ClientConfig::for_testing(base_url)).unwrap();
+    let ctx = ToolContext::new(Arc::new(client));
+    create_router(tools::register_all_tools(), ctx, None, auth_token.map(str::to_owned))
+}
+
+fn build_router_with(options: HttpOptions) -> axum::Router {
+    let client = SemanticScholarClient::new(ClientConfig::for_testing("http://unused.localhost"));
+    let ctx = ToolContext::new(Arc::new(client.unwrap()));
+    create_router_with_options(tools::register_all_tools(), ctx, options)
+}
+
+async fn serve_tcp(router: axum::Router) -> std::net::SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    addr
+}
+
+/// Next JSON-RPC message from the server, skipping pings.
+async fn receive<S>(ws: &mut S) -> serde_json::Value
+where
+    S: futures::Stream<Item = tokio_tungstenite::tungstenite::Result<Message>> + Unpin,
+{
+    loop {
+        let frame = tokio::time::timeout(Duration::from_secs(10), ws.next())
+            .await
+            .expect("no message from server")
+            .expect("connection closed")
+            .unwrap();
+        if let Message::Text(text) = frame {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+async fn send<S>(ws: &mut S, message: &serde_json::Value)
+where
+    S: futures::Sink<Message, Error = tokio_tungstenite::tungstenite::Error> + Unpin,
+{
+    ws.send(Message::text(message.to_string())).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_websocket_streams_notifications_before_result() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/graph/v1/paper/s\d/citations$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "offset": 0,
+            "data": [{"citingPaper": {"paperId": "c1", "title": "Citing"}}]
+        })))
+        .mount(&mock_server)
+        .await;
+    let addr = serve_tcp(build_router(&mock_server.uri(), None)).await;
+
+    let (mut ws, response) =
+        tokio_tungstenite::connect_async(format!("ws://{addr}/ws")).await.unwrap();
+    assert!(response.headers().contains_key("Mcp-Session-Id"));
+
+    send(&mut ws, &json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}})).await;
+    let reply = receive(&mut ws).await;
+    assert_eq!(reply["id"], 1);
+    assert!(reply["result"]["capabilities"].is_object());
+
+    ws.send(Message::text("not json")).await.unwrap();
+    assert_eq!(receive(&mut ws).await["error"]["code"], -32700);
+
+    send(
+        &mut ws,
+        &json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": {
+                "name": "citation_snowball",
+                "arguments": {"seedPaperIds": ["s1", "s2"], "direction": "citations", "depth": 1},
+                "_meta": {"progressToken": "tok"}
+            }
+        }),
+    )
+    .await;
+    let mut progress = Vec::new();
+    let result = loop {
+        let message = receive(&mut ws).await;
+        if message["id"] == 7 {
+            break message;
+        }
+        assert_eq!(message["method"], "notifications/progress");
+        progress.push(message);
+    };
+    assert_eq!(progress.len(), 2, "both progress notifications precede the result");
+    assert_eq!(progress[0]["params"]["progressToken"], "tok");
+    assert!(result["result"]["structuredContent"].is_object());
+
+    send(
+        &mut ws,
+        &json!([
+            {"jsonrpc": "2.0", "id": 8, "method": "ping"},
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "id": 9, "method": "ping"}
+        ]),
+    )
+    .await;
+    let batch = receive(&mut ws).await;
+    assert_eq!(batch.as_array().unwrap().len(), 2);
+    assert_eq!(batch[1]["id"], 9);
+
+    ws.close(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_websocket_requires_token_when_auth_is_enabled() {
+    let addr = serve_tcp(build_router("http://unused.localhost", Some("secret"))).await;
+
+    let error = tokio_tungstenite::connect_async(format!("ws://{addr}/ws")).await.unwrap_err();
+    assert!(error.to_string().contains("401"), "{error}");
+
+    // Browsers cannot set headers on a WebSocket, so the token may come in the query.
+    let (mut ws, _) =
+        tokio_tungstenite::connect_async(format!("ws://{addr}/ws?token=secret")).await.unwrap();
+    send(&mut ws, &json!({"jsonrpc": "2.0", "id": 1, "method": "ping"})).await;
+    assert_eq!(receive(&mut ws).await["id"], 1);
+
+    let mut request = format!("ws://{addr}/ws").into_client_request().unwrap();
+    request.headers_mut().insert("Authorization", "Bearer secret".parse().unwrap());
+    tokio_tungstenite::connect_async(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_websocket_query_token_accepts_oauth_tokens() {
+    let store = Arc::new(OAuthStore::new());
+    let client = store.register_client(None, vec!["https://cb".into()]).await;
+    let access = store.create_token_pair(&client.client_id, "mcp").await.access_token;
+    let addr = serve_tcp(build_router_with(HttpOptions {
+        auth_token: Some("secret".into()),
+        oauth_store: Some(store),
+        ..HttpOptions::default()
+    }))
+    .await;
+
+    let (mut ws, _) =
+        tokio_tungstenite::connect_async(format!("ws://{addr}/ws?token={access}")).await.unwrap();
+    send(&mut ws, &json!({"jsonrpc": "2.0", "id": 1, "method": "ping"})).await;
+    assert_eq!(receive(&mut ws).await["id"], 1);
+
+    // The whole parameter must match, not just contain the token.
+    for query in ["token=secret-not", "xtoken=secret", "token=nope&other=token=secret"] {
+        let error =
+            tokio_tungstenite::connect_async(format!("ws://{addr}/ws?{query}")).await.unwrap_err();
+        assert!(error.to_string().contains("401"), "{query}: {error}");
+    }
+}
+
+#[tokio::test]
+async fn test_sessions_belong_to_the_client_that_created_them() {
+    let store = Arc::new(OAuthStore::new());
+    let mut tokens = Vec::new();
+    for name in ["Owner", "Other"] {
+        let client = store.register_client(Some(name.into()), vec!["https://cb".into()]).await;
+        tokens.push(store.create_token_pair(&client.client_id, "mcp").await.access_token);
+    }
+    let (owner, other) = (&tokens[0], &tokens[1]);
+    let router = build_router_with(HttpOptions {
+        auth_token: Some("secret".into()),
+        oauth_store: Some(store),
+        ..HttpOptions::default()
+    });
+    let addr = serve_tcp(router.clone()).await;
+
+    let (ws, response) =
+        tokio_tungstenite::connect_async(format!("ws://{addr}/ws?token={owner}")).await.unwrap();
+    let session = response.headers()["Mcp-Session-Id"].to_str().unwrap().to_string();
+    drop(ws);
+
+    let attach = |token: &str| {
+        tokio_tungstenite::connect_async(format!(
+            "ws://{addr}/ws?token={token}&sessionId={session}"
+        ))
+    };
+    let error = attach(other).await.unwrap_err();
+    assert!(error.to_string().contains("404"), "{error}");
+    let error = attach("secret").await.unwrap_err();
+    assert!(error.to_string().contains("404"), "{error}");
+    attach(owner).await.unwrap();
+
+    let delete = |token: &str| {
+        let request = axum::http::Request::delete("/mcp")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Mcp-Session-Id", &session)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        tower::ServiceExt::oneshot(router.clone(), request)
+    };
+    assert_eq!(delete(other).await.unwrap().status(), 404);
+    assert_eq!(delete(owner).await.unwrap().status(), 204);
+}
+
+#[tokio::test]
+async fn test_websocket_refuses_cross_origin_upgrades() {
+    let addr = serve_tcp(build_router_with(HttpOptions {
+        allowed_origins: vec!["https://app.example.org/".into()],
+        ..HttpOptions::default()
+    }))
+    .await;
+    let connect = |origin: Option<String>| {
+        let mut request = format!("ws://{addr}/ws").into_client_request().unwrap();
+        if let Some(origin) = origin {
+            request.headers_mut().insert("Origin", origin.parse().unwrap());
+        }
+        tokio_tungstenite::connect_async(request)
+    };
+
+    let error = connect(Some("https://evil.example".into())).await.unwrap_err();
+    assert!(error.to_string().contains("403"), "{error}");
+    assert!(connect(Some("null".into())).await.is_err());
+
+    // Non-browser clients, the server's own pages and allowed origins.
+    connect(None).await.unwrap();
+    connect(Some(format!("http://{addr}"))).await.unwrap();
+    connect(Some("https://app.example.org".into())).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_unix_socket_serves_http_and_websocket() {
+    let dir = std::env::temp_dir().join(format!("s2-socket-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("mcp.sock");
+    let listener = unix::bind(&path).await.unwrap();
+    let router = build_router("http://unused.localhost", None);
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let body = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}).to_string();
+    let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+    let request = format!(
+        "POST /mcp HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.to_lowercase().contains("mcp-session-id: "), "{response}");
+    assert!(response.contains("\"exhaustive_search\""), "{response}");
+
+    let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+    let (mut ws, _) = tokio_tungstenite::client_async("ws://localhost/ws", stream).await.unwrap();
+    send(&mut ws, &json!({"jsonrpc": "2.0", "id": 2, "method": "ping"})).await;
+    assert_eq!(receive(&mut ws).await, json!({"jsonrpc": "2.0", "id": 2, "result": {}}));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
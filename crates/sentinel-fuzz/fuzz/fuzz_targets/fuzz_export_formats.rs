@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Export papers built from the input and parse every format back
+    sentinel_fuzz::export::formats(data);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // POST arbitrary bodies to /mcp or /message; every reply must be valid JSON-RPC
+    sentinel_fuzz::rpc::http(data);
+});
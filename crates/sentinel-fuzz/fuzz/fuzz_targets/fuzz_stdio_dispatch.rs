@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Feed arbitrary lines to the stdio transport; every reply must be valid JSON-RPC
+    sentinel_fuzz::rpc::stdio(data);
+});
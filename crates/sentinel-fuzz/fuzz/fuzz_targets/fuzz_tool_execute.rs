@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Run a tool chosen by the first byte with the rest as its arguments
+    sentinel_fuzz::tools::execute(data);
+});
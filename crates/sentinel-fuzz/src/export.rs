@@ -0,0 +1,351 @@
+//! Reference manager exports, read back by round-trip parsers.
+//!
+//! Papers are built from the input, written in every format, and parsed
+//! again: each format must keep its record structure (balanced BibTeX
+//! braces, one CSV row per paper, one RIS/EndNote tag per line) and give
+//! back the values it was given.
+
+use std::collections::HashMap;
+
+use semantic_scholar_mcp::formatters::{format_bibtex, format_csv, format_endnote, format_ris};
+use semantic_scholar_mcp::models::{AuthorRef, ExternalIds, Paper};
+
+/// Separates papers in the input.
+const RECORD: char = '\u{1e}';
+/// Separates a paper's fields: id, title, year, venue, abstract, DOI, then
+/// one field per author.
+const FIELD: char = '\u{1f}';
+
+/// Build papers from `data` and check every format with and without
+/// abstracts.
+pub fn formats(data: &[u8]) {
+    let papers = papers(data);
+    for include_abstract in [false, true] {
+        check_bibtex(&papers, &format_bibtex(&papers, include_abstract), include_abstract);
+        check_csv(&papers, &format_csv(&papers, include_abstract), include_abstract);
+        check_ris(&papers, &format_ris(&papers, include_abstract), include_abstract);
+        check_endnote(&papers, &format_endnote(&papers, include_abstract), include_abstract);
+    }
+}
+
+/// Papers described by `data`; missing fields are left unset.
+#[must_use]
+pub fn papers(data: &[u8]) -> Vec<Paper> {
+    let text = String::from_utf8_lossy(data);
+    text.split(RECORD)
+        .map(|record| {
+            let mut fields = record.split(FIELD);
+            let mut next = || fields.next().map(str::to_owned);
+            let paper_id = next().unwrap_or_default();
+            let title = next();
+            let year = next().and_then(|year| year.parse().ok());
+            let venue = next();
+            let r#abstract = next();
+            let doi = next();
+            let authors = std::iter::from_fn(next)
+                .map(|name| AuthorRef { author_id: None, name: Some(name) })
+                .collect();
+            Paper {
+                paper_id,
+                title,
+                year,
+                venue,
+                r#abstract,
+                authors,
+                external_ids: doi
+                    .map(|doi| ExternalIds { doi: Some(doi), ..ExternalIds::default() }),
+                ..Paper::default()
+            }
+        })
+        .collect()
+}
+
+/// Value a line-based format (RIS, EndNote) should write for `s`.
+fn single_line(s: &str) -> String {
+    s.replace('\r', "").replace('\n', " ")
+}
+
+/// Value a CSV field should hold for `s`: spreadsheet formula prefixes are
+/// neutralised with a leading quote.
+fn csv_value(s: &str) -> String {
+    if s.starts_with(['=', '+', '-', '@']) { format!("'{s}") } else { s.to_owned() }
+}
+
+// =============================================================================
+// BibTeX
+// =============================================================================
+
+fn check_bibtex(papers: &[Paper], output: &str, include_abstract: bool) {
+    let entries = parse_bibtex(output);
+    assert_eq!(entries.len(), papers.len(), "{output}");
+
+    for (paper, (key, fields)) in papers.iter().zip(&entries) {
+        assert!(
+            !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '-'),
+            "bad citation key {key:?}"
+        );
+        let field = |name: &str| fields.get(name).map(String::as_str);
+        assert_eq!(field("title"), Some(paper.title_or_default()));
+        assert_eq!(field("author"), Some(paper.author_names().as_str()));
+        assert_eq!(field("journal"), paper.venue.as_deref());
+        assert_eq!(field("doi"), paper.doi());
+        let year = paper.year.filter(|year| *year > 0).map(|year| year.to_string());
+        assert_eq!(field("year"), year.as_deref());
+        let r#abstract = paper.r#abstract.as_deref().filter(|_| include_abstract);
+        assert_eq!(field("abstract"), r#abstract);
+    }
+}
+
+/// Parse the entries `format_bibtex` writes into their keys and unescaped
+/// field values, panicking on anything malformed.
+fn parse_bibtex(output: &str) -> Vec<(String, HashMap<String, String>)> {
+    let mut entries = Vec::new();
+    let mut rest = output;
+    while !rest.is_empty() {
+        rest = rest.strip_prefix("@article{").unwrap_or_else(|| panic!("expected entry: {rest}"));
+        let (key, after) = rest.split_once(",\n").unwrap_or_else(|| panic!("no key: {rest}"));
+        rest = after;
+
+        let mut fields = HashMap::new();
+        loop {
+            if let Some(after) = rest.strip_prefix("}\n\n") {
+                rest = after;
+                break;
+            }
+            let line = rest.strip_prefix("  ").unwrap_or_else(|| panic!("expected field: {rest}"));
+            let (name, after) =
+                line.split_once(" = {").unwrap_or_else(|| panic!("expected value: {line}"));
+            assert!(name.chars().all(|c| c.is_ascii_lowercase()), "bad field name {name:?}");
+            let (value, after) = braced_value(after);
+            rest =
+                after.strip_prefix(",\n").unwrap_or_else(|| panic!("unterminated field: {after}"));
+            assert!(
+                fields.insert(name.to_owned(), unescape_bibtex(value)).is_none(),
+                "{name} twice"
+            );
+        }
+        entries.push((key.to_owned(), fields));
+    }
+    entries
+}
+
+/// Split `s` after the brace closing a value whose opening brace was just
+/// consumed. BibTeX counts every brace, escaped or not.
+fn braced_value(s: &str) -> (&str, &str) {
+    let mut depth = 1;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return (&s[..i], &s[i + 1..]);
+        }
+    }
+    panic!("unbalanced braces: {s}");
+}
+
+/// Undo `escape_bibtex`, panicking on any character it should have escaped.
+fn unescape_bibtex(value: &str) -> String {
+    const COMMANDS: &[(&str, char)] = &[
+        ("textbackslash{}", '\\'),
+        ("textbraceleft{}", '{'),
+        ("textbraceright{}", '}'),
+        ("textasciicircum{}", '^'),
+        ("textasciitilde{}", '~'),
+    ];
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '\\' => {
+                if let Some(&(command, c)) = COMMANDS.iter().find(|(cmd, _)| rest.starts_with(cmd))
+                {
+                    unescaped.push(c);
+                    rest = &rest[command.len()..];
+                } else if let Some(c) = rest.chars().next().filter(|c| "&%$#_".contains(*c)) {
+                    unescaped.push(c);
+                    rest = &rest[1..];
+                } else {
+                    panic!("stray backslash in {value:?}");
+                }
+            }
+            '{' | '}' | '^' | '~' | '&' | '%' | '$' | '#' | '_' => {
+                panic!("unescaped {c:?} in {value:?}")
+            }
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+// =============================================================================
+// CSV
+// =============================================================================
+
+fn check_csv(papers: &[Paper], output: &str, include_abstract: bool) {
+    let records = parse_csv(output);
+    assert_eq!(records.len(), papers.len() + 1, "{output}");
+    let header = &records[0];
+    assert_eq!(header.len(), if include_abstract { 8 } else { 7 });
+
+    for (paper, record) in papers.iter().zip(&records[1..]) {
+        assert_eq!(record.len(), header.len(), "{record:?}");
+        assert_eq!(record[0], csv_value(&paper.paper_id));
+        assert_eq!(record[1], csv_value(paper.title_or_default()));
+        assert_eq!(record[2], csv_value(&paper.author_names()));
+        assert_eq!(record[4], csv_value(paper.venue.as_deref().unwrap_or("")));
+        assert_eq!(record[6], csv_value(paper.doi().unwrap_or("")));
+        if include_abstract {
+            assert_eq!(record[7], csv_value(paper.r#abstract.as_deref().unwrap_or("")));
+        }
+    }
+}
+
+/// Parse RFC 4180 CSV with `\n` record ends, panicking on malformed quoting.
+fn parse_csv(output: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut chars = output.chars().peekable();
+    while chars.peek().is_some() {
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => panic!("unterminated quoted field: {output}"),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c == ',' || c == '\n' {
+                    break;
+                }
+                assert!(c != '"' && c != '\r', "{c:?} in unquoted field: {output}");
+                field.push(c);
+                chars.next();
+            }
+        }
+        record.push(field);
+        match chars.next() {
+            Some(',') => {}
+            Some('\n') => records.push(std::mem::take(&mut record)),
+            other => panic!("expected field end, found {other:?}: {output}"),
+        }
+    }
+    assert!(record.is_empty(), "last record not terminated: {output}");
+    records
+}
+
+// =============================================================================
+// RIS and EndNote
+// =============================================================================
+
+fn check_ris(papers: &[Paper], output: &str, include_abstract: bool) {
+    let records = parse_tagged(output, "ER  - ", |line| {
+        let (tag, value) = line.split_at_checked(6)?;
+        let tag = tag.strip_suffix("  - ")?;
+        tag.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()).then_some((tag, value))
+    });
+    assert_eq!(records.len(), papers.len(), "{output}");
+
+    for (paper, record) in papers.iter().zip(&records) {
+        check_tagged(paper, record, ["TY", "TI", "AU", "JO", "AB", "DO"], include_abstract);
+        assert_eq!(values(record, "ID"), [single_line(&paper.paper_id)]);
+    }
+}
+
+fn check_endnote(papers: &[Paper], output: &str, include_abstract: bool) {
+    let records = parse_tagged(output, "", |line| {
+        let tag = line.get(..2).filter(|tag| tag.starts_with('%'))?;
+        Some((tag, line[2..].strip_prefix(' ')?))
+    });
+    assert_eq!(records.len(), papers.len(), "{output}");
+
+    for (paper, record) in papers.iter().zip(&records) {
+        check_tagged(paper, record, ["%0", "%T", "%A", "%J", "%X", "%R"], include_abstract);
+    }
+}
+
+/// Check a tagged record; `tags` are the type, title, author, venue,
+/// abstract and DOI tags of its format.
+fn check_tagged(
+    paper: &Paper,
+    record: &[(String, String)],
+    tags: [&str; 6],
+    include_abstract: bool,
+) {
+    let [kind, title, author, venue, r#abstract, doi] = tags;
+    assert_eq!(record[0].0, kind, "{record:?}");
+    assert_eq!(values(record, title), [single_line(paper.title_or_default())]);
+    let authors: Vec<_> = paper.authors.iter().filter_map(|a| a.name.as_deref()).collect();
+    assert_eq!(values(record, author), authors.iter().map(|a| single_line(a)).collect::<Vec<_>>());
+    assert_eq!(
+        values(record, venue),
+        paper.venue.iter().map(|v| single_line(v)).collect::<Vec<_>>()
+    );
+    let abstracts = paper.r#abstract.iter().filter(|_| include_abstract);
+    assert_eq!(values(record, r#abstract), abstracts.map(|a| single_line(a)).collect::<Vec<_>>());
+    assert_eq!(values(record, doi), paper.doi().map(single_line).into_iter().collect::<Vec<_>>());
+}
+
+/// Split line-based output into records of `(tag, value)` pairs. A record
+/// ends with the line `end` (if not empty) followed by a blank line; every
+/// other line must be accepted by `tag_line`.
+fn parse_tagged<'a>(
+    output: &'a str,
+    end: &str,
+    tag_line: impl Fn(&'a str) -> Option<(&'a str, &'a str)>,
+) -> Vec<Vec<(String, String)>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut lines = output.split('\n');
+    while let Some(line) = lines.next() {
+        if line.is_empty() && lines.clone().next().is_none() {
+            break;
+        }
+        if (end.is_empty() && line.is_empty()) || (!end.is_empty() && line == end) {
+            if !end.is_empty() {
+                assert_eq!(lines.next(), Some(""), "no blank line after record: {output}");
+            }
+            assert!(!record.is_empty(), "empty record: {output}");
+            records.push(std::mem::take(&mut record));
+            continue;
+        }
+        assert!(!line.contains('\r'), "CR in {line:?}");
+        let (tag, value) = tag_line(line).unwrap_or_else(|| panic!("not a tag line: {line:?}"));
+        record.push((tag.to_owned(), value.to_owned()));
+    }
+    assert!(record.is_empty(), "last record not terminated: {output}");
+    records
+}
+
+fn values<'a>(record: &'a [(String, String)], tag: &str) -> Vec<&'a str> {
+    record.iter().filter(|(t, _)| t == tag).map(|(_, value)| value.as_str()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_awkward_values_round_trip() {
+        let inputs = [
+            "",
+            "id\u{1f}{Title} with \\ back\\slash, \"quotes\" & 50% $x$ #1 a_b ^ ~\u{1f}2017\u{1f}=SUM(A1)\u{1f}line\nbreak\r\nand CR\u{1f}10.1/x_{y}\u{1f}Ann {O}'Neil\u{1f}-Bob, Jr.",
+            "a\u{1f}\u{1f}-3\u{1e}b\u{1f}}}}{{{\u{1f}x\u{1f}@home\u{1f}\u{1f}\u{1f}\u{1f}\u{1f}",
+            "\u{1e}\u{1e}\n\u{1f}\r",
+        ];
+        for input in inputs {
+            formats(input.as_bytes());
+        }
+    }
+}
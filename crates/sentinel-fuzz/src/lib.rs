@@ -1,13 +1,66 @@
 //! Fuzzing library for semantic-scholar-mcp.
 //!
 //! This crate provides fuzzing targets for testing JSON deserialization
-//! of the Semantic Scholar API models.
+//! of the Semantic Scholar API models, and harnesses that the targets in
+//! `fuzz/` drive with arbitrary bytes:
+//!
+//! - [`rpc`]: JSON-RPC messages through the stdio and HTTP transports.
+//! - [`tools`]: every tool's `execute` against the mock API.
+//! - [`export`]: the reference manager formats, checked by round-trip parsers.
+//!
+//! Each harness panics when an invariant is broken, which libFuzzer reports
+//! as a crash.
 //!
 //! # Usage
 //!
 //! ```bash
 //! cd crates/sentinel-fuzz
 //! cargo +nightly fuzz run fuzz_paper_parse -- -max_total_time=60
+//! cargo +nightly fuzz run fuzz_export_formats -- -max_total_time=60
 //! ```
 
+use std::sync::{Arc, OnceLock};
+
+use semantic_scholar_mcp::client::SemanticScholarClient;
+use semantic_scholar_mcp::config::ClientConfig;
+use semantic_scholar_mcp::mock::{self, Dataset, MockOptions};
+use semantic_scholar_mcp::tools::ToolContext;
+use tokio::runtime::Runtime;
+
+pub mod export;
+pub mod rpc;
+pub mod tools;
+
 pub use semantic_scholar_mcp::models;
+
+/// Runtime shared by all inputs, so each one does not pay for a new one.
+pub fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .expect("fuzz runtime")
+    })
+}
+
+/// Tool context whose client talks to an in-process mock API serving the
+/// sample dataset, started on first use.
+pub fn mock_context() -> ToolContext {
+    static CONTEXT: OnceLock<ToolContext> = OnceLock::new();
+    CONTEXT
+        .get_or_init(|| {
+            let base = runtime().block_on(async {
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+                let base = format!("http://{}", listener.local_addr()?);
+                let router = mock::router(Dataset::sample(), MockOptions::default());
+                tokio::spawn(async move { axum::serve(listener, router).await });
+                std::io::Result::Ok(base)
+            });
+            let base = base.expect("mock API listener");
+            let client = SemanticScholarClient::new(ClientConfig::for_testing(&base)).expect("client");
+            ToolContext::new(Arc::new(client))
+        })
+        .clone()
+}
@@ -0,0 +1,187 @@
+//! JSON-RPC dispatch through the stdio and HTTP transports.
+//!
+//! Whatever the input, every reply must be a well-formed JSON-RPC response
+//! (or a server notification on stdio) whose id was sent by the client.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::Value;
+use tower::ServiceExt;
+
+use semantic_scholar_mcp::server::{stdio, transport};
+use semantic_scholar_mcp::tools;
+
+use crate::{mock_context, runtime};
+
+/// Longest an input may take before it counts as a hang.
+const DEADLINE: Duration = Duration::from_secs(30);
+
+/// Feed `data` to the stdio transport as its input and check every line it
+/// writes back.
+pub fn stdio(data: &[u8]) {
+    let ids = request_ids(data);
+    let ctx = mock_context();
+    let output = runtime().block_on(async {
+        let (writer, mut reader) = tokio::io::duplex(64 * 1024);
+        let serve = stdio::serve(data, writer, tools::register_all_tools(), ctx, None);
+        let mut output = Vec::new();
+        let read = tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut output);
+        // Input that is not UTF-8 ends the session with an error, not a panic.
+        let (_, read) = tokio::time::timeout(DEADLINE, async { tokio::join!(serve, read) })
+            .await
+            .expect("stdio session did not finish");
+        read.expect("read stdio output");
+        output
+    });
+
+    let output = String::from_utf8(output).expect("stdio output is UTF-8");
+    for line in output.lines() {
+        let message: Value = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("stdio wrote a line that is not JSON ({e}): {line}"));
+        match &message {
+            Value::Array(replies) => check_batch(replies, &ids),
+            Value::Object(object) if object.contains_key("method") => {
+                assert!(!object.contains_key("id"), "server notification with an id: {line}");
+                assert_eq!(message["jsonrpc"], "2.0", "{line}");
+            }
+            _ => check_response(&message, &ids),
+        }
+    }
+}
+
+/// POST `data` to one of the HTTP message endpoints, chosen by its first
+/// byte, and check the reply.
+pub fn http(data: &[u8]) {
+    let Some((&selector, body)) = data.split_first() else {
+        return;
+    };
+    let path = if selector % 2 == 0 { "/mcp" } else { "/message" };
+    let ids = request_ids(body);
+    let router = router().clone();
+    let body = body.to_vec();
+
+    let (status, session, reply) = runtime().block_on(async move {
+        let request = Request::post(path)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .body(Body::from(body))
+            .expect("request");
+        let response = tokio::time::timeout(DEADLINE, router.clone().oneshot(request))
+            .await
+            .expect("HTTP request did not finish")
+            .expect("infallible router");
+        let status = response.status();
+        let session = response.headers().get("Mcp-Session-Id").cloned();
+        let reply = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+        let reply = reply.expect("read HTTP body");
+
+        // Each request without a session header opens one; close it so a
+        // long run does not pile them up.
+        if let Some(session) = &session {
+            let request = Request::delete("/mcp")
+                .header("Mcp-Session-Id", session)
+                .body(Body::empty())
+                .expect("request");
+            let _ = router.oneshot(request).await;
+        }
+        (status, session, reply)
+    });
+
+    assert!(!status.is_server_error(), "{path} answered {status}");
+    match status {
+        StatusCode::OK => {
+            assert!(session.is_some(), "{path} answered without Mcp-Session-Id");
+            let message: Value = serde_json::from_slice(&reply).unwrap_or_else(|e| {
+                panic!("{path} answered with a body that is not JSON ({e}): {reply:?}")
+            });
+            match &message {
+                Value::Array(replies) => check_batch(replies, &ids),
+                _ => check_response(&message, &ids),
+            }
+        }
+        StatusCode::ACCEPTED => assert!(reply.is_empty(), "202 with a body: {reply:?}"),
+        _ => {}
+    }
+}
+
+/// The HTTP router, built once inside the runtime its background tasks run on.
+fn router() -> &'static Router {
+    static ROUTER: OnceLock<Router> = OnceLock::new();
+    ROUTER.get_or_init(|| {
+        let _guard = runtime().enter();
+        transport::create_router(tools::register_all_tools(), mock_context(), None, None)
+    })
+}
+
+/// Ids of the requests in `data`, read line by line; a reply may echo any of
+/// them (or `null`).
+fn request_ids(data: &[u8]) -> Vec<Value> {
+    let mut ids = vec![Value::Null];
+    for line in data.split(|b| *b == b'\n') {
+        match serde_json::from_slice::<Value>(line) {
+            Ok(Value::Object(request)) => ids.extend(request.get("id").cloned()),
+            Ok(Value::Array(items)) => {
+                ids.extend(items.iter().filter_map(|i| i.get("id")).cloned())
+            }
+            _ => {}
+        }
+    }
+    ids
+}
+
+fn check_batch(replies: &[Value], ids: &[Value]) {
+    assert!(!replies.is_empty(), "empty batch reply");
+    for reply in replies {
+        check_response(reply, ids);
+    }
+}
+
+fn check_response(reply: &Value, ids: &[Value]) {
+    let object = reply.as_object().unwrap_or_else(|| panic!("reply is not an object: {reply}"));
+    assert_eq!(reply["jsonrpc"], "2.0", "{reply}");
+    let id = object.get("id").unwrap_or_else(|| panic!("reply without an id: {reply}"));
+    assert!(ids.contains(id), "reply to an id that was never sent: {reply}");
+    match (object.get("result"), object.get("error")) {
+        (Some(_), None) => {}
+        (None, Some(error)) => {
+            assert!(error["code"].is_i64(), "error without an integer code: {reply}");
+            assert!(error["message"].is_string(), "error without a message: {reply}");
+        }
+        _ => panic!("reply needs exactly one of result and error: {reply}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGES: &[&str] = &[
+        r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#,
+        r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#,
+        r#"{"jsonrpc": "2.0", "method": "ping"}"#,
+        r#"{"jsonrpc": "2.0", "id": "a", "method": "tools/list"}"#,
+        r#"[{"jsonrpc": "2.0", "id": 2, "method": "ping"}, 7, {"id": 3}]"#,
+        r#"{"jsonrpc": "2.0", "id": 4, "method": "tools/call", "params": {"name": "paper_title_match", "arguments": {"title": "Attention Is All You Need"}}}"#,
+        r#"{"jsonrpc": "2.0", "id": 5, "method": "tools/call", "params": {"name": "reference_export", "arguments": {"paperIds": []}}}"#,
+        "[]",
+        "{\"id\": [1, {}",
+    ];
+
+    #[test]
+    fn test_stdio_seeds() {
+        stdio(MESSAGES.join("\n").as_bytes());
+        stdio(b"\xff\xfe\n");
+    }
+
+    #[test]
+    fn test_http_seeds() {
+        for message in MESSAGES {
+            http(format!("\0{message}").as_bytes());
+            http(format!("\x01{message}").as_bytes());
+        }
+    }
+}
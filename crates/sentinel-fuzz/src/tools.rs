@@ -0,0 +1,69 @@
+//! Tool execution against the mock API.
+//!
+//! The first byte of the input picks a tool and the rest is its arguments.
+//! Arguments go straight to `execute`, without the schema validation the
+//! transports do first, so the tools' own parsing is exercised too.
+
+use std::time::Duration;
+
+use serde_json::Value;
+
+use semantic_scholar_mcp::tools::{self, McpTool};
+
+use crate::{mock_context, runtime};
+
+/// Longest a tool may run against the mock before it counts as a hang.
+const DEADLINE: Duration = Duration::from_secs(30);
+
+/// Run the tool chosen by `data[0]` with `data[1..]` as its JSON arguments.
+pub fn execute(data: &[u8]) {
+    let Some((&selector, arguments)) = data.split_first() else {
+        return;
+    };
+    let Ok(arguments) = serde_json::from_slice::<Value>(arguments) else {
+        return;
+    };
+    let tools = tools::register_all_tools();
+    let tool = &tools[usize::from(selector) % tools.len()];
+    run(tool.as_ref(), arguments);
+}
+
+/// Run `tool` and check its output; errors are fine, panics and hangs are not.
+fn run(tool: &dyn McpTool, arguments: Value) {
+    let ctx = mock_context();
+    let result = runtime()
+        .block_on(async { tokio::time::timeout(DEADLINE, tool.execute(&ctx, arguments)).await })
+        .unwrap_or_else(|_| panic!("{} did not finish", tool.name()));
+    match result {
+        Ok(output) => assert!(
+            output.structured.is_object(),
+            "{} returned non-object structured output: {}",
+            tool.name(),
+            output.structured
+        ),
+        Err(e) => assert!(!e.to_string().is_empty(), "{} failed without a message", tool.name()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_every_tool_runs_against_the_mock() {
+        let arguments = [
+            json!({}),
+            json!(null),
+            json!({"query": "transformer", "paperIds": ["ARXIV:1706.03762"], "limit": 3}),
+            json!({"seedPaperIds": ["ARXIV:1706.03762"], "positivePaperIds": ["ARXIV:1706.03762"]}),
+            json!({"title": "Attention Is All You Need", "authorId": "1701686", "format": "bibtex"}),
+        ];
+        for tool in tools::register_all_tools() {
+            for arguments in &arguments {
+                run(tool.as_ref(), arguments.clone());
+            }
+        }
+    }
+}